@@ -0,0 +1,160 @@
+// .NET wrapper over the C ABI exported by zmk-studio-api's `ffi` feature.
+//
+// Build the native library with
+//   cargo rustc --lib --crate-type cdylib --release --features ffi
+// and ship zmk_studio_api.dll (libzmk_studio_api.so / .dylib) next to your application.
+
+using System;
+using System.Runtime.InteropServices;
+
+namespace ZmkStudio
+{
+    /// <summary>Error raised when a native client call fails.</summary>
+    public sealed class ZmkStudioException : Exception
+    {
+        public ZmkStudioException(string message) : base(message) { }
+    }
+
+    /// <summary>Studio lock state, matching the protobuf <c>LockState</c> values.</summary>
+    public enum LockState
+    {
+        Locked = 0,
+        Unlocked = 1,
+    }
+
+    internal static class Native
+    {
+        private const string Library = "zmk_studio_api";
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern IntPtr zmk_last_error();
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void zmk_string_free(IntPtr text);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern ClientHandle zmk_client_open_serial(
+            [MarshalAs(UnmanagedType.LPUTF8Str)] string path);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern ClientHandle zmk_client_open_mock();
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void zmk_client_free(IntPtr client);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern int zmk_client_get_lock_state(ClientHandle client);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern IntPtr zmk_client_get_key_at(ClientHandle client, uint layerId, int keyPosition);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern int zmk_client_set_key_at(
+            ClientHandle client,
+            uint layerId,
+            int keyPosition,
+            [MarshalAs(UnmanagedType.LPUTF8Str)] string binding);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern IntPtr zmk_client_get_keymap_snapshot(ClientHandle client);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern int zmk_client_check_unsaved_changes(ClientHandle client);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern int zmk_client_save_changes(ClientHandle client);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        internal static extern int zmk_client_discard_changes(ClientHandle client);
+
+        internal static ZmkStudioException LastError()
+        {
+            IntPtr message = zmk_last_error();
+            return new ZmkStudioException(
+                message == IntPtr.Zero ? "unknown error" : Marshal.PtrToStringUTF8(message)!);
+        }
+
+        internal static int Check(int status)
+        {
+            if (status < 0)
+            {
+                throw LastError();
+            }
+            return status;
+        }
+
+        internal static string TakeString(IntPtr text)
+        {
+            if (text == IntPtr.Zero)
+            {
+                throw LastError();
+            }
+            try
+            {
+                return Marshal.PtrToStringUTF8(text)!;
+            }
+            finally
+            {
+                zmk_string_free(text);
+            }
+        }
+    }
+
+    internal sealed class ClientHandle : SafeHandle
+    {
+        private ClientHandle() : base(IntPtr.Zero, ownsHandle: true) { }
+
+        public override bool IsInvalid => handle == IntPtr.Zero;
+
+        protected override bool ReleaseHandle()
+        {
+            Native.zmk_client_free(handle);
+            return true;
+        }
+    }
+
+    /// <summary>
+    /// Client for a ZMK Studio-capable keyboard. Bindings use ZMK keymap syntax
+    /// such as <c>&amp;kp LC(C)</c>, with layer parameters given as layer IDs.
+    /// </summary>
+    /// <remarks>Instances are not thread-safe.</remarks>
+    public sealed class StudioClient : IDisposable
+    {
+        private readonly ClientHandle _handle;
+
+        private StudioClient(ClientHandle handle)
+        {
+            if (handle.IsInvalid)
+            {
+                throw Native.LastError();
+            }
+            _handle = handle;
+        }
+
+        /// <summary>Opens a client on a serial port such as <c>COM5</c>.</summary>
+        public static StudioClient OpenSerial(string path) => new(Native.zmk_client_open_serial(path));
+
+        /// <summary>Opens a client on an in-process mock keyboard, for tests without hardware.</summary>
+        public static StudioClient OpenMock() => new(Native.zmk_client_open_mock());
+
+        public LockState GetLockState() => (LockState)Native.Check(Native.zmk_client_get_lock_state(_handle));
+
+        public string GetKeyAt(uint layerId, int keyPosition) =>
+            Native.TakeString(Native.zmk_client_get_key_at(_handle, layerId, keyPosition));
+
+        public void SetKeyAt(uint layerId, int keyPosition, string binding) =>
+            Native.Check(Native.zmk_client_set_key_at(_handle, layerId, keyPosition, binding));
+
+        /// <summary>Returns the whole keymap in the crate's snapshot text format.</summary>
+        public string GetKeymapSnapshot() => Native.TakeString(Native.zmk_client_get_keymap_snapshot(_handle));
+
+        public bool CheckUnsavedChanges() => Native.Check(Native.zmk_client_check_unsaved_changes(_handle)) != 0;
+
+        public void SaveChanges() => Native.Check(Native.zmk_client_save_changes(_handle));
+
+        /// <summary>Reverts pending changes; returns whether anything was discarded.</summary>
+        public bool DiscardChanges() => Native.Check(Native.zmk_client_discard_changes(_handle)) != 0;
+
+        public void Dispose() => _handle.Dispose();
+    }
+}
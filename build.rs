@@ -16,6 +16,8 @@ fn main() {
     let mut config = prost_build::Config::new();
     config.include_file("proto_mod.rs");
     config.protoc_executable(protoc);
+    // Implemented in src/proto.rs to keep the serial number out of logs.
+    config.skip_debug([".zmk.core.GetDeviceInfoResponse"]);
     config
         .compile_protos(&protos, &["proto/zmk"])
         .expect("failed to compile protobuf definitions");
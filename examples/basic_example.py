@@ -14,11 +14,14 @@ def run(client: zmk.StudioClient) -> None:
         details = client.get_behavior_details_bytes(behavior_ids[0])
         print("First behavior details bytes:", len(details))
 
-    keymap_bytes = client.get_keymap_bytes()
-    print("Keymap bytes:", len(keymap_bytes))
+    print("Device:", client.get_device_info().name)
 
-    layouts_bytes = client.get_physical_layouts_bytes()
-    print("Physical layouts bytes:", len(layouts_bytes))
+    for layer in client.get_keymap().layers:
+        print(f"Layer {layer.id} {layer.name!r}: {len(layer)} keys")
+
+    layouts = client.get_physical_layouts()
+    for layout in layouts.layouts:
+        print("Physical layout:", layout.name, len(layout.keys), "keys")
 
     # Demonstrate typed behavior get/set at (layer 0, position 0).
     before = client.get_key_at(0, 0)
@@ -42,14 +45,18 @@ def main() -> int:
     serial.add_argument("port", help="Serial port path (for example COM3)")
 
     ble = sub.add_parser("ble", help="Connect over BLE")
-    ble.add_argument("device_id", help="BLE device id from discovery")
+    ble.add_argument(
+        "device_id",
+        nargs="?",
+        help="BLE device id from zmk.discover_ble_devices() (default: first found)",
+    )
 
     args = parser.parse_args()
 
     if args.transport == "serial":
         client = zmk.StudioClient.open_serial(args.port)
     else:
-        client = zmk.StudioClient.open_ble(args.device_id)
+        client = zmk.StudioClient.connect_ble(device_id=args.device_id)
 
     run(client)
     return 0
@@ -1,12 +1,12 @@
 use std::error::Error;
-use std::io::{Read, Write};
 use std::process::ExitCode;
 
+use zmk_studio_api::transport::Transport;
 #[cfg(feature = "ble")]
 use zmk_studio_api::transport::ble::BleTransport;
 #[cfg(feature = "serial")]
 use zmk_studio_api::transport::serial::SerialTransport;
-use zmk_studio_api::{Behavior, ClientError, HidUsage, Keycode, StudioClient};
+use zmk_studio_api::{Behavior, HidUsage, Keycode, StudioClient};
 
 fn main() -> ExitCode {
     match run() {
@@ -56,6 +56,21 @@ fn run() -> Result<(), Box<dyn Error>> {
                 Err("built without `ble` feature".into())
             }
         }
+        "ble-name" => {
+            #[cfg(feature = "ble")]
+            {
+                let Some(name) = args.next() else {
+                    print_usage();
+                    return Ok(());
+                };
+                let client = StudioClient::new(BleTransport::connect_by_name(&name)?);
+                run_example(client)
+            }
+            #[cfg(not(feature = "ble"))]
+            {
+                Err("built without `ble` feature".into())
+            }
+        }
         _ => {
             print_usage();
             Ok(())
@@ -63,7 +78,7 @@ fn run() -> Result<(), Box<dyn Error>> {
     }
 }
 
-fn run_example<T: Read + Write>(mut client: StudioClient<T>) -> Result<(), Box<dyn Error>> {
+fn run_example<T: Transport>(mut client: StudioClient<T>) -> Result<(), Box<dyn Error>> {
     let info = client.get_device_info()?;
     println!("Device: {}", info.name);
     println!("Lock: {:?}", client.get_lock_state()?);
@@ -77,8 +92,8 @@ fn run_example<T: Read + Write>(mut client: StudioClient<T>) -> Result<(), Box<d
 
     let keymap = match client.get_keymap() {
         Ok(keymap) => keymap,
-        Err(ClientError::Meta(_)) => {
-            println!("Keymap request denied (likely locked); press `&studio_unlock` then rerun.");
+        Err(err) if err.is_locked() => {
+            println!("Keyboard is locked; press `&studio_unlock` then rerun.");
             return Ok(());
         }
         Err(err) => return Err(Box::new(err)),
@@ -127,4 +142,5 @@ fn print_usage() {
     println!("Usage:");
     println!("  cargo run --example basic_example -- serial <PORT>");
     println!("  cargo run --example basic_example --features ble -- ble <DEVICE_ID>");
+    println!("  cargo run --example basic_example --features ble -- ble-name <NAME>");
 }
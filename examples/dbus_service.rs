@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::process::ExitCode;
+
+use zmk_studio_api::dbus;
+use zmk_studio_api::transport::serial::SerialTransport;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let Some(port) = std::env::args().nth(1) else {
+        println!("Usage:");
+        println!("  cargo run --example dbus_service --features dbus -- <PORT>");
+        return Ok(());
+    };
+
+    println!(
+        "Serving {port} as {} at {}",
+        dbus::DBUS_SERVICE_NAME,
+        dbus::DBUS_OBJECT_PATH
+    );
+    dbus::serve(SerialTransport::open(&port)?)?;
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::keymap::{KeymapView, TypedKeymap};
+use crate::privacy::fnv1a_64;
+use crate::snapshot::behavior_to_text;
+
+const ANNOTATIONS_HEADER: &str = "zmk-studio-annotations 1";
+const ANNOTATIONS_EXTENSION: &str = "zmknotes";
+
+/// Errors from reading or writing key annotations.
+#[derive(Debug)]
+pub enum AnnotationError {
+    Io(std::io::Error),
+    /// A line is not of the form `layer_id/position note`.
+    InvalidLine {
+        line: usize,
+    },
+}
+
+impl std::fmt::Display for AnnotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Annotation I/O error: {err}"),
+            Self::InvalidLine { line } => {
+                write!(f, "Annotation line {line}: expected `layer/position note`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnnotationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::InvalidLine { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AnnotationError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// User notes for the keys of one keyboard, keyed by layer ID and key position.
+///
+/// The firmware has nowhere to keep labels like "thumb cluster" or "don't
+/// touch", so they live on the host in an [`AnnotationStore`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyAnnotations {
+    notes: BTreeMap<(u32, usize), String>,
+}
+
+impl KeyAnnotations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, layer_id: u32, position: usize) -> Option<&str> {
+        self.notes.get(&(layer_id, position)).map(String::as_str)
+    }
+
+    /// Sets the note for a key; line breaks are replaced by spaces.
+    pub fn set(&mut self, layer_id: u32, position: usize, note: impl Into<String>) {
+        let note = note.into().replace(['\r', '\n'], " ");
+        self.notes.insert((layer_id, position), note);
+    }
+
+    pub fn remove(&mut self, layer_id: u32, position: usize) -> Option<String> {
+        self.notes.remove(&(layer_id, position))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Iterates over `(layer_id, position, note)` in layer, then position order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, usize, &str)> {
+        self.notes
+            .iter()
+            .map(|((layer_id, position), note)| (*layer_id, *position, note.as_str()))
+    }
+
+    /// Renders the file format: a header line, then one `layer_id/position note` line per key.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{ANNOTATIONS_HEADER}\n");
+        for (layer_id, position, note) in self.iter() {
+            out.push_str(&format!("{layer_id}/{position} {note}\n"));
+        }
+        out
+    }
+
+    /// Parses the format written by [`KeyAnnotations::to_text`].
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn from_text(text: &str) -> Result<Self, AnnotationError> {
+        let mut annotations = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line == ANNOTATIONS_HEADER {
+                continue;
+            }
+            let invalid = || AnnotationError::InvalidLine { line: index + 1 };
+            let (location, note) = line.split_once(' ').ok_or_else(invalid)?;
+            let (layer_id, position) = location
+                .split_once('/')
+                .and_then(|(layer, pos)| Some((layer.parse().ok()?, pos.parse().ok()?)))
+                .ok_or_else(invalid)?;
+            annotations.set(layer_id, position, note.trim());
+        }
+        Ok(annotations)
+    }
+}
+
+/// Directory-backed store of [`KeyAnnotations`], one file per keyboard.
+///
+/// Keyboards are identified by a hash of their serial number, so notes follow
+/// the keyboard across ports, hosts' BLE IDs and firmware updates.
+#[derive(Debug, Clone)]
+pub struct AnnotationStore {
+    dir: PathBuf,
+}
+
+impl AnnotationStore {
+    /// Opens (and creates if needed) an annotation store rooted at `dir`.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, AnnotationError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Loads the annotations for the keyboard with `serial_number`, or none if nothing was saved.
+    pub fn load(&self, serial_number: &[u8]) -> Result<KeyAnnotations, AnnotationError> {
+        match std::fs::read_to_string(self.path(serial_number)) {
+            Ok(text) => KeyAnnotations::from_text(&text),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(KeyAnnotations::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(
+        &self,
+        serial_number: &[u8],
+        annotations: &KeyAnnotations,
+    ) -> Result<(), AnnotationError> {
+        std::fs::write(self.path(serial_number), annotations.to_text())?;
+        Ok(())
+    }
+
+    fn path(&self, serial_number: &[u8]) -> PathBuf {
+        self.dir.join(format!(
+            "{:016x}.{ANNOTATIONS_EXTENSION}",
+            fnv1a_64(serial_number)
+        ))
+    }
+}
+
+impl TypedKeymap {
+    /// Like [`TypedKeymap::to_snapshot_text`], with each annotated binding
+    /// preceded by a `# note` comment line.
+    ///
+    /// The comments are ignored when parsing, so the result still round-trips
+    /// through [`TypedKeymap::from_snapshot_text`].
+    pub fn to_annotated_snapshot_text(&self, annotations: &KeyAnnotations) -> String {
+        let mut out = String::new();
+        for line in self.to_snapshot_text().lines() {
+            let note = line
+                .split_once(' ')
+                .and_then(|(location, _)| location.split_once('/'))
+                .and_then(|(layer, pos)| Some((layer.parse().ok()?, pos.parse().ok()?)))
+                .and_then(|(layer_id, position)| annotations.get(layer_id, position));
+            if let Some(note) = note {
+                out.push_str(&format!("# {note}\n"));
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl KeymapView {
+    /// Copies matching notes into each key's [`crate::keymap::KeyView::annotation`].
+    pub fn apply_annotations(&mut self, annotations: &KeyAnnotations) {
+        for layer in &mut self.layers {
+            for key in &mut layer.keys {
+                key.annotation = annotations
+                    .get(key.layer_id, key.position)
+                    .map(str::to_string);
+            }
+        }
+    }
+}
+
+/// Formats a key for a one-line listing, e.g. `0/12 KeyPress SPC  # thumb cluster`.
+pub fn describe_key(
+    keymap: &TypedKeymap,
+    annotations: &KeyAnnotations,
+    layer_id: u32,
+    position: usize,
+) -> Option<String> {
+    let behavior = keymap.layer(layer_id)?.bindings.get(position)?;
+    let mut text = format!("{layer_id}/{position} {}", behavior_to_text(behavior));
+    if let Some(note) = annotations.get(layer_id, position) {
+        text.push_str(&format!("  # {note}"));
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Behavior;
+    use crate::keymap::KeymapBuilder;
+
+    #[test]
+    fn round_trips_and_merges_into_snapshots() {
+        let mut annotations = KeyAnnotations::new();
+        annotations.set(0, 1, "thumb cluster");
+        annotations.set(1, 0, "don't\ntouch");
+
+        let parsed = KeyAnnotations::from_text(&annotations.to_text()).expect("valid text");
+        assert_eq!(parsed, annotations);
+        assert_eq!(parsed.get(1, 0), Some("don't touch"));
+
+        let keymap = KeymapBuilder::new()
+            .layer("Base", [Behavior::None, Behavior::Transparent])
+            .build();
+        let text = keymap.to_annotated_snapshot_text(&annotations);
+        assert!(text.contains("# thumb cluster\n0/1 Transparent\n"));
+        assert_eq!(TypedKeymap::from_snapshot_text(&text), Ok(keymap));
+
+        assert!(KeyAnnotations::from_text("zmk-studio-annotations 1\nnote").is_err());
+    }
+}
@@ -0,0 +1,437 @@
+//! Regenerates `src/keycode_zmk_generated.rs` from a ZMK checkout.
+//!
+//! ```text
+//! cargo run --features keycode-gen --bin zmk-keycode-gen -- path/to/zmk > src/keycode_zmk_generated.rs
+//! ```
+//!
+//! Every `#define` in `app/include/dt-bindings/zmk/keys.h` becomes either a
+//! `Keycode` variant or, if it names another key (or repeats an earlier
+//! key's value), a `strum` alias of that variant. A variant with aliases also
+//! lists its own name, so every name parses. Variants are ordered by value.
+//! Values are evaluated with the usage and modifier macros from the
+//! neighbouring headers.
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+
+const HEADER_DIR: &str = "app/include/dt-bindings/zmk";
+const SUPPORT_HEADERS: &[&str] = &["hid_usage_pages.h", "hid_usage.h", "modifiers.h"];
+const KEYS_HEADER: &str = "keys.h";
+
+const PREAMBLE: &str = r#"// @generated by `zmk-keycode-gen` from ZMK's `dt-bindings/zmk/keys.h`. Do not edit by hand;
+// see `src/bin/zmk-keycode-gen.rs` for how to regenerate.
+
+/// ZMK keycode value
+///
+/// This is the primary key type used by typed behavior APIs.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    TryFromPrimitive,
+    AsRefStr,
+    EnumString,
+    IntoStaticStr,
+    EnumIter,
+)]
+#[repr(u32)]
+#[allow(non_camel_case_types)]
+pub enum Keycode {
+"#;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [zmk_root] = args.as_slice() else {
+        eprintln!("usage: zmk-keycode-gen <zmk checkout>");
+        return ExitCode::from(2);
+    };
+    match generate_from_checkout(Path::new(zmk_root)) {
+        Ok(source) => {
+            print!("{source}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn generate_from_checkout(root: &Path) -> Result<String, Box<dyn Error>> {
+    let dir = root.join(HEADER_DIR);
+    let mut support = String::new();
+    for header in SUPPORT_HEADERS {
+        support.push_str(&std::fs::read_to_string(dir.join(header))?);
+        support.push('\n');
+    }
+    let keys = std::fs::read_to_string(dir.join(KEYS_HEADER))?;
+    Ok(generate(&support, &keys))
+}
+
+/// Renders the generated file from the support headers and `keys.h`.
+fn generate(support: &str, keys: &str) -> String {
+    let mut macros = Macros::default();
+    macros.define_all(support);
+    let key_defines = macros.define_all(keys);
+
+    // Canonical name per value, and the aliases collected for each canonical name.
+    let mut canonical_by_value: BTreeMap<u32, String> = BTreeMap::new();
+    let mut canonical_by_name: HashMap<String, String> = HashMap::new();
+    let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+
+    for name in key_defines {
+        if canonical_by_name.contains_key(&name) {
+            continue;
+        }
+        let Some(value) = macros.evaluate_name(&name) else {
+            eprintln!("warning: skipping `{name}`: not a constant expression");
+            continue;
+        };
+        let target = macros
+            .alias_target(&name)
+            .and_then(|target| canonical_by_name.get(&target).cloned())
+            .or_else(|| canonical_by_value.get(&value).cloned());
+        match target {
+            Some(canonical) => {
+                aliases
+                    .entry(canonical.clone())
+                    .or_default()
+                    .push(name.clone());
+                canonical_by_name.insert(name, canonical);
+            }
+            None => {
+                canonical_by_value.insert(value, name.clone());
+                canonical_by_name.insert(name.clone(), name);
+            }
+        }
+    }
+
+    let mut out = String::from(PREAMBLE);
+    for (value, name) in &canonical_by_value {
+        // The longest alias (the last of equals, in `keys.h` order) is
+        // `Keycode::to_name`; the other aliases and the full name still parse.
+        let mut aliases = aliases.remove(name).unwrap_or_default();
+        if let Some(preferred) = (0..aliases.len()).max_by_key(|&index| aliases[index].len()) {
+            let preferred = aliases.remove(preferred);
+            out.push_str(&format!("    #[strum(to_string = \"{preferred}\")]\n"));
+            for alias in aliases.iter().chain([name]) {
+                out.push_str(&format!("    #[strum(serialize = \"{alias}\")]\n"));
+            }
+        }
+        out.push_str(&format!("    {name} = 0x{value:08X},\n"));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(u64),
+    Ident(String),
+    Open,
+    Close,
+    Comma,
+    Or,
+    Shl,
+}
+
+struct Macro {
+    params: Option<Vec<String>>,
+    body: Vec<Token>,
+}
+
+/// The subset of the C preprocessor needed to evaluate ZMK's key definitions.
+#[derive(Default)]
+struct Macros {
+    defines: HashMap<String, Macro>,
+}
+
+impl Macros {
+    /// Records every `#define` in `source`, returning the defined names in order.
+    fn define_all(&mut self, source: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        for line in strip_comments(source).lines() {
+            let Some(rest) = line.trim().strip_prefix("#define") else {
+                continue;
+            };
+            let rest = rest.trim_start();
+            let name_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let (name, rest) = rest.split_at(name_len);
+            let (params, body) = match rest.strip_prefix('(') {
+                Some(rest) => {
+                    let Some((params, body)) = rest.split_once(')') else {
+                        continue;
+                    };
+                    let params = params.split(',').map(|p| p.trim().to_string()).collect();
+                    (Some(params), body)
+                }
+                None => (None, rest),
+            };
+            let Some(body) = tokenize(body) else {
+                continue;
+            };
+            if body.is_empty() {
+                continue;
+            }
+            self.defines
+                .insert(name.to_string(), Macro { params, body });
+            names.push(name.to_string());
+        }
+        names
+    }
+
+    /// If `name` is defined as just another macro's name, returns that name.
+    fn alias_target(&self, name: &str) -> Option<String> {
+        let body = &self.defines.get(name)?.body;
+        match unwrap_parens(body) {
+            [Token::Ident(target)] if self.defines.contains_key(target) => Some(target.clone()),
+            _ => None,
+        }
+    }
+
+    fn evaluate_name(&self, name: &str) -> Option<u32> {
+        let value = self.evaluate(&[Token::Ident(name.to_string())], 0)?;
+        u32::try_from(value).ok()
+    }
+
+    fn evaluate(&self, tokens: &[Token], depth: usize) -> Option<u64> {
+        // Guards against self-referential definitions.
+        if depth > 32 {
+            return None;
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            macros: self,
+            depth,
+        };
+        let value = parser.or()?;
+        (parser.pos == tokens.len()).then_some(value)
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    macros: &'a Macros,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn or(&mut self) -> Option<u64> {
+        let mut value = self.shift()?;
+        while self.eat(&Token::Or) {
+            value |= self.shift()?;
+        }
+        Some(value)
+    }
+
+    fn shift(&mut self) -> Option<u64> {
+        let mut value = self.primary()?;
+        while self.eat(&Token::Shl) {
+            value = value.checked_shl(u32::try_from(self.primary()?).ok()?)?;
+        }
+        Some(value)
+    }
+
+    fn primary(&mut self) -> Option<u64> {
+        match self.tokens.get(self.pos)?.clone() {
+            Token::Number(value) => {
+                self.pos += 1;
+                Some(value)
+            }
+            Token::Open => {
+                self.pos += 1;
+                let value = self.or()?;
+                self.eat(&Token::Close).then_some(value)
+            }
+            Token::Ident(name) => {
+                self.pos += 1;
+                let definition = self.macros.defines.get(&name)?;
+                match &definition.params {
+                    None => self.macros.evaluate(&definition.body, self.depth + 1),
+                    Some(params) => {
+                        let args = self.arguments()?;
+                        if args.len() != params.len() {
+                            return None;
+                        }
+                        let expanded = substitute(&definition.body, params, &args);
+                        self.macros.evaluate(&expanded, self.depth + 1)
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads a parenthesized, comma-separated argument list.
+    fn arguments(&mut self) -> Option<Vec<Vec<Token>>> {
+        if !self.eat(&Token::Open) {
+            return None;
+        }
+        let mut args = vec![Vec::new()];
+        let mut nesting = 0;
+        loop {
+            let token = self.tokens.get(self.pos)?.clone();
+            self.pos += 1;
+            match token {
+                Token::Close if nesting == 0 => return Some(args),
+                Token::Comma if nesting == 0 => args.push(Vec::new()),
+                token => {
+                    match token {
+                        Token::Open => nesting += 1,
+                        Token::Close => nesting -= 1,
+                        _ => {}
+                    }
+                    args.last_mut()?.push(token);
+                }
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let matched = self.tokens.get(self.pos) == Some(token);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+}
+
+/// Replaces macro parameters in `body` with the (parenthesized) arguments.
+fn substitute(body: &[Token], params: &[String], args: &[Vec<Token>]) -> Vec<Token> {
+    let mut out = Vec::new();
+    for token in body {
+        match token {
+            Token::Ident(name) => match params.iter().position(|param| param == name) {
+                Some(index) => {
+                    out.push(Token::Open);
+                    out.extend(args[index].iter().cloned());
+                    out.push(Token::Close);
+                }
+                None => out.push(token.clone()),
+            },
+            _ => out.push(token.clone()),
+        }
+    }
+    out
+}
+
+fn unwrap_parens(mut tokens: &[Token]) -> &[Token] {
+    while let [Token::Open, inner @ .., Token::Close] = tokens {
+        tokens = inner;
+    }
+    tokens
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ',' => tokens.push(Token::Comma),
+            '|' => tokens.push(Token::Or),
+            '<' if chars.next_if(|&(_, c)| c == '<').is_some() => tokens.push(Token::Shl),
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((index, c)) =
+                    chars.next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    end = index + c.len_utf8();
+                }
+                let word = &text[start..end];
+                if c.is_ascii_digit() {
+                    tokens.push(Token::Number(parse_number(word)?));
+                } else {
+                    tokens.push(Token::Ident(word.to_string()));
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+fn parse_number(word: &str) -> Option<u64> {
+    let word = word.trim_end_matches(['u', 'U', 'l', 'L']);
+    match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => word.parse().ok(),
+    }
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(index) = rest.find(['/']) {
+        let (before, after) = rest.split_at(index);
+        out.push_str(before);
+        if let Some(after) = after.strip_prefix("/*") {
+            rest = after.split_once("*/").map_or("", |(_, tail)| tail);
+            out.push(' ');
+        } else if let Some(after) = after.strip_prefix("//") {
+            rest = after.find('\n').map_or("", |newline| &after[newline..]);
+        } else {
+            out.push('/');
+            rest = &after[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+
+    #[test]
+    fn generates_variants_and_aliases_from_headers() {
+        let support = "
+            #define HID_USAGE_KEY (0x07)
+            #define HID_USAGE_KEY_KEYBOARD_A_AND_A (0x04) /* Keyboard a and A */
+            #define HID_USAGE_KEY_KEYBOARD_1_AND_EXCLAMATION 0x1E
+            #define ZMK_HID_USAGE(page, id) ((page << 16) | id)
+            #define MOD_LSFT (0x02)
+            #define APPLY_MODS(mods, keycode) (mods << 24 | keycode)
+            #define LS(keycode) APPLY_MODS(MOD_LSFT, keycode)
+        ";
+        let keys = "
+            #pragma once
+            // Keyboard 1 and Bang
+            #define NUMBER_1 (ZMK_HID_USAGE(HID_USAGE_KEY, HID_USAGE_KEY_KEYBOARD_1_AND_EXCLAMATION))
+            #define NUM_1 (NUMBER_1)
+            #define N1 (NUMBER_1)
+            #define A (ZMK_HID_USAGE(HID_USAGE_KEY, HID_USAGE_KEY_KEYBOARD_A_AND_A))
+            #define EXCLAMATION (LS(NUMBER_1))
+            #define EXCL (EXCLAMATION)
+            #define BANG (LS(N1))
+        ";
+
+        let generated = generate(support, keys);
+        let variants = generated.split_once("pub enum Keycode {\n").unwrap().1;
+        assert_eq!(
+            variants,
+            "    A = 0x00070004,
+    #[strum(to_string = \"NUM_1\")]
+    #[strum(serialize = \"N1\")]
+    #[strum(serialize = \"NUMBER_1\")]
+    NUMBER_1 = 0x0007001E,
+    #[strum(to_string = \"BANG\")]
+    #[strum(serialize = \"EXCL\")]
+    #[strum(serialize = \"EXCLAMATION\")]
+    EXCLAMATION = 0x0207001E,
+}
+"
+        );
+    }
+}
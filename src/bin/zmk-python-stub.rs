@@ -0,0 +1,290 @@
+//! Regenerates `zmk_studio_api.pyi`, the type stub maturin ships with the
+//! Python extension.
+//!
+//! ```text
+//! cargo run --features python-stub --bin zmk-python-stub > zmk_studio_api.pyi
+//! ```
+//!
+//! Classes and functions are written out below and must be kept in step with
+//! `src/python.rs`; the `Keycode` members are generated from [`Keycode`] so
+//! they always match the `IntEnum` the module builds at import time.
+
+use std::fmt::Write as _;
+
+use strum::IntoEnumIterator;
+use zmk_studio_api::Keycode;
+
+const HEADER: &str = r#"# @generated by `zmk-python-stub`. Do not edit by hand;
+# see `src/bin/zmk-python-stub.rs` for how to regenerate.
+
+from __future__ import annotations
+
+import enum
+from typing import Any, Optional, Union
+
+class Keycode(enum.IntEnum):
+"#;
+
+const BODY: &str = r#"
+KeyLike = Union[Keycode, int, str]
+
+class ZmkError(RuntimeError): ...
+class ZmkIoError(ZmkError): ...
+class ZmkConnectionError(ZmkIoError): ...
+class ZmkTimeoutError(ZmkIoError): ...
+class ZmkProtocolError(ZmkError): ...
+class ZmkLockedError(ZmkError): ...
+class ZmkKeymapError(ZmkError): ...
+class ZmkSaveFailed(ZmkKeymapError): ...
+
+class Behavior:
+    @property
+    def kind(self) -> str: ...
+    @property
+    def key(self) -> Optional[int]: ...
+    @property
+    def layer_id(self) -> Optional[int]: ...
+    @property
+    def hold(self) -> Optional[int]: ...
+    @property
+    def tap(self) -> Optional[int]: ...
+    @property
+    def command(self) -> Optional[int]: ...
+    @property
+    def value(self) -> Optional[int]: ...
+    @property
+    def behavior_id(self) -> Optional[int]: ...
+    @property
+    def param1(self) -> int: ...
+    @property
+    def param2(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> Behavior: ...
+    @staticmethod
+    def parse(text: str) -> Behavior: ...
+    def to_zmk(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Layer:
+    @property
+    def id(self) -> int: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def bindings(self) -> list[Behavior]: ...
+    def __len__(self) -> int: ...
+
+class Keymap:
+    @staticmethod
+    def from_snapshot_text(text: str) -> Keymap: ...
+    def to_snapshot_text(self) -> str: ...
+    @property
+    def layer_ids(self) -> list[int]: ...
+    @property
+    def layers(self) -> list[Layer]: ...
+    def get_key(self, layer_id: int, position: int) -> Optional[Behavior]: ...
+    def fingerprint(self) -> str: ...
+
+class KeymapDifference:
+    @property
+    def kind(self) -> str: ...
+    @property
+    def layer_id(self) -> int: ...
+    @property
+    def position(self) -> Optional[int]: ...
+    @property
+    def old(self) -> Union[str, Behavior, None]: ...
+    @property
+    def new(self) -> Union[str, Behavior, None]: ...
+
+class DeviceInfo:
+    @property
+    def name(self) -> str: ...
+    @property
+    def serial_number(self) -> bytes: ...
+
+class KeyPhysicalAttrs:
+    @property
+    def width(self) -> int: ...
+    @property
+    def height(self) -> int: ...
+    @property
+    def x(self) -> int: ...
+    @property
+    def y(self) -> int: ...
+    @property
+    def r(self) -> int: ...
+    @property
+    def rx(self) -> int: ...
+    @property
+    def ry(self) -> int: ...
+
+class PhysicalLayout:
+    @property
+    def name(self) -> str: ...
+    @property
+    def keys(self) -> list[KeyPhysicalAttrs]: ...
+
+class PhysicalLayouts:
+    @property
+    def active_layout_index(self) -> int: ...
+    @property
+    def layouts(self) -> list[PhysicalLayout]: ...
+    @property
+    def active(self) -> Optional[PhysicalLayout]: ...
+
+class ParameterDescription:
+    @property
+    def name(self) -> str: ...
+    @property
+    def kind(self) -> str: ...
+    @property
+    def constant(self) -> Optional[int]: ...
+    @property
+    def min(self) -> Optional[int]: ...
+    @property
+    def max(self) -> Optional[int]: ...
+    @property
+    def keyboard_max(self) -> Optional[int]: ...
+    @property
+    def consumer_max(self) -> Optional[int]: ...
+
+class BehaviorInfo:
+    @property
+    def id(self) -> int: ...
+    @property
+    def display_name(self) -> str: ...
+    @property
+    def role(self) -> Optional[str]: ...
+    @property
+    def metadata(
+        self,
+    ) -> list[tuple[list[ParameterDescription], list[ParameterDescription]]]: ...
+
+class BleDevice:
+    @property
+    def device_id(self) -> str: ...
+    @property
+    def name(self) -> Optional[str]: ...
+    @property
+    def adapter(self) -> str: ...
+    @property
+    def rssi(self) -> Optional[int]: ...
+    @property
+    def is_connected(self) -> bool: ...
+    @property
+    def battery_level(self) -> Optional[int]: ...
+
+class SerialPort:
+    @property
+    def path(self) -> str: ...
+    @property
+    def is_zmk(self) -> bool: ...
+    @property
+    def vid(self) -> Optional[int]: ...
+    @property
+    def pid(self) -> Optional[int]: ...
+    @property
+    def manufacturer(self) -> Optional[str]: ...
+    @property
+    def product(self) -> Optional[str]: ...
+    @property
+    def serial_number(self) -> Optional[str]: ...
+
+class StudioClient:
+    @staticmethod
+    def open_serial(path: str) -> StudioClient: ...
+    @staticmethod
+    def open_ble(device_id: str) -> StudioClient: ...
+    @staticmethod
+    def connect_ble(device_id: Optional[str] = None) -> StudioClient: ...
+    @staticmethod
+    def open_mock() -> StudioClient: ...
+    def get_lock_state(self) -> str: ...
+    def reset_settings(self) -> bool: ...
+    def list_all_behaviors(self) -> list[int]: ...
+    def list_behaviors(self) -> list[BehaviorInfo]: ...
+    def get_device_info(self) -> DeviceInfo: ...
+    def get_device_info_bytes(self) -> bytes: ...
+    def get_behavior_details_bytes(self, behavior_id: int) -> bytes: ...
+    def get_keymap_bytes(self) -> bytes: ...
+    def get_physical_layouts_bytes(self) -> bytes: ...
+    def get_physical_layouts(self) -> PhysicalLayouts: ...
+    def get_keymap(self) -> Keymap: ...
+    def get_typed_keymap(self) -> Keymap: ...
+    def apply_diff(self, diff: list[KeymapDifference]) -> int: ...
+    def get_key_at(self, layer_id: int, key_position: int) -> Behavior: ...
+    def set_key_at(self, layer_id: int, key_position: int, behavior: Behavior) -> None: ...
+    def check_unsaved_changes(self) -> bool: ...
+    def save_changes(self) -> None: ...
+    def discard_changes(self) -> bool: ...
+    def is_connected(self) -> bool: ...
+    def reconnect(self) -> None: ...
+
+def KeyPress(key: KeyLike) -> Behavior: ...
+def KeyToggle(key: KeyLike) -> Behavior: ...
+def LayerTap(layer_id: int, tap: KeyLike) -> Behavior: ...
+def ModTap(hold: KeyLike, tap: KeyLike) -> Behavior: ...
+def StickyKey(key: KeyLike) -> Behavior: ...
+def StickyLayer(layer_id: int) -> Behavior: ...
+def MomentaryLayer(layer_id: int) -> Behavior: ...
+def ToggleLayer(layer_id: int) -> Behavior: ...
+def ToLayer(layer_id: int) -> Behavior: ...
+def Bluetooth(command: int, value: int) -> Behavior: ...
+def ExternalPower(value: int) -> Behavior: ...
+def OutputSelection(value: int) -> Behavior: ...
+def Backlight(command: int, value: int) -> Behavior: ...
+def Underglow(command: int, value: int) -> Behavior: ...
+def MouseKeyPress(value: int) -> Behavior: ...
+def MouseMove(value: int) -> Behavior: ...
+def MouseScroll(value: int) -> Behavior: ...
+def CapsWord() -> Behavior: ...
+def KeyRepeat() -> Behavior: ...
+def Reset() -> Behavior: ...
+def Bootloader() -> Behavior: ...
+def SoftOff() -> Behavior: ...
+def StudioUnlock() -> Behavior: ...
+def GraveEscape() -> Behavior: ...
+def Transparent() -> Behavior: ...
+def NoBehavior() -> Behavior: ...
+def Raw(behavior_id: int, param1: int, param2: int) -> Behavior: ...
+def diff_keymaps(a: Keymap, b: Keymap) -> list[KeymapDifference]: ...
+def list_serial_ports() -> list[tuple[str, bool]]: ...
+def discover_serial_ports() -> list[SerialPort]: ...
+def discover_ble_devices() -> list[BleDevice]: ...
+"#;
+
+fn generate() -> String {
+    let mut stub = String::from(HEADER);
+    for keycode in Keycode::iter() {
+        writeln!(
+            stub,
+            "    {} = 0x{:08X}",
+            keycode.to_name(),
+            keycode.to_hid_usage()
+        )
+        .unwrap();
+    }
+    stub.push_str(BODY);
+    stub
+}
+
+fn main() {
+    print!("{}", generate());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+
+    #[test]
+    fn checked_in_stub_is_up_to_date() {
+        assert!(
+            generate() == include_str!("../../zmk_studio_api.pyi"),
+            "zmk_studio_api.pyi is stale; regenerate it with \
+             `cargo run --features python-stub --bin zmk-python-stub > zmk_studio_api.pyi`"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::error::Error;
+use std::process::ExitCode;
+
+use zmk_studio_api::StudioClient;
+use zmk_studio_api::presets::PresetRegistry;
+use zmk_studio_api::script::{self, ApplyOptions, Script};
+
+const USAGE: &str = "\
+Usage:
+  zmk-studio apply [--dry-run] [--presets <FILE>] --port <PORT> <SCRIPT.json>
+  zmk-studio --help
+
+Script bindings may reference presets as `@name`.";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Help,
+    Apply(ApplyArgs),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ApplyArgs {
+    dry_run: bool,
+    port: String,
+    file: String,
+    presets_file: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}");
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Command::Apply(args) => apply(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(String::as_str) {
+        Some("apply") => parse_apply_args(&args[1..]).map(Command::Apply),
+        Some("-h" | "--help") => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command {other:?}")),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_apply_args(args: &[String]) -> Result<ApplyArgs, String> {
+    let mut dry_run = false;
+    let mut port = None;
+    let mut file = None;
+    let mut presets_file = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{arg} needs a value"))
+        };
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--port" => port = Some(value()?),
+            "--presets" => presets_file = Some(value()?),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag:?}")),
+            _ if file.is_some() => return Err(format!("unexpected argument {arg:?}")),
+            _ => file = Some(arg.clone()),
+        }
+    }
+
+    Ok(ApplyArgs {
+        dry_run,
+        port: port.ok_or("missing --port")?,
+        file: file.ok_or("missing script file")?,
+        presets_file,
+    })
+}
+
+fn apply(args: ApplyArgs) -> Result<(), Box<dyn Error>> {
+    let mut presets = PresetRegistry::with_defaults();
+    if let Some(presets_file) = &args.presets_file {
+        presets.extend(PresetRegistry::load(presets_file)?);
+    }
+
+    let script = Script::from_json(&std::fs::read_to_string(&args.file)?)?;
+    let mut client = StudioClient::open_serial(&args.port)?;
+    let options = ApplyOptions {
+        dry_run: args.dry_run,
+        presets: Some(&presets),
+    };
+    let report = script::apply(&mut client, &script, options)?;
+
+    for (index, step) in report.steps.iter().enumerate() {
+        println!("[{index}] {step}");
+    }
+    if args.dry_run {
+        println!("Dry run: no changes were made");
+    } else if !report.saved && client.check_unsaved_changes()? {
+        println!("Changes are staged but not saved; add a `save` operation to persist them");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApplyArgs, Command, parse_args};
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn parses_apply_arguments_and_rejects_the_rest() {
+        assert_eq!(
+            parse(&[
+                "apply",
+                "--port",
+                "/dev/ttyACM0",
+                "--dry-run",
+                "script.json"
+            ]),
+            Ok(Command::Apply(ApplyArgs {
+                dry_run: true,
+                port: "/dev/ttyACM0".to_string(),
+                file: "script.json".to_string(),
+                presets_file: None,
+            }))
+        );
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+
+        for args in [
+            &[][..],
+            &["frobnicate"],
+            &["apply", "--port", "COM3", "--dryrun", "script.json"],
+            &["apply", "--port", "COM3", "a.json", "b.json"],
+            &["apply", "script.json", "--port"],
+            &["apply", "--port", "COM3"],
+            &["apply", "script.json"],
+        ] {
+            assert!(parse(args).is_err(), "{args:?}");
+        }
+    }
+}
@@ -1,5 +1,14 @@
-use crate::hid_usage::HidUsage;
+use std::collections::{BTreeMap, HashMap};
 
+use crate::hid_usage::{HID_USAGE_KEYBOARD, HidUsage};
+use crate::proto::zmk;
+use crate::proto::zmk::behaviors::BehaviorParameterValueDescription;
+use crate::proto::zmk::behaviors::behavior_parameter_value_description::ValueType;
+use crate::zmk_syntax::{ZmkSyntaxError, format_binding, parse_binding};
+
+const HID_USAGE_PAGE_CONSUMER: u16 = 0x0C;
+
+/// Firmware behavior a [`Behavior`] variant is bound to, recognized by its display name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BehaviorRole {
     KeyPress,
@@ -30,11 +39,53 @@ pub enum BehaviorRole {
     None,
 }
 
+impl BehaviorRole {
+    /// Display name the firmware gives this behavior.
+    ///
+    /// Mouse movement, mouse scrolling and soft off have no display name in
+    /// firmware, which reports their devicetree node names instead; for those,
+    /// this is a label of this crate's own that
+    /// [`role_from_display_name`] also recognizes.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::KeyPress => "Key Press",
+            Self::KeyToggle => "Key Toggle",
+            Self::LayerTap => "Layer-Tap",
+            Self::ModTap => "Mod-Tap",
+            Self::StickyKey => "Sticky Key",
+            Self::StickyLayer => "Sticky Layer",
+            Self::MomentaryLayer => "Momentary Layer",
+            Self::ToggleLayer => "Toggle Layer",
+            Self::ToLayer => "To Layer",
+            Self::Bluetooth => "Bluetooth",
+            Self::ExternalPower => "External Power",
+            Self::OutputSelection => "Output Selection",
+            Self::Backlight => "Backlight",
+            Self::Underglow => "Underglow",
+            Self::MouseKeyPress => "Mouse Key Press",
+            Self::MouseMove => "Mouse Move",
+            Self::MouseScroll => "Mouse Scroll",
+            Self::CapsWord => "Caps Word",
+            Self::KeyRepeat => "Key Repeat",
+            Self::Reset => "Reset",
+            Self::Bootloader => "Bootloader",
+            Self::SoftOff => "Soft Off",
+            Self::StudioUnlock => "Studio Unlock",
+            Self::GraveEscape => "Grave/Escape",
+            Self::Transparent => "Transparent",
+            Self::None => "None",
+        }
+    }
+}
+
 /// Lossless typed behavior value for a single key binding.
 ///
 /// Used by [`crate::StudioClient::get_key_at`] and [`crate::StudioClient::set_key_at`].
 /// Unknown behavior IDs are represented by [`Behavior::Unknown`].
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Values are totally ordered by variant, in declaration order, and then by
+/// their fields, so they can be used as `BTreeMap` keys as well as `HashMap` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Behavior {
     KeyPress(HidUsage),
     KeyToggle(HidUsage),
@@ -102,6 +153,356 @@ pub enum Behavior {
     },
 }
 
+impl Behavior {
+    /// Firmware behavior this value is bound to, or `None` for [`Behavior::Unknown`].
+    pub(crate) fn role(&self) -> Option<BehaviorRole> {
+        let role = match self {
+            Self::KeyPress(_) => BehaviorRole::KeyPress,
+            Self::KeyToggle(_) => BehaviorRole::KeyToggle,
+            Self::LayerTap { .. } => BehaviorRole::LayerTap,
+            Self::ModTap { .. } => BehaviorRole::ModTap,
+            Self::StickyKey(_) => BehaviorRole::StickyKey,
+            Self::StickyLayer { .. } => BehaviorRole::StickyLayer,
+            Self::MomentaryLayer { .. } => BehaviorRole::MomentaryLayer,
+            Self::ToggleLayer { .. } => BehaviorRole::ToggleLayer,
+            Self::ToLayer { .. } => BehaviorRole::ToLayer,
+            Self::Bluetooth { .. } => BehaviorRole::Bluetooth,
+            Self::ExternalPower { .. } => BehaviorRole::ExternalPower,
+            Self::OutputSelection { .. } => BehaviorRole::OutputSelection,
+            Self::Backlight { .. } => BehaviorRole::Backlight,
+            Self::Underglow { .. } => BehaviorRole::Underglow,
+            Self::MouseKeyPress { .. } => BehaviorRole::MouseKeyPress,
+            Self::MouseMove { .. } => BehaviorRole::MouseMove,
+            Self::MouseScroll { .. } => BehaviorRole::MouseScroll,
+            Self::CapsWord => BehaviorRole::CapsWord,
+            Self::KeyRepeat => BehaviorRole::KeyRepeat,
+            Self::Reset => BehaviorRole::Reset,
+            Self::Bootloader => BehaviorRole::Bootloader,
+            Self::SoftOff => BehaviorRole::SoftOff,
+            Self::StudioUnlock => BehaviorRole::StudioUnlock,
+            Self::GraveEscape => BehaviorRole::GraveEscape,
+            Self::Transparent => BehaviorRole::Transparent,
+            Self::None => BehaviorRole::None,
+            Self::Unknown { .. } => return None,
+        };
+        Some(role)
+    }
+}
+
+impl Behavior {
+    /// Parses a single binding written in ZMK keymap syntax, such as `&kp LS(A)`,
+    /// `&mt LCTRL ESC`, `&lt 2 SPACE` or `&trans`.
+    ///
+    /// Layer parameters must be layer IDs; use [`crate::keymap!`] to refer to
+    /// layers by name.
+    pub fn parse_zmk(text: &str) -> Result<Behavior, ZmkSyntaxError> {
+        parse_binding(text, &|_| None)
+    }
+
+    /// Renders the binding in ZMK keymap syntax, such as `&kp A` or `&mt LSHIFT ESC`,
+    /// for use in a firmware `.keymap` file.
+    ///
+    /// [`Behavior::Unknown`] values are first [normalized](Behavior::normalize)
+    /// against `catalog`. Those that remain unknown have no devicetree form and
+    /// render as `&none` followed by a comment recording the raw binding.
+    pub fn to_zmk_string(&self, catalog: &BehaviorCatalog) -> String {
+        match self.normalize(catalog) {
+            Self::Unknown {
+                behavior_id,
+                param1,
+                param2,
+            } => {
+                let name = u32::try_from(behavior_id)
+                    .ok()
+                    .and_then(|id| catalog.details(id))
+                    .map_or_else(
+                        || format!("behavior {behavior_id}"),
+                        |details| format!("{} (behavior {behavior_id})", details.display_name),
+                    );
+                format!("&none /* {name} 0x{param1:X} 0x{param2:X} */")
+            }
+            behavior => format_binding(&behavior),
+        }
+    }
+
+    /// Raw `(param1, param2)` values of this binding, as sent to the firmware.
+    pub(crate) fn params(&self) -> (u32, u32) {
+        match *self {
+            Self::KeyPress(key) | Self::KeyToggle(key) | Self::StickyKey(key) => {
+                (key.to_hid_usage(), 0)
+            }
+            Self::LayerTap { layer_id, tap } => (layer_id, tap.to_hid_usage()),
+            Self::ModTap { hold, tap } => (hold.to_hid_usage(), tap.to_hid_usage()),
+            Self::StickyLayer { layer_id }
+            | Self::MomentaryLayer { layer_id }
+            | Self::ToggleLayer { layer_id }
+            | Self::ToLayer { layer_id } => (layer_id, 0),
+            Self::Bluetooth { command, value }
+            | Self::Backlight { command, value }
+            | Self::Underglow { command, value } => (command, value),
+            Self::ExternalPower { value }
+            | Self::OutputSelection { value }
+            | Self::MouseKeyPress { value }
+            | Self::MouseMove { value }
+            | Self::MouseScroll { value } => (value, 0),
+            Self::CapsWord
+            | Self::KeyRepeat
+            | Self::Reset
+            | Self::Bootloader
+            | Self::SoftOff
+            | Self::StudioUnlock
+            | Self::GraveEscape
+            | Self::Transparent
+            | Self::None => (0, 0),
+            Self::Unknown { param1, param2, .. } => (param1, param2),
+        }
+    }
+
+    /// Resolves a [`Behavior::Unknown`] whose behavior ID is in `catalog` to its typed value.
+    ///
+    /// Typed values, and unknown values the catalog cannot resolve, are returned unchanged.
+    /// Useful when comparing bindings decoded before the catalog was available.
+    pub fn normalize(&self, catalog: &BehaviorCatalog) -> Behavior {
+        match *self {
+            Self::Unknown {
+                behavior_id,
+                param1,
+                param2,
+            } => catalog.resolve(&zmk::keymap::BehaviorBinding {
+                behavior_id,
+                param1,
+                param2,
+            }),
+            _ => self.clone(),
+        }
+    }
+
+    /// Compares two bindings after [normalizing](Behavior::normalize) both against `catalog`.
+    pub fn semantically_eq(&self, other: &Behavior, catalog: &BehaviorCatalog) -> bool {
+        self.normalize(catalog) == other.normalize(catalog)
+    }
+}
+
+/// Mapping between firmware behavior IDs and the typed [`Behavior`] variants they implement.
+///
+/// Firmware assigns behavior IDs per build, so decoding raw bindings needs the
+/// catalog of the device they came from.
+#[derive(Debug, Clone, Default)]
+pub struct BehaviorCatalog {
+    role_by_id: HashMap<u32, BehaviorRole>,
+    id_by_role: HashMap<BehaviorRole, u32>,
+    details: BTreeMap<u32, zmk::behaviors::GetBehaviorDetailsResponse>,
+}
+
+impl BehaviorCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a firmware behavior by its display name.
+    ///
+    /// Behaviors whose display name this crate does not recognize are ignored.
+    pub fn insert(&mut self, id: u32, display_name: &str) {
+        if let Some(role) = role_from_display_name(display_name) {
+            self.role_by_id.insert(id, role);
+            self.id_by_role.entry(role).or_insert(id);
+        }
+    }
+
+    /// Registers a firmware behavior with its full details, keeping its parameter metadata.
+    pub fn insert_details(&mut self, details: zmk::behaviors::GetBehaviorDetailsResponse) {
+        self.insert(details.id, &details.display_name);
+        self.details.insert(details.id, details);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.role_by_id.is_empty() && self.details.is_empty()
+    }
+
+    /// Details of every behavior registered with [`BehaviorCatalog::insert_details`], by ID.
+    pub fn behaviors(&self) -> impl Iterator<Item = &zmk::behaviors::GetBehaviorDetailsResponse> {
+        self.details.values()
+    }
+
+    /// Display name and parameter metadata of the behavior with this ID.
+    pub fn details(&self, id: u32) -> Option<&zmk::behaviors::GetBehaviorDetailsResponse> {
+        self.details.get(&id)
+    }
+
+    /// Role of the behavior with this ID, if this crate recognizes it.
+    pub fn role(&self, id: u32) -> Option<BehaviorRole> {
+        self.role_by_id.get(&id).copied()
+    }
+
+    /// Returns whether the firmware provides the behavior `behavior` is bound to.
+    pub fn supports(&self, behavior: &Behavior) -> bool {
+        behavior
+            .role()
+            .is_some_and(|role| self.id_by_role.contains_key(&role))
+    }
+
+    /// Checks a binding's parameters against the firmware's parameter metadata.
+    ///
+    /// Behaviors without registered details or metadata are not checked, and
+    /// layer IDs are left for the device to validate. On failure, returns the
+    /// offending parameter (1 or 2) and the values it would have accepted.
+    pub(crate) fn check_params(
+        &self,
+        binding: &zmk::keymap::BehaviorBinding,
+    ) -> Result<(), (u8, Vec<BehaviorParameterValueDescription>)> {
+        let Some(details) = u32::try_from(binding.behavior_id)
+            .ok()
+            .and_then(|id| self.details.get(&id))
+        else {
+            return Ok(());
+        };
+        if details.metadata.is_empty() {
+            return Ok(());
+        }
+
+        let sets: Vec<_> = details
+            .metadata
+            .iter()
+            .filter(|set| param_matches(&set.param1, binding.param1))
+            .collect();
+        if sets.is_empty() {
+            let allowed = details.metadata.iter().flat_map(|set| set.param1.clone());
+            return Err((1, allowed.collect()));
+        }
+        if sets
+            .iter()
+            .any(|set| param_matches(&set.param2, binding.param2))
+        {
+            return Ok(());
+        }
+        Err((2, sets.iter().flat_map(|set| set.param2.clone()).collect()))
+    }
+
+    /// ID of the firmware behavior implementing `role`.
+    pub fn id_for(&self, role: BehaviorRole) -> Option<u32> {
+        self.id_by_role.get(&role).copied()
+    }
+
+    /// Decodes a raw firmware binding, falling back to [`Behavior::Unknown`].
+    pub fn resolve(&self, binding: &zmk::keymap::BehaviorBinding) -> Behavior {
+        let Ok(binding_behavior_id) = u32::try_from(binding.behavior_id) else {
+            return Behavior::Unknown {
+                behavior_id: binding.behavior_id,
+                param1: binding.param1,
+                param2: binding.param2,
+            };
+        };
+        let Some(role) = self.role_by_id.get(&binding_behavior_id).copied() else {
+            return Behavior::Unknown {
+                behavior_id: binding.behavior_id,
+                param1: binding.param1,
+                param2: binding.param2,
+            };
+        };
+
+        match role {
+            BehaviorRole::KeyPress => Behavior::KeyPress(HidUsage::from_encoded(binding.param1)),
+            BehaviorRole::KeyToggle => Behavior::KeyToggle(HidUsage::from_encoded(binding.param1)),
+            BehaviorRole::LayerTap => Behavior::LayerTap {
+                layer_id: binding.param1,
+                tap: HidUsage::from_encoded(binding.param2),
+            },
+            BehaviorRole::ModTap => Behavior::ModTap {
+                hold: HidUsage::from_encoded(binding.param1),
+                tap: HidUsage::from_encoded(binding.param2),
+            },
+            BehaviorRole::StickyKey => Behavior::StickyKey(HidUsage::from_encoded(binding.param1)),
+            BehaviorRole::StickyLayer => Behavior::StickyLayer {
+                layer_id: binding.param1,
+            },
+            BehaviorRole::MomentaryLayer => Behavior::MomentaryLayer {
+                layer_id: binding.param1,
+            },
+            BehaviorRole::ToggleLayer => Behavior::ToggleLayer {
+                layer_id: binding.param1,
+            },
+            BehaviorRole::ToLayer => Behavior::ToLayer {
+                layer_id: binding.param1,
+            },
+            BehaviorRole::Bluetooth => Behavior::Bluetooth {
+                command: binding.param1,
+                value: binding.param2,
+            },
+            BehaviorRole::ExternalPower => Behavior::ExternalPower {
+                value: binding.param1,
+            },
+            BehaviorRole::OutputSelection => Behavior::OutputSelection {
+                value: binding.param1,
+            },
+            BehaviorRole::Backlight => Behavior::Backlight {
+                command: binding.param1,
+                value: binding.param2,
+            },
+            BehaviorRole::Underglow => Behavior::Underglow {
+                command: binding.param1,
+                value: binding.param2,
+            },
+            BehaviorRole::MouseKeyPress => Behavior::MouseKeyPress {
+                value: binding.param1,
+            },
+            BehaviorRole::MouseMove => Behavior::MouseMove {
+                value: binding.param1,
+            },
+            BehaviorRole::MouseScroll => Behavior::MouseScroll {
+                value: binding.param1,
+            },
+            BehaviorRole::CapsWord => Behavior::CapsWord,
+            BehaviorRole::KeyRepeat => Behavior::KeyRepeat,
+            BehaviorRole::Reset => Behavior::Reset,
+            BehaviorRole::Bootloader => Behavior::Bootloader,
+            BehaviorRole::SoftOff => Behavior::SoftOff,
+            BehaviorRole::StudioUnlock => Behavior::StudioUnlock,
+            BehaviorRole::GraveEscape => Behavior::GraveEscape,
+            BehaviorRole::Transparent => Behavior::Transparent,
+            BehaviorRole::None => Behavior::None,
+        }
+    }
+}
+
+/// Whether `value` is accepted by any of `values`; an empty list accepts anything.
+fn param_matches(values: &[BehaviorParameterValueDescription], value: u32) -> bool {
+    values.is_empty()
+        || values
+            .iter()
+            .any(|description| match &description.value_type {
+                Some(ValueType::Nil(_)) => value == 0,
+                Some(ValueType::Constant(constant)) => value == *constant,
+                Some(ValueType::Range(range)) => {
+                    (i64::from(range.min)..=i64::from(range.max)).contains(&i64::from(value))
+                }
+                Some(ValueType::HidUsage(limits)) => {
+                    let usage = HidUsage::from_encoded(value);
+                    let max = match usage.page() {
+                        HID_USAGE_KEYBOARD => limits.keyboard_max,
+                        HID_USAGE_PAGE_CONSUMER => limits.consumer_max,
+                        _ => return true,
+                    };
+                    u32::from(usage.id()) <= max
+                }
+                Some(ValueType::LayerId(_)) | None => true,
+            })
+}
+
+/// Short human-readable form of a parameter description, for error messages.
+pub(crate) fn describe_param_value(description: &BehaviorParameterValueDescription) -> String {
+    match &description.value_type {
+        Some(ValueType::Nil(_)) => "0".to_string(),
+        Some(ValueType::Constant(constant)) => constant.to_string(),
+        Some(ValueType::Range(range)) => format!("{}..={}", range.min, range.max),
+        Some(ValueType::HidUsage(limits)) => format!(
+            "HID usage (keyboard <= {:#x}, consumer <= {:#x})",
+            limits.keyboard_max, limits.consumer_max
+        ),
+        Some(ValueType::LayerId(_)) => "layer ID".to_string(),
+        None => "any value".to_string(),
+    }
+}
+
 pub fn role_from_display_name(name: &str) -> Option<BehaviorRole> {
     let n = name.trim().to_ascii_lowercase();
     match n.as_str() {
@@ -130,9 +531,197 @@ pub fn role_from_display_name(name: &str) -> Option<BehaviorRole> {
         "transparent" => Some(BehaviorRole::Transparent),
         "none" => Some(BehaviorRole::None),
         // Behaviors without display-name that use DEVICE_DT_NAME(node_id)
-        "mouse_move" => Some(BehaviorRole::MouseMove),
-        "mouse_scroll" => Some(BehaviorRole::MouseScroll),
-        "z_so_off" => Some(BehaviorRole::SoftOff),
+        "mouse_move" | "mouse move" => Some(BehaviorRole::MouseMove),
+        "mouse_scroll" | "mouse scroll" => Some(BehaviorRole::MouseScroll),
+        "z_so_off" | "soft off" => Some(BehaviorRole::SoftOff),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keycode;
+
+    #[test]
+    fn normalizes_unknown_bindings_with_catalog() {
+        let mut catalog = BehaviorCatalog::new();
+        catalog.insert(7, "Key Press");
+
+        let raw = Behavior::Unknown {
+            behavior_id: 7,
+            param1: Keycode::A.to_hid_usage(),
+            param2: 0,
+        };
+        let typed = Behavior::KeyPress(HidUsage::from_encoded(Keycode::A.to_hid_usage()));
+        assert_eq!(raw.normalize(&catalog), typed);
+        assert!(raw.semantically_eq(&typed, &catalog));
+        assert!(!raw.semantically_eq(&typed, &BehaviorCatalog::new()));
+
+        let other = Behavior::Unknown {
+            behavior_id: 8,
+            param1: 0,
+            param2: 0,
+        };
+        assert_eq!(other.normalize(&catalog), other);
+    }
+
+    #[test]
+    fn parses_zmk_bindings() {
+        assert_eq!(
+            Behavior::parse_zmk("&kp LS(A)"),
+            Ok(Behavior::KeyPress(HidUsage::from_parts(
+                0x07,
+                0x04,
+                crate::MOD_LSFT
+            )))
+        );
+        assert_eq!(
+            Behavior::parse_zmk("&lt 2 SPACE"),
+            Ok(Behavior::LayerTap {
+                layer_id: 2,
+                tap: HidUsage::from_encoded(Keycode::SPACE.to_hid_usage()),
+            })
+        );
+        assert_eq!(Behavior::parse_zmk("&none"), Ok(Behavior::None));
+        assert!(Behavior::parse_zmk("&mo NAV").is_err());
+        assert!(Behavior::parse_zmk("&trans &trans").is_err());
+    }
+
+    #[test]
+    fn renders_zmk_bindings() {
+        let mut catalog = BehaviorCatalog::new();
+        catalog.insert(1, "Mod-Tap");
+        catalog.insert_details(zmk::behaviors::GetBehaviorDetailsResponse {
+            id: 9,
+            display_name: "Vendor Macro".to_string(),
+            metadata: Vec::new(),
+        });
+
+        let mod_tap = Behavior::Unknown {
+            behavior_id: 1,
+            param1: Keycode::LEFT_SHIFT.to_hid_usage(),
+            param2: Keycode::ESCAPE.to_hid_usage(),
+        };
+        assert_eq!(mod_tap.to_zmk_string(&catalog), "&mt LSHIFT ESC");
+        assert_eq!(
+            Behavior::MomentaryLayer { layer_id: 1 }.to_zmk_string(&catalog),
+            "&mo 1"
+        );
+        let vendor = Behavior::Unknown {
+            behavior_id: 9,
+            param1: 1,
+            param2: 0,
+        };
+        assert_eq!(
+            vendor.to_zmk_string(&catalog),
+            "&none /* Vendor Macro (behavior 9) 0x1 0x0 */"
+        );
+    }
+
+    #[test]
+    fn keeps_behavior_details_by_id() {
+        let mut catalog = BehaviorCatalog::new();
+        for (id, name) in [(4, "Momentary Layer"), (2, "Vendor Macro")] {
+            catalog.insert_details(zmk::behaviors::GetBehaviorDetailsResponse {
+                id,
+                display_name: name.to_string(),
+                metadata: Vec::new(),
+            });
+        }
+
+        assert_eq!(
+            catalog.behaviors().map(|b| b.id).collect::<Vec<_>>(),
+            [2, 4]
+        );
+        assert_eq!(catalog.role(4), Some(BehaviorRole::MomentaryLayer));
+        assert_eq!(catalog.role(2), None);
+        assert_eq!(catalog.id_for(BehaviorRole::MomentaryLayer), Some(4));
+        assert_eq!(
+            catalog.details(2).map(|b| b.display_name.as_str()),
+            Some("Vendor Macro")
+        );
+    }
+
+    #[test]
+    fn checks_params_against_metadata() {
+        use zmk::behaviors::{
+            BehaviorBindingParametersSet, BehaviorParameterHidUsage, BehaviorParameterNil,
+            BehaviorParameterValueDescriptionRange,
+        };
+
+        let value = |value_type| BehaviorParameterValueDescription {
+            name: String::new(),
+            value_type: Some(value_type),
+        };
+        let mut catalog = BehaviorCatalog::new();
+        catalog.insert_details(zmk::behaviors::GetBehaviorDetailsResponse {
+            id: 1,
+            display_name: "Key Press".to_string(),
+            metadata: vec![BehaviorBindingParametersSet {
+                param1: vec![value(ValueType::HidUsage(BehaviorParameterHidUsage {
+                    keyboard_max: 0xFF,
+                    consumer_max: 0xFF,
+                }))],
+                param2: Vec::new(),
+            }],
+        });
+        catalog.insert_details(zmk::behaviors::GetBehaviorDetailsResponse {
+            id: 2,
+            display_name: "Bluetooth".to_string(),
+            metadata: vec![
+                BehaviorBindingParametersSet {
+                    param1: vec![value(ValueType::Constant(0))],
+                    param2: vec![value(ValueType::Nil(BehaviorParameterNil {}))],
+                },
+                BehaviorBindingParametersSet {
+                    param1: vec![value(ValueType::Constant(3))],
+                    param2: vec![value(ValueType::Range(
+                        BehaviorParameterValueDescriptionRange { min: 0, max: 4 },
+                    ))],
+                },
+            ],
+        });
+        let binding = |behavior_id, param1, param2| zmk::keymap::BehaviorBinding {
+            behavior_id,
+            param1,
+            param2,
+        };
+
+        assert!(
+            catalog
+                .check_params(&binding(1, Keycode::A.to_hid_usage(), 0))
+                .is_ok()
+        );
+        assert_eq!(
+            catalog
+                .check_params(&binding(
+                    1,
+                    HidUsage::from_parts(0x07, 0x100, 0).to_hid_usage(),
+                    0
+                ))
+                .map_err(|(param, _)| param),
+            Err(1)
+        );
+        assert!(catalog.check_params(&binding(2, 3, 4)).is_ok());
+        let (param, allowed) = catalog.check_params(&binding(2, 3, 5)).unwrap_err();
+        assert_eq!(param, 2);
+        assert_eq!(
+            allowed.iter().map(describe_param_value).collect::<Vec<_>>(),
+            ["0..=4"]
+        );
+        assert_eq!(catalog.check_params(&binding(2, 1, 0)).unwrap_err().0, 1);
+        assert!(catalog.check_params(&binding(9, 1, 1)).is_ok());
+    }
+
+    #[test]
+    fn behaviors_are_ordered_and_hashable() {
+        let a = Behavior::KeyPress(HidUsage::from_encoded(Keycode::A.to_hid_usage()));
+        let b = Behavior::KeyPress(HidUsage::from_encoded(Keycode::B.to_hid_usage()));
+        let set: std::collections::BTreeSet<_> = [Behavior::None, b.clone(), a.clone(), a.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), [a, b, Behavior::None]);
+        assert!(Keycode::A < Keycode::B);
+    }
+}
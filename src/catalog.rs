@@ -0,0 +1,438 @@
+use serde_json::{Value, json};
+
+use crate::binding::role_from_display_name;
+use crate::client::{ClientError, StudioClient};
+use crate::proto::zmk::behaviors::{
+    BehaviorBindingParametersSet, BehaviorParameterValueDescription,
+    behavior_parameter_value_description::ValueType,
+};
+use crate::transport::Transport;
+
+/// Serializes the device's behaviors to pretty-printed JSON.
+///
+/// Each behavior lists its firmware ID, display name, the role this crate
+/// detected for it (the matching [`crate::Behavior`] variant name, or `null`),
+/// and the parameter metadata reported by the firmware:
+///
+/// ```json
+/// {
+///   "behaviors": [
+///     {
+///       "id": 3,
+///       "display_name": "Momentary Layer",
+///       "role": "MomentaryLayer",
+///       "metadata": [
+///         { "param1": [{ "name": "", "type": "layer_id" }], "param2": [] }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+pub fn export_behavior_catalog<T: Transport>(
+    client: &mut StudioClient<T>,
+) -> Result<String, ClientError> {
+    let catalog = catalog_value(client)?;
+    Ok(serde_json::to_string_pretty(&catalog).expect("JSON values always serialize"))
+}
+
+/// A behavior entry as it appears in an exported catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogBehavior {
+    pub id: u32,
+    pub display_name: String,
+}
+
+/// One difference between an exported catalog and a device's current catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogChange {
+    /// The behavior exists only on the device.
+    Added(CatalogBehavior),
+    /// The behavior exists only in the exported catalog.
+    Removed(CatalogBehavior),
+    /// The same behavior ID now reports a different display name.
+    Renamed {
+        id: u32,
+        old_name: String,
+        new_name: String,
+    },
+    /// A behavior with the same display name moved to a different ID.
+    ///
+    /// Bindings reference behaviors by ID, so stored keymaps that used
+    /// `old_id` no longer decode to this behavior.
+    Renumbered {
+        display_name: String,
+        old_id: u32,
+        new_id: u32,
+    },
+}
+
+impl std::fmt::Display for CatalogChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(behavior) => {
+                write!(f, "added {:?} (id {})", behavior.display_name, behavior.id)
+            }
+            Self::Removed(behavior) => {
+                write!(
+                    f,
+                    "removed {:?} (id {})",
+                    behavior.display_name, behavior.id
+                )
+            }
+            Self::Renamed {
+                id,
+                old_name,
+                new_name,
+            } => write!(f, "renamed id {id} from {old_name:?} to {new_name:?}"),
+            Self::Renumbered {
+                display_name,
+                old_id,
+                new_id,
+            } => write!(f, "{display_name:?} moved from id {old_id} to id {new_id}"),
+        }
+    }
+}
+
+/// Result of [`diff_behavior_catalog`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogDiff {
+    pub changes: Vec<CatalogChange>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub enum CatalogDiffError {
+    Client(ClientError),
+    Json(serde_json::Error),
+    /// The JSON does not have the shape written by [`export_behavior_catalog`].
+    InvalidCatalog,
+}
+
+impl std::fmt::Display for CatalogDiffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Client(err) => write!(f, "{err}"),
+            Self::Json(err) => write!(f, "Invalid catalog JSON: {err}"),
+            Self::InvalidCatalog => write!(f, "Catalog JSON is missing behavior IDs or names"),
+        }
+    }
+}
+
+impl std::error::Error for CatalogDiffError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Client(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::InvalidCatalog => None,
+        }
+    }
+}
+
+impl From<ClientError> for CatalogDiffError {
+    fn from(value: ClientError) -> Self {
+        Self::Client(value)
+    }
+}
+
+impl From<serde_json::Error> for CatalogDiffError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// Compares a catalog previously written by [`export_behavior_catalog`] with
+/// the connected device's current behaviors.
+///
+/// Run this after a firmware update to see why stored bindings now decode
+/// differently: behaviors are matched by ID first, and unmatched behaviors
+/// with the same display name are reported as renumbered.
+pub fn diff_behavior_catalog<T: Transport>(
+    client: &mut StudioClient<T>,
+    exported: &str,
+) -> Result<CatalogDiff, CatalogDiffError> {
+    let old = parse_catalog(&serde_json::from_str(exported)?)?;
+    let new = parse_catalog(&catalog_value(client)?)?;
+    Ok(diff_catalogs(&old, &new))
+}
+
+fn catalog_value<T: Transport>(client: &mut StudioClient<T>) -> Result<Value, ClientError> {
+    let mut behaviors = Vec::new();
+    for id in client.list_all_behaviors()? {
+        let details = client.get_behavior_details(id)?;
+        let role = role_from_display_name(&details.display_name).map(|role| format!("{role:?}"));
+        behaviors.push(json!({
+            "id": details.id,
+            "display_name": details.display_name,
+            "role": role,
+            "metadata": details.metadata.iter().map(parameter_set).collect::<Vec<_>>(),
+        }));
+    }
+
+    Ok(json!({ "behaviors": behaviors }))
+}
+
+fn parse_catalog(catalog: &Value) -> Result<Vec<CatalogBehavior>, CatalogDiffError> {
+    let behaviors = catalog["behaviors"]
+        .as_array()
+        .ok_or(CatalogDiffError::InvalidCatalog)?;
+    behaviors
+        .iter()
+        .map(|behavior| {
+            let id = behavior["id"]
+                .as_u64()
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or(CatalogDiffError::InvalidCatalog)?;
+            let display_name = behavior["display_name"]
+                .as_str()
+                .ok_or(CatalogDiffError::InvalidCatalog)?;
+            Ok(CatalogBehavior {
+                id,
+                display_name: display_name.to_owned(),
+            })
+        })
+        .collect()
+}
+
+fn diff_catalogs(old: &[CatalogBehavior], new: &[CatalogBehavior]) -> CatalogDiff {
+    let mut changes = Vec::new();
+    let mut unmatched_new: Vec<&CatalogBehavior> = new
+        .iter()
+        .filter(|behavior| !old.iter().any(|other| other.id == behavior.id))
+        .collect();
+
+    for old_behavior in old {
+        if let Some(new_behavior) = new.iter().find(|other| other.id == old_behavior.id) {
+            if new_behavior.display_name != old_behavior.display_name {
+                changes.push(CatalogChange::Renamed {
+                    id: old_behavior.id,
+                    old_name: old_behavior.display_name.clone(),
+                    new_name: new_behavior.display_name.clone(),
+                });
+            }
+        } else if let Some(index) = unmatched_new
+            .iter()
+            .position(|other| other.display_name == old_behavior.display_name)
+        {
+            let new_behavior = unmatched_new.remove(index);
+            changes.push(CatalogChange::Renumbered {
+                display_name: old_behavior.display_name.clone(),
+                old_id: old_behavior.id,
+                new_id: new_behavior.id,
+            });
+        } else {
+            changes.push(CatalogChange::Removed(old_behavior.clone()));
+        }
+    }
+    changes.extend(
+        unmatched_new
+            .into_iter()
+            .map(|behavior| CatalogChange::Added(behavior.clone())),
+    );
+
+    CatalogDiff { changes }
+}
+
+fn parameter_set(set: &BehaviorBindingParametersSet) -> Value {
+    json!({
+        "param1": set.param1.iter().map(parameter).collect::<Vec<_>>(),
+        "param2": set.param2.iter().map(parameter).collect::<Vec<_>>(),
+    })
+}
+
+fn parameter(description: &BehaviorParameterValueDescription) -> Value {
+    let name = &description.name;
+    match &description.value_type {
+        Some(ValueType::Nil(_)) => json!({ "name": name, "type": "nil" }),
+        Some(ValueType::Constant(value)) => {
+            json!({ "name": name, "type": "constant", "value": value })
+        }
+        Some(ValueType::Range(range)) => {
+            json!({ "name": name, "type": "range", "min": range.min, "max": range.max })
+        }
+        Some(ValueType::HidUsage(usage)) => json!({
+            "name": name,
+            "type": "hid_usage",
+            "keyboard_max": usage.keyboard_max,
+            "consumer_max": usage.consumer_max,
+        }),
+        Some(ValueType::LayerId(_)) => json!({ "name": name, "type": "layer_id" }),
+        None => json!({ "name": name, "type": null }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::zmk::behaviors::{
+        BehaviorParameterHidUsage, BehaviorParameterLayerId, BehaviorParameterNil,
+        BehaviorParameterValueDescriptionRange, GetBehaviorDetailsResponse,
+    };
+    use crate::transport::mock::MockDevice;
+
+    fn behavior(id: u32, display_name: &str) -> CatalogBehavior {
+        CatalogBehavior {
+            id,
+            display_name: display_name.to_owned(),
+        }
+    }
+
+    #[test]
+    fn reports_added_removed_renamed_and_renumbered() {
+        let old = [
+            behavior(1, "Key Press"),
+            behavior(2, "Momentary Layer"),
+            behavior(3, "Bluetooth"),
+            behavior(4, "Caps Word"),
+        ];
+        let new = [
+            behavior(1, "Key Press"),
+            behavior(2, "Momentary"),
+            behavior(5, "Bluetooth"),
+            behavior(6, "Key Repeat"),
+        ];
+
+        let diff = diff_catalogs(&old, &new);
+        assert_eq!(
+            diff.changes,
+            vec![
+                CatalogChange::Renamed {
+                    id: 2,
+                    old_name: "Momentary Layer".to_owned(),
+                    new_name: "Momentary".to_owned(),
+                },
+                CatalogChange::Renumbered {
+                    display_name: "Bluetooth".to_owned(),
+                    old_id: 3,
+                    new_id: 5,
+                },
+                CatalogChange::Removed(behavior(4, "Caps Word")),
+                CatalogChange::Added(behavior(6, "Key Repeat")),
+            ]
+        );
+        assert!(diff_catalogs(&old, &old).is_empty());
+    }
+
+    fn described(name: &str, value_type: ValueType) -> BehaviorParameterValueDescription {
+        BehaviorParameterValueDescription {
+            name: name.to_owned(),
+            value_type: Some(value_type),
+        }
+    }
+
+    #[test]
+    fn exports_a_stable_catalog_that_diffs_clean() {
+        let device = MockDevice::new().with_behavior_details([
+            GetBehaviorDetailsResponse {
+                id: 1,
+                display_name: "Key Press".to_owned(),
+                metadata: vec![BehaviorBindingParametersSet {
+                    param1: vec![described(
+                        "Key",
+                        ValueType::HidUsage(BehaviorParameterHidUsage {
+                            keyboard_max: 255,
+                            consumer_max: 1023,
+                        }),
+                    )],
+                    param2: vec![],
+                }],
+            },
+            GetBehaviorDetailsResponse {
+                id: 9,
+                display_name: "Bluetooth".to_owned(),
+                metadata: vec![BehaviorBindingParametersSet {
+                    param1: vec![described("BT_SEL", ValueType::Constant(3))],
+                    param2: vec![
+                        described(
+                            "Profile",
+                            ValueType::Range(BehaviorParameterValueDescriptionRange {
+                                min: 0,
+                                max: 4,
+                            }),
+                        ),
+                        described("", ValueType::Nil(BehaviorParameterNil {})),
+                    ],
+                }],
+            },
+            GetBehaviorDetailsResponse {
+                id: 12,
+                display_name: "Momentary Layer".to_owned(),
+                metadata: vec![BehaviorBindingParametersSet {
+                    param1: vec![described(
+                        "",
+                        ValueType::LayerId(BehaviorParameterLayerId {}),
+                    )],
+                    param2: vec![],
+                }],
+            },
+            GetBehaviorDetailsResponse {
+                id: 20,
+                display_name: "Custom Macro".to_owned(),
+                metadata: vec![],
+            },
+        ]);
+        let mut client = StudioClient::new(device);
+
+        let exported = export_behavior_catalog(&mut client).expect("export");
+        let golden = json!({
+            "behaviors": [
+                {
+                    "id": 1,
+                    "display_name": "Key Press",
+                    "role": "KeyPress",
+                    "metadata": [{
+                        "param1": [{
+                            "name": "Key",
+                            "type": "hid_usage",
+                            "keyboard_max": 255,
+                            "consumer_max": 1023,
+                        }],
+                        "param2": [],
+                    }],
+                },
+                {
+                    "id": 9,
+                    "display_name": "Bluetooth",
+                    "role": "Bluetooth",
+                    "metadata": [{
+                        "param1": [{ "name": "BT_SEL", "type": "constant", "value": 3 }],
+                        "param2": [
+                            { "name": "Profile", "type": "range", "min": 0, "max": 4 },
+                            { "name": "", "type": "nil" },
+                        ],
+                    }],
+                },
+                {
+                    "id": 12,
+                    "display_name": "Momentary Layer",
+                    "role": "MomentaryLayer",
+                    "metadata": [{
+                        "param1": [{ "name": "", "type": "layer_id" }],
+                        "param2": [],
+                    }],
+                },
+                {
+                    "id": 20,
+                    "display_name": "Custom Macro",
+                    "role": null,
+                    "metadata": [],
+                },
+            ]
+        });
+        assert_eq!(
+            serde_json::from_str::<Value>(&exported).expect("valid JSON"),
+            golden
+        );
+        assert_eq!(
+            exported,
+            serde_json::to_string_pretty(&golden).expect("serialize")
+        );
+
+        let diff = diff_behavior_catalog(&mut client, &exported).expect("diff");
+        assert!(diff.is_empty());
+    }
+}
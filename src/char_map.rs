@@ -0,0 +1,137 @@
+use crate::hid_usage::{HID_USAGE_KEYBOARD, HidUsage, MOD_LSFT, MOD_RSFT};
+
+/// Printable keys of the US layout as `(usage id, unshifted, shifted)`.
+const US_KEYS: &[(u16, char, char)] = &[
+    (0x1E, '1', '!'),
+    (0x1F, '2', '@'),
+    (0x20, '3', '#'),
+    (0x21, '4', '$'),
+    (0x22, '5', '%'),
+    (0x23, '6', '^'),
+    (0x24, '7', '&'),
+    (0x25, '8', '*'),
+    (0x26, '9', '('),
+    (0x27, '0', ')'),
+    (0x2C, ' ', ' '),
+    (0x2D, '-', '_'),
+    (0x2E, '=', '+'),
+    (0x2F, '[', '{'),
+    (0x30, ']', '}'),
+    (0x31, '\\', '|'),
+    (0x33, ';', ':'),
+    (0x34, '\'', '"'),
+    (0x35, '`', '~'),
+    (0x36, ',', '<'),
+    (0x37, '.', '>'),
+    (0x38, '/', '?'),
+];
+
+/// Control characters typed by a single unmodified key.
+const US_CONTROL_KEYS: &[(u16, char)] = &[
+    (0x28, '\n'),
+    (0x29, '\u{1b}'),
+    (0x2A, '\u{8}'),
+    (0x2B, '\t'),
+];
+
+const USAGE_A: u16 = 0x04;
+
+/// Returns the key that types `c` on a US layout, with left shift applied for
+/// shifted characters.
+///
+/// Newline, tab, escape and backspace map to their keys. Returns `None` for
+/// characters the layout cannot type directly. To type a whole string:
+///
+/// ```
+/// use zmk_studio_api::char_map::char_to_usage;
+///
+/// let keys: Option<Vec<_>> = "Hi!".chars().map(char_to_usage).collect();
+/// let names: Vec<_> = keys.unwrap().iter().map(|key| key.to_zmk_string()).collect();
+/// assert_eq!(names, ["LS(H)", "I", "EXCL"]);
+/// ```
+pub fn char_to_usage(c: char) -> Option<HidUsage> {
+    let key = |id, modifiers| Some(HidUsage::from_parts(HID_USAGE_KEYBOARD, id, modifiers));
+
+    if c.is_ascii_lowercase() {
+        return key(USAGE_A + (c as u16 - 'a' as u16), 0);
+    }
+    if c.is_ascii_uppercase() {
+        return key(USAGE_A + (c as u16 - 'A' as u16), MOD_LSFT);
+    }
+    if let Some(&(id, _)) = US_CONTROL_KEYS.iter().find(|(_, control)| *control == c) {
+        return key(id, 0);
+    }
+    US_KEYS.iter().find_map(|&(id, plain, shifted)| {
+        if c == plain {
+            key(id, 0)
+        } else if c == shifted {
+            key(id, MOD_LSFT)
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the character `usage` types on a US layout; the inverse of
+/// [`char_to_usage`].
+///
+/// Either shift key counts as shift. Returns `None` for keys that type no
+/// character and for any other modifier.
+pub fn usage_to_char(usage: HidUsage) -> Option<char> {
+    if usage.page() != HID_USAGE_KEYBOARD {
+        return None;
+    }
+    let shifted = match usage.modifiers() {
+        0 => false,
+        modifiers if modifiers & !(MOD_LSFT | MOD_RSFT) == 0 => true,
+        _ => return None,
+    };
+    let id = usage.id();
+
+    if (USAGE_A..USAGE_A + 26).contains(&id) {
+        let offset = (id - USAGE_A) as u8;
+        let base = if shifted { b'A' } else { b'a' };
+        return Some(char::from(base + offset));
+    }
+    if !shifted
+        && let Some(&(_, control)) = US_CONTROL_KEYS.iter().find(|(control, _)| *control == id)
+    {
+        return Some(control);
+    }
+    US_KEYS
+        .iter()
+        .find(|(key, _, _)| *key == id)
+        .map(|&(_, plain, shifted_char)| if shifted { shifted_char } else { plain })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keycode;
+
+    #[test]
+    fn maps_printable_ascii_both_ways() {
+        assert_eq!(char_to_usage('a'), Some(HidUsage::from(Keycode::A)));
+        assert_eq!(
+            char_to_usage('A'),
+            Some(HidUsage::from(Keycode::A).with_lshift())
+        );
+        assert_eq!(
+            char_to_usage('{'),
+            Some(HidUsage::from_parts(HID_USAGE_KEYBOARD, 0x2F, MOD_LSFT))
+        );
+        assert_eq!(char_to_usage('\n'), Some(HidUsage::from(Keycode::RETURN)));
+        assert_eq!(char_to_usage('é'), None);
+
+        for c in (' '..='~').chain(['\n', '\t']) {
+            let usage = char_to_usage(c).expect("printable ASCII is mapped");
+            assert_eq!(usage_to_char(usage), Some(c), "{c:?}");
+        }
+        assert_eq!(
+            usage_to_char(HidUsage::from(Keycode::NUMBER_1).with_rshift()),
+            Some('!')
+        );
+        assert_eq!(usage_to_char(HidUsage::from(Keycode::A).with_lctrl()), None);
+        assert_eq!(usage_to_char(HidUsage::from(Keycode::F1)), None);
+    }
+}
@@ -1,16 +1,50 @@
-use std::collections::{HashMap, VecDeque};
-use std::io::{Read, Write};
-
-use crate::binding::{Behavior, BehaviorRole, role_from_display_name};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+use crate::annotations::{AnnotationError, AnnotationStore, KeyAnnotations};
+use crate::binding::{Behavior, BehaviorCatalog, BehaviorRole, describe_param_value};
+use crate::events::{ChangeListeners, KeymapChange, Listeners};
 use crate::framing::FrameDecoder;
-use crate::hid_usage::HidUsage;
+use crate::history::{HistoryError, HistoryStore, KeymapRevision, RollbackReport};
+use crate::keymap::{
+    ApplyKeymapReport, KeymapFingerprint, KeymapValidationError, KeymapView, LayerRemapReport,
+    LayerScope, RemappedBinding, TypedKeymap, TypedLayer, index_after_move, index_after_removal,
+    referenced_layer, remap_layer_reference,
+};
+#[cfg(feature = "json")]
+use crate::keymap_export::{ImportOptions, ImportReport, KeymapDocument, UnsupportedBinding};
+use crate::notification::{Notification, NotificationFilter};
+use crate::privacy::SerialRedaction;
 use crate::proto::zmk;
 use crate::proto::zmk::studio;
-use crate::protocol::{ProtocolError, decode_responses, encode_request};
+use crate::protocol::{
+    ProtocolError, decode_responses_with_frames, encode_request, request_response_bytes,
+};
+use crate::queue::{BoundedQueue, OverflowPolicy, QueueFull};
+use crate::snapshot::SnapshotParseError;
+use crate::sync::{
+    SyncAction, SyncDifference, SyncReport, diff_snapshots, pull_difference, read_snapshot_file,
+};
+use crate::transcript::Transcript;
+use crate::transport::Transport;
 #[cfg(feature = "ble")]
-use crate::transport::ble::{BleDeviceInfo, BleTransport, BleTransportError};
+use crate::transport::ble::{BleDeviceInfo, BleTransport, BleTransportError, SavedBleDevice};
 #[cfg(feature = "serial")]
-use crate::transport::serial::{SerialTransport, SerialTransportError};
+use crate::transport::serial::{SerialOptions, SerialTransport, SerialTransportError};
+#[cfg(feature = "sim")]
+use crate::transport::sim::{SimTransport, SimTransportError};
+#[cfg(feature = "tcp")]
+use crate::transport::tcp::{TcpTransport, TcpTransportError};
+#[cfg(feature = "usb")]
+use crate::transport::usb::{UsbDeviceInfo, UsbTransport, UsbTransportError};
+
+/// Timed-out requests whose late responses are still skipped; older ones are forgotten.
+const MAX_ABANDONED_REQUESTS: usize = 16;
+
+/// [`ClientError::UnknownEnumValue`] field naming a meta error condition.
+const META_CONDITION_FIELD: &str = "meta.simple_error";
 
 /// High-level error type returned by [`StudioClient`] operations.
 #[derive(Debug)]
@@ -22,8 +56,14 @@ pub enum ClientError {
     MissingResponseType,
     MissingSubsystem,
     UnexpectedSubsystem(&'static str),
-    UnexpectedRequestId { expected: u32, actual: u32 },
-    UnknownEnumValue { field: &'static str, value: i32 },
+    UnexpectedRequestId {
+        expected: u32,
+        actual: u32,
+    },
+    UnknownEnumValue {
+        field: &'static str,
+        value: i32,
+    },
     SetLayerBindingFailed(zmk::keymap::SetLayerBindingResponse),
     SaveChangesFailed(zmk::keymap::SaveChangesErrorCode),
     SetActivePhysicalLayoutFailed(zmk::keymap::SetActivePhysicalLayoutErrorCode),
@@ -32,9 +72,37 @@ pub enum ClientError {
     RemoveLayerFailed(zmk::keymap::RemoveLayerErrorCode),
     RestoreLayerFailed(zmk::keymap::RestoreLayerErrorCode),
     SetLayerPropsFailed(zmk::keymap::SetLayerPropsResponse),
-    InvalidLayerOrPosition { layer_id: u32, key_position: i32 },
+    InvalidLayerOrPosition {
+        layer_id: u32,
+        key_position: i32,
+    },
+    /// No layer on the device has this name.
+    UnknownLayerName(String),
     MissingBehaviorRole(&'static str),
-    BehaviorIdOutOfRange { behavior_id: u32 },
+    BehaviorIdOutOfRange {
+        behavior_id: u32,
+    },
+    /// A binding parameter is outside the values the firmware reports for its behavior.
+    InvalidBehaviorParam {
+        /// Display name of the behavior.
+        behavior: String,
+        /// Which parameter was rejected, 1 or 2.
+        param: u8,
+        value: u32,
+        /// Descriptions of the values the parameter accepts.
+        allowed: Vec<zmk::behaviors::BehaviorParameterValueDescription>,
+    },
+    History(HistoryError),
+    Annotation(AnnotationError),
+    SnapshotParse(SnapshotParseError),
+    InvalidKeymap(KeymapValidationError),
+    /// A queue configured with [`OverflowPolicy::Error`] was full.
+    QueueFull(&'static str),
+    /// No response arrived within the request timeout.
+    Timeout,
+    /// The transport lost its connection to the device, named by
+    /// [`Transport::description`]. See [`StudioClient::reconnect`].
+    Disconnected(String),
 }
 
 impl std::fmt::Display for ClientError {
@@ -89,12 +157,90 @@ impl std::fmt::Display for ClientError {
                 f,
                 "Invalid layer/position: layer_id={layer_id}, key_position={key_position}"
             ),
+            Self::UnknownLayerName(name) => write!(f, "No layer named {name:?}"),
             Self::MissingBehaviorRole(role) => {
                 write!(f, "Missing required behavior role in firmware: {role}")
             }
             Self::BehaviorIdOutOfRange { behavior_id } => {
                 write!(f, "Behavior ID is out of i32 range: {behavior_id}")
             }
+            Self::InvalidBehaviorParam {
+                behavior,
+                param,
+                value,
+                allowed,
+            } => {
+                let allowed: Vec<_> = allowed.iter().map(describe_param_value).collect();
+                write!(
+                    f,
+                    "Invalid param{param} for {behavior}: {value:#x} (allowed: {})",
+                    allowed.join(", ")
+                )
+            }
+            Self::History(err) => write!(f, "History error: {err}"),
+            Self::Annotation(err) => write!(f, "{err}"),
+            Self::SnapshotParse(err) => write!(f, "{err}"),
+            Self::InvalidKeymap(err) => write!(f, "Invalid keymap: {err}"),
+            Self::QueueFull(queue) => write!(f, "The {queue} queue is full"),
+            Self::Timeout => write!(f, "Timed out waiting for a response"),
+            Self::Disconnected(device) => write!(f, "Disconnected from {device}"),
+        }
+    }
+}
+
+impl ClientError {
+    /// Whether the device refused the request until it is unlocked, e.g. with
+    /// `&studio_unlock`.
+    pub fn is_locked(&self) -> bool {
+        matches!(self, Self::Meta(zmk::meta::ErrorConditions::UnlockRequired))
+    }
+
+    /// Whether no response arrived in time, either within the request timeout
+    /// or within a transport read timeout.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout => true,
+            Self::Io(err) => is_read_timeout(err),
+            _ => false,
+        }
+    }
+
+    /// Whether sending the same request again may succeed: a timeout, an
+    /// interrupted read, or a frame garbled in either direction. A lost
+    /// connection is not transient; see [`StudioClient::reconnect`].
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Io(err) => is_read_timeout(err) || err.kind() == std::io::ErrorKind::Interrupted,
+            Self::Protocol(_) | Self::UnexpectedRequestId { .. } => true,
+            Self::Meta(condition) => matches!(
+                condition,
+                zmk::meta::ErrorConditions::MsgDecodeFailed
+                    | zmk::meta::ErrorConditions::MsgEncodeFailed
+            ),
+            _ => self.is_timeout(),
+        }
+    }
+
+    /// Whether the firmware lacks the requested RPC or a behavior the
+    /// operation needs.
+    pub fn is_unsupported(&self) -> bool {
+        matches!(
+            self,
+            Self::Meta(zmk::meta::ErrorConditions::RpcNotFound) | Self::MissingBehaviorRole(_)
+        )
+    }
+
+    /// The device's meta error condition as sent, including values newer
+    /// than the bundled protos, which are reported as
+    /// [`ClientError::UnknownEnumValue`].
+    pub fn meta_condition(&self) -> Option<i32> {
+        match self {
+            Self::Meta(condition) => Some(*condition as i32),
+            Self::UnknownEnumValue {
+                field: META_CONDITION_FIELD,
+                value,
+            } => Some(*value),
+            _ => None,
         }
     }
 }
@@ -104,6 +250,10 @@ impl std::error::Error for ClientError {
         match self {
             Self::Io(err) => Some(err),
             Self::Protocol(err) => Some(err),
+            Self::History(err) => Some(err),
+            Self::Annotation(err) => Some(err),
+            Self::SnapshotParse(err) => Some(err),
+            Self::InvalidKeymap(err) => Some(err),
             _ => None,
         }
     }
@@ -121,24 +271,167 @@ impl From<ProtocolError> for ClientError {
     }
 }
 
+impl From<HistoryError> for ClientError {
+    fn from(value: HistoryError) -> Self {
+        Self::History(value)
+    }
+}
+
+impl From<AnnotationError> for ClientError {
+    fn from(value: AnnotationError) -> Self {
+        Self::Annotation(value)
+    }
+}
+
+impl From<SnapshotParseError> for ClientError {
+    fn from(value: SnapshotParseError) -> Self {
+        Self::SnapshotParse(value)
+    }
+}
+
+impl From<KeymapValidationError> for ClientError {
+    fn from(value: KeymapValidationError) -> Self {
+        Self::InvalidKeymap(value)
+    }
+}
+
+/// What [`StudioClient::close`] and dropping the client do with changes staged through it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsavedChangesPolicy {
+    /// Leave changes staged on the device.
+    #[default]
+    Keep,
+    Discard,
+    Save,
+}
+
+/// How layer-targeting bindings (`&mo`, `&lt`, `&to`, ...) refer to layers on this device.
+///
+/// Decides whether [`StudioClient::move_layer`] and [`StudioClient::remove_layer`]
+/// rewrite those bindings afterwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayerReferenceMode {
+    /// Parameters are stable layer IDs; moves and removals leave bindings alone.
+    #[default]
+    Ids,
+    /// Parameters are layer indices; bindings are rewritten to follow their
+    /// target layer, and the changes are reported by
+    /// [`StudioClient::last_layer_remap_report`].
+    Indices,
+}
+
+/// How [`StudioClient`] retries requests that fail transiently; see
+/// [`StudioClient::set_retry_policy`].
+///
+/// The delay before retrying starts at `initial_backoff` and doubles after
+/// every failed attempt, up to `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Also retry requests that change more when repeated: adding, moving,
+    /// removing and restoring layers, saving or discarding changes, and
+    /// resetting settings. If only the response was lost, a retry applies
+    /// such a request twice.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+
+    /// Whether `subsystem` may be sent again after a failed attempt.
+    fn allows(&self, subsystem: &studio::request::Subsystem) -> bool {
+        self.retry_non_idempotent || is_idempotent(subsystem)
+    }
+}
+
+/// A layer removed through [`StudioClient::remove_layer`] during this session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemovedLayer {
+    pub id: u32,
+    pub name: String,
+    /// Index the layer had before it was removed.
+    pub index: u32,
+    /// Bindings captured just before removal.
+    pub bindings: Vec<zmk::keymap::BehaviorBinding>,
+}
+
+/// Device identity returned by [`StudioClient::device_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeviceInfo {
+    pub name: String,
+    /// Serial number as lowercase hex, redacted per [`StudioClient::set_serial_redaction`].
+    pub serial_number: String,
+}
+
+/// Response returned by [`StudioClient::call_raw`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawResponse {
+    /// The response as far as this crate's protos decode it.
+    pub response: studio::RequestResponse,
+    /// The encoded `zmk.studio.RequestResponse` as received, including
+    /// fields the protos do not know.
+    pub bytes: Vec<u8>,
+}
+
 /// High-level synchronous ZMK Studio RPC client.
 ///
-/// The generic parameter `T` is any transport implementing [`Read`] + [`Write`]
-/// (for example [`crate::transport::serial::SerialTransport`]).
-pub struct StudioClient<T> {
+/// The generic parameter `T` is any [`Transport`] (for example
+/// [`crate::transport::serial::SerialTransport`]).
+pub struct StudioClient<T: Transport> {
     io: T,
     next_request_id: u32,
     decoder: FrameDecoder,
     read_buffer: Vec<u8>,
-    responses: VecDeque<studio::Response>,
-    notifications: VecDeque<studio::Notification>,
-    behavior_role_by_id: HashMap<u32, BehaviorRole>,
-    behavior_id_by_role: HashMap<BehaviorRole, u32>,
+    /// Responses read ahead, each with the frame it was decoded from.
+    responses: BoundedQueue<(studio::Response, Vec<u8>)>,
+    /// Responses decoded after the response queue refused one, kept for the next receive.
+    undelivered: VecDeque<(studio::Response, Vec<u8>)>,
+    notifications: BoundedQueue<studio::Notification>,
+    notification_filter: NotificationFilter,
+    behavior_catalog: BehaviorCatalog,
+    physical_layouts: Option<zmk::keymap::PhysicalLayouts>,
+    keymap: Option<zmk::keymap::Keymap>,
+    history: Option<HistoryStore>,
+    serial_redaction: SerialRedaction,
+    transcript: Option<Transcript>,
+    auto_save_delay: Option<Duration>,
+    last_mutation: Option<Instant>,
+    staged_changes: bool,
+    unsaved_changes_policy: UnsavedChangesPolicy,
+    removed_layers: Vec<RemovedLayer>,
+    layer_reference_mode: LayerReferenceMode,
+    last_layer_remap: Option<LayerRemapReport>,
+    change_listeners: ChangeListeners,
+    notification_listeners: Listeners<Notification>,
+    request_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    abandoned_requests: Vec<u32>,
 }
 
-impl<T: Read + Write> StudioClient<T> {
+impl<T: Transport> StudioClient<T> {
+    /// Wraps `io`, reading [`Transport::read_chunk_size`] bytes at a time.
     pub fn new(io: T) -> Self {
-        Self::with_read_buffer(io, 256)
+        let read_buffer_size = io.read_chunk_size();
+        Self::with_read_buffer(io, read_buffer_size)
     }
 
     fn with_read_buffer(io: T, read_buffer_size: usize) -> Self {
@@ -147,16 +440,301 @@ impl<T: Read + Write> StudioClient<T> {
             next_request_id: 0,
             decoder: FrameDecoder::new(),
             read_buffer: vec![0; read_buffer_size.max(1)],
-            responses: VecDeque::new(),
-            notifications: VecDeque::new(),
-            behavior_role_by_id: HashMap::new(),
-            behavior_id_by_role: HashMap::new(),
+            responses: BoundedQueue::unbounded(),
+            undelivered: VecDeque::new(),
+            notifications: BoundedQueue::unbounded(),
+            notification_filter: NotificationFilter::all(),
+            behavior_catalog: BehaviorCatalog::new(),
+            physical_layouts: None,
+            keymap: None,
+            history: None,
+            serial_redaction: SerialRedaction::None,
+            transcript: None,
+            auto_save_delay: None,
+            last_mutation: None,
+            staged_changes: false,
+            unsaved_changes_policy: UnsavedChangesPolicy::Keep,
+            removed_layers: Vec::new(),
+            layer_reference_mode: LayerReferenceMode::Ids,
+            last_layer_remap: None,
+            change_listeners: ChangeListeners::default(),
+            notification_listeners: Listeners::default(),
+            request_timeout: None,
+            retry_policy: None,
+            abandoned_requests: Vec::new(),
+        }
+    }
+
+    /// Sets how the device serial number is rendered by [`StudioClient::device_serial`]
+    /// and integrations built on this client.
+    pub fn set_serial_redaction(&mut self, redaction: SerialRedaction) {
+        self.serial_redaction = redaction;
+    }
+
+    /// Returns the configured serial number redaction mode.
+    pub fn serial_redaction(&self) -> SerialRedaction {
+        self.serial_redaction
+    }
+
+    /// Enables (or with `None`, disables) keymap snapshots after every successful save.
+    pub fn set_history_store(&mut self, store: Option<HistoryStore>) {
+        self.history = store;
+    }
+
+    /// Returns the attached history store, if any.
+    pub fn history_store(&self) -> Option<&HistoryStore> {
+        self.history.as_ref()
+    }
+
+    /// Starts recording every request, response and notification as decoded text.
+    ///
+    /// Replaces any transcript already being recorded. Serial numbers in the
+    /// transcript are rendered with `redaction`.
+    pub fn start_transcript(&mut self, redaction: SerialRedaction) {
+        self.transcript = Some(Transcript::new(redaction));
+    }
+
+    /// Returns the transcript being recorded, if any.
+    pub fn transcript(&self) -> Option<&Transcript> {
+        self.transcript.as_ref()
+    }
+
+    /// Stops recording and returns the transcript.
+    pub fn take_transcript(&mut self) -> Option<Transcript> {
+        self.transcript.take()
+    }
+
+    /// Bounds how long each request waits for its response (`None` to wait indefinitely).
+    ///
+    /// The transport's own read timeout must be shorter than `timeout`: reads
+    /// that time out are retried until the deadline, after which the request
+    /// fails with [`ClientError::Timeout`]. A response that arrives after that
+    /// is discarded, so later requests are unaffected. Without a request
+    /// timeout, the first transport read timeout fails the request.
+    pub fn set_request_timeout(&mut self, timeout: Option<Duration>) {
+        self.request_timeout = timeout;
+    }
+
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Retries requests that fail with a [transient](ClientError::is_transient)
+    /// error, such as a timeout or a garbled frame, according to `policy`.
+    /// Off (`None`) by default.
+    ///
+    /// Batched binding writes ([`StudioClient::set_keys`]) and
+    /// [`StudioClient::call_raw`] are not retried. Without a request timeout,
+    /// the transport's read timeout decides when an attempt has failed.
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.retry_policy = policy;
+    }
+
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy
+    }
+
+    /// Describes the device this client talks to, for example `serial /dev/ttyACM0`.
+    pub fn transport_description(&self) -> String {
+        self.io.description()
+    }
+
+    /// Returns whether the transport still believes it is connected.
+    ///
+    /// Once this is `false`, requests fail with [`ClientError::Disconnected`]
+    /// until [`StudioClient::reconnect`] succeeds.
+    pub fn is_connected(&self) -> bool {
+        self.io.is_connected()
+    }
+
+    /// Reopens the transport to the same device and drops all cached state.
+    ///
+    /// Pending responses and the cached keymap and physical layouts are
+    /// discarded, since the device may have restarted in the meantime.
+    pub fn reconnect(&mut self) -> Result<(), ClientError> {
+        self.io.reconnect()?;
+        #[cfg(feature = "tracing")]
+        tracing::info!(transport = %self.io.description(), "reconnected");
+        self.decoder = FrameDecoder::new();
+        while self.responses.pop().is_some() {}
+        self.abandoned_requests.clear();
+        self.keymap = None;
+        self.physical_layouts = None;
+        Ok(())
+    }
+
+    /// Sets what happens to changes staged through this client when it is closed or dropped.
+    ///
+    /// Only changes made through this client since its last save or discard
+    /// are considered. On drop the policy is applied best-effort and errors
+    /// are ignored; use [`StudioClient::close`] to observe them.
+    pub fn set_unsaved_changes_policy(&mut self, policy: UnsavedChangesPolicy) {
+        self.unsaved_changes_policy = policy;
+    }
+
+    pub fn unsaved_changes_policy(&self) -> UnsavedChangesPolicy {
+        self.unsaved_changes_policy
+    }
+
+    /// Sets how layer-targeting bindings refer to layers; see [`LayerReferenceMode`].
+    pub fn set_layer_reference_mode(&mut self, mode: LayerReferenceMode) {
+        self.layer_reference_mode = mode;
+    }
+
+    pub fn layer_reference_mode(&self) -> LayerReferenceMode {
+        self.layer_reference_mode
+    }
+
+    /// Bindings rewritten by the last [`StudioClient::move_layer`] or
+    /// [`StudioClient::remove_layer`] in [`LayerReferenceMode::Indices`] mode.
+    pub fn last_layer_remap_report(&self) -> Option<&LayerRemapReport> {
+        self.last_layer_remap.as_ref()
+    }
+
+    /// Calls `listener` with every [`KeymapChange`] made through this client.
+    pub fn add_change_listener(
+        &mut self,
+        mut listener: impl FnMut(&KeymapChange) + Send + 'static,
+    ) {
+        self.change_listeners.add(move |change| {
+            listener(change);
+            true
+        });
+    }
+
+    /// Returns a channel receiving every [`KeymapChange`] made through this client.
+    ///
+    /// Dropping the receiver unsubscribes it.
+    pub fn subscribe_changes(&mut self) -> Receiver<KeymapChange> {
+        self.change_listeners.subscribe()
+    }
+
+    /// Calls `listener` with every device [`Notification`] that passes the
+    /// [`NotificationFilter`], as soon as it is read from the transport.
+    ///
+    /// Notifications are read while waiting for responses and by
+    /// [`StudioClient::poll_notifications`]; they are still queued for
+    /// [`StudioClient::next_notification`] as well.
+    pub fn add_notification_listener(
+        &mut self,
+        mut listener: impl FnMut(&Notification) + Send + 'static,
+    ) {
+        self.notification_listeners.add(move |notification| {
+            listener(notification);
+            true
+        });
+    }
+
+    /// Returns a channel receiving every device [`Notification`], like
+    /// [`StudioClient::add_notification_listener`].
+    ///
+    /// Dropping the receiver unsubscribes it.
+    pub fn subscribe_notifications(&mut self) -> Receiver<Notification> {
+        self.notification_listeners.subscribe()
+    }
+
+    /// Applies the [`UnsavedChangesPolicy`] and closes the client.
+    pub fn close(mut self) -> Result<(), ClientError> {
+        let result = self.apply_unsaved_changes_policy();
+        // Already applied; don't repeat it on drop.
+        self.unsaved_changes_policy = UnsavedChangesPolicy::Keep;
+        result
+    }
+
+    fn apply_unsaved_changes_policy(&mut self) -> Result<(), ClientError> {
+        if !self.staged_changes {
+            return Ok(());
+        }
+        match self.unsaved_changes_policy {
+            UnsavedChangesPolicy::Keep => Ok(()),
+            UnsavedChangesPolicy::Discard => self.discard_changes().map(|_| ()),
+            UnsavedChangesPolicy::Save => self.save_changes(),
+        }
+    }
+
+    /// Saves staged changes automatically once `delay` has passed without further mutations.
+    ///
+    /// The client has no background thread: the save happens on the next
+    /// keymap request or [`StudioClient::poll_auto_save`] call after the delay.
+    /// Idle loops should call `poll_auto_save` periodically. `None` disables
+    /// auto-save and cancels any pending save.
+    pub fn set_auto_save(&mut self, delay: Option<Duration>) {
+        self.auto_save_delay = delay;
+        if delay.is_none() {
+            self.last_mutation = None;
+        }
+    }
+
+    /// Saves staged changes if the auto-save delay has elapsed.
+    ///
+    /// Returns `true` if a save was performed.
+    pub fn poll_auto_save(&mut self) -> Result<bool, ClientError> {
+        if self.auto_save_due_in() != Some(Duration::ZERO) {
+            return Ok(false);
         }
+
+        let last_mutation = self.last_mutation.take();
+        if let Err(err) = self.save_changes_with_description("Auto-saved changes") {
+            self.last_mutation = last_mutation;
+            return Err(err);
+        }
+        Ok(true)
+    }
+
+    /// Returns the time left until the pending auto-save, if one is pending.
+    pub fn auto_save_due_in(&self) -> Option<Duration> {
+        let delay = self.auto_save_delay?;
+        let last_mutation = self.last_mutation?;
+        Some(delay.saturating_sub(last_mutation.elapsed()))
+    }
+
+    /// Cancels the pending auto-save, leaving changes staged.
+    ///
+    /// The next mutation schedules a new auto-save.
+    pub fn cancel_auto_save(&mut self) {
+        self.last_mutation = None;
+    }
+
+    /// Limits how many undrained notifications are kept (`None` for no limit).
+    ///
+    /// Notifications received while waiting for a response are queued until
+    /// read with [`StudioClient::next_notification`]. Applications that never
+    /// drain them should set a limit.
+    pub fn set_notification_queue_limit(
+        &mut self,
+        capacity: Option<usize>,
+        policy: OverflowPolicy,
+    ) {
+        self.notifications.set_limit(capacity, policy);
+    }
+
+    /// Limits how many decoded but not yet consumed responses are kept (`None` for no limit).
+    pub fn set_response_queue_limit(&mut self, capacity: Option<usize>, policy: OverflowPolicy) {
+        self.responses.set_limit(capacity, policy);
+    }
+
+    /// Number of notifications discarded because the notification queue was full.
+    pub fn dropped_notifications(&self) -> u64 {
+        self.notifications.dropped()
+    }
+
+    /// Number of responses discarded because the response queue was full.
+    pub fn dropped_responses(&self) -> u64 {
+        self.responses.dropped()
+    }
+
+    /// Selects which notification kinds are kept; others are discarded before queuing.
+    pub fn set_notification_filter(&mut self, filter: NotificationFilter) {
+        self.notification_filter = filter;
+    }
+
+    pub fn notification_filter(&self) -> NotificationFilter {
+        self.notification_filter
     }
 
     /// Returns the next queued notification, if any.
     pub fn next_notification(&mut self) -> Option<studio::Notification> {
-        self.notifications.pop_front()
+        self.notifications.pop()
     }
 
     /// Blocks until a notification arrives and returns it.
@@ -166,10 +744,72 @@ impl<T: Read + Write> StudioClient<T> {
                 return Ok(notification);
             }
 
-            let _ = self.read_next_response()?;
+            let (response, frame) = self.read_next_response()?;
+            if let Some(studio::response::Type::Notification(notification)) = response.r#type
+                && self.notification_filter.accepts_notification(&notification)
+            {
+                self.notification_listeners
+                    .emit(Notification::from_frame(notification, &frame));
+                return Ok(notification);
+            }
+        }
+    }
+
+    /// Reads whatever the device has sent without issuing a request, and
+    /// delivers the notifications to listeners and the notification queue.
+    ///
+    /// Returns how many notifications were read once the transport has nothing
+    /// more to offer (a read times out or would block). Call it periodically, or
+    /// from an event loop, to react to device-side events while otherwise idle.
+    pub fn poll_notifications(&mut self) -> Result<usize, ClientError> {
+        let mut received = 0;
+        loop {
+            match self.read_next_response() {
+                Ok((
+                    studio::Response {
+                        r#type: Some(studio::response::Type::Notification(notification)),
+                    },
+                    frame,
+                )) => {
+                    if self.accept_notification(notification, &frame)? {
+                        received += 1;
+                    }
+                }
+                // Responses to requests abandoned after an error are dropped.
+                Ok(_) => {}
+                Err(ClientError::Io(err)) if is_read_timeout(&err) => return Ok(received),
+                Err(err) => return Err(err),
+            }
         }
     }
 
+    /// Filters, announces and queues a notification that arrived in `frame`;
+    /// returns whether it was kept.
+    fn accept_notification(
+        &mut self,
+        notification: studio::Notification,
+        frame: &[u8],
+    ) -> Result<bool, ClientError> {
+        if let Some(studio::notification::Subsystem::Keymap(zmk::keymap::Notification {
+            notification_type:
+                Some(zmk::keymap::notification::NotificationType::UnsavedChangesStatusChanged(unsaved)),
+        })) = &notification.subsystem
+            && *unsaved != self.staged_changes
+        {
+            // Someone else changed, saved or discarded the keymap.
+            self.keymap = None;
+        }
+        if !self.notification_filter.accepts_notification(&notification) {
+            return Ok(false);
+        }
+        self.notification_listeners
+            .emit(Notification::from_frame(notification, frame));
+        self.notifications
+            .push(notification)
+            .map_err(|_| ClientError::QueueFull("notification"))?;
+        Ok(true)
+    }
+
     /// Returns static device information.
     pub fn get_device_info(&mut self) -> Result<zmk::core::GetDeviceInfoResponse, ClientError> {
         let response = self.call_core(zmk::core::request::RequestType::GetDeviceInfo(true))?;
@@ -179,6 +819,20 @@ impl<T: Read + Write> StudioClient<T> {
         }
     }
 
+    /// Returns the device name and serial number, decoded from [`StudioClient::get_device_info`].
+    pub fn device_info(&mut self) -> Result<DeviceInfo, ClientError> {
+        let info = self.get_device_info()?;
+        Ok(DeviceInfo {
+            name: info.name,
+            serial_number: self.serial_redaction.apply(&info.serial_number),
+        })
+    }
+
+    /// Returns the device serial number as hex, redacted per [`StudioClient::set_serial_redaction`].
+    pub fn device_serial(&mut self) -> Result<String, ClientError> {
+        Ok(self.device_info()?.serial_number)
+    }
+
     /// Returns the current Studio lock state.
     pub fn get_lock_state(&mut self) -> Result<zmk::core::LockState, ClientError> {
         let response = self.call_core(zmk::core::request::RequestType::GetLockState(true))?;
@@ -199,7 +853,11 @@ impl<T: Read + Write> StudioClient<T> {
     pub fn reset_settings(&mut self) -> Result<bool, ClientError> {
         let response = self.call_core(zmk::core::request::RequestType::ResetSettings(true))?;
         match response.response_type {
-            Some(zmk::core::response::ResponseType::ResetSettings(ok)) => Ok(ok),
+            Some(zmk::core::response::ResponseType::ResetSettings(ok)) => {
+                self.physical_layouts = None;
+                self.keymap = None;
+                Ok(ok)
+            }
             _ => Err(ClientError::MissingResponseType),
         }
     }
@@ -233,25 +891,76 @@ impl<T: Read + Write> StudioClient<T> {
         }
     }
 
+    /// Returns the device's behaviors with their roles and parameter metadata.
+    ///
+    /// The catalog is fetched on first use and kept for the connection, since
+    /// behaviors are fixed for a firmware build.
+    pub fn behavior_catalog(&mut self) -> Result<&BehaviorCatalog, ClientError> {
+        self.ensure_behavior_catalog()?;
+        Ok(&self.behavior_catalog)
+    }
+
     /// Returns the current keymap state from the device.
+    ///
+    /// The keymap is fetched once and then kept up to date by this client's own
+    /// changes, so reading many keys costs a single request. The cache is
+    /// dropped when a change cannot be applied locally and when a notification
+    /// shows the device's unsaved changes diverged from this client's; call
+    /// [`StudioClient::refresh_keymap`] if another program may have changed it.
     pub fn get_keymap(&mut self) -> Result<zmk::keymap::Keymap, ClientError> {
+        if let Some(keymap) = &self.keymap {
+            return Ok(keymap.clone());
+        }
         let response = self.call_keymap(zmk::keymap::request::RequestType::GetKeymap(true))?;
         match response.response_type {
-            Some(zmk::keymap::response::ResponseType::GetKeymap(keymap)) => Ok(keymap),
+            Some(zmk::keymap::response::ResponseType::GetKeymap(keymap)) => {
+                self.keymap = Some(keymap.clone());
+                Ok(keymap)
+            }
             _ => Err(ClientError::MissingResponseType),
         }
     }
 
+    /// Drops the cached keymap and fetches it from the device again.
+    pub fn refresh_keymap(&mut self) -> Result<zmk::keymap::Keymap, ClientError> {
+        self.invalidate_keymap();
+        self.get_keymap()
+    }
+
+    /// Drops the cached [`StudioClient::get_keymap`] response, so the next read
+    /// fetches it from the device again.
+    pub fn invalidate_keymap(&mut self) {
+        self.keymap = None;
+    }
+
     /// Returns available physical layouts and the active layout index.
+    ///
+    /// The layouts are fixed for a firmware build, so the first response is cached
+    /// and served to later calls. The cache is dropped when the active layout may
+    /// have changed (see [`StudioClient::set_active_physical_layout`]); call
+    /// [`StudioClient::invalidate_physical_layouts`] after swapping firmware under
+    /// a live connection.
     pub fn get_physical_layouts(&mut self) -> Result<zmk::keymap::PhysicalLayouts, ClientError> {
+        if let Some(layouts) = &self.physical_layouts {
+            return Ok(layouts.clone());
+        }
         let response =
             self.call_keymap(zmk::keymap::request::RequestType::GetPhysicalLayouts(true))?;
         match response.response_type {
-            Some(zmk::keymap::response::ResponseType::GetPhysicalLayouts(layouts)) => Ok(layouts),
+            Some(zmk::keymap::response::ResponseType::GetPhysicalLayouts(layouts)) => {
+                self.physical_layouts = Some(layouts.clone());
+                Ok(layouts)
+            }
             _ => Err(ClientError::MissingResponseType),
         }
     }
 
+    /// Drops the cached [`StudioClient::get_physical_layouts`] response, so the next call
+    /// fetches it from the device again.
+    pub fn invalidate_physical_layouts(&mut self) {
+        self.physical_layouts = None;
+    }
+
     /// Sets a raw behavior binding for a specific layer position.
     pub fn set_layer_binding(
         &mut self,
@@ -267,7 +976,17 @@ impl<T: Read + Write> StudioClient<T> {
 
         let response =
             self.call_keymap(zmk::keymap::request::RequestType::SetLayerBinding(request))?;
+        self.finish_set_layer_binding(layer_id, key_position, binding, response)
+    }
 
+    /// Interprets a `SetLayerBinding` response and announces the change.
+    fn finish_set_layer_binding(
+        &mut self,
+        layer_id: u32,
+        key_position: i32,
+        binding: zmk::keymap::BehaviorBinding,
+        response: zmk::keymap::Response,
+    ) -> Result<(), ClientError> {
         match response.response_type {
             Some(zmk::keymap::response::ResponseType::SetLayerBinding(raw)) => {
                 let code = zmk::keymap::SetLayerBindingResponse::try_from(raw).map_err(|_| {
@@ -278,6 +997,19 @@ impl<T: Read + Write> StudioClient<T> {
                 })?;
 
                 if code == zmk::keymap::SetLayerBindingResponse::SetLayerBindingRespOk {
+                    if let Some(slot) = self
+                        .keymap
+                        .as_mut()
+                        .and_then(|keymap| binding_slot(keymap, layer_id, key_position))
+                    {
+                        *slot = binding;
+                    }
+                    let behavior = self.resolve_binding(&binding);
+                    self.change_listeners.emit(KeymapChange::KeySet {
+                        layer_id,
+                        position: key_position,
+                        behavior,
+                    });
                     Ok(())
                 } else {
                     Err(ClientError::SetLayerBindingFailed(code))
@@ -306,6 +1038,30 @@ impl<T: Read + Write> StudioClient<T> {
         Ok(self.resolve_binding(&binding))
     }
 
+    /// Returns the ID of the first layer named `name`.
+    ///
+    /// Names are matched exactly. The lookup uses the cached keymap, so it
+    /// only costs a request the first time.
+    pub fn layer_id_by_name(&mut self, name: &str) -> Result<u32, ClientError> {
+        let keymap = self.get_keymap()?;
+        keymap
+            .layers
+            .iter()
+            .find(|layer| layer.name == name)
+            .map(|layer| layer.id)
+            .ok_or_else(|| ClientError::UnknownLayerName(name.to_string()))
+    }
+
+    /// Reads a behavior from a key position on the layer named `layer_name`.
+    pub fn get_key_in_layer(
+        &mut self,
+        layer_name: &str,
+        key_position: i32,
+    ) -> Result<Behavior, ClientError> {
+        let layer_id = self.layer_id_by_name(layer_name)?;
+        self.get_key_at(layer_id, key_position)
+    }
+
     /// Fetches the keymap and resolves every binding into a typed [`Behavior`].
     ///
     /// Returns a `Vec` of layers, each layer being a `Vec<Behavior>` matching
@@ -330,87 +1086,410 @@ impl<T: Read + Write> StudioClient<T> {
         Ok(layers)
     }
 
-    fn resolve_binding(&self, binding: &zmk::keymap::BehaviorBinding) -> Behavior {
-        let Ok(binding_behavior_id) = u32::try_from(binding.behavior_id) else {
-            return Behavior::Unknown {
-                behavior_id: binding.behavior_id,
-                param1: binding.param1,
-                param2: binding.param2,
-            };
+    /// Reads the keymap with every binding resolved to a typed [`Behavior`].
+    ///
+    /// Its snapshot text form ([`TypedKeymap::to_snapshot_text`]) is suitable
+    /// for committing to version control.
+    pub fn get_typed_keymap(&mut self) -> Result<TypedKeymap, ClientError> {
+        self.ensure_behavior_catalog()?;
+        let keymap = self.get_keymap()?;
+
+        let layers = keymap
+            .layers
+            .iter()
+            .map(|layer| TypedLayer {
+                id: layer.id,
+                name: layer.name.clone(),
+                bindings: layer
+                    .bindings
+                    .iter()
+                    .map(|binding| self.resolve_binding(binding))
+                    .collect(),
+            })
+            .collect();
+
+        Ok(TypedKeymap { layers })
+    }
+
+    /// Returns the typed keymap joined with the active physical layout's key geometry.
+    ///
+    /// Fails with [`ClientError::MissingResponseType`] if the device reports no
+    /// active layout, and with [`ClientError::InvalidKeymap`] if a layer's
+    /// binding count does not match the layout.
+    pub fn get_keymap_view(&mut self) -> Result<KeymapView, ClientError> {
+        let keymap = self.get_typed_keymap()?;
+        let layouts = self.get_physical_layouts()?;
+        let layout = usize::try_from(layouts.active_layout_index)
+            .ok()
+            .and_then(|index| layouts.layouts.get(index))
+            .ok_or(ClientError::MissingResponseType)?;
+        Ok(KeymapView::new(
+            &keymap,
+            layouts.active_layout_index,
+            layout,
+        )?)
+    }
+
+    /// Loads this keyboard's notes from `store`, keyed by its serial number.
+    pub fn load_annotations(
+        &mut self,
+        store: &AnnotationStore,
+    ) -> Result<KeyAnnotations, ClientError> {
+        let info = self.get_device_info()?;
+        Ok(store.load(&info.serial_number)?)
+    }
+
+    /// Saves notes for this keyboard to `store`, keyed by its serial number.
+    pub fn save_annotations(
+        &mut self,
+        store: &AnnotationStore,
+        annotations: &KeyAnnotations,
+    ) -> Result<(), ClientError> {
+        let info = self.get_device_info()?;
+        Ok(store.save(&info.serial_number, annotations)?)
+    }
+
+    /// Like [`StudioClient::get_keymap_view`], with this keyboard's notes from
+    /// `store` filled into each [`crate::keymap::KeyView::annotation`].
+    pub fn get_annotated_keymap_view(
+        &mut self,
+        store: &AnnotationStore,
+    ) -> Result<KeymapView, ClientError> {
+        let annotations = self.load_annotations(store)?;
+        let mut view = self.get_keymap_view()?;
+        view.apply_annotations(&annotations);
+        Ok(view)
+    }
+
+    /// Exports the keymap as a [`KeymapDocument`], with behaviors named by
+    /// their firmware display names and keys by their ZMK keycode names.
+    #[cfg(feature = "json")]
+    pub fn export_keymap(&mut self) -> Result<KeymapDocument, ClientError> {
+        self.ensure_behavior_catalog()?;
+        let behavior_names = self
+            .behavior_catalog
+            .behaviors()
+            .map(|details| (details.id, details.display_name.clone()))
+            .collect();
+        let keymap = self.get_keymap()?;
+        Ok(KeymapDocument::from_keymap(
+            &keymap,
+            &behavior_names,
+            &self.behavior_catalog,
+        ))
+    }
+
+    /// Like [`StudioClient::export_keymap`], rendered as pretty-printed JSON.
+    #[cfg(feature = "json")]
+    pub fn export_keymap_json(&mut self) -> Result<String, ClientError> {
+        Ok(self.export_keymap()?.to_json())
+    }
+
+    /// Renders the keymap as a ZMK devicetree `keymap` node for a zmk-config
+    /// `.keymap` file, laid out in rows following the active physical layout.
+    ///
+    /// See [`keymap_export::to_devicetree`](crate::keymap_export::to_devicetree).
+    #[cfg(feature = "json")]
+    pub fn export_devicetree(&mut self) -> Result<String, ClientError> {
+        let keymap = self.get_typed_keymap()?;
+        let layouts = self.get_physical_layouts()?;
+        let layout = usize::try_from(layouts.active_layout_index)
+            .ok()
+            .and_then(|index| layouts.layouts.get(index));
+        Ok(crate::keymap_export::to_devicetree(
+            &keymap,
+            &self.behavior_catalog,
+            layout,
+        ))
+    }
+
+    /// Restores a keymap exported with [`StudioClient::export_keymap`] or read
+    /// from a `.keymap` file with [`crate::dts::parse_keymap`].
+    ///
+    /// Document layers are matched to device layers by ID, then to any
+    /// remaining device layers in order; missing layers are added, surplus
+    /// layers removed, and the rest moved into document order. Behaviors are
+    /// looked up by display name, or failing that by the role this crate
+    /// recognizes from the name, so the document survives firmware updates that
+    /// renumber them, and layer parameters are translated to the device's layer
+    /// IDs. Only names and bindings that differ are written. Every layer must
+    /// have one binding per device key; nothing is changed otherwise.
+    ///
+    /// Changes are staged unless [`ImportOptions::save`] is set.
+    #[cfg(feature = "json")]
+    pub fn import_keymap(
+        &mut self,
+        document: &KeymapDocument,
+        options: ImportOptions,
+    ) -> Result<ImportReport, ClientError> {
+        self.ensure_behavior_catalog()?;
+        let mut behavior_ids = std::collections::HashMap::new();
+        for details in self.behavior_catalog.behaviors() {
+            behavior_ids
+                .entry(details.display_name.clone())
+                .or_insert(details.id);
+        }
+
+        let device = self.get_keymap()?;
+        let key_count = device
+            .layers
+            .first()
+            .map_or(0, |layer| layer.bindings.len());
+        if let Some(layer) = document
+            .layers
+            .iter()
+            .find(|layer| layer.bindings.len() != key_count)
+        {
+            return Err(KeymapValidationError::BindingCount {
+                layer_id: layer.id,
+                expected: key_count,
+                actual: layer.bindings.len(),
+            }
+            .into());
+        }
+
+        let mut report = ImportReport::default();
+        let mut order: Vec<u32> = device.layers.iter().map(|layer| layer.id).collect();
+        let mut assigned: Vec<Option<u32>> = document
+            .layers
+            .iter()
+            .map(|layer| order.contains(&layer.id).then_some(layer.id))
+            .collect();
+        let mut spare: VecDeque<u32> = order
+            .iter()
+            .copied()
+            .filter(|id| !assigned.contains(&Some(*id)))
+            .collect();
+        for slot in assigned.iter_mut().filter(|slot| slot.is_none()) {
+            if let Some(id) = spare.pop_front() {
+                *slot = Some(id);
+                continue;
+            }
+            let layer = self
+                .add_layer()?
+                .layer
+                .ok_or(ClientError::MissingResponseType)?;
+            order.push(layer.id);
+            *slot = Some(layer.id);
+            report.layers_added += 1;
+        }
+        let assigned: Vec<u32> = assigned.into_iter().flatten().collect();
+
+        for id in spare.into_iter().rev() {
+            let index = order
+                .iter()
+                .position(|&layer_id| layer_id == id)
+                .expect("spare layers are on the device");
+            self.remove_layer(index as u32)?;
+            order.remove(index);
+            report.layers_removed += 1;
+        }
+        for (dest, id) in assigned.iter().enumerate() {
+            let start = order
+                .iter()
+                .position(|layer_id| layer_id == id)
+                .expect("assigned layers are on the device");
+            if start != dest {
+                self.move_layer(start as u32, dest as u32)?;
+                let layer_id = order.remove(start);
+                order.insert(dest, layer_id);
+                report.layers_moved += 1;
+            }
+        }
+
+        let device_layer_id = |layer_id: u32| {
+            document
+                .layers
+                .iter()
+                .position(|layer| layer.id == layer_id)
+                .map_or(layer_id, |index| assigned[index])
         };
-        let Some(role) = self.behavior_role_by_id.get(&binding_behavior_id).copied() else {
-            return Behavior::Unknown {
-                behavior_id: binding.behavior_id,
-                param1: binding.param1,
-                param2: binding.param2,
-            };
+        let current = self.get_keymap()?;
+        for (layer, &layer_id) in document.layers.iter().zip(&assigned) {
+            let current_layer = current
+                .layers
+                .iter()
+                .find(|current_layer| current_layer.id == layer_id)
+                .ok_or(ClientError::MissingResponseType)?;
+            if current_layer.name != layer.name {
+                self.set_layer_props(layer_id, layer.name.clone())?;
+                report.layers_renamed += 1;
+            }
+
+            for (position, binding) in layer.bindings.iter().enumerate() {
+                let behavior_id = match &binding.behavior {
+                    Some(name) => match behavior_ids.get(name).copied().or_else(|| {
+                        binding
+                            .role()
+                            .and_then(|role| self.behavior_catalog.id_for(role))
+                    }) {
+                        Some(id) => i32::try_from(id)
+                            .map_err(|_| ClientError::BehaviorIdOutOfRange { behavior_id: id })?,
+                        None => {
+                            report.unsupported.push(UnsupportedBinding {
+                                layer_id: layer.id,
+                                position,
+                                behavior: name.clone(),
+                            });
+                            continue;
+                        }
+                    },
+                    None => binding.behavior_id,
+                };
+                let param1 = if binding.references_layer() {
+                    device_layer_id(binding.param1)
+                } else {
+                    binding.param1
+                };
+                let raw = zmk::keymap::BehaviorBinding {
+                    behavior_id,
+                    param1,
+                    param2: binding.param2,
+                };
+                if current_layer.bindings.get(position) == Some(&raw) {
+                    continue;
+                }
+                self.set_layer_binding(layer_id, position as i32, raw)?;
+                report.bindings_written += 1;
+            }
+        }
+
+        if options.save {
+            self.save_changes()?;
+            report.saved = true;
+        }
+        Ok(report)
+    }
+
+    /// Computes the [`KeymapFingerprint`] of the device's current keymap.
+    pub fn keymap_fingerprint(&mut self) -> Result<KeymapFingerprint, ClientError> {
+        Ok(self.get_typed_keymap()?.fingerprint())
+    }
+
+    /// Synchronizes the device keymap with a snapshot file at `path`.
+    ///
+    /// Every [`SyncDifference`] between the file and the device is passed to
+    /// `resolve`, which decides whether to push the file's value to the device,
+    /// pull the device's value into the file, or skip it. A missing file is
+    /// treated as empty. The file is rewritten only if something was pulled;
+    /// pushed changes are staged and must be persisted with [`StudioClient::save_changes`].
+    ///
+    /// Layers can only be pulled, since creating or deleting device layers
+    /// would change layer IDs; pushing them is counted as skipped.
+    pub fn sync(
+        &mut self,
+        path: impl AsRef<Path>,
+        mut resolve: impl FnMut(&SyncDifference) -> SyncAction,
+    ) -> Result<SyncReport, ClientError> {
+        let path = path.as_ref();
+        let mut local = match read_snapshot_file(path)? {
+            Some(text) => TypedKeymap::from_snapshot_text(&text)?,
+            None => TypedKeymap::default(),
         };
+        let device = self.get_typed_keymap()?;
+
+        let mut report = SyncReport::default();
+        for diff in diff_snapshots(&local, &device) {
+            match resolve(&diff) {
+                SyncAction::Push => {
+                    if self.push_sync_difference(&diff)? {
+                        report.pushed += 1;
+                    } else {
+                        report.skipped += 1;
+                    }
+                }
+                SyncAction::Pull => {
+                    pull_difference(&mut local, &device, &diff);
+                    report.pulled += 1;
+                }
+                SyncAction::Skip => report.skipped += 1,
+            }
+        }
 
-        match role {
-            BehaviorRole::KeyPress => Behavior::KeyPress(HidUsage::from_encoded(binding.param1)),
-            BehaviorRole::KeyToggle => Behavior::KeyToggle(HidUsage::from_encoded(binding.param1)),
-            BehaviorRole::LayerTap => Behavior::LayerTap {
-                layer_id: binding.param1,
-                tap: HidUsage::from_encoded(binding.param2),
-            },
-            BehaviorRole::ModTap => Behavior::ModTap {
-                hold: HidUsage::from_encoded(binding.param1),
-                tap: HidUsage::from_encoded(binding.param2),
-            },
-            BehaviorRole::StickyKey => Behavior::StickyKey(HidUsage::from_encoded(binding.param1)),
-            BehaviorRole::StickyLayer => Behavior::StickyLayer {
-                layer_id: binding.param1,
-            },
-            BehaviorRole::MomentaryLayer => Behavior::MomentaryLayer {
-                layer_id: binding.param1,
-            },
-            BehaviorRole::ToggleLayer => Behavior::ToggleLayer {
-                layer_id: binding.param1,
-            },
-            BehaviorRole::ToLayer => Behavior::ToLayer {
-                layer_id: binding.param1,
-            },
-            BehaviorRole::Bluetooth => Behavior::Bluetooth {
-                command: binding.param1,
-                value: binding.param2,
-            },
-            BehaviorRole::ExternalPower => Behavior::ExternalPower {
-                value: binding.param1,
-            },
-            BehaviorRole::OutputSelection => Behavior::OutputSelection {
-                value: binding.param1,
-            },
-            BehaviorRole::Backlight => Behavior::Backlight {
-                command: binding.param1,
-                value: binding.param2,
-            },
-            BehaviorRole::Underglow => Behavior::Underglow {
-                command: binding.param1,
-                value: binding.param2,
-            },
-            BehaviorRole::MouseKeyPress => Behavior::MouseKeyPress {
-                value: binding.param1,
-            },
-            BehaviorRole::MouseMove => Behavior::MouseMove {
-                value: binding.param1,
-            },
-            BehaviorRole::MouseScroll => Behavior::MouseScroll {
-                value: binding.param1,
-            },
-            BehaviorRole::CapsWord => Behavior::CapsWord,
-            BehaviorRole::KeyRepeat => Behavior::KeyRepeat,
-            BehaviorRole::Reset => Behavior::Reset,
-            BehaviorRole::Bootloader => Behavior::Bootloader,
-            BehaviorRole::SoftOff => Behavior::SoftOff,
-            BehaviorRole::StudioUnlock => Behavior::StudioUnlock,
-            BehaviorRole::GraveEscape => Behavior::GraveEscape,
-            BehaviorRole::Transparent => Behavior::Transparent,
-            BehaviorRole::None => Behavior::None,
+        if report.pulled > 0 {
+            std::fs::write(path, local.to_snapshot_text())?;
         }
+
+        Ok(report)
+    }
+
+    /// Replaces every binding equal to `from` within `scope` with `to`.
+    ///
+    /// Returns the `(layer_id, position)` of each rewritten binding. Changes
+    /// are staged; persist with [`StudioClient::save_changes`].
+    pub fn replace_bindings(
+        &mut self,
+        from: &Behavior,
+        to: &Behavior,
+        scope: &LayerScope,
+    ) -> Result<Vec<(u32, usize)>, ClientError> {
+        if from == to {
+            return Ok(Vec::new());
+        }
+        let matches = self.get_typed_keymap()?.find_bindings(from, scope);
+        for &(layer_id, position) in &matches {
+            self.set_key_at(layer_id, position as i32, to.clone())?;
+        }
+        Ok(matches)
+    }
+
+    /// Pushes the file side of each difference to the device.
+    ///
+    /// Differences that cannot be pushed (layers only on one side, or bindings
+    /// missing from the file) are counted as skipped. Changes are staged and
+    /// must be persisted with [`StudioClient::save_changes`].
+    pub fn apply_differences(
+        &mut self,
+        differences: &[SyncDifference],
+    ) -> Result<SyncReport, ClientError> {
+        let mut report = SyncReport::default();
+        for diff in differences {
+            if self.push_sync_difference(diff)? {
+                report.pushed += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+        Ok(report)
+    }
+
+    fn push_sync_difference(&mut self, diff: &SyncDifference) -> Result<bool, ClientError> {
+        match diff {
+            SyncDifference::LayerName {
+                layer_id, local, ..
+            } => {
+                self.set_layer_props(*layer_id, local.clone())?;
+                Ok(true)
+            }
+            SyncDifference::Binding {
+                layer_id,
+                position,
+                local: Some(behavior),
+                ..
+            } => {
+                self.set_key_at(*layer_id, *position as i32, behavior.clone())?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns whether the firmware provides the behavior `behavior` is bound to.
+    ///
+    /// [`Behavior::Unknown`] values are device-specific and never considered supported.
+    pub fn supports_behavior(&mut self, behavior: &Behavior) -> Result<bool, ClientError> {
+        self.ensure_behavior_catalog()?;
+        Ok(self.behavior_catalog.supports(behavior))
+    }
+
+    fn resolve_binding(&self, binding: &zmk::keymap::BehaviorBinding) -> Behavior {
+        self.behavior_catalog.resolve(binding)
     }
 
     /// Set a behavior at a specific layer/key position.
     ///
+    /// Parameters outside the ranges the firmware reports for the behavior are
+    /// rejected with [`ClientError::InvalidBehaviorParam`] before anything is sent.
     /// Persist with [`StudioClient::save_changes`] or revert with [`StudioClient::discard_changes`].
     pub fn set_key_at(
         &mut self,
@@ -419,163 +1498,131 @@ impl<T: Read + Write> StudioClient<T> {
         behavior: Behavior,
     ) -> Result<(), ClientError> {
         self.ensure_behavior_catalog()?;
-        let binding = match behavior {
-            Behavior::KeyPress(key) => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::KeyPress, "Key Press")?,
-                param1: key.to_hid_usage(),
-                param2: 0,
-            },
-            Behavior::KeyToggle(key) => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::KeyToggle, "Key Toggle")?,
-                param1: key.to_hid_usage(),
-                param2: 0,
-            },
-            Behavior::LayerTap {
-                layer_id: hold_layer_id,
-                tap,
-            } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::LayerTap, "Layer-Tap")?,
-                param1: hold_layer_id,
-                param2: tap.to_hid_usage(),
-            },
-            Behavior::ModTap { hold, tap } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::ModTap, "Mod-Tap")?,
-                param1: hold.to_hid_usage(),
-                param2: tap.to_hid_usage(),
-            },
-            Behavior::StickyKey(key) => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::StickyKey, "Sticky Key")?,
-                param1: key.to_hid_usage(),
-                param2: 0,
-            },
-            Behavior::StickyLayer {
-                layer_id: target_layer_id,
-            } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::StickyLayer, "Sticky Layer")?,
-                param1: target_layer_id,
-                param2: 0,
-            },
-            Behavior::MomentaryLayer {
-                layer_id: hold_layer_id,
-            } => zmk::keymap::BehaviorBinding {
-                behavior_id: self
-                    .behavior_id_for(BehaviorRole::MomentaryLayer, "Momentary Layer")?,
-                param1: hold_layer_id,
-                param2: 0,
-            },
-            Behavior::ToggleLayer {
-                layer_id: target_layer_id,
-            } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::ToggleLayer, "Toggle Layer")?,
-                param1: target_layer_id,
-                param2: 0,
-            },
-            Behavior::ToLayer {
-                layer_id: target_layer_id,
-            } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::ToLayer, "To Layer")?,
-                param1: target_layer_id,
-                param2: 0,
-            },
-            Behavior::Bluetooth { command, value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::Bluetooth, "Bluetooth")?,
-                param1: command,
-                param2: value,
-            },
-            Behavior::ExternalPower { value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::ExternalPower, "External Power")?,
-                param1: value,
-                param2: 0,
-            },
-            Behavior::OutputSelection { value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self
-                    .behavior_id_for(BehaviorRole::OutputSelection, "Output Selection")?,
-                param1: value,
-                param2: 0,
-            },
-            Behavior::Backlight { command, value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::Backlight, "Backlight")?,
-                param1: command,
-                param2: value,
-            },
-            Behavior::Underglow { command, value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::Underglow, "Underglow")?,
-                param1: command,
-                param2: value,
-            },
-            Behavior::MouseKeyPress { value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self
-                    .behavior_id_for(BehaviorRole::MouseKeyPress, "Mouse Key Press")?,
-                param1: value,
-                param2: 0,
-            },
-            Behavior::MouseMove { value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::MouseMove, "Mouse Move")?,
-                param1: value,
-                param2: 0,
-            },
-            Behavior::MouseScroll { value } => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::MouseScroll, "Mouse Scroll")?,
-                param1: value,
-                param2: 0,
-            },
-            Behavior::CapsWord => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::CapsWord, "Caps Word")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::KeyRepeat => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::KeyRepeat, "Key Repeat")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::Reset => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::Reset, "Reset")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::Bootloader => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::Bootloader, "Bootloader")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::SoftOff => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::SoftOff, "Soft Off")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::StudioUnlock => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::StudioUnlock, "Studio Unlock")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::GraveEscape => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::GraveEscape, "Grave/Escape")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::Transparent => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::Transparent, "Transparent")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::None => zmk::keymap::BehaviorBinding {
-                behavior_id: self.behavior_id_for(BehaviorRole::None, "None")?,
-                param1: 0,
-                param2: 0,
-            },
-            Behavior::Unknown {
-                behavior_id,
-                param1,
-                param2,
-            } => zmk::keymap::BehaviorBinding {
-                behavior_id,
-                param1,
-                param2,
-            },
+        let binding = self.binding_for(behavior)?;
+        self.set_layer_binding(layer_id, key_position, binding)
+    }
+
+    /// Sets a behavior at a key position on the layer named `layer_name`.
+    ///
+    /// Persist with [`StudioClient::save_changes`] or revert with [`StudioClient::discard_changes`].
+    pub fn set_key_in_layer(
+        &mut self,
+        layer_name: &str,
+        key_position: i32,
+        behavior: Behavior,
+    ) -> Result<(), ClientError> {
+        let layer_id = self.layer_id_by_name(layer_name)?;
+        self.set_key_at(layer_id, key_position, behavior)
+    }
+
+    /// Sets many bindings at once, as `(layer_id, key_position, behavior)`.
+    ///
+    /// All requests are written before any response is read, so the batch
+    /// costs about one round trip instead of one per key. Returns one result
+    /// per binding, in order; a failed binding does not stop the others.
+    /// Transport and protocol errors abort the whole batch.
+    /// Persist with [`StudioClient::save_changes`] or revert with [`StudioClient::discard_changes`].
+    pub fn set_keys(
+        &mut self,
+        bindings: &[(u32, i32, Behavior)],
+    ) -> Result<Vec<Result<(), ClientError>>, ClientError> {
+        self.ensure_behavior_catalog()?;
+        self.poll_auto_save()?;
+
+        let mut results = Vec::with_capacity(bindings.len());
+        // (result index, request ID, layer ID, key position, binding)
+        let mut pending: Vec<(usize, u32, u32, i32, zmk::keymap::BehaviorBinding)> = Vec::new();
+        for (index, (layer_id, key_position, behavior)) in bindings.iter().enumerate() {
+            let binding = match self.binding_for(behavior.clone()) {
+                Ok(binding) => binding,
+                Err(err) => {
+                    results.push(Err(err));
+                    continue;
+                }
+            };
+            let request = zmk::keymap::Request {
+                request_type: Some(zmk::keymap::request::RequestType::SetLayerBinding(
+                    zmk::keymap::SetLayerBindingRequest {
+                        layer_id: *layer_id,
+                        key_position: *key_position,
+                        binding: Some(binding),
+                    },
+                )),
+            };
+            let request_id = match self.send_request(studio::request::Subsystem::Keymap(request)) {
+                Ok(request_id) => request_id,
+                Err(err) => {
+                    for (_, request_id, ..) in pending {
+                        self.abandon_request(request_id);
+                    }
+                    return Err(err);
+                }
+            };
+            #[cfg(feature = "tracing")]
+            tracing::debug!(request_id, layer_id, key_position, "queued batched binding");
+            pending.push((index, request_id, *layer_id, *key_position, binding));
+            results.push(Ok(()));
+        }
+        if !pending.is_empty() {
+            self.last_mutation = Some(Instant::now());
+            self.staged_changes = true;
+        }
+
+        let mut pending = pending.into_iter();
+        while let Some((index, request_id, layer_id, key_position, binding)) = pending.next() {
+            let rr = match self.receive_response(request_id, self.request_timeout) {
+                Ok((rr, _)) => rr,
+                Err(err) => {
+                    for (_, request_id, ..) in pending {
+                        self.abandon_request(request_id);
+                    }
+                    return Err(err);
+                }
+            };
+            results[index] = check_meta(rr)
+                .and_then(keymap_response)
+                .and_then(|response| {
+                    self.finish_set_layer_binding(layer_id, key_position, binding, response)
+                });
+        }
+
+        Ok(results)
+    }
+
+    /// Encodes `behavior` with this firmware's behavior IDs, checking its
+    /// parameters against the firmware's metadata.
+    ///
+    /// The behavior catalog must already be loaded.
+    fn binding_for(&self, behavior: Behavior) -> Result<zmk::keymap::BehaviorBinding, ClientError> {
+        let behavior_id = match (&behavior, behavior.role()) {
+            (Behavior::Unknown { behavior_id, .. }, _) => *behavior_id,
+            (_, Some(role)) => self.behavior_id_for(role, role.display_name())?,
+            (_, None) => unreachable!("only unknown behaviors lack a role"),
+        };
+        let (param1, param2) = behavior.params();
+        let binding = zmk::keymap::BehaviorBinding {
+            behavior_id,
+            param1,
+            param2,
         };
 
-        self.set_layer_binding(layer_id, key_position, binding)
+        self.behavior_catalog
+            .check_params(&binding)
+            .map_err(|(param, allowed)| ClientError::InvalidBehaviorParam {
+                behavior: u32::try_from(binding.behavior_id)
+                    .ok()
+                    .and_then(|id| self.behavior_catalog.details(id))
+                    .map(|details| details.display_name.clone())
+                    .unwrap_or_default(),
+                param,
+                value: if param == 1 {
+                    binding.param1
+                } else {
+                    binding.param2
+                },
+                allowed,
+            })?;
+        Ok(binding)
     }
 
     /// Returns whether there are pending unsaved keymap/layout changes.
@@ -594,10 +1641,144 @@ impl<T: Read + Write> StudioClient<T> {
     ///
     /// After this succeeds, changes are persisted on the device.
     pub fn save_changes(&mut self) -> Result<(), ClientError> {
+        self.save_changes_with_description("Saved changes")
+    }
+
+    /// Like [`StudioClient::save_changes`], recording `description` with the history snapshot.
+    ///
+    /// If a [`HistoryStore`] is attached, the saved keymap is snapshotted after
+    /// the device confirms the save. A [`ClientError::History`] error means the
+    /// save itself succeeded but the snapshot could not be written.
+    pub fn save_changes_with_description(&mut self, description: &str) -> Result<(), ClientError> {
+        self.save_changes_raw()?;
+
+        if self.history.is_some() {
+            let keymap = self.get_keymap()?;
+            if let Some(store) = &self.history {
+                store.record(&keymap, description)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stages the bindings and layer names of `revision` on the device.
+    ///
+    /// Only positions that differ from the current keymap are written. Layers
+    /// that no longer exist are reported in [`RollbackReport::missing_layers`].
+    /// Persist with [`StudioClient::save_changes`] or revert with [`StudioClient::discard_changes`].
+    pub fn rollback_to(
+        &mut self,
+        revision: &KeymapRevision,
+    ) -> Result<RollbackReport, ClientError> {
+        self.restore_keymap(&revision.keymap)
+    }
+
+    /// Stages the bindings and layer names of a previously fetched raw keymap.
+    ///
+    /// Behaves like [`StudioClient::rollback_to`] for keymaps not taken from a history store.
+    pub fn restore_keymap(
+        &mut self,
+        keymap: &zmk::keymap::Keymap,
+    ) -> Result<RollbackReport, ClientError> {
+        let current = self.get_keymap()?;
+        let mut report = RollbackReport::default();
+
+        for layer in &keymap.layers {
+            let Some(current_layer) = current.layers.iter().find(|l| l.id == layer.id) else {
+                report.missing_layers.push(layer.id);
+                continue;
+            };
+
+            if current_layer.name != layer.name {
+                self.set_layer_props(layer.id, layer.name.clone())?;
+                report.layers_renamed += 1;
+            }
+
+            for (position, binding) in layer.bindings.iter().enumerate() {
+                if current_layer.bindings.get(position) == Some(binding) {
+                    continue;
+                }
+                self.set_layer_binding(layer.id, position as i32, *binding)?;
+                report.bindings_written += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Stages a complete keymap, typically composed offline with [`crate::keymap::KeymapBuilder`].
+    ///
+    /// Layers are matched to device layers by index: missing layers are added,
+    /// surplus layers are removed from the end, and layer references are
+    /// translated to the device's layer IDs. Only names and bindings that
+    /// differ are written. The keymap is validated against the device's key
+    /// count before anything is changed.
+    /// Persist with [`StudioClient::save_changes`] or revert with [`StudioClient::discard_changes`].
+    pub fn apply_keymap(&mut self, keymap: &TypedKeymap) -> Result<ApplyKeymapReport, ClientError> {
+        let mut device = self.get_typed_keymap()?;
+        let key_count = device
+            .layers
+            .first()
+            .map_or(0, |layer| layer.bindings.len());
+        keymap.validate_key_count(key_count)?;
+
+        let mut report = ApplyKeymapReport::default();
+        while device.layers.len() < keymap.layers.len() {
+            let details = self.add_layer()?;
+            let layer = details.layer.ok_or(ClientError::MissingResponseType)?;
+            device.layers.push(TypedLayer {
+                id: layer.id,
+                name: layer.name,
+                bindings: layer
+                    .bindings
+                    .iter()
+                    .map(|binding| self.resolve_binding(binding))
+                    .collect(),
+            });
+            report.layers_added += 1;
+        }
+        while device.layers.len() > keymap.layers.len() {
+            self.remove_layer(device.layers.len() as u32 - 1)?;
+            device.layers.pop();
+            report.layers_removed += 1;
+        }
+
+        let device_layer_id = |layer_id: u32| {
+            keymap
+                .layers
+                .iter()
+                .position(|layer| layer.id == layer_id)
+                .map_or(layer_id, |index| device.layers[index].id)
+        };
+
+        for (layer, device_layer) in keymap.layers.iter().zip(&device.layers) {
+            if layer.name != device_layer.name {
+                self.set_layer_props(device_layer.id, layer.name.clone())?;
+                report.layers_renamed += 1;
+            }
+
+            for (position, behavior) in layer.bindings.iter().enumerate() {
+                let behavior = remap_layer_reference(behavior, device_layer_id);
+                if device_layer.bindings.get(position) == Some(&behavior) {
+                    continue;
+                }
+                self.set_key_at(device_layer.id, position as i32, behavior)?;
+                report.bindings_written += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn save_changes_raw(&mut self) -> Result<(), ClientError> {
         let response = self.call_keymap(zmk::keymap::request::RequestType::SaveChanges(true))?;
         match response.response_type {
             Some(zmk::keymap::response::ResponseType::SaveChanges(save)) => match save.result {
-                Some(zmk::keymap::save_changes_response::Result::Ok(_)) => Ok(()),
+                Some(zmk::keymap::save_changes_response::Result::Ok(_)) => {
+                    self.change_listeners.emit(KeymapChange::Saved);
+                    Ok(())
+                }
                 Some(zmk::keymap::save_changes_response::Result::Err(raw)) => {
                     let err = zmk::keymap::SaveChangesErrorCode::try_from(raw).map_err(|_| {
                         ClientError::UnknownEnumValue {
@@ -613,13 +1794,53 @@ impl<T: Read + Write> StudioClient<T> {
         }
     }
 
+    /// Runs `f` as a transaction: its changes are saved if it returns `Ok`, and
+    /// discarded if it returns `Err` or panics.
+    ///
+    /// Changes already staged before the call are saved or discarded along with
+    /// the transaction's. If saving fails, its error is returned and the changes
+    /// stay staged. If discarding fails after an error, the closure's error is
+    /// returned; after a panic, the panic is resumed.
+    pub fn with_transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, E>,
+    ) -> Result<R, E>
+    where
+        E: From<ClientError>,
+    {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(self)));
+        match result {
+            Ok(Ok(value)) => {
+                self.save_changes()?;
+                Ok(value)
+            }
+            Ok(Err(err)) => {
+                let _ = self.discard_changes();
+                Err(err)
+            }
+            Err(panic) => {
+                let _ = self.discard_changes();
+                std::panic::resume_unwind(panic)
+            }
+        }
+    }
+
     /// Discards pending keymap/layout mutations made since the last save.
     ///
     /// Returns `true` if there were pending changes and they were discarded.
     pub fn discard_changes(&mut self) -> Result<bool, ClientError> {
         let response = self.call_keymap(zmk::keymap::request::RequestType::DiscardChanges(true))?;
         match response.response_type {
-            Some(zmk::keymap::response::ResponseType::DiscardChanges(discarded)) => Ok(discarded),
+            Some(zmk::keymap::response::ResponseType::DiscardChanges(discarded)) => {
+                // Discarding brings removed layers back on the device, and
+                // reverts an unsaved physical layout selection.
+                self.removed_layers.clear();
+                self.physical_layouts = None;
+                self.change_listeners.emit(KeymapChange::Discarded {
+                    had_changes: discarded,
+                });
+                Ok(discarded)
+            }
             _ => Err(ClientError::MissingResponseType),
         }
     }
@@ -636,6 +1857,9 @@ impl<T: Read + Write> StudioClient<T> {
             Some(zmk::keymap::response::ResponseType::SetActivePhysicalLayout(resp)) => {
                 match resp.result {
                     Some(zmk::keymap::set_active_physical_layout_response::Result::Ok(keymap)) => {
+                        self.physical_layouts = None;
+                        self.change_listeners
+                            .emit(KeymapChange::PhysicalLayoutSelected { index });
                         Ok(keymap)
                     }
                     Some(zmk::keymap::set_active_physical_layout_response::Result::Err(raw)) => {
@@ -654,6 +1878,9 @@ impl<T: Read + Write> StudioClient<T> {
     }
 
     /// Moves a layer from `start_index` to `dest_index` and returns the updated keymap.
+    ///
+    /// In [`LayerReferenceMode::Indices`] mode, layer-targeting bindings are
+    /// rewritten to keep pointing at the same layers.
     pub fn move_layer(
         &mut self,
         start_index: u32,
@@ -666,7 +1893,19 @@ impl<T: Read + Write> StudioClient<T> {
         let response = self.call_keymap(zmk::keymap::request::RequestType::MoveLayer(request))?;
         match response.response_type {
             Some(zmk::keymap::response::ResponseType::MoveLayer(resp)) => match resp.result {
-                Some(zmk::keymap::move_layer_response::Result::Ok(keymap)) => Ok(keymap),
+                Some(zmk::keymap::move_layer_response::Result::Ok(keymap)) => {
+                    self.change_listeners.emit(KeymapChange::LayerMoved {
+                        start_index,
+                        dest_index,
+                    });
+                    if self.layer_reference_mode == LayerReferenceMode::Indices {
+                        self.remap_layer_indices(|index| {
+                            Some(index_after_move(index, start_index, dest_index))
+                        })?;
+                        return self.get_keymap();
+                    }
+                    Ok(keymap)
+                }
                 Some(zmk::keymap::move_layer_response::Result::Err(raw)) => {
                     let err = zmk::keymap::MoveLayerErrorCode::try_from(raw).map_err(|_| {
                         ClientError::UnknownEnumValue {
@@ -689,7 +1928,15 @@ impl<T: Read + Write> StudioClient<T> {
         ))?;
         match response.response_type {
             Some(zmk::keymap::response::ResponseType::AddLayer(resp)) => match resp.result {
-                Some(zmk::keymap::add_layer_response::Result::Ok(details)) => Ok(details),
+                Some(zmk::keymap::add_layer_response::Result::Ok(details)) => {
+                    if let Some(layer) = &details.layer {
+                        self.change_listeners.emit(KeymapChange::LayerAdded {
+                            layer_id: layer.id,
+                            index: details.index,
+                        });
+                    }
+                    Ok(details)
+                }
                 Some(zmk::keymap::add_layer_response::Result::Err(raw)) => {
                     let err = zmk::keymap::AddLayerErrorCode::try_from(raw).map_err(|_| {
                         ClientError::UnknownEnumValue {
@@ -706,12 +1953,38 @@ impl<T: Read + Write> StudioClient<T> {
     }
 
     /// Removes a layer by index.
+    ///
+    /// The layer's ID, name and bindings are remembered so it can be brought
+    /// back with [`StudioClient::undo_remove_layer`]. In
+    /// [`LayerReferenceMode::Indices`] mode, bindings pointing past the removed
+    /// layer are shifted down; those pointing at it are reported as dangling.
     pub fn remove_layer(&mut self, layer_index: u32) -> Result<(), ClientError> {
+        let keymap = self.get_keymap()?;
+        let captured = usize::try_from(layer_index)
+            .ok()
+            .and_then(|index| keymap.layers.into_iter().nth(index))
+            .map(|layer| RemovedLayer {
+                id: layer.id,
+                name: layer.name,
+                index: layer_index,
+                bindings: layer.bindings,
+            });
+
         let request = zmk::keymap::RemoveLayerRequest { layer_index };
         let response = self.call_keymap(zmk::keymap::request::RequestType::RemoveLayer(request))?;
         match response.response_type {
             Some(zmk::keymap::response::ResponseType::RemoveLayer(resp)) => match resp.result {
-                Some(zmk::keymap::remove_layer_response::Result::Ok(_)) => Ok(()),
+                Some(zmk::keymap::remove_layer_response::Result::Ok(_)) => {
+                    self.change_listeners.emit(KeymapChange::LayerRemoved {
+                        layer_id: captured.as_ref().map(|layer| layer.id),
+                        index: layer_index,
+                    });
+                    self.removed_layers.extend(captured);
+                    if self.layer_reference_mode == LayerReferenceMode::Indices {
+                        self.remap_layer_indices(|index| index_after_removal(index, layer_index))?;
+                    }
+                    Ok(())
+                }
                 Some(zmk::keymap::remove_layer_response::Result::Err(raw)) => {
                     let err = zmk::keymap::RemoveLayerErrorCode::try_from(raw).map_err(|_| {
                         ClientError::UnknownEnumValue {
@@ -738,7 +2011,14 @@ impl<T: Read + Write> StudioClient<T> {
             self.call_keymap(zmk::keymap::request::RequestType::RestoreLayer(request))?;
         match response.response_type {
             Some(zmk::keymap::response::ResponseType::RestoreLayer(resp)) => match resp.result {
-                Some(zmk::keymap::restore_layer_response::Result::Ok(layer)) => Ok(layer),
+                Some(zmk::keymap::restore_layer_response::Result::Ok(layer)) => {
+                    self.removed_layers.retain(|removed| removed.id != layer_id);
+                    self.change_listeners.emit(KeymapChange::LayerRestored {
+                        layer_id,
+                        index: at_index,
+                    });
+                    Ok(layer)
+                }
                 Some(zmk::keymap::restore_layer_response::Result::Err(raw)) => {
                     let err = zmk::keymap::RestoreLayerErrorCode::try_from(raw).map_err(|_| {
                         ClientError::UnknownEnumValue {
@@ -754,15 +2034,64 @@ impl<T: Read + Write> StudioClient<T> {
         }
     }
 
+    /// Rewrites layer references after a move or removal, where `map` returns
+    /// a layer's new index (or `None` if it no longer exists).
+    fn remap_layer_indices(&mut self, map: impl Fn(u32) -> Option<u32>) -> Result<(), ClientError> {
+        let keymap = self.get_typed_keymap()?;
+        let mut report = LayerRemapReport::default();
+        for layer in &keymap.layers {
+            for (position, behavior) in layer.bindings.iter().enumerate() {
+                let Some(index) = referenced_layer(behavior) else {
+                    continue;
+                };
+                match map(index) {
+                    Some(new_index) if new_index != index => {
+                        let after = remap_layer_reference(behavior, |_| new_index);
+                        self.set_key_at(layer.id, position as i32, after.clone())?;
+                        report.rewritten.push(RemappedBinding {
+                            layer_id: layer.id,
+                            position,
+                            before: behavior.clone(),
+                            after,
+                        });
+                    }
+                    Some(_) => {}
+                    None => report.dangling.push((layer.id, position, behavior.clone())),
+                }
+            }
+        }
+        self.last_layer_remap = Some(report);
+        Ok(())
+    }
+
+    /// Layers removed through this client and not restored since, oldest first.
+    ///
+    /// Cleared by [`StudioClient::discard_changes`], which restores them on the device.
+    pub fn removed_layers(&self) -> &[RemovedLayer] {
+        &self.removed_layers
+    }
+
+    /// Restores the most recently removed layer at its original index.
+    ///
+    /// Returns `Ok(None)` if no layer was removed through this client.
+    pub fn undo_remove_layer(&mut self) -> Result<Option<zmk::keymap::Layer>, ClientError> {
+        let Some(removed) = self.removed_layers.last() else {
+            return Ok(None);
+        };
+        let (id, index) = (removed.id, removed.index);
+        self.restore_layer(id, index).map(Some)
+    }
+
     /// Sets user-facing properties for a layer (currently just `name`).
     pub fn set_layer_props(
         &mut self,
         layer_id: u32,
         name: impl Into<String>,
     ) -> Result<(), ClientError> {
+        let name = name.into();
         let request = zmk::keymap::SetLayerPropsRequest {
             layer_id,
-            name: name.into(),
+            name: name.clone(),
         };
         let response =
             self.call_keymap(zmk::keymap::request::RequestType::SetLayerProps(request))?;
@@ -776,6 +2105,8 @@ impl<T: Read + Write> StudioClient<T> {
                 })?;
 
                 if code == zmk::keymap::SetLayerPropsResponse::SetLayerPropsRespOk {
+                    self.change_listeners
+                        .emit(KeymapChange::LayerRenamed { layer_id, name });
                     Ok(())
                 } else {
                     Err(ClientError::SetLayerPropsFailed(code))
@@ -791,32 +2122,36 @@ impl<T: Read + Write> StudioClient<T> {
         display_name: &'static str,
     ) -> Result<i32, ClientError> {
         let behavior_id = self
-            .behavior_id_by_role
-            .get(&role)
-            .copied()
+            .behavior_catalog
+            .id_for(role)
             .ok_or(ClientError::MissingBehaviorRole(display_name))?;
         i32::try_from(behavior_id).map_err(|_| ClientError::BehaviorIdOutOfRange { behavior_id })
     }
 
     fn ensure_behavior_catalog(&mut self) -> Result<(), ClientError> {
-        if !self.behavior_role_by_id.is_empty() {
+        if !self.behavior_catalog.is_empty() {
             return Ok(());
         }
 
         let ids = self.list_all_behaviors()?;
         for id in ids {
             let details = self.get_behavior_details(id)?;
-            let role = role_from_display_name(&details.display_name);
-            if let Some(role) = role {
-                self.behavior_role_by_id.insert(id, role);
-                self.behavior_id_by_role.entry(role).or_insert(id);
-            }
+            self.behavior_catalog.insert_details(details);
         }
 
         Ok(())
     }
 
-    fn call_core(
+    /// Sends a core-subsystem request and returns the device's core response as is.
+    ///
+    /// The typed core methods fail with [`ClientError::MissingResponseType`]
+    /// when the response type is not one they expect; this passthrough
+    /// returns it anyway, with `response_type: None` for a type this crate
+    /// does not know, e.g. from newer firmware. ZMK Studio does not report
+    /// the connection state of split peripherals yet, so there is no typed
+    /// API for it. Unknown core notifications arrive as
+    /// [`Notification::Unknown`].
+    pub fn call_core(
         &mut self,
         request_type: zmk::core::request::RequestType,
     ) -> Result<zmk::core::Response, ClientError> {
@@ -852,22 +2187,174 @@ impl<T: Read + Write> StudioClient<T> {
         &mut self,
         request_type: zmk::keymap::request::RequestType,
     ) -> Result<zmk::keymap::Response, ClientError> {
+        use zmk::keymap::request::RequestType;
+
+        let ends_changes = matches!(
+            request_type,
+            RequestType::SaveChanges(_) | RequestType::DiscardChanges(_)
+        );
+        let mutates = matches!(
+            request_type,
+            RequestType::SetLayerBinding(_)
+                | RequestType::SetActivePhysicalLayout(_)
+                | RequestType::MoveLayer(_)
+                | RequestType::AddLayer(_)
+                | RequestType::RemoveLayer(_)
+                | RequestType::RestoreLayer(_)
+                | RequestType::SetLayerProps(_)
+        );
+        if !ends_changes {
+            self.poll_auto_save()?;
+        }
+
+        // Binding changes are applied to the cached keymap once confirmed;
+        // other changes are not worth mirroring.
+        let invalidates = matches!(request_type, RequestType::DiscardChanges(_))
+            || (mutates && !matches!(request_type, RequestType::SetLayerBinding(_)));
+        if invalidates {
+            self.keymap = None;
+        }
+
+        // Updated before sending, so an unsaved-changes notification that
+        // arrives ahead of the response is recognized as our own.
+        let was_staged = self.staged_changes;
+        if ends_changes {
+            self.staged_changes = false;
+        } else if mutates {
+            self.staged_changes = true;
+        }
+
         let request = zmk::keymap::Request {
             request_type: Some(request_type),
         };
-        let rr = self.call(studio::request::Subsystem::Keymap(request))?;
+        let rr = match self.call(studio::request::Subsystem::Keymap(request)) {
+            Ok(rr) => rr,
+            Err(err) => {
+                self.staged_changes = was_staged;
+                return Err(err);
+            }
+        };
 
-        match rr.subsystem {
-            Some(studio::request_response::Subsystem::Keymap(resp)) => Ok(resp),
-            Some(_) => Err(ClientError::UnexpectedSubsystem("keymap")),
-            None => Err(ClientError::MissingSubsystem),
+        if ends_changes {
+            self.last_mutation = None;
+        } else if mutates {
+            self.last_mutation = Some(Instant::now());
         }
+
+        keymap_response(rr)
     }
 
     fn call(
         &mut self,
         subsystem: studio::request::Subsystem,
     ) -> Result<studio::RequestResponse, ClientError> {
+        self.call_with_deadline(subsystem, self.request_timeout)
+    }
+
+    /// Sends a raw request and waits at most `timeout` for its response.
+    ///
+    /// Unlike the typed methods, this bypasses auto-save and unsaved-change
+    /// tracking, and any keymap request drops the cached keymap. See
+    /// [`StudioClient::set_request_timeout`] for how timeouts and late responses
+    /// are handled.
+    pub fn call_with_timeout(
+        &mut self,
+        subsystem: studio::request::Subsystem,
+        timeout: Duration,
+    ) -> Result<studio::RequestResponse, ClientError> {
+        if matches!(subsystem, studio::request::Subsystem::Keymap(_)) {
+            self.keymap = None;
+        }
+        self.call_with_deadline(subsystem, Some(timeout))
+    }
+
+    /// Sends a raw request and returns the response uninterpreted, together
+    /// with its encoded bytes.
+    ///
+    /// An escape hatch for firmware newer than this crate: fields, response
+    /// types and subsystems the bundled protos do not know are dropped from
+    /// [`RawResponse::response`] but kept in [`RawResponse::bytes`]. Device
+    /// errors are returned as meta responses rather than as
+    /// [`ClientError::Meta`]. Like [`StudioClient::call_with_timeout`], this
+    /// bypasses auto-save and unsaved-change tracking.
+    pub fn call_raw(
+        &mut self,
+        subsystem: studio::request::Subsystem,
+    ) -> Result<RawResponse, ClientError> {
+        if matches!(subsystem, studio::request::Subsystem::Keymap(_)) {
+            self.keymap = None;
+        }
+        let request_id = self.send_request(subsystem)?;
+        let (response, frame) = self.receive_response(request_id, self.request_timeout)?;
+        let bytes = request_response_bytes(&frame)
+            .unwrap_or_else(|| prost::Message::encode_to_vec(&response));
+        Ok(RawResponse { response, bytes })
+    }
+
+    fn call_with_deadline(
+        &mut self,
+        subsystem: studio::request::Subsystem,
+        timeout: Option<Duration>,
+    ) -> Result<studio::RequestResponse, ClientError> {
+        let policy = self.retry_policy.filter(|policy| policy.allows(&subsystem));
+        let mut attempt = 0;
+        loop {
+            match self.call_once(subsystem.clone(), timeout) {
+                Err(err)
+                    if err.is_transient()
+                        && policy.is_some_and(|policy| attempt + 1 < policy.max_attempts) =>
+                {
+                    let backoff = policy.map_or(Duration::ZERO, |policy| policy.backoff(attempt));
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(attempt, error = %err, ?backoff, "retrying request");
+                    std::thread::sleep(backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends one request and waits for its response.
+    fn call_once(
+        &mut self,
+        subsystem: studio::request::Subsystem,
+        timeout: Option<Duration>,
+    ) -> Result<studio::RequestResponse, ClientError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "rpc",
+            subsystem = subsystem_name(&subsystem),
+            request_id = self.next_request_id,
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        let started = Instant::now();
+
+        let request_id = self.send_request(subsystem)?;
+        let result = self
+            .receive_response(request_id, timeout)
+            .and_then(|(rr, _)| check_meta(rr));
+        if let Err(err) = &result
+            && err.is_transient()
+            && !matches!(err, ClientError::Timeout | ClientError::Meta(_))
+        {
+            // The response may still arrive; don't mistake it for the next
+            // one's. Timeouts have abandoned it already, and a meta error is
+            // the response itself.
+            self.abandon_request(request_id);
+        }
+
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(_) => tracing::debug!(elapsed = ?started.elapsed(), "rpc completed"),
+            Err(err) => tracing::debug!(elapsed = ?started.elapsed(), error = %err, "rpc failed"),
+        }
+        result
+    }
+
+    /// Writes a request without waiting for its response and returns its request ID.
+    fn send_request(&mut self, subsystem: studio::request::Subsystem) -> Result<u32, ClientError> {
         let request_id = self.next_request_id;
         self.next_request_id = self.next_request_id.wrapping_add(1);
 
@@ -875,73 +2362,233 @@ impl<T: Read + Write> StudioClient<T> {
             request_id,
             subsystem: Some(subsystem),
         };
+        if let Some(transcript) = &mut self.transcript {
+            transcript.record_request(&request);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::trace!(?request, "sending request");
         let bytes = encode_request(&request);
-        self.io.write_all(&bytes)?;
+        if let Err(err) = self.io.write_all(&bytes) {
+            return Err(self.transport_error(err));
+        }
+        Ok(request_id)
+    }
 
+    /// Waits for the response to `request_id`, queuing notifications that
+    /// arrive first, and returns it with the frame it was decoded from.
+    fn receive_response(
+        &mut self,
+        request_id: u32,
+        timeout: Option<Duration>,
+    ) -> Result<(studio::RequestResponse, Vec<u8>), ClientError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
         loop {
-            let response = self.read_next_response()?;
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(request_id, "request timed out");
+                self.abandon_request(request_id);
+                return Err(ClientError::Timeout);
+            }
+            let (response, frame) = match self.read_next_response() {
+                Ok(received) => received,
+                Err(ClientError::Io(err)) if deadline.is_some() && is_read_timeout(&err) => {
+                    continue;
+                }
+                Err(err @ ClientError::QueueFull(_)) => {
+                    // The response may already be buffered or still on its way.
+                    self.abandon_request(request_id);
+                    return Err(err);
+                }
+                Err(err) => return Err(err),
+            };
             match response.r#type {
                 Some(studio::response::Type::Notification(notification)) => {
-                    self.notifications.push_back(notification);
+                    if let Err(err) = self.accept_notification(notification, &frame) {
+                        self.abandon_request(request_id);
+                        return Err(err);
+                    }
                 }
                 Some(studio::response::Type::RequestResponse(rr)) => {
+                    if let Some(index) = self
+                        .abandoned_requests
+                        .iter()
+                        .position(|&id| id == rr.request_id)
+                    {
+                        self.abandoned_requests.remove(index);
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(
+                            request_id = rr.request_id,
+                            "discarding late response to an abandoned request"
+                        );
+                        continue;
+                    }
                     if rr.request_id != request_id {
                         return Err(ClientError::UnexpectedRequestId {
                             expected: request_id,
                             actual: rr.request_id,
                         });
                     }
-
-                    if let Some(studio::request_response::Subsystem::Meta(meta)) = &rr.subsystem {
-                        match meta.response_type {
-                            Some(zmk::meta::response::ResponseType::NoResponse(true)) => {
-                                return Err(ClientError::NoResponse);
-                            }
-                            Some(zmk::meta::response::ResponseType::SimpleError(raw)) => {
-                                let cond =
-                                    zmk::meta::ErrorConditions::try_from(raw).map_err(|_| {
-                                        ClientError::UnknownEnumValue {
-                                            field: "meta.simple_error",
-                                            value: raw,
-                                        }
-                                    })?;
-                                return Err(ClientError::Meta(cond));
-                            }
-                            _ => return Err(ClientError::MissingResponseType),
-                        }
-                    }
-
-                    return Ok(rr);
+                    return Ok((rr, frame));
                 }
                 None => return Err(ClientError::MissingResponseType),
             }
         }
     }
 
-    fn read_next_response(&mut self) -> Result<studio::Response, ClientError> {
-        if let Some(response) = self.responses.pop_front() {
+    /// Remembers a timed-out request so its late response is skipped.
+    fn abandon_request(&mut self, request_id: u32) {
+        if self.abandoned_requests.len() == MAX_ABANDONED_REQUESTS {
+            self.abandoned_requests.remove(0);
+        }
+        self.abandoned_requests.push(request_id);
+    }
+
+    /// Reports an I/O error as [`ClientError::Disconnected`] once the transport
+    /// knows its connection is gone.
+    fn transport_error(&self, err: std::io::Error) -> ClientError {
+        if self.io.is_connected() {
+            ClientError::Io(err)
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(transport = %self.io.description(), error = %err, "transport disconnected");
+            ClientError::Disconnected(self.io.description())
+        }
+    }
+
+    fn read_next_response(&mut self) -> Result<(studio::Response, Vec<u8>), ClientError> {
+        if let Some(response) = self.responses.pop() {
+            return Ok(response);
+        }
+        if let Some(response) = self.undelivered.pop_front() {
             return Ok(response);
         }
 
         loop {
-            let read = self.io.read(&mut self.read_buffer)?;
-            if read == 0 {
-                return Err(ClientError::Io(std::io::Error::new(
-                    std::io::ErrorKind::UnexpectedEof,
-                    "Transport reached EOF",
-                )));
-            }
+            let read = match self.io.read(&mut self.read_buffer) {
+                Ok(0) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(transport = %self.io.description(), "transport reached EOF");
+                    return Err(ClientError::Disconnected(self.io.description()));
+                }
+                Ok(read) => read,
+                Err(err) => return Err(self.transport_error(err)),
+            };
 
-            let decoded = decode_responses(&mut self.decoder, &self.read_buffer[..read])?;
-            self.responses.extend(decoded);
+            let decoded =
+                decode_responses_with_frames(&mut self.decoder, &self.read_buffer[..read])?;
+            if let Some(transcript) = &mut self.transcript {
+                for (response, _) in &decoded {
+                    transcript.record_response(response);
+                }
+            }
+            #[cfg(feature = "tracing")]
+            for (response, _) in &decoded {
+                tracing::trace!(?response, "received response");
+            }
+            let mut decoded = decoded.into_iter();
+            while let Some(received) = decoded.next() {
+                if let Err(QueueFull(received)) = self.responses.push(received) {
+                    // Keep the refused response and the rest of this read for
+                    // the next receive.
+                    self.undelivered.push_back(received);
+                    self.undelivered.extend(decoded);
+                    return Err(ClientError::QueueFull("response"));
+                }
+            }
 
-            if let Some(response) = self.responses.pop_front() {
+            if let Some(response) = self.responses.pop() {
                 return Ok(response);
             }
         }
     }
 }
 
+impl<T: Transport> Drop for StudioClient<T> {
+    fn drop(&mut self) {
+        let _ = self.apply_unsaved_changes_policy();
+    }
+}
+
+/// Turns a meta-subsystem response (the device's generic error reply) into an error.
+fn check_meta(rr: studio::RequestResponse) -> Result<studio::RequestResponse, ClientError> {
+    let Some(studio::request_response::Subsystem::Meta(meta)) = &rr.subsystem else {
+        return Ok(rr);
+    };
+    match meta.response_type {
+        Some(zmk::meta::response::ResponseType::NoResponse(true)) => Err(ClientError::NoResponse),
+        Some(zmk::meta::response::ResponseType::SimpleError(raw)) => {
+            let cond = zmk::meta::ErrorConditions::try_from(raw).map_err(|_| {
+                ClientError::UnknownEnumValue {
+                    field: META_CONDITION_FIELD,
+                    value: raw,
+                }
+            })?;
+            Err(ClientError::Meta(cond))
+        }
+        _ => Err(ClientError::MissingResponseType),
+    }
+}
+
+fn keymap_response(rr: studio::RequestResponse) -> Result<zmk::keymap::Response, ClientError> {
+    match rr.subsystem {
+        Some(studio::request_response::Subsystem::Keymap(resp)) => Ok(resp),
+        Some(_) => Err(ClientError::UnexpectedSubsystem("keymap")),
+        None => Err(ClientError::MissingSubsystem),
+    }
+}
+
+/// Whether sending `subsystem` twice has the same effect and response as sending it once.
+fn is_idempotent(subsystem: &studio::request::Subsystem) -> bool {
+    use zmk::keymap::request::RequestType as Keymap;
+
+    match subsystem {
+        studio::request::Subsystem::Core(request) => !matches!(
+            request.request_type,
+            Some(zmk::core::request::RequestType::ResetSettings(_))
+        ),
+        studio::request::Subsystem::Behaviors(_) => true,
+        studio::request::Subsystem::Keymap(request) => !matches!(
+            request.request_type,
+            Some(
+                Keymap::SaveChanges(_)
+                    | Keymap::DiscardChanges(_)
+                    | Keymap::MoveLayer(_)
+                    | Keymap::AddLayer(_)
+                    | Keymap::RemoveLayer(_)
+                    | Keymap::RestoreLayer(_)
+            )
+        ),
+    }
+}
+
+/// Name of the subsystem a request targets, for log fields.
+#[cfg(feature = "tracing")]
+fn subsystem_name(subsystem: &studio::request::Subsystem) -> &'static str {
+    match subsystem {
+        studio::request::Subsystem::Core(_) => "core",
+        studio::request::Subsystem::Behaviors(_) => "behaviors",
+        studio::request::Subsystem::Keymap(_) => "keymap",
+    }
+}
+
+/// Whether a transport read failed only because no data arrived in time.
+fn is_read_timeout(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+    )
+}
+
+fn binding_slot(
+    keymap: &mut zmk::keymap::Keymap,
+    layer_id: u32,
+    key_position: i32,
+) -> Option<&mut zmk::keymap::BehaviorBinding> {
+    let pos = usize::try_from(key_position).ok()?;
+    let layer = keymap.layers.iter_mut().find(|l| l.id == layer_id)?;
+    layer.bindings.get_mut(pos)
+}
+
 fn binding_at(
     keymap: &zmk::keymap::Keymap,
     layer_id: u32,
@@ -958,6 +2605,47 @@ impl StudioClient<SerialTransport> {
     pub fn open_serial(path: &str) -> Result<Self, SerialTransportError> {
         Ok(Self::new(SerialTransport::open(path)?))
     }
+
+    /// Like [`StudioClient::open_serial`], with explicit port settings.
+    pub fn open_serial_with_options(
+        path: &str,
+        options: SerialOptions,
+    ) -> Result<Self, SerialTransportError> {
+        Ok(Self::new(SerialTransport::open_with_options(
+            path, options,
+        )?))
+    }
+}
+
+#[cfg(feature = "tcp")]
+impl StudioClient<TcpTransport> {
+    /// Convenience constructor for connecting to a TCP bridge at `host:port`.
+    pub fn open_tcp(addr: impl std::net::ToSocketAddrs) -> Result<Self, TcpTransportError> {
+        Ok(Self::new(TcpTransport::connect(addr)?))
+    }
+}
+
+#[cfg(feature = "usb")]
+impl StudioClient<UsbTransport> {
+    /// Convenience constructor for opening a raw HID transport by path.
+    ///
+    /// Without a path, opens the first interface of a keyboard with ZMK's
+    /// default vendor and product IDs.
+    pub fn open_usb(path: Option<&str>) -> Result<Self, UsbTransportError> {
+        let transport = match path {
+            Some(path) => UsbTransport::open(path)?,
+            None => UsbTransport::open_first(UsbDeviceInfo::is_zmk_device)?,
+        };
+        Ok(Self::new(transport))
+    }
+}
+
+#[cfg(feature = "sim")]
+impl StudioClient<SimTransport> {
+    /// Convenience constructor for connecting to a `native_sim` firmware build by PTY path or TCP address.
+    pub fn open_sim(endpoint: &str) -> Result<Self, SimTransportError> {
+        Ok(Self::new(crate::transport::sim::connect(endpoint)?))
+    }
 }
 
 #[cfg(feature = "ble")]
@@ -971,4 +2659,384 @@ impl StudioClient<BleTransport> {
     pub fn open_ble(device_id: &str) -> Result<Self, BleTransportError> {
         Ok(Self::new(BleTransport::connect_device(device_id)?))
     }
+
+    /// Opens the BLE keyboard whose advertised name contains `name`; see
+    /// [`BleTransport::connect_by_name`].
+    pub fn open_ble_by_name(name: &str) -> Result<Self, BleTransportError> {
+        Ok(Self::new(BleTransport::connect_by_name(name)?))
+    }
+
+    /// Reconnects to a saved BLE keyboard, even if the host assigned it a new peripheral ID.
+    ///
+    /// After connecting, the device's serial number is compared with the one
+    /// recorded in `saved` (if any). On success `saved` is updated with the
+    /// peripheral ID and serial number actually used, so callers can persist it
+    /// again.
+    pub fn open_ble_saved(saved: &mut SavedBleDevice) -> Result<Self, BleTransportError> {
+        let (transport, device_id) = BleTransport::connect_saved(saved)?;
+        let mut client = Self::new(transport);
+        let info = client
+            .get_device_info()
+            .map_err(|err| BleTransportError::Verification(Box::new(err)))?;
+        if saved
+            .serial_number
+            .as_ref()
+            .is_some_and(|serial| *serial != info.serial_number)
+        {
+            return Err(BleTransportError::DeviceMismatch(device_id));
+        }
+
+        saved.device_id = device_id;
+        saved.serial_number = Some(info.serial_number);
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::Write;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use super::{ClientError, StudioClient, UnsavedChangesPolicy};
+    use crate::privacy::SerialRedaction;
+    use crate::proto::zmk::keymap;
+    use crate::proto::zmk::studio::request;
+    use crate::protocol::{FrameDecoder, decode_requests};
+    use crate::transcript::TranscriptDirection;
+    use crate::transport::mock::MockDevice;
+    use crate::transport::recording::RecordingTransport;
+    use crate::{Behavior, Keycode, OverflowPolicy};
+
+    #[test]
+    fn abandons_sent_bindings_when_a_batch_write_fails() {
+        // The batch starts by loading the behavior catalog.
+        let mut probe = StudioClient::new(MockDevice::new());
+        probe.start_transcript(SerialRedaction::None);
+        probe.ensure_behavior_catalog().expect("catalog");
+        let catalog_requests = probe
+            .transcript()
+            .expect("transcript")
+            .entries()
+            .iter()
+            .filter(|entry| entry.direction == TranscriptDirection::Request)
+            .count();
+
+        let mut device = MockDevice::new();
+        device.fail_write_after(catalog_requests + 1);
+        let mut client = StudioClient::new(device);
+        let bindings = [
+            (0, 0, Behavior::None),
+            (0, 1, Behavior::None),
+            (0, 2, Behavior::None),
+        ];
+
+        let err = client.set_keys(&bindings).expect_err("write fails");
+        assert!(matches!(err, ClientError::Io(_)));
+        // The response to the first binding is skipped, not mistaken for this one.
+        assert_eq!(
+            client.get_key_at(0, 0).expect("get binding"),
+            Behavior::None
+        );
+        assert!(client.check_unsaved_changes().expect("status"));
+    }
+
+    /// A device that has queued two lock-state notifications for the next read.
+    fn relocked_device() -> MockDevice {
+        let mut device = MockDevice::new();
+        device.set_locked(true);
+        device.set_locked(false);
+        device
+    }
+
+    #[test]
+    fn abandons_the_request_when_the_notification_queue_is_full() {
+        let mut client = StudioClient::new(relocked_device());
+        client.set_notification_queue_limit(Some(1), OverflowPolicy::Error);
+
+        let err = client.get_device_info().expect_err("queue is full");
+        assert!(matches!(err, ClientError::QueueFull("notification")));
+        // The response that arrived behind the notifications is skipped.
+        client.get_device_info().expect("device info");
+        assert!(client.next_notification().is_some());
+        assert!(client.next_notification().is_none());
+    }
+
+    #[test]
+    fn keeps_decoded_frames_when_the_response_queue_is_full() {
+        let mut device = relocked_device();
+        device.set_locked(true);
+        let mut client = StudioClient::new(device);
+        client.set_response_queue_limit(Some(1), OverflowPolicy::Error);
+
+        let err = client.get_device_info().expect_err("queue is full");
+        assert!(matches!(err, ClientError::QueueFull("response")));
+        client.get_device_info().expect("device info");
+        let mut received = 0;
+        while client.next_notification().is_some() {
+            received += 1;
+        }
+        assert_eq!(received, 3);
+    }
+
+    #[test]
+    fn auto_saves_on_the_next_keymap_request_after_the_delay() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.set_auto_save(Some(Duration::ZERO));
+
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        assert_eq!(client.auto_save_due_in(), Some(Duration::ZERO));
+        // The status request polls first, so it sees the saved state.
+        assert!(!client.check_unsaved_changes().expect("status"));
+        assert_eq!(client.auto_save_due_in(), None);
+
+        client
+            .set_key_at(0, 1, Behavior::None)
+            .expect("set binding");
+        assert!(client.poll_auto_save().expect("poll"));
+        assert!(!client.poll_auto_save().expect("poll"));
+    }
+
+    #[test]
+    fn waits_for_the_auto_save_delay_and_can_be_cancelled() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.set_auto_save(Some(Duration::from_secs(3600)));
+
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        assert!(!client.poll_auto_save().expect("poll"));
+        assert!(client.auto_save_due_in().expect("pending") > Duration::ZERO);
+        assert!(client.check_unsaved_changes().expect("status"));
+
+        client.cancel_auto_save();
+        assert_eq!(client.auto_save_due_in(), None);
+        client.set_auto_save(Some(Duration::ZERO));
+        assert!(!client.poll_auto_save().expect("poll"));
+        assert!(client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn skips_auto_save_without_staged_changes() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.set_auto_save(Some(Duration::ZERO));
+        assert!(!client.poll_auto_save().expect("poll"));
+
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        client.save_changes().expect("save");
+        assert_eq!(client.auto_save_due_in(), None);
+        assert!(!client.poll_auto_save().expect("poll"));
+    }
+
+    /// Capture sink that stays readable after the client owning it is dropped.
+    #[derive(Clone, Default)]
+    struct SharedCapture(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedCapture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedCapture {
+        /// Keymap requests the client wrote, in order.
+        fn keymap_requests(&self) -> Vec<keymap::request::RequestType> {
+            let capture = String::from_utf8(self.0.borrow().clone()).expect("utf-8 capture");
+            let written: Vec<u8> = capture
+                .lines()
+                .filter_map(|line| line.split_once("] write ").map(|(_, hex)| hex))
+                .flat_map(|hex| {
+                    (0..hex.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex"))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            decode_requests(&mut FrameDecoder::new(), &written)
+                .expect("requests")
+                .into_iter()
+                .filter_map(|request| match request.subsystem {
+                    Some(request::Subsystem::Keymap(keymap)) => keymap.request_type,
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    fn staged_client(
+        policy: UnsavedChangesPolicy,
+    ) -> (
+        StudioClient<RecordingTransport<MockDevice, SharedCapture>>,
+        SharedCapture,
+    ) {
+        let capture = SharedCapture::default();
+        let transport =
+            RecordingTransport::new(MockDevice::new(), capture.clone()).expect("capture");
+        let mut client = StudioClient::new(transport);
+        client.set_unsaved_changes_policy(policy);
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        (client, capture)
+    }
+
+    fn ends_with(requests: &[keymap::request::RequestType]) -> &'static str {
+        match requests.last() {
+            Some(keymap::request::RequestType::SaveChanges(_)) => "save",
+            Some(keymap::request::RequestType::DiscardChanges(_)) => "discard",
+            _ => "none",
+        }
+    }
+
+    #[test]
+    fn applies_the_unsaved_changes_policy_on_drop_and_close() {
+        for (policy, expected) in [
+            (UnsavedChangesPolicy::Save, "save"),
+            (UnsavedChangesPolicy::Discard, "discard"),
+            (UnsavedChangesPolicy::Keep, "none"),
+        ] {
+            let (client, capture) = staged_client(policy);
+            drop(client);
+            assert_eq!(
+                ends_with(&capture.keymap_requests()),
+                expected,
+                "drop with {policy:?}"
+            );
+
+            let (client, capture) = staged_client(policy);
+            client.close().expect("close");
+            let requests = capture.keymap_requests();
+            assert_eq!(ends_with(&requests), expected, "close with {policy:?}");
+            // Closing applies the policy once, not again on drop.
+            let ending = requests
+                .iter()
+                .filter(|request| {
+                    matches!(
+                        request,
+                        keymap::request::RequestType::SaveChanges(_)
+                            | keymap::request::RequestType::DiscardChanges(_)
+                    )
+                })
+                .count();
+            assert_eq!(
+                ending,
+                usize::from(expected != "none"),
+                "close with {policy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn drops_without_staged_changes_quietly() {
+        let capture = SharedCapture::default();
+        let transport =
+            RecordingTransport::new(MockDevice::new(), capture.clone()).expect("capture");
+        let mut client = StudioClient::new(transport);
+        client.set_unsaved_changes_policy(UnsavedChangesPolicy::Save);
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        client.save_changes().expect("save");
+        drop(client);
+
+        let saves = capture
+            .keymap_requests()
+            .into_iter()
+            .filter(|request| matches!(request, keymap::request::RequestType::SaveChanges(_)))
+            .count();
+        assert_eq!(saves, 1);
+    }
+
+    #[test]
+    fn saves_or_discards_transactions() {
+        let mut client = StudioClient::new(MockDevice::new());
+
+        let result: Result<(), ClientError> = client.with_transaction(|tx| {
+            tx.set_key_at(0, 0, Behavior::None)?;
+            tx.set_key_at(0, 1, Behavior::None)
+        });
+        result.expect("transaction");
+        assert!(!client.check_unsaved_changes().expect("status"));
+        assert_eq!(
+            client.get_key_at(0, 1).expect("get binding"),
+            Behavior::None
+        );
+
+        let result = client.with_transaction(|tx| {
+            tx.set_key_at(0, 2, Behavior::None)?;
+            tx.set_key_at(0, 99, Behavior::None)
+        });
+        assert!(result.is_err());
+        assert!(!client.check_unsaved_changes().expect("status"));
+        assert_ne!(
+            client.get_key_at(0, 2).expect("get binding"),
+            Behavior::None
+        );
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _: Result<(), ClientError> = client.with_transaction(|tx| {
+                tx.set_key_at(0, 3, Behavior::None)?;
+                panic!("script bug");
+            });
+        }));
+        assert!(panicked.is_err());
+        assert!(!client.check_unsaved_changes().expect("status"));
+        assert_ne!(
+            client.get_key_at(0, 3).expect("get binding"),
+            Behavior::None
+        );
+
+        let result: Result<(), ClientError> = client.with_transaction(|tx| {
+            tx.set_key_at(0, 3, Behavior::None)?;
+            tx.io.fail_write_after(0);
+            Ok(())
+        });
+        assert!(matches!(result, Err(ClientError::Io(_))));
+        // A failed save leaves the changes staged.
+        assert!(client.check_unsaved_changes().expect("status"));
+        assert_eq!(
+            client.get_key_at(0, 3).expect("get binding"),
+            Behavior::None
+        );
+    }
+
+    #[test]
+    fn undoes_a_layer_removal_in_place() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.add_layer().expect("add layer");
+        let nav = client.get_keymap().expect("keymap").layers[1].id;
+        client.set_layer_props(nav, "Nav").expect("rename");
+        client
+            .set_key_at(nav, 2, Behavior::KeyPress(Keycode::Z.into()))
+            .expect("set binding");
+        let before = client.get_keymap().expect("keymap");
+
+        client.remove_layer(1).expect("remove");
+        assert_eq!(client.get_keymap().expect("keymap").layers.len(), 2);
+
+        let restored = client
+            .undo_remove_layer()
+            .expect("undo")
+            .expect("restored layer");
+        assert_eq!(restored.name, "Nav");
+        let after = client.get_keymap().expect("keymap");
+        assert_eq!(after, before);
+        assert_eq!(after.layers[1].id, nav);
+        assert_eq!(
+            client.get_key_at(nav, 2).expect("get binding"),
+            Behavior::KeyPress(Keycode::Z.into())
+        );
+        assert!(client.removed_layers().is_empty());
+        assert!(client.undo_remove_layer().expect("undo").is_none());
+    }
 }
@@ -0,0 +1,354 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use zbus::blocking::connection;
+use zbus::blocking::object_server::InterfaceRef;
+use zbus::fdo;
+use zbus::interface;
+use zbus::object_server::SignalEmitter;
+
+use crate::client::{ClientError, StudioClient};
+use crate::privacy::SerialRedaction;
+use crate::proto::zmk;
+use crate::proto::zmk::studio;
+use crate::transport::Transport;
+
+/// Well-known bus name claimed by [`serve`].
+pub const DBUS_SERVICE_NAME: &str = "io.github.srwi.ZmkStudio";
+/// Object path the keyboard interface is exported at.
+pub const DBUS_OBJECT_PATH: &str = "/io/github/srwi/ZmkStudio/Keyboard";
+
+/// How often the device is polled for notifications, and how long each poll
+/// may wait for data while holding the client.
+const NOTIFICATION_POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// Request timeout once reads time out after [`NOTIFICATION_POLL_INTERVAL`].
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Transport requirements for a keyboard exported over D-Bus.
+pub trait ServiceTransport: Transport + Send {}
+impl<T: Transport + Send> ServiceTransport for T {}
+
+type SharedClient = Arc<Mutex<StudioClient<Box<dyn ServiceTransport>>>>;
+
+/// Errors from running the D-Bus service.
+#[derive(Debug)]
+pub enum DbusServiceError {
+    Zbus(zbus::Error),
+    Client(ClientError),
+    ClientPoisoned,
+}
+
+impl std::fmt::Display for DbusServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Zbus(err) => write!(f, "D-Bus error: {err}"),
+            Self::Client(err) => write!(f, "Client error: {err}"),
+            Self::ClientPoisoned => write!(f, "Client mutex is poisoned"),
+        }
+    }
+}
+
+impl std::error::Error for DbusServiceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Zbus(err) => Some(err),
+            Self::Client(err) => Some(err),
+            Self::ClientPoisoned => None,
+        }
+    }
+}
+
+impl From<zbus::Error> for DbusServiceError {
+    fn from(value: zbus::Error) -> Self {
+        Self::Zbus(value)
+    }
+}
+
+impl From<ClientError> for DbusServiceError {
+    fn from(value: ClientError) -> Self {
+        Self::Client(value)
+    }
+}
+
+/// D-Bus object wrapping a connected keyboard.
+///
+/// Exported as `io.github.srwi.ZmkStudio.Keyboard1`. Device notifications are
+/// re-emitted as the `LockStateChanged` and `UnsavedChangesChanged` signals.
+pub struct KeyboardService {
+    client: SharedClient,
+}
+
+#[interface(name = "io.github.srwi.ZmkStudio.Keyboard1")]
+impl KeyboardService {
+    /// Returns the device name and the serial number as a hex string.
+    ///
+    /// The serial number honors the redaction mode passed to [`serve_with_redaction`].
+    fn get_device_info(&self) -> fdo::Result<(String, String)> {
+        self.with_client(|client| {
+            let info = client.get_device_info()?;
+            let serial = client.serial_redaction().apply(&info.serial_number);
+            Ok((info.name, serial))
+        })
+    }
+
+    /// Returns `"locked"` or `"unlocked"`.
+    fn get_lock_state(&self) -> fdo::Result<String> {
+        let state = self.with_client(|client| client.get_lock_state())?;
+        Ok(lock_state_label(state).to_string())
+    }
+
+    /// Returns every layer as `(id, name, [(behavior_id, param1, param2)])`.
+    #[allow(clippy::type_complexity)]
+    fn get_keymap(&self) -> fdo::Result<Vec<(u32, String, Vec<(i32, u32, u32)>)>> {
+        let keymap = self.with_client(|client| client.get_keymap())?;
+        Ok(keymap
+            .layers
+            .into_iter()
+            .map(|layer| {
+                let bindings = layer
+                    .bindings
+                    .iter()
+                    .map(|b| (b.behavior_id, b.param1, b.param2))
+                    .collect();
+                (layer.id, layer.name, bindings)
+            })
+            .collect())
+    }
+
+    /// Sets a raw behavior binding for a specific layer position.
+    fn set_layer_binding(
+        &self,
+        layer_id: u32,
+        key_position: i32,
+        behavior_id: i32,
+        param1: u32,
+        param2: u32,
+    ) -> fdo::Result<()> {
+        let binding = zmk::keymap::BehaviorBinding {
+            behavior_id,
+            param1,
+            param2,
+        };
+        self.with_client(|client| client.set_layer_binding(layer_id, key_position, binding))
+    }
+
+    fn check_unsaved_changes(&self) -> fdo::Result<bool> {
+        self.with_client(|client| client.check_unsaved_changes())
+    }
+
+    fn save_changes(&self) -> fdo::Result<()> {
+        self.with_client(|client| client.save_changes())
+    }
+
+    fn discard_changes(&self) -> fdo::Result<bool> {
+        self.with_client(|client| client.discard_changes())
+    }
+
+    #[zbus(signal)]
+    async fn lock_state_changed(emitter: &SignalEmitter<'_>, state: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn unsaved_changes_changed(
+        emitter: &SignalEmitter<'_>,
+        has_changes: bool,
+    ) -> zbus::Result<()>;
+}
+
+impl KeyboardService {
+    fn with_client<R>(
+        &self,
+        f: impl FnOnce(&mut StudioClient<Box<dyn ServiceTransport>>) -> Result<R, ClientError>,
+    ) -> fdo::Result<R> {
+        let mut client = lock_client(&self.client)
+            .map_err(|_| fdo::Error::Failed("client mutex is poisoned".to_string()))?;
+        f(&mut client).map_err(|err| fdo::Error::Failed(err.to_string()))
+    }
+}
+
+/// Exports `transport` on the session bus and forwards device notifications as signals.
+///
+/// Blocks until the transport fails; read timeouts while waiting for
+/// notifications are not treated as failures. The transport's read timeout
+/// is shortened so that polling for notifications does not hold up D-Bus
+/// method calls; transports with a fixed read timeout delay them by up to
+/// that timeout.
+pub fn serve<T: ServiceTransport + 'static>(transport: T) -> Result<(), DbusServiceError> {
+    serve_with_redaction(transport, SerialRedaction::None)
+}
+
+/// Like [`serve`], redacting the serial number reported by `GetDeviceInfo`.
+pub fn serve_with_redaction<T: ServiceTransport + 'static>(
+    transport: T,
+    redaction: SerialRedaction,
+) -> Result<(), DbusServiceError> {
+    let client: SharedClient = Arc::new(Mutex::new(service_client(Box::new(transport), redaction)));
+    let service = KeyboardService {
+        client: Arc::clone(&client),
+    };
+
+    let connection = connection::Builder::session()?
+        .name(DBUS_SERVICE_NAME)?
+        .serve_at(DBUS_OBJECT_PATH, service)?
+        .build()?;
+    let iface = connection
+        .object_server()
+        .interface::<_, KeyboardService>(DBUS_OBJECT_PATH)?;
+
+    loop {
+        for notification in poll_device(&client)? {
+            emit_notification(&iface, notification)?;
+        }
+
+        // Give pending D-Bus method calls a chance to take the client lock.
+        thread::sleep(NOTIFICATION_POLL_INTERVAL);
+    }
+}
+
+fn service_client(
+    mut transport: Box<dyn ServiceTransport>,
+    redaction: SerialRedaction,
+) -> StudioClient<Box<dyn ServiceTransport>> {
+    let short_reads = transport
+        .set_read_timeout(NOTIFICATION_POLL_INTERVAL)
+        .is_ok();
+    let mut client = StudioClient::new(transport);
+    if short_reads {
+        // Reads now time out long before the device answers a slow request.
+        client.set_request_timeout(Some(REQUEST_TIMEOUT));
+    }
+    client.set_serial_redaction(redaction);
+    client
+}
+
+/// Reads what the device has sent and returns the queued notifications,
+/// including those that arrived during method calls.
+///
+/// Holds the client only until a read times out.
+fn poll_device(client: &SharedClient) -> Result<Vec<studio::Notification>, DbusServiceError> {
+    let mut client = lock_client(client)?;
+    client.poll_notifications()?;
+    Ok(std::iter::from_fn(|| client.next_notification()).collect())
+}
+
+fn emit_notification(
+    iface: &InterfaceRef<KeyboardService>,
+    notification: studio::Notification,
+) -> zbus::Result<()> {
+    let emitter = iface.signal_emitter();
+    match notification.subsystem {
+        Some(studio::notification::Subsystem::Core(core)) => match core.notification_type {
+            Some(zmk::core::notification::NotificationType::LockStateChanged(raw)) => {
+                let Ok(state) = zmk::core::LockState::try_from(raw) else {
+                    return Ok(());
+                };
+                zbus::block_on(KeyboardService::lock_state_changed(
+                    emitter,
+                    lock_state_label(state),
+                ))
+            }
+            None => Ok(()),
+        },
+        Some(studio::notification::Subsystem::Keymap(keymap)) => match keymap.notification_type {
+            Some(zmk::keymap::notification::NotificationType::UnsavedChangesStatusChanged(
+                has_changes,
+            )) => zbus::block_on(KeyboardService::unsaved_changes_changed(
+                emitter,
+                has_changes,
+            )),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    }
+}
+
+fn lock_state_label(state: zmk::core::LockState) -> &'static str {
+    match state {
+        zmk::core::LockState::ZmkStudioCoreLockStateLocked => "locked",
+        zmk::core::LockState::ZmkStudioCoreLockStateUnlocked => "unlocked",
+    }
+}
+
+fn lock_client(
+    client: &SharedClient,
+) -> Result<MutexGuard<'_, StudioClient<Box<dyn ServiceTransport>>>, DbusServiceError> {
+    client.lock().map_err(|_| DbusServiceError::ClientPoisoned)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{KeyboardService, REQUEST_TIMEOUT, SharedClient, poll_device, service_client};
+    use crate::privacy::SerialRedaction;
+    use crate::proto::zmk;
+    use crate::proto::zmk::studio;
+    use crate::transport::mock::MockDevice;
+    use crate::transport::recording::ReplayTransport;
+
+    fn shared(device: MockDevice, redaction: SerialRedaction) -> SharedClient {
+        Arc::new(Mutex::new(service_client(Box::new(device), redaction)))
+    }
+
+    #[test]
+    fn forwards_device_notifications_once() {
+        let mut device = MockDevice::new();
+        device.set_locked(true);
+        let client = shared(device, SerialRedaction::None);
+
+        let notifications = poll_device(&client).expect("poll");
+        assert!(matches!(
+            notifications.as_slice(),
+            [studio::Notification {
+                subsystem: Some(studio::notification::Subsystem::Core(_)),
+            }]
+        ));
+        assert!(poll_device(&client).expect("poll").is_empty());
+    }
+
+    #[test]
+    fn serves_keymap_edits_and_redacted_device_info() {
+        let service = KeyboardService {
+            client: shared(MockDevice::new(), SerialRedaction::Truncate { visible: 2 }),
+        };
+
+        let (name, serial) = service.get_device_info().expect("device info");
+        assert_eq!(
+            (name.as_str(), serial.as_str()),
+            ("Mock Keyboard", "******4b")
+        );
+        assert_eq!(service.get_lock_state().expect("lock state"), "unlocked");
+
+        service
+            .set_layer_binding(0, 1, 3, 0, 0)
+            .expect("set binding");
+        assert!(service.check_unsaved_changes().expect("status"));
+        assert_eq!(service.get_keymap().expect("keymap")[0].2[1], (3, 0, 0));
+        assert!(service.discard_changes().expect("discard"));
+        assert!(!service.check_unsaved_changes().expect("status"));
+
+        // The edits raised unsaved-changes notifications for the signal loop.
+        let notifications = poll_device(&service.client).expect("poll");
+        assert!(notifications.iter().all(|notification| matches!(
+            notification.subsystem,
+            Some(studio::notification::Subsystem::Keymap(
+                zmk::keymap::Notification {
+                    notification_type: Some(
+                        zmk::keymap::notification::NotificationType::UnsavedChangesStatusChanged(_)
+                    ),
+                }
+            ))
+        )));
+        assert_eq!(notifications.len(), 2);
+    }
+
+    #[test]
+    fn bounds_requests_only_when_reads_are_shortened() {
+        let client = service_client(Box::new(MockDevice::new()), SerialRedaction::None);
+        assert_eq!(client.request_timeout(), Some(REQUEST_TIMEOUT));
+
+        let fixed = ReplayTransport::from_capture("").expect("empty capture");
+        let client = service_client(Box::new(fixed), SerialRedaction::None);
+        assert_eq!(client.request_timeout(), None);
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+
+use crate::keymap_export::{
+    BindingDocument, KEYMAP_DOCUMENT_VERSION, KeymapDocument, LayerDocument,
+};
+use crate::zmk_syntax::{ZmkSyntaxError, format_binding, is_builtin_behavior, parse_binding};
+
+/// Reads the layers of a ZMK `.keymap` devicetree file into a [`KeymapDocument`]
+/// for [`crate::StudioClient::import_keymap`].
+///
+/// Layers are taken from the node with `compatible = "zmk,keymap"`, in order,
+/// and get their index as ID. A layer is named by its `display-name` (or
+/// legacy `label`) property, falling back to its node name. Comments and
+/// `#include`s are skipped, and simple `#define NAME value` macros are expanded
+/// inside bindings, so `&mo NAV` works with `#define NAV 2`. Layer parameters
+/// may also name a layer directly.
+///
+/// Bindings to behaviors defined in the keymap itself, such as macros or custom
+/// hold-taps, cannot be set over Studio RPC by name; they are kept in the
+/// document with `&name` as their behavior, and the import reports them as
+/// unsupported.
+pub fn parse_keymap(text: &str) -> Result<KeymapDocument, ZmkSyntaxError> {
+    let (text, defines) = preprocess(text);
+    let keymap = keymap_body(&text)
+        .ok_or_else(|| ZmkSyntaxError::new("no node with compatible = \"zmk,keymap\""))?;
+
+    let nodes = child_nodes(keymap)?;
+    let names: Vec<(String, String)> = nodes
+        .iter()
+        .map(|(node, body)| {
+            let display_name = properties(body)
+                .into_iter()
+                .find(|(name, _)| *name == "display-name" || *name == "label")
+                .map(|(_, value)| unquote(value));
+            (
+                node.to_string(),
+                display_name.unwrap_or_else(|| node.to_string()),
+            )
+        })
+        .collect();
+    let layer_index = |word: &str| {
+        names
+            .iter()
+            .position(|(node, display_name)| node == word || display_name == word)
+            .map(|index| index as u32)
+    };
+
+    let mut layers = Vec::with_capacity(nodes.len());
+    for (index, (_, body)) in nodes.iter().enumerate() {
+        let name = &names[index].1;
+        let Some((_, bindings)) = properties(body)
+            .into_iter()
+            .find(|(name, _)| *name == "bindings")
+        else {
+            return Err(ZmkSyntaxError::new("missing bindings property").in_layer(name));
+        };
+        let bindings = bindings
+            .trim()
+            .strip_prefix('<')
+            .and_then(|value| value.strip_suffix('>'))
+            .ok_or_else(|| {
+                ZmkSyntaxError::new("bindings must be a `< ... >` list").in_layer(name)
+            })?;
+
+        let bindings = split_bindings(bindings)
+            .into_iter()
+            .map(|binding| {
+                binding_document(&expand(binding, &defines), &layer_index)
+                    .map_err(|err| err.in_layer(name))
+            })
+            .collect::<Result<_, _>>()?;
+        layers.push(LayerDocument {
+            id: index as u32,
+            name: name.clone(),
+            bindings,
+        });
+    }
+
+    Ok(KeymapDocument {
+        version: KEYMAP_DOCUMENT_VERSION,
+        layers,
+    })
+}
+
+fn binding_document(
+    text: &str,
+    layer_index: &dyn Fn(&str) -> Option<u32>,
+) -> Result<BindingDocument, ZmkSyntaxError> {
+    let name = text
+        .trim_start_matches('&')
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default();
+    if !is_builtin_behavior(name) {
+        return Ok(BindingDocument {
+            binding: text.to_string(),
+            behavior: Some(format!("&{name}")),
+            behavior_id: -1,
+            param1: 0,
+            param2: 0,
+        });
+    }
+
+    let behavior = parse_binding(text, layer_index)?;
+    let (param1, param2) = behavior.params();
+    let role = behavior.role();
+    Ok(BindingDocument {
+        binding: format_binding(&behavior),
+        behavior: role.map(|role| role.display_name().to_string()),
+        behavior_id: match behavior {
+            crate::Behavior::Unknown { behavior_id, .. } => behavior_id,
+            _ => -1,
+        },
+        param1,
+        param2,
+    })
+}
+
+/// Strips comments and preprocessor lines, returning the remaining text and
+/// the object-like `#define`s.
+fn preprocess(text: &str) -> (String, HashMap<String, String>) {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("//") {
+            rest = tail.find('\n').map_or("", |end| &tail[end..]);
+        } else if let Some(tail) = rest.strip_prefix("/*") {
+            rest = tail.find("*/").map_or("", |end| &tail[end + 2..]);
+            stripped.push(' ');
+        } else if let Some(tail) = rest.strip_prefix('"') {
+            let end = tail.find('"').map_or(tail.len(), |end| end + 1);
+            stripped.push('"');
+            stripped.push_str(&tail[..end]);
+            rest = &tail[end..];
+        } else {
+            let c = rest.chars().next().expect("rest is not empty");
+            stripped.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    let mut defines = HashMap::new();
+    let mut text = String::with_capacity(stripped.len());
+    for line in stripped.lines() {
+        let trimmed = line.trim_start();
+        if let Some(directive) = trimmed.strip_prefix('#') {
+            let mut words = directive.trim_start().splitn(3, char::is_whitespace);
+            if let (Some("define"), Some(name), Some(value)) =
+                (words.next(), words.next(), words.next())
+                && !name.contains('(')
+            {
+                defines.insert(name.to_string(), value.trim().to_string());
+            }
+        } else {
+            text.push_str(line);
+        }
+        text.push('\n');
+    }
+    (text, defines)
+}
+
+/// Body of the node declaring `compatible = "zmk,keymap"`.
+fn keymap_body(text: &str) -> Option<&str> {
+    let compatible = text.find("\"zmk,keymap\"")?;
+    let mut depth = 0;
+    let open = text[..compatible].rfind(|c| {
+        match c {
+            '}' => depth += 1,
+            '{' if depth == 0 => return true,
+            '{' => depth -= 1,
+            _ => {}
+        }
+        false
+    })?;
+    let close = matching_brace(text, open)?;
+    Some(&text[open + 1..close])
+}
+
+/// Index of the `}` closing the `{` at `open`.
+fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    for (index, c) in text[open..].char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Child nodes of a node body as `(node name, body)`, without labels.
+fn child_nodes(body: &str) -> Result<Vec<(&str, &str)>, ZmkSyntaxError> {
+    let mut nodes = Vec::new();
+    let mut rest = body;
+    while let Some(end) = rest.find(['{', ';']) {
+        if rest.as_bytes()[end] == b';' {
+            rest = &rest[end + 1..];
+            continue;
+        }
+        let header = rest[..end].trim();
+        let name = header.rsplit(':').next().unwrap_or(header).trim();
+        let close = matching_brace(rest, end)
+            .ok_or_else(|| ZmkSyntaxError::new(format!("unclosed node `{name}`")))?;
+        nodes.push((name, &rest[end + 1..close]));
+        rest = &rest[close + 1..];
+    }
+    Ok(nodes)
+}
+
+/// Properties of a node body as `(name, value)`, skipping child nodes.
+fn properties(body: &str) -> Vec<(&str, &str)> {
+    let mut properties = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    let mut in_string = false;
+    for (index, c) in body.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => depth -= 1,
+            ';' if !in_string && depth == 0 => {
+                let statement = body[start..index].trim();
+                if let Some((name, value)) = statement.split_once('=')
+                    && !value.contains('{')
+                {
+                    properties.push((name.trim(), value.trim()));
+                }
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    properties
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+/// Splits a bindings list into single bindings at each top-level `&`.
+fn split_bindings(list: &str) -> Vec<&str> {
+    let mut bindings = Vec::new();
+    let mut start = None;
+    let mut depth = 0;
+    for (index, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '&' if depth == 0 => {
+                if let Some(start) = start {
+                    bindings.push(list[start..index].trim());
+                }
+                start = Some(index);
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = start {
+        bindings.push(list[start..].trim());
+    }
+    bindings
+}
+
+/// Replaces `#define`d words in a binding, normalizing whitespace.
+fn expand(binding: &str, defines: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(binding.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        let mut value = word.as_str();
+        // Bounded, in case of recursive defines.
+        for _ in 0..8 {
+            match defines.get(value) {
+                Some(expanded) => value = expanded,
+                None => break,
+            }
+        }
+        out.push_str(value);
+        word.clear();
+    };
+    for c in binding.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        flush(&mut word, &mut out);
+        if c.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap_export::ImportOptions;
+    use crate::transport::mock::MockDevice;
+    use crate::{Behavior, HidUsage, Keycode, StudioClient};
+
+    const KEYMAP: &str = r#"
+#include <behaviors.dtsi>
+#include <dt-bindings/zmk/keys.h>
+
+#define NAV 1
+
+/ {
+    macros {
+        hello: hello {
+            compatible = "zmk,behavior-macro";
+            #binding-cells = <0>;
+            bindings = <&kp H &kp I>;
+        };
+    };
+
+    keymap {
+        compatible = "zmk,keymap";
+
+        default_layer {
+            display-name = "Base"; // shown in Studio
+            bindings = <
+                &kp A  &mt LSHIFT ESC
+                &mo NAV  /* comment */ &kp LS(N1)
+            >;
+        };
+
+        nav: nav_layer {
+            bindings = <&trans &hello &tog default_layer &bt BT_SEL 0>;
+        };
+    };
+};
+"#;
+
+    #[test]
+    fn parses_layers_from_a_keymap_file() {
+        let document = parse_keymap(KEYMAP).expect("parse");
+        assert_eq!(document.layers.len(), 2);
+        assert_eq!(document.layers[0].name, "Base");
+        assert_eq!(document.layers[1].name, "nav_layer");
+
+        let bindings: Vec<_> = document.layers[0]
+            .bindings
+            .iter()
+            .map(|binding| binding.binding.as_str())
+            .collect();
+        assert_eq!(bindings, ["&kp A", "&mt LSHIFT ESC", "&mo 1", "&kp EXCL"]);
+        assert_eq!(
+            document.layers[0].bindings[2].behavior.as_deref(),
+            Some("Momentary Layer")
+        );
+        assert_eq!(document.layers[0].bindings[2].param1, 1);
+
+        let nav = &document.layers[1].bindings;
+        assert_eq!(nav[1].behavior.as_deref(), Some("&hello"));
+        assert_eq!(nav[2].binding, "&tog 0");
+    }
+
+    #[test]
+    fn imports_a_keymap_file_and_reports_custom_behaviors() {
+        let document = parse_keymap(KEYMAP).expect("parse");
+        let mut client = StudioClient::new(MockDevice::new());
+        let report = client
+            .import_keymap(&document, ImportOptions::default())
+            .expect("import");
+
+        assert_eq!(report.layers_renamed, 1);
+        assert_eq!(
+            report
+                .unsupported
+                .iter()
+                .map(|binding| binding.behavior.as_str())
+                .collect::<Vec<_>>(),
+            ["&hello", "Bluetooth"]
+        );
+        assert_eq!(
+            client.get_key_at(0, 2).expect("get binding"),
+            Behavior::MomentaryLayer { layer_id: 1 }
+        );
+        assert_eq!(
+            client.get_key_at(0, 3).expect("get binding"),
+            Behavior::KeyPress(HidUsage::from_encoded(
+                Keycode::NUMBER_1.to_hid_usage() | (u32::from(crate::MOD_LSFT) << 24)
+            ))
+        );
+    }
+
+    #[test]
+    fn translates_layer_parameters_by_behavior_name() {
+        let document = parse_keymap(KEYMAP).expect("parse");
+        assert!(
+            document
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.bindings)
+                .all(|binding| binding.behavior_id == -1)
+        );
+
+        // Leave the device with layer IDs that differ from the file's indices.
+        let mut client = StudioClient::new(MockDevice::new());
+        client.remove_layer(0).expect("remove layer");
+        client.add_layer().expect("add layer");
+        client
+            .import_keymap(&document, ImportOptions::default())
+            .expect("import");
+
+        let keymap = client.get_typed_keymap().expect("keymap");
+        let ids: Vec<u32> = keymap.layers.iter().map(|layer| layer.id).collect();
+        assert_eq!(ids, [2, 1]);
+        assert_eq!(
+            keymap.layers[0].bindings[2],
+            Behavior::MomentaryLayer { layer_id: 1 }
+        );
+        assert_eq!(
+            keymap.layers[1].bindings[2],
+            Behavior::ToggleLayer { layer_id: 2 }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_builtin_bindings() {
+        let err = parse_keymap(
+            r#"/ { keymap { compatible = "zmk,keymap"; base { bindings = <&kp>; }; }; };"#,
+        )
+        .expect_err("invalid binding");
+        assert_eq!(err.layer.as_deref(), Some("base"));
+        assert!(parse_keymap("/ { };").is_err());
+    }
+}
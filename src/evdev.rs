@@ -0,0 +1,168 @@
+use crate::hid_usage::{
+    HID_USAGE_CONSUMER, HID_USAGE_GENERIC_DESKTOP, HID_USAGE_KEYBOARD, HidUsage,
+};
+use crate::keycode::Keycode;
+
+/// Linux evdev key code for each HID keyboard-page usage ID (`0` when unmapped).
+///
+/// Mirrors the `hid_keyboard` table in the kernel's `drivers/hid/hid-input.c`.
+#[rustfmt::skip]
+const HID_KEYBOARD_TO_EVDEV: [u16; 256] = [
+      0,   0,   0,   0,  30,  48,  46,  32,  18,  33,  34,  35,  23,  36,  37,  38,
+     50,  49,  24,  25,  16,  19,  31,  20,  22,  47,  17,  45,  21,  44,   2,   3,
+      4,   5,   6,   7,   8,   9,  10,  11,  28,   1,  14,  15,  57,  12,  13,  26,
+     27,  43,  43,  39,  40,  41,  51,  52,  53,  58,  59,  60,  61,  62,  63,  64,
+     65,  66,  67,  68,  87,  88,  99,  70, 119, 110, 102, 104, 111, 107, 109, 106,
+    105, 108, 103,  69,  98,  55,  74,  78,  96,  79,  80,  81,  75,  76,  77,  71,
+     72,  73,  82,  83,  86, 127, 116, 117, 183, 184, 185, 186, 187, 188, 189, 190,
+    191, 192, 193, 194, 134, 138, 130, 132, 128, 129, 131, 137, 133, 135, 136, 113,
+    115, 114,   0,   0,   0, 121,   0,  89,  93, 124,  92,  94,  95,   0,   0,   0,
+    122, 123,  90,  91,  85,   0,   0,   0,   0,   0,   0,   0, 111,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0, 179, 180,   0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,   0, 111,   0,   0,   0,   0,   0,   0,   0,
+     29,  42,  56, 125,  97,  54, 100, 126, 164, 166, 165, 163, 161, 115, 114, 113,
+    150, 158, 159, 128, 136, 177, 178, 176, 142, 152, 173, 140,   0,   0,   0,   0,
+];
+
+/// Returns the HID keyboard-page usage ID for an evdev key code.
+///
+/// Codes reachable from several usages resolve to the lowest usage ID.
+pub(crate) fn evdev_to_hid_keyboard(code: u16) -> Option<u16> {
+    if code == 0 {
+        return None;
+    }
+    HID_KEYBOARD_TO_EVDEV
+        .iter()
+        .position(|&c| c == code)
+        .map(|usage_id| usage_id as u16)
+}
+
+/// Linux evdev key codes for the consumer-page usages ZMK keymaps commonly bind.
+///
+/// Pairs of `(usage ID, evdev code)`, taken from the kernel's consumer-page mapping.
+const HID_CONSUMER_TO_EVDEV: &[(u16, u16)] = &[
+    (0x006F, 225), // BRIGHTNESSUP
+    (0x0070, 224), // BRIGHTNESSDOWN
+    (0x00B0, 207), // PLAY
+    (0x00B1, 201), // PAUSECD
+    (0x00B3, 208), // FASTFORWARD
+    (0x00B4, 168), // REWIND
+    (0x00B5, 163), // NEXTSONG
+    (0x00B6, 165), // PREVIOUSSONG
+    (0x00B7, 166), // STOPCD
+    (0x00B8, 161), // EJECTCD
+    (0x00CD, 164), // PLAYPAUSE
+    (0x00E2, 113), // MUTE
+    (0x00E9, 115), // VOLUMEUP
+    (0x00EA, 114), // VOLUMEDOWN
+    (0x0183, 171), // CONFIG
+    (0x018A, 155), // MAIL
+    (0x0192, 140), // CALC
+    (0x0194, 144), // FILE
+    (0x0221, 217), // SEARCH
+    (0x0223, 172), // HOMEPAGE
+    (0x0224, 158), // BACK
+    (0x0225, 159), // FORWARD
+    (0x0226, 128), // STOP
+    (0x0227, 173), // REFRESH
+    (0x022A, 156), // BOOKMARKS
+];
+
+/// Linux evdev key codes for the Generic Desktop system-control usages, as `(usage ID, evdev code)`.
+const HID_GENERIC_DESKTOP_TO_EVDEV: &[(u16, u16)] = &[
+    (0x0081, 116), // POWER
+    (0x0082, 142), // SLEEP
+    (0x0083, 143), // WAKEUP
+];
+
+impl HidUsage {
+    /// Returns the Linux evdev `KEY_*` code for this usage's base key.
+    ///
+    /// Modifiers are ignored, since evdev reports them as separate keys.
+    /// Returns `None` for usages the kernel does not map to a key code.
+    pub fn to_evdev(self) -> Option<u16> {
+        let code = match self.page() {
+            HID_USAGE_KEYBOARD => *HID_KEYBOARD_TO_EVDEV.get(usize::from(self.id()))?,
+            HID_USAGE_CONSUMER => HID_CONSUMER_TO_EVDEV
+                .iter()
+                .find(|(usage_id, _)| *usage_id == self.id())
+                .map(|(_, code)| *code)?,
+            HID_USAGE_GENERIC_DESKTOP => HID_GENERIC_DESKTOP_TO_EVDEV
+                .iter()
+                .find(|(usage_id, _)| *usage_id == self.id())
+                .map(|(_, code)| *code)?,
+            _ => return None,
+        };
+        (code != 0).then_some(code)
+    }
+
+    /// Converts a Linux evdev `KEY_*` code into a HID usage without modifiers.
+    ///
+    /// Keyboard-page usages are preferred over consumer-page and Generic Desktop
+    /// usages when the kernel maps both to the same code (for example `KEY_MUTE`
+    /// or `KEY_POWER`).
+    pub fn from_evdev(code: u16) -> Option<Self> {
+        if let Some(usage_id) = evdev_to_hid_keyboard(code) {
+            return Some(Self::from_parts(HID_USAGE_KEYBOARD, usage_id, 0));
+        }
+        [
+            (HID_USAGE_CONSUMER, HID_CONSUMER_TO_EVDEV),
+            (HID_USAGE_GENERIC_DESKTOP, HID_GENERIC_DESKTOP_TO_EVDEV),
+        ]
+        .into_iter()
+        .find_map(|(page, table)| {
+            table
+                .iter()
+                .find(|(_, other)| *other == code)
+                .map(|(usage_id, _)| Self::from_parts(page, *usage_id, 0))
+        })
+    }
+}
+
+impl Keycode {
+    /// Returns the Linux evdev `KEY_*` code for this keycode's base key.
+    ///
+    /// See [`HidUsage::to_evdev`].
+    pub fn to_evdev(self) -> Option<u16> {
+        HidUsage::from_encoded(self.to_hid_usage()).to_evdev()
+    }
+
+    /// Converts a Linux evdev `KEY_*` code into a known [`Keycode`].
+    pub fn from_evdev(code: u16) -> Option<Self> {
+        HidUsage::from_evdev(code)?.known_keycode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_keycodes_and_evdev() {
+        assert_eq!(Keycode::A.to_evdev(), Some(30));
+        assert_eq!(Keycode::from_evdev(30), Some(Keycode::A));
+        assert_eq!(Keycode::from_evdev(42), Some(Keycode::LEFT_SHIFT));
+
+        let volume_up = HidUsage::from_parts(HID_USAGE_CONSUMER, 0xE9, 0);
+        assert_eq!(volume_up.to_evdev(), Some(115));
+        assert_eq!(
+            HidUsage::from_evdev(115).map(HidUsage::page),
+            Some(HID_USAGE_KEYBOARD)
+        );
+        assert_eq!(
+            HidUsage::from_evdev(225),
+            Some(HidUsage::from_parts(HID_USAGE_CONSUMER, 0x6F, 0))
+        );
+
+        assert_eq!(
+            HidUsage::from_parts(HID_USAGE_KEYBOARD, 0x03, 0).to_evdev(),
+            None
+        );
+        assert_eq!(HidUsage::from_evdev(0), None);
+
+        assert_eq!(Keycode::SYSTEM_SLEEP.to_evdev(), Some(142));
+        assert_eq!(Keycode::from_evdev(143), Some(Keycode::SYSTEM_WAKE_UP));
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use crate::binding::Behavior;
+
+/// A keymap mutation performed through a [`crate::StudioClient`].
+///
+/// Emitted after the device confirms the change, to every listener added with
+/// [`crate::StudioClient::add_change_listener`] or
+/// [`crate::StudioClient::subscribe_changes`]. Changes made by other clients
+/// or on the keyboard itself are not reported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeymapChange {
+    KeySet {
+        layer_id: u32,
+        position: i32,
+        behavior: Behavior,
+    },
+    LayerAdded {
+        layer_id: u32,
+        index: u32,
+    },
+    /// `layer_id` is `None` if the layer could not be read before removal.
+    LayerRemoved {
+        layer_id: Option<u32>,
+        index: u32,
+    },
+    LayerRestored {
+        layer_id: u32,
+        index: u32,
+    },
+    LayerRenamed {
+        layer_id: u32,
+        name: String,
+    },
+    LayerMoved {
+        start_index: u32,
+        dest_index: u32,
+    },
+    PhysicalLayoutSelected {
+        index: u32,
+    },
+    Saved,
+    /// `had_changes` is whether the device reported anything to discard.
+    Discarded {
+        had_changes: bool,
+    },
+}
+
+impl std::fmt::Display for KeymapChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KeySet {
+                layer_id,
+                position,
+                behavior,
+            } => write!(f, "set {layer_id}/{position} to {behavior:?}"),
+            Self::LayerAdded { layer_id, index } => {
+                write!(f, "added layer {layer_id} at index {index}")
+            }
+            Self::LayerRemoved {
+                layer_id: Some(layer_id),
+                index,
+            } => write!(f, "removed layer {layer_id} from index {index}"),
+            Self::LayerRemoved {
+                layer_id: None,
+                index,
+            } => write!(f, "removed layer at index {index}"),
+            Self::LayerRestored { layer_id, index } => {
+                write!(f, "restored layer {layer_id} at index {index}")
+            }
+            Self::LayerRenamed { layer_id, name } => {
+                write!(f, "renamed layer {layer_id} to {name:?}")
+            }
+            Self::LayerMoved {
+                start_index,
+                dest_index,
+            } => write!(f, "moved layer from index {start_index} to {dest_index}"),
+            Self::PhysicalLayoutSelected { index } => {
+                write!(f, "selected physical layout {index}")
+            }
+            Self::Saved => write!(f, "saved changes"),
+            Self::Discarded { had_changes } => {
+                if *had_changes {
+                    write!(f, "discarded changes")
+                } else {
+                    write!(f, "discarded changes (none pending)")
+                }
+            }
+        }
+    }
+}
+
+type Listener<E> = Box<dyn FnMut(&E) -> bool + Send>;
+
+/// Listeners registered on a client; each returns `false` to unsubscribe.
+pub(crate) struct Listeners<E> {
+    listeners: Vec<Listener<E>>,
+}
+
+/// Listeners for [`KeymapChange`]s made through a client.
+pub(crate) type ChangeListeners = Listeners<KeymapChange>;
+
+impl<E: Clone + Send + 'static> Listeners<E> {
+    pub(crate) fn add(&mut self, listener: impl FnMut(&E) -> bool + Send + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Returns a receiver that gets every event until it is dropped.
+    pub(crate) fn subscribe(&mut self) -> Receiver<E> {
+        let (sender, receiver): (Sender<E>, _) = channel();
+        self.add(move |event| sender.send(event.clone()).is_ok());
+        receiver
+    }
+
+    pub(crate) fn emit(&mut self, event: E) {
+        self.listeners.retain_mut(|listener| listener(&event));
+    }
+}
+
+impl<E> Default for Listeners<E> {
+    fn default() -> Self {
+        Self {
+            listeners: Vec::new(),
+        }
+    }
+}
+
+impl<E> std::fmt::Debug for Listeners<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Listeners")
+            .field("len", &self.listeners.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeListeners, KeymapChange};
+
+    #[test]
+    fn drops_disconnected_subscribers() {
+        let mut listeners = ChangeListeners::default();
+        let receiver = listeners.subscribe();
+        let dropped = listeners.subscribe();
+        drop(dropped);
+
+        listeners.emit(KeymapChange::Saved);
+        assert_eq!(receiver.try_recv(), Ok(KeymapChange::Saved));
+        assert_eq!(listeners.listeners.len(), 1);
+    }
+}
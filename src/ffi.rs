@@ -0,0 +1,318 @@
+//! C ABI over [`StudioClient`], consumed by the .NET wrapper in `bindings/dotnet`.
+//!
+//! Every function that can fail returns `ZMK_OK` / a non-negative value on success and
+//! `ZMK_ERROR` (or a null pointer) on failure; [`zmk_last_error`] then describes the failure.
+//! Bindings cross the boundary as ZMK keymap syntax such as `&kp LC(C)`, with layer
+//! parameters given as layer IDs. Strings returned by this module must be released with
+//! [`zmk_string_free`].
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use crate::StudioClient;
+use crate::transport::Transport;
+use crate::transport::mock::MockDevice;
+#[cfg(feature = "serial")]
+use crate::transport::serial::SerialTransport;
+use crate::zmk_syntax::{format_binding, parse_binding};
+
+/// Status returned by successful calls.
+pub const ZMK_OK: i32 = 0;
+/// Status returned by failed calls; see [`zmk_last_error`].
+pub const ZMK_ERROR: i32 = -1;
+
+trait TransportSend: Transport + Send {}
+impl<T: Transport + Send> TransportSend for T {}
+
+/// Opaque client handle created by one of the `zmk_client_open_*` functions.
+pub struct ZmkClient {
+    inner: StudioClient<Box<dyn TransportSend>>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = message.into().replace('\0', " ");
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = CString::new(message).ok());
+}
+
+/// Runs `f`, recording its error (or a panic) as the last error and returning `fallback` instead.
+fn guard<R>(fallback: R, f: impl FnOnce() -> Result<R, String>) -> R {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(message)) => {
+            set_last_error(message);
+            fallback
+        }
+        Err(_) => {
+            set_last_error("panic inside zmk-studio-api");
+            fallback
+        }
+    }
+}
+
+fn into_c_string(text: String) -> Result<*mut c_char, String> {
+    CString::new(text)
+        .map(CString::into_raw)
+        .map_err(|err| err.to_string())
+}
+
+/// # Safety
+///
+/// `text` must be null or a valid NUL-terminated string.
+unsafe fn read_str<'a>(text: *const c_char, what: &str) -> Result<&'a str, String> {
+    if text.is_null() {
+        return Err(format!("{what} must not be null"));
+    }
+    // SAFETY: checked for null above; validity is the caller's contract.
+    unsafe { CStr::from_ptr(text) }
+        .to_str()
+        .map_err(|_| format!("{what} is not valid UTF-8"))
+}
+
+/// # Safety
+///
+/// `client` must be null or a live handle returned by a `zmk_client_open_*` function.
+unsafe fn client_mut<'a>(client: *mut ZmkClient) -> Result<&'a mut ZmkClient, String> {
+    // SAFETY: validity is the caller's contract.
+    unsafe { client.as_mut() }.ok_or_else(|| "client handle must not be null".to_owned())
+}
+
+fn boxed(inner: StudioClient<Box<dyn TransportSend>>) -> *mut ZmkClient {
+    Box::into_raw(Box::new(ZmkClient { inner }))
+}
+
+/// Returns the message of the last error raised on this thread, or null if none.
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn zmk_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Releases a string returned by this library.
+///
+/// # Safety
+///
+/// `text` must be null or a string returned by this library that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the string was created by `CString::into_raw` in this module.
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Opens a client on a serial port such as `COM5` or `/dev/ttyACM0`. Returns null on failure.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_open_serial(path: *const c_char) -> *mut ZmkClient {
+    guard(ptr::null_mut(), || {
+        // SAFETY: forwarded caller contract.
+        let path = unsafe { read_str(path, "path") }?;
+        open_serial(path)
+    })
+}
+
+#[cfg(feature = "serial")]
+fn open_serial(path: &str) -> Result<*mut ZmkClient, String> {
+    let transport = SerialTransport::open(path)
+        .map_err(|err| format!("failed to open serial transport: {err}"))?;
+    Ok(boxed(StudioClient::new(Box::new(transport))))
+}
+
+#[cfg(not(feature = "serial"))]
+fn open_serial(_path: &str) -> Result<*mut ZmkClient, String> {
+    Err("serial support is disabled for this build".to_owned())
+}
+
+/// Opens a client on an in-process mock keyboard, for testing tools without hardware.
+#[unsafe(no_mangle)]
+pub extern "C" fn zmk_client_open_mock() -> *mut ZmkClient {
+    boxed(StudioClient::new(Box::new(MockDevice::new())))
+}
+
+/// Closes a client and releases its handle.
+///
+/// # Safety
+///
+/// `client` must be null or a live handle; it must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_free(client: *mut ZmkClient) {
+    if !client.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` in this module.
+        drop(unsafe { Box::from_raw(client) });
+    }
+}
+
+/// Returns the `LockState` protobuf value (0 locked, 1 unlocked) or `ZMK_ERROR`.
+///
+/// # Safety
+///
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_get_lock_state(client: *mut ZmkClient) -> i32 {
+    guard(ZMK_ERROR, || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        let state = client
+            .inner
+            .get_lock_state()
+            .map_err(|err| err.to_string())?;
+        Ok(state as i32)
+    })
+}
+
+/// Returns the binding at a layer/key position in ZMK syntax, or null on failure.
+///
+/// # Safety
+///
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_get_key_at(
+    client: *mut ZmkClient,
+    layer_id: u32,
+    key_position: i32,
+) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        let behavior = client
+            .inner
+            .get_key_at(layer_id, key_position)
+            .map_err(|err| err.to_string())?;
+        into_c_string(format_binding(&behavior))
+    })
+}
+
+/// Sets the binding at a layer/key position from ZMK syntax such as `&mt LSHIFT ESC`.
+///
+/// # Safety
+///
+/// `client` must be a live handle and `binding` a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_set_key_at(
+    client: *mut ZmkClient,
+    layer_id: u32,
+    key_position: i32,
+    binding: *const c_char,
+) -> i32 {
+    guard(ZMK_ERROR, || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        // SAFETY: forwarded caller contract.
+        let binding = unsafe { read_str(binding, "binding") }?;
+        let behavior = parse_binding(binding, &|_| None).map_err(|err| err.to_string())?;
+        client
+            .inner
+            .set_key_at(layer_id, key_position, behavior)
+            .map_err(|err| err.to_string())?;
+        Ok(ZMK_OK)
+    })
+}
+
+/// Returns the whole keymap as snapshot text (see [`crate::snapshot`]), or null on failure.
+///
+/// # Safety
+///
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_get_keymap_snapshot(client: *mut ZmkClient) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        let keymap = client
+            .inner
+            .get_typed_keymap()
+            .map_err(|err| err.to_string())?;
+        into_c_string(keymap.to_snapshot_text())
+    })
+}
+
+/// Returns 1 if the device has unsaved changes, 0 if not, or `ZMK_ERROR`.
+///
+/// # Safety
+///
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_check_unsaved_changes(client: *mut ZmkClient) -> i32 {
+    guard(ZMK_ERROR, || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        let unsaved = client
+            .inner
+            .check_unsaved_changes()
+            .map_err(|err| err.to_string())?;
+        Ok(i32::from(unsaved))
+    })
+}
+
+/// Persists pending keymap changes on the device.
+///
+/// # Safety
+///
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_save_changes(client: *mut ZmkClient) -> i32 {
+    guard(ZMK_ERROR, || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        client.inner.save_changes().map_err(|err| err.to_string())?;
+        Ok(ZMK_OK)
+    })
+}
+
+/// Reverts pending keymap changes. Returns 1 if anything was discarded, 0 if not, or `ZMK_ERROR`.
+///
+/// # Safety
+///
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zmk_client_discard_changes(client: *mut ZmkClient) -> i32 {
+    guard(ZMK_ERROR, || {
+        // SAFETY: forwarded caller contract.
+        let client = unsafe { client_mut(client) }?;
+        let discarded = client
+            .inner
+            .discard_changes()
+            .map_err(|err| err.to_string())?;
+        Ok(i32::from(discarded))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_binding_through_the_c_abi() {
+        let client = zmk_client_open_mock();
+        let binding = CString::new("&kp ESC").unwrap();
+        unsafe {
+            assert_eq!(
+                zmk_client_set_key_at(client, 0, 1, binding.as_ptr()),
+                ZMK_OK
+            );
+            assert_eq!(zmk_client_check_unsaved_changes(client), 1);
+
+            let text = zmk_client_get_key_at(client, 0, 1);
+            assert_eq!(CStr::from_ptr(text).to_str().unwrap(), "&kp ESC");
+            zmk_string_free(text);
+
+            assert!(zmk_client_get_key_at(client, 0, 99).is_null());
+            assert!(!zmk_last_error().is_null());
+            zmk_client_free(client);
+        }
+    }
+}
@@ -2,8 +2,9 @@ pub const FRAMING_SOF: u8 = 0xAB;
 pub const FRAMING_ESC: u8 = 0xAC;
 pub const FRAMING_EOF: u8 = 0xAD;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum DecodeState {
+    #[default]
     Idle,
     AwaitingData,
     Escaped,
@@ -44,7 +45,8 @@ pub fn encode_frame(payload: &[u8]) -> Vec<u8> {
     out
 }
 
-#[derive(Debug)]
+/// Incremental decoder that reassembles frames from arbitrarily split chunks.
+#[derive(Debug, Default)]
 pub struct FrameDecoder {
     state: DecodeState,
     data: Vec<u8>,
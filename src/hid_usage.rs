@@ -1,8 +1,18 @@
 use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Sub};
 
 use crate::keycode::Keycode;
+use crate::zmk_syntax::{ZmkSyntaxError, format_usage, parse_usage};
 
+/// Generic Desktop usage page, home of the system power, sleep and wake keys.
+pub const HID_USAGE_GENERIC_DESKTOP: u16 = 0x01;
 pub const HID_USAGE_KEYBOARD: u16 = 0x07;
+#[cfg(any(
+    feature = "evdev-codes",
+    feature = "web-codes",
+    feature = "windows-codes"
+))]
+pub(crate) const HID_USAGE_CONSUMER: u16 = 0x0C;
 
 pub const MOD_LCTL: u8 = 0x01;
 pub const MOD_LSFT: u8 = 0x02;
@@ -13,6 +23,18 @@ pub const MOD_RSFT: u8 = 0x20;
 pub const MOD_RALT: u8 = 0x40;
 pub const MOD_RGUI: u8 = 0x80;
 
+/// ZMK modifier functions such as `LC(...)`, in the order they are applied when formatting.
+pub(crate) const MODIFIER_FUNCTIONS: [(&str, u8); 8] = [
+    ("LC", MOD_LCTL),
+    ("LS", MOD_LSFT),
+    ("LA", MOD_LALT),
+    ("LG", MOD_LGUI),
+    ("RC", MOD_RCTL),
+    ("RS", MOD_RSFT),
+    ("RA", MOD_RALT),
+    ("RG", MOD_RGUI),
+];
+
 /// Lossless decoded ZMK HID usage value (base usage + modifiers).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct HidUsage {
@@ -51,6 +73,22 @@ impl HidUsage {
         }
     }
 
+    /// Parses a key written as in a ZMK keymap, such as `A`, `LS(LC(T))` or
+    /// `0x00070004`, with modifier functions (`LC`, `LS`, `LA`, `LG`, `RC`, `RS`,
+    /// `RA`, `RG`) applied to the key's modifier bits.
+    pub fn parse(text: &str) -> Result<Self, ZmkSyntaxError> {
+        parse_usage(text)
+    }
+
+    /// Formats the key as in a ZMK keymap; the inverse of [`HidUsage::parse`].
+    ///
+    /// Keys with a name of their own, such as `EXCL`, use it; otherwise the
+    /// base key is wrapped in modifier functions. Unknown usages are written
+    /// as hex.
+    pub fn to_zmk_string(self) -> String {
+        format_usage(self)
+    }
+
     pub fn to_hid_usage(self) -> u32 {
         ((self.modifiers as u32) << 24) | ((self.page as u32) << 16) | self.id as u32
     }
@@ -83,6 +121,78 @@ impl HidUsage {
         Keycode::from_hid_usage(self.base().to_hid_usage())
     }
 
+    /// Splits the usage into its base keycode (if known) and its modifiers.
+    pub fn split(self) -> (Option<Keycode>, ModifierSet) {
+        (self.known_base_keycode(), self.modifier_set())
+    }
+
+    pub fn modifier_set(self) -> ModifierSet {
+        ModifierSet::from_bits(self.modifiers)
+    }
+
+    /// Returns the same base usage with its modifiers replaced by `modifiers`.
+    pub fn with_modifier_set(self, modifiers: ModifierSet) -> Self {
+        Self {
+            modifiers: modifiers.bits(),
+            ..self
+        }
+    }
+
+    /// Adds `modifiers`, given as a [`ModifierSet`] or `MOD_*` bits, to the usage.
+    ///
+    /// ```
+    /// use zmk_studio_api::{HidUsage, Keycode, MOD_LALT};
+    ///
+    /// let reopen_tab = HidUsage::from(Keycode::T).with_lctrl().with_lshift();
+    /// assert_eq!(reopen_tab.to_zmk_string(), "LS(LC(T))");
+    /// assert_eq!(reopen_tab.with(MOD_LALT).without_modifiers(), HidUsage::from(Keycode::T));
+    /// ```
+    pub fn with(self, modifiers: impl Into<ModifierSet>) -> Self {
+        self.with_modifier_set(self.modifier_set() | modifiers.into())
+    }
+
+    /// Removes `modifiers` from the usage, keeping any others.
+    pub fn without(self, modifiers: impl Into<ModifierSet>) -> Self {
+        self.with_modifier_set(self.modifier_set() - modifiers.into())
+    }
+
+    /// Returns the base usage with no modifiers; the same as [`HidUsage::base`].
+    pub fn without_modifiers(self) -> Self {
+        self.base()
+    }
+
+    pub fn with_lctrl(self) -> Self {
+        self.with(ModifierSet::LCTL)
+    }
+
+    pub fn with_lshift(self) -> Self {
+        self.with(ModifierSet::LSFT)
+    }
+
+    pub fn with_lalt(self) -> Self {
+        self.with(ModifierSet::LALT)
+    }
+
+    pub fn with_lgui(self) -> Self {
+        self.with(ModifierSet::LGUI)
+    }
+
+    pub fn with_rctrl(self) -> Self {
+        self.with(ModifierSet::RCTL)
+    }
+
+    pub fn with_rshift(self) -> Self {
+        self.with(ModifierSet::RSFT)
+    }
+
+    pub fn with_ralt(self) -> Self {
+        self.with(ModifierSet::RALT)
+    }
+
+    pub fn with_rgui(self) -> Self {
+        self.with(ModifierSet::RGUI)
+    }
+
     pub fn modifier_labels(self) -> Vec<&'static str> {
         let mut labels = Vec::new();
         let mods = self.modifiers;
@@ -114,6 +224,25 @@ impl HidUsage {
     }
 }
 
+impl From<Keycode> for HidUsage {
+    fn from(keycode: Keycode) -> Self {
+        Self::from_encoded(keycode.to_hid_usage())
+    }
+}
+
+/// Orders usages by their encoded value (modifiers, then page, then usage ID).
+impl Ord for HidUsage {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_hid_usage().cmp(&other.to_hid_usage())
+    }
+}
+
+impl PartialOrd for HidUsage {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for HidUsage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(keycode) = self.known_keycode() {
@@ -130,3 +259,163 @@ impl fmt::Display for HidUsage {
         )
     }
 }
+
+/// Set of the eight HID modifier keys, as carried in a ZMK usage's top byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ModifierSet(u8);
+
+impl ModifierSet {
+    pub const EMPTY: Self = Self(0);
+    pub const LCTL: Self = Self(MOD_LCTL);
+    pub const LSFT: Self = Self(MOD_LSFT);
+    pub const LALT: Self = Self(MOD_LALT);
+    pub const LGUI: Self = Self(MOD_LGUI);
+    pub const RCTL: Self = Self(MOD_RCTL);
+    pub const RSFT: Self = Self(MOD_RSFT);
+    pub const RALT: Self = Self(MOD_RALT);
+    pub const RGUI: Self = Self(MOD_RGUI);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// Number of modifiers in the set.
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// ZMK modifier function names (`LC`, `LS`, ...) of the modifiers in the set.
+    pub fn function_names(self) -> impl Iterator<Item = &'static str> {
+        MODIFIER_FUNCTIONS
+            .iter()
+            .filter(move |(_, bit)| self.0 & bit != 0)
+            .map(|(name, _)| *name)
+    }
+
+    /// Wraps `key` in ZMK modifier functions, e.g. `LS(LC(T))` for Ctrl+Shift and `T`.
+    pub fn wrap(self, key: &str) -> String {
+        self.function_names()
+            .fold(key.to_string(), |text, function| {
+                format!("{function}({text})")
+            })
+    }
+}
+
+impl fmt::Display for ModifierSet {
+    /// Formats the set as `+`-separated ZMK modifier functions, e.g. `LC+LS`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self.function_names().collect();
+        f.write_str(&names.join("+"))
+    }
+}
+
+/// Converts `MOD_*` bits, such as `MOD_LCTL | MOD_LSFT`.
+impl From<u8> for ModifierSet {
+    fn from(bits: u8) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl BitOr for ModifierSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ModifierSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for ModifierSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Sub for ModifierSet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_and_edits_modifiers() {
+        let usage = HidUsage::from_encoded(Keycode::T.to_hid_usage())
+            .with_modifier_set(ModifierSet::LCTL | ModifierSet::LSFT);
+        let (base, modifiers) = usage.split();
+        assert_eq!(base, Some(Keycode::T));
+        assert!(modifiers.contains(ModifierSet::LSFT));
+        assert_eq!(modifiers.len(), 2);
+        assert_eq!(modifiers.wrap("T"), "LS(LC(T))");
+        assert_eq!(modifiers.to_string(), "LC+LS");
+
+        let edited = usage.with_modifier_set(modifiers - ModifierSet::LCTL);
+        assert_eq!(edited.modifier_set(), ModifierSet::LSFT);
+        assert_eq!(edited.base(), usage.base());
+    }
+
+    #[test]
+    fn composes_modifiers_fluently() {
+        let usage = HidUsage::from(Keycode::T).with_lctrl().with(MOD_LSFT);
+        assert_eq!(usage.modifier_set(), ModifierSet::LCTL | ModifierSet::LSFT);
+        assert_eq!(usage.without(ModifierSet::LCTL).modifiers(), MOD_LSFT);
+        assert_eq!(
+            usage.with_rgui().with(MOD_RALT | MOD_RSFT).modifiers(),
+            MOD_LCTL | MOD_LSFT | MOD_RGUI | MOD_RALT | MOD_RSFT
+        );
+        assert_eq!(usage.without_modifiers(), HidUsage::from(Keycode::T));
+    }
+
+    #[test]
+    fn parses_and_formats_modifier_functions() {
+        let usage = HidUsage::parse("LS(LC(T))").expect("parse");
+        assert_eq!(usage.base().known_keycode(), Some(Keycode::T));
+        assert_eq!(usage.modifiers(), MOD_LCTL | MOD_LSFT);
+        assert_eq!(usage.to_zmk_string(), "LS(LC(T))");
+        assert_eq!(HidUsage::parse(&usage.to_zmk_string()), Ok(usage));
+
+        assert_eq!(
+            HidUsage::parse("RA(0x0007002C)").map(|usage| usage.to_hid_usage()),
+            Ok(0x4007_002C)
+        );
+        assert_eq!(
+            HidUsage::parse("LS(N1)").expect("parse").to_zmk_string(),
+            "EXCL"
+        );
+        assert!(HidUsage::parse("XX(A)").is_err());
+        assert!(HidUsage::parse("LS(A").is_err());
+        assert!(HidUsage::parse("A B").is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+
+use crate::client::{ClientError, StudioClient};
+use crate::proto::zmk;
+use crate::transport::Transport;
+
+/// Serial port path of the device under test.
+pub const HIL_SERIAL_ENV: &str = "ZMK_STUDIO_HIL_SERIAL";
+/// BLE device ID of the device under test.
+pub const HIL_BLE_ENV: &str = "ZMK_STUDIO_HIL_BLE";
+/// `native_sim` endpoint (PTY path or `host:port`) of the device under test.
+pub const HIL_SIM_ENV: &str = "ZMK_STUDIO_HIL_SIM";
+
+/// Transport requirements for a hardware-in-the-loop client.
+pub trait HilTransport: Transport {}
+impl<T: Transport> HilTransport for T {}
+
+/// Client connected to the device under test.
+pub type HilClient = StudioClient<Box<dyn HilTransport>>;
+
+/// Device under test, as configured through the environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HilDevice {
+    Serial(String),
+    Ble(String),
+    Sim(String),
+}
+
+/// Errors from hardware-in-the-loop helpers.
+#[derive(Debug)]
+pub enum HilError {
+    /// The configured transport is not compiled into this build.
+    TransportDisabled(&'static str),
+    Connect(Box<dyn std::error::Error>),
+    Client(ClientError),
+    /// The device could not be returned to its captured state.
+    Restore(ClientError),
+    /// The operation failed, and so did returning the device to its captured
+    /// state afterwards; the device may be left modified.
+    RestoreAfterFailure {
+        error: ClientError,
+        restore: ClientError,
+    },
+}
+
+impl std::fmt::Display for HilError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TransportDisabled(feature) => {
+                write!(f, "HIL device requires the `{feature}` feature")
+            }
+            Self::Connect(err) => write!(f, "Failed to connect to HIL device: {err}"),
+            Self::Client(err) => write!(f, "HIL operation failed: {err}"),
+            Self::Restore(err) => write!(f, "Failed to restore HIL device state: {err}"),
+            Self::RestoreAfterFailure { error, restore } => write!(
+                f,
+                "HIL operation failed: {error}; restoring the device state also failed: {restore}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HilError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::TransportDisabled(_) => None,
+            Self::Connect(err) => Some(err.as_ref()),
+            Self::Client(err)
+            | Self::Restore(err)
+            | Self::RestoreAfterFailure { error: err, .. } => Some(err),
+        }
+    }
+}
+
+impl From<ClientError> for HilError {
+    fn from(value: ClientError) -> Self {
+        Self::Client(value)
+    }
+}
+
+/// Returns the device under test configured through the environment, if any.
+///
+/// Checked in order: [`HIL_SERIAL_ENV`], [`HIL_BLE_ENV`], [`HIL_SIM_ENV`].
+pub fn locate() -> Option<HilDevice> {
+    let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+    var(HIL_SERIAL_ENV)
+        .map(HilDevice::Serial)
+        .or_else(|| var(HIL_BLE_ENV).map(HilDevice::Ble))
+        .or_else(|| var(HIL_SIM_ENV).map(HilDevice::Sim))
+}
+
+/// Connects to the device under test, or returns `Ok(None)` when none is configured.
+pub fn connect() -> Result<Option<HilClient>, HilError> {
+    let Some(device) = locate() else {
+        return Ok(None);
+    };
+
+    // Every arm is a value, so builds without any transport compile cleanly.
+    let transport: Result<Box<dyn HilTransport>, HilError> = match device {
+        #[cfg(feature = "serial")]
+        HilDevice::Serial(path) => crate::transport::serial::SerialTransport::open(&path)
+            .map(|transport| Box::new(transport) as Box<dyn HilTransport>)
+            .map_err(|err| HilError::Connect(Box::new(err))),
+        #[cfg(not(feature = "serial"))]
+        HilDevice::Serial(_) => Err(HilError::TransportDisabled("serial")),
+        #[cfg(feature = "ble")]
+        HilDevice::Ble(device_id) => {
+            crate::transport::ble::BleTransport::connect_device(&device_id)
+                .map(|transport| Box::new(transport) as Box<dyn HilTransport>)
+                .map_err(|err| HilError::Connect(Box::new(err)))
+        }
+        #[cfg(not(feature = "ble"))]
+        HilDevice::Ble(_) => Err(HilError::TransportDisabled("ble")),
+        #[cfg(feature = "sim")]
+        HilDevice::Sim(endpoint) => crate::transport::sim::connect(&endpoint)
+            .map(|transport| Box::new(transport) as Box<dyn HilTransport>)
+            .map_err(|err| HilError::Connect(Box::new(err))),
+        #[cfg(not(feature = "sim"))]
+        HilDevice::Sim(_) => Err(HilError::TransportDisabled("sim")),
+    };
+
+    Ok(Some(StudioClient::new(transport?)))
+}
+
+/// Connects to the device under test, printing a skip notice when none is configured.
+///
+/// Intended for the first line of an integration test:
+///
+/// ```no_run
+/// # fn test() {
+/// let Some(mut client) = zmk_studio_api::hil::connect_or_skip() else {
+///     return;
+/// };
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a device is configured but cannot be connected.
+pub fn connect_or_skip() -> Option<HilClient> {
+    match connect() {
+        Ok(Some(client)) => Some(client),
+        Ok(None) => {
+            eprintln!(
+                "skipping HIL test: set {HIL_SERIAL_ENV}, {HIL_BLE_ENV} or {HIL_SIM_ENV} to run it"
+            );
+            None
+        }
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Device state captured before a destructive test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceState {
+    pub keymap: zmk::keymap::Keymap,
+    pub active_layout_index: u32,
+}
+
+/// Captures the keymap and active physical layout of the device.
+pub fn capture<T: Transport>(client: &mut StudioClient<T>) -> Result<DeviceState, HilError> {
+    let keymap = client.get_keymap()?;
+    let active_layout_index = client.get_physical_layouts()?.active_layout_index;
+    Ok(DeviceState {
+        keymap,
+        active_layout_index,
+    })
+}
+
+/// Returns the device to a captured state, saving only if something had to change.
+pub fn restore<T: Transport>(
+    client: &mut StudioClient<T>,
+    state: &DeviceState,
+) -> Result<(), HilError> {
+    restore_inner(client, state).map_err(HilError::Restore)
+}
+
+fn restore_inner<T: Transport>(
+    client: &mut StudioClient<T>,
+    state: &DeviceState,
+) -> Result<(), ClientError> {
+    client.discard_changes()?;
+
+    if client.get_physical_layouts()?.active_layout_index != state.active_layout_index {
+        client.set_active_physical_layout(state.active_layout_index)?;
+    }
+    client.restore_keymap(&state.keymap)?;
+
+    if client.check_unsaved_changes()? {
+        client.save_changes()?;
+    }
+    Ok(())
+}
+
+/// Runs a destructive operation and restores the captured device state afterwards.
+///
+/// The state is restored whether `f` succeeds, fails, or panics; panics are
+/// resumed after the restore. If both `f` and the restore fail, the error is
+/// [`HilError::RestoreAfterFailure`]; a failed restore after a panic is
+/// printed to stderr. Layers added or removed by `f` are not undone.
+pub fn with_restored_device<T: Transport, R>(
+    client: &mut StudioClient<T>,
+    f: impl FnOnce(&mut StudioClient<T>) -> Result<R, ClientError>,
+) -> Result<R, HilError> {
+    let state = capture(client)?;
+    let outcome = catch_unwind(AssertUnwindSafe(|| f(client)));
+    let restored = restore_inner(client, &state);
+
+    match (outcome, restored) {
+        (Ok(Ok(value)), Ok(())) => Ok(value),
+        (Ok(Ok(_)), Err(restore)) => Err(HilError::Restore(restore)),
+        (Ok(Err(error)), Ok(())) => Err(HilError::Client(error)),
+        (Ok(Err(error)), Err(restore)) => Err(HilError::RestoreAfterFailure { error, restore }),
+        (Err(payload), restored) => {
+            if let Err(err) = restored {
+                eprintln!("failed to restore HIL device state after a panic: {err}");
+            }
+            resume_unwind(payload)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    use super::{HilError, capture, restore, with_restored_device};
+    use crate::proto::zmk::core;
+    use crate::transport::mock::MockDevice;
+    use crate::{Behavior, ClientError, Keycode, StudioClient};
+
+    fn edited(client: &mut StudioClient<MockDevice>) -> Result<(), ClientError> {
+        client.set_key_at(0, 0, Behavior::KeyPress(Keycode::Z.into()))?;
+        client.save_changes()
+    }
+
+    #[test]
+    fn restores_a_captured_state() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let state = capture(&mut client).expect("capture");
+        edited(&mut client).expect("edit");
+        assert_ne!(client.get_keymap().expect("keymap"), state.keymap);
+
+        restore(&mut client, &state).expect("restore");
+        assert_eq!(client.get_keymap().expect("keymap"), state.keymap);
+        assert!(!client.check_unsaved_changes().expect("unsaved"));
+    }
+
+    #[test]
+    fn restores_after_success_failure_and_panic() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let original = client.get_keymap().expect("keymap");
+
+        assert_eq!(
+            with_restored_device(&mut client, |client| edited(client).map(|()| 7)).expect("ok"),
+            7
+        );
+        assert_eq!(client.get_keymap().expect("keymap"), original);
+
+        let failed = with_restored_device(&mut client, |client| {
+            edited(client)?;
+            Err::<(), _>(ClientError::Timeout)
+        });
+        assert!(matches!(
+            failed,
+            Err(HilError::Client(ClientError::Timeout))
+        ));
+        assert_eq!(client.get_keymap().expect("keymap"), original);
+
+        let panicked = catch_unwind(AssertUnwindSafe(|| {
+            with_restored_device(&mut client, |client| -> Result<(), ClientError> {
+                edited(client)?;
+                panic!("test failure");
+            })
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(client.get_keymap().expect("keymap"), original);
+    }
+
+    #[test]
+    fn reports_a_failed_restore_with_the_original_error() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let result = with_restored_device(&mut client, |client| {
+            edited(client)?;
+            // A locked device rejects the restore.
+            client.call_core(core::request::RequestType::Lock(true))?;
+            Err::<(), _>(ClientError::Timeout)
+        });
+        match result {
+            Err(HilError::RestoreAfterFailure { error, restore }) => {
+                assert!(matches!(error, ClientError::Timeout));
+                assert!(restore.is_locked());
+            }
+            other => panic!("expected RestoreAfterFailure, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use prost::Message;
+
+use crate::proto::zmk;
+
+const REVISION_EXTENSION: &str = "zmkrev";
+
+/// On-disk record for a single revision.
+#[derive(Clone, PartialEq, Message)]
+struct RevisionRecord {
+    #[prost(uint64, tag = "1")]
+    timestamp_ms: u64,
+    #[prost(string, tag = "2")]
+    description: String,
+    #[prost(message, optional, tag = "3")]
+    keymap: Option<zmk::keymap::Keymap>,
+}
+
+/// Errors from reading or writing the keymap history store.
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(std::io::Error),
+    Decode(prost::DecodeError),
+    MissingKeymap { id: u64 },
+    RevisionNotFound { id: u64 },
+}
+
+impl std::fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "History I/O error: {err}"),
+            Self::Decode(err) => write!(f, "History decode error: {err}"),
+            Self::MissingKeymap { id } => write!(f, "History revision {id} has no keymap"),
+            Self::RevisionNotFound { id } => write!(f, "History revision {id} not found"),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            Self::MissingKeymap { .. } | Self::RevisionNotFound { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for HistoryError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<prost::DecodeError> for HistoryError {
+    fn from(value: prost::DecodeError) -> Self {
+        Self::Decode(value)
+    }
+}
+
+/// A keymap snapshot taken after a successful save.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapRevision {
+    /// Store-unique identifier; milliseconds since the Unix epoch at capture time.
+    pub id: u64,
+    pub timestamp: SystemTime,
+    pub description: String,
+    pub keymap: zmk::keymap::Keymap,
+}
+
+/// Directory-backed store of keymap revisions.
+///
+/// Attach it with [`crate::StudioClient::set_history_store`] to snapshot the
+/// keymap every time [`crate::StudioClient::save_changes`] succeeds. Each
+/// revision is written to its own file, so the directory can be pruned by hand.
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    dir: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens (and creates if needed) a history store rooted at `dir`.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, HistoryError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Stores a new revision and returns it.
+    pub fn record(
+        &self,
+        keymap: &zmk::keymap::Keymap,
+        description: &str,
+    ) -> Result<KeymapRevision, HistoryError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        // Ids double as file names; bump past revisions captured in the same millisecond.
+        let mut id = now;
+        while self.revision_path(id).exists() {
+            id += 1;
+        }
+
+        let record = RevisionRecord {
+            timestamp_ms: id,
+            description: description.to_string(),
+            keymap: Some(keymap.clone()),
+        };
+        std::fs::write(self.revision_path(id), record.encode_to_vec())?;
+
+        Ok(KeymapRevision {
+            id,
+            timestamp: UNIX_EPOCH + Duration::from_millis(id),
+            description: record.description,
+            keymap: keymap.clone(),
+        })
+    }
+
+    /// Lists all stored revisions, oldest first.
+    pub fn list(&self) -> Result<Vec<KeymapRevision>, HistoryError> {
+        let mut ids = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(REVISION_EXTENSION) {
+                continue;
+            }
+            if let Some(id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            {
+                ids.push(id);
+            }
+        }
+        ids.sort_unstable();
+
+        ids.into_iter().map(|id| self.get(id)).collect()
+    }
+
+    /// Returns the most recent revision, if any.
+    pub fn latest(&self) -> Result<Option<KeymapRevision>, HistoryError> {
+        Ok(self.list()?.pop())
+    }
+
+    /// Loads a revision by id.
+    pub fn get(&self, id: u64) -> Result<KeymapRevision, HistoryError> {
+        let bytes = match std::fs::read(self.revision_path(id)) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(HistoryError::RevisionNotFound { id });
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let record = RevisionRecord::decode(bytes.as_slice())?;
+        let keymap = record.keymap.ok_or(HistoryError::MissingKeymap { id })?;
+
+        Ok(KeymapRevision {
+            id,
+            timestamp: UNIX_EPOCH + Duration::from_millis(record.timestamp_ms),
+            description: record.description,
+            keymap,
+        })
+    }
+
+    /// Deletes a revision by id.
+    pub fn remove(&self, id: u64) -> Result<(), HistoryError> {
+        match std::fs::remove_file(self.revision_path(id)) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(HistoryError::RevisionNotFound { id })
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn revision_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id:016}.{REVISION_EXTENSION}"))
+    }
+}
+
+/// Summary of the changes staged by [`crate::StudioClient::rollback_to`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RollbackReport {
+    pub bindings_written: usize,
+    pub layers_renamed: usize,
+    /// Layer IDs present in the revision but not on the device.
+    pub missing_layers: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HistoryError, HistoryStore};
+    use crate::proto::zmk;
+
+    #[test]
+    fn records_and_lists_revisions() {
+        let dir = std::env::temp_dir().join(format!("zmk-studio-history-{}", std::process::id()));
+        let store = HistoryStore::open(&dir).expect("store should open");
+
+        let keymap = zmk::keymap::Keymap {
+            layers: vec![zmk::keymap::Layer {
+                id: 0,
+                name: "Base".to_string(),
+                bindings: vec![zmk::keymap::BehaviorBinding {
+                    behavior_id: 1,
+                    param1: 0x0007_0004,
+                    param2: 0,
+                }],
+            }],
+            available_layers: 4,
+            max_layer_name_length: 20,
+        };
+
+        let first = store
+            .record(&keymap, "first")
+            .expect("record should succeed");
+        let second = store
+            .record(&keymap, "second")
+            .expect("record should succeed");
+        assert!(second.id > first.id);
+
+        let revisions = store.list().expect("list should succeed");
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].description, "first");
+        assert_eq!(revisions[1].keymap, keymap);
+
+        store.remove(first.id).expect("remove should succeed");
+        assert!(matches!(
+            store.get(first.id),
+            Err(HistoryError::RevisionNotFound { .. })
+        ));
+
+        std::fs::remove_dir_all(&dir).expect("cleanup should succeed");
+    }
+}
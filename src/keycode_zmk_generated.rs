@@ -0,0 +1,743 @@
+// @generated by `zmk-keycode-gen` from ZMK's `dt-bindings/zmk/keys.h`. Do not edit by hand;
+// see `src/bin/zmk-keycode-gen.rs` for how to regenerate.
+
+/// ZMK keycode value
+///
+/// This is the primary key type used by typed behavior APIs.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    TryFromPrimitive,
+    AsRefStr,
+    EnumString,
+    IntoStaticStr,
+    EnumIter,
+)]
+#[repr(u32)]
+#[allow(non_camel_case_types)]
+pub enum Keycode {
+    #[strum(to_string = "SYS_PWR")]
+    #[strum(serialize = "SYSTEM_POWER")]
+    SYSTEM_POWER = 0x00010081,
+    #[strum(to_string = "SYS_SLEEP")]
+    #[strum(serialize = "SYSTEM_SLEEP")]
+    SYSTEM_SLEEP = 0x00010082,
+    #[strum(to_string = "SYS_WAKE")]
+    #[strum(serialize = "SYSTEM_WAKE_UP")]
+    SYSTEM_WAKE_UP = 0x00010083,
+    A = 0x00070004,
+    B = 0x00070005,
+    C = 0x00070006,
+    D = 0x00070007,
+    E = 0x00070008,
+    F = 0x00070009,
+    G = 0x0007000A,
+    H = 0x0007000B,
+    I = 0x0007000C,
+    J = 0x0007000D,
+    K = 0x0007000E,
+    L = 0x0007000F,
+    M = 0x00070010,
+    N = 0x00070011,
+    O = 0x00070012,
+    P = 0x00070013,
+    Q = 0x00070014,
+    R = 0x00070015,
+    S = 0x00070016,
+    T = 0x00070017,
+    U = 0x00070018,
+    V = 0x00070019,
+    W = 0x0007001A,
+    X = 0x0007001B,
+    Y = 0x0007001C,
+    Z = 0x0007001D,
+    #[strum(to_string = "NUM_1")]
+    #[strum(serialize = "N1")]
+    #[strum(serialize = "NUMBER_1")]
+    NUMBER_1 = 0x0007001E,
+    #[strum(to_string = "NUM_2")]
+    #[strum(serialize = "N2")]
+    #[strum(serialize = "NUMBER_2")]
+    NUMBER_2 = 0x0007001F,
+    #[strum(to_string = "NUM_3")]
+    #[strum(serialize = "N3")]
+    #[strum(serialize = "NUMBER_3")]
+    NUMBER_3 = 0x00070020,
+    #[strum(to_string = "NUM_4")]
+    #[strum(serialize = "N4")]
+    #[strum(serialize = "NUMBER_4")]
+    NUMBER_4 = 0x00070021,
+    #[strum(to_string = "NUM_5")]
+    #[strum(serialize = "N5")]
+    #[strum(serialize = "NUMBER_5")]
+    NUMBER_5 = 0x00070022,
+    #[strum(to_string = "NUM_6")]
+    #[strum(serialize = "N6")]
+    #[strum(serialize = "NUMBER_6")]
+    NUMBER_6 = 0x00070023,
+    #[strum(to_string = "NUM_7")]
+    #[strum(serialize = "N7")]
+    #[strum(serialize = "NUMBER_7")]
+    NUMBER_7 = 0x00070024,
+    #[strum(to_string = "NUM_8")]
+    #[strum(serialize = "N8")]
+    #[strum(serialize = "NUMBER_8")]
+    NUMBER_8 = 0x00070025,
+    #[strum(to_string = "NUM_9")]
+    #[strum(serialize = "N9")]
+    #[strum(serialize = "NUMBER_9")]
+    NUMBER_9 = 0x00070026,
+    #[strum(to_string = "NUM_0")]
+    #[strum(serialize = "N0")]
+    #[strum(serialize = "NUMBER_0")]
+    NUMBER_0 = 0x00070027,
+    #[strum(to_string = "ENTER")]
+    #[strum(serialize = "RET")]
+    #[strum(serialize = "RETURN")]
+    RETURN = 0x00070028,
+    #[strum(to_string = "ESC")]
+    #[strum(serialize = "ESCAPE")]
+    ESCAPE = 0x00070029,
+    #[strum(to_string = "BSPC")]
+    #[strum(serialize = "BKSP")]
+    #[strum(serialize = "BACKSPACE")]
+    BACKSPACE = 0x0007002A,
+    TAB = 0x0007002B,
+    #[strum(to_string = "SPC")]
+    #[strum(serialize = "SPACE")]
+    SPACE = 0x0007002C,
+    MINUS = 0x0007002D,
+    #[strum(to_string = "EQL")]
+    #[strum(serialize = "EQUAL")]
+    EQUAL = 0x0007002E,
+    #[strum(to_string = "BSLH")]
+    #[strum(serialize = "BACKSLASH")]
+    BACKSLASH = 0x00070031,
+    #[strum(to_string = "NUHS")]
+    #[strum(serialize = "NON_US_HASH")]
+    NON_US_HASH = 0x00070032,
+    #[strum(to_string = "SEMI")]
+    #[strum(serialize = "SCLN")]
+    #[strum(serialize = "SEMICOLON")]
+    SEMICOLON = 0x00070033,
+    #[strum(to_string = "APOSTROPHE")]
+    #[strum(serialize = "APOS")]
+    #[strum(serialize = "QUOT")]
+    #[strum(serialize = "SQT")]
+    #[strum(serialize = "SINGLE_QUOTE")]
+    SINGLE_QUOTE = 0x00070034,
+    #[strum(to_string = "GRAV")]
+    #[strum(serialize = "GRAVE")]
+    GRAVE = 0x00070035,
+    #[strum(to_string = "CMMA")]
+    #[strum(serialize = "COMMA")]
+    COMMA = 0x00070036,
+    #[strum(to_string = "DOT")]
+    #[strum(serialize = "PERIOD")]
+    PERIOD = 0x00070037,
+    #[strum(to_string = "FSLH")]
+    #[strum(serialize = "SLASH")]
+    SLASH = 0x00070038,
+    #[strum(to_string = "CLCK")]
+    #[strum(serialize = "CAPS")]
+    #[strum(serialize = "CAPSLOCK")]
+    CAPSLOCK = 0x00070039,
+    F1 = 0x0007003A,
+    F2 = 0x0007003B,
+    F3 = 0x0007003C,
+    F4 = 0x0007003D,
+    F5 = 0x0007003E,
+    F6 = 0x0007003F,
+    F7 = 0x00070040,
+    F8 = 0x00070041,
+    F9 = 0x00070042,
+    F10 = 0x00070043,
+    F11 = 0x00070044,
+    F12 = 0x00070045,
+    #[strum(to_string = "PSCRN")]
+    #[strum(serialize = "PRSC")]
+    #[strum(serialize = "PRINTSCREEN")]
+    PRINTSCREEN = 0x00070046,
+    #[strum(to_string = "SLCK")]
+    #[strum(serialize = "SCLK")]
+    #[strum(serialize = "SCROLLLOCK")]
+    SCROLLLOCK = 0x00070047,
+    #[strum(to_string = "PAUS")]
+    #[strum(serialize = "PAUSE_BREAK")]
+    PAUSE_BREAK = 0x00070048,
+    #[strum(to_string = "INS")]
+    #[strum(serialize = "INSERT")]
+    INSERT = 0x00070049,
+    HOME = 0x0007004A,
+    #[strum(to_string = "PG_UP")]
+    #[strum(serialize = "PGUP")]
+    #[strum(serialize = "PAGE_UP")]
+    PAGE_UP = 0x0007004B,
+    #[strum(to_string = "DEL")]
+    #[strum(serialize = "DELETE")]
+    DELETE = 0x0007004C,
+    END = 0x0007004D,
+    #[strum(to_string = "PG_DN")]
+    #[strum(serialize = "PGDN")]
+    #[strum(serialize = "PAGE_DOWN")]
+    PAGE_DOWN = 0x0007004E,
+    #[strum(to_string = "RIGHT")]
+    #[strum(serialize = "RARW")]
+    #[strum(serialize = "RIGHT_ARROW")]
+    RIGHT_ARROW = 0x0007004F,
+    #[strum(to_string = "LEFT")]
+    #[strum(serialize = "LARW")]
+    #[strum(serialize = "LEFT_ARROW")]
+    LEFT_ARROW = 0x00070050,
+    #[strum(to_string = "DOWN")]
+    #[strum(serialize = "DARW")]
+    #[strum(serialize = "DOWN_ARROW")]
+    DOWN_ARROW = 0x00070051,
+    #[strum(to_string = "UARW")]
+    #[strum(serialize = "UP")]
+    #[strum(serialize = "UP_ARROW")]
+    UP_ARROW = 0x00070052,
+    #[strum(to_string = "KP_NLCK")]
+    #[strum(serialize = "KP_NUM")]
+    #[strum(serialize = "KP_NUMLOCK")]
+    KP_NUMLOCK = 0x00070053,
+    #[strum(to_string = "KP_SLASH")]
+    #[strum(serialize = "KDIV")]
+    #[strum(serialize = "KP_DIVIDE")]
+    KP_DIVIDE = 0x00070054,
+    #[strum(to_string = "KP_MULTIPLY")]
+    #[strum(serialize = "KMLT")]
+    #[strum(serialize = "KP_ASTERISK")]
+    KP_ASTERISK = 0x00070055,
+    #[strum(to_string = "KP_MINUS")]
+    #[strum(serialize = "KMIN")]
+    #[strum(serialize = "KP_SUBTRACT")]
+    KP_SUBTRACT = 0x00070056,
+    #[strum(to_string = "KPLS")]
+    #[strum(serialize = "KP_PLUS")]
+    KP_PLUS = 0x00070057,
+    KP_ENTER = 0x00070058,
+    #[strum(to_string = "KP_N1")]
+    #[strum(serialize = "KP_NUMBER_1")]
+    KP_NUMBER_1 = 0x00070059,
+    #[strum(to_string = "KP_N2")]
+    #[strum(serialize = "KP_NUMBER_2")]
+    KP_NUMBER_2 = 0x0007005A,
+    #[strum(to_string = "KP_N3")]
+    #[strum(serialize = "KP_NUMBER_3")]
+    KP_NUMBER_3 = 0x0007005B,
+    #[strum(to_string = "KP_N4")]
+    #[strum(serialize = "KP_NUMBER_4")]
+    KP_NUMBER_4 = 0x0007005C,
+    #[strum(to_string = "KP_N5")]
+    #[strum(serialize = "KP_NUMBER_5")]
+    KP_NUMBER_5 = 0x0007005D,
+    #[strum(to_string = "KP_N6")]
+    #[strum(serialize = "KP_NUMBER_6")]
+    KP_NUMBER_6 = 0x0007005E,
+    #[strum(to_string = "KP_N7")]
+    #[strum(serialize = "KP_NUMBER_7")]
+    KP_NUMBER_7 = 0x0007005F,
+    #[strum(to_string = "KP_N8")]
+    #[strum(serialize = "KP_NUMBER_8")]
+    KP_NUMBER_8 = 0x00070060,
+    #[strum(to_string = "KP_N9")]
+    #[strum(serialize = "KP_NUMBER_9")]
+    KP_NUMBER_9 = 0x00070061,
+    #[strum(to_string = "KP_N0")]
+    #[strum(serialize = "KP_NUMBER_0")]
+    KP_NUMBER_0 = 0x00070062,
+    KP_DOT = 0x00070063,
+    #[strum(to_string = "K_APPLICATION")]
+    #[strum(serialize = "GUI")]
+    #[strum(serialize = "K_APP")]
+    #[strum(serialize = "K_CMENU")]
+    #[strum(serialize = "K_CONTEXT_MENU")]
+    K_CONTEXT_MENU = 0x00070065,
+    #[strum(to_string = "K_PWR")]
+    #[strum(serialize = "K_POWER")]
+    K_POWER = 0x00070066,
+    KP_EQUAL = 0x00070067,
+    F13 = 0x00070068,
+    F14 = 0x00070069,
+    F15 = 0x0007006A,
+    F16 = 0x0007006B,
+    F17 = 0x0007006C,
+    F18 = 0x0007006D,
+    F19 = 0x0007006E,
+    F20 = 0x0007006F,
+    F21 = 0x00070070,
+    F22 = 0x00070071,
+    F23 = 0x00070072,
+    F24 = 0x00070073,
+    #[strum(to_string = "K_EXEC")]
+    #[strum(serialize = "K_EXECUTE")]
+    K_EXECUTE = 0x00070074,
+    K_HELP = 0x00070075,
+    K_MENU = 0x00070076,
+    K_SELECT = 0x00070077,
+    K_STOP = 0x00070078,
+    #[strum(to_string = "K_REDO")]
+    #[strum(serialize = "K_AGAIN")]
+    K_AGAIN = 0x00070079,
+    #[strum(to_string = "UNDO")]
+    #[strum(serialize = "K_UNDO")]
+    K_UNDO = 0x0007007A,
+    #[strum(to_string = "CUT")]
+    #[strum(serialize = "K_CUT")]
+    K_CUT = 0x0007007B,
+    #[strum(to_string = "COPY")]
+    #[strum(serialize = "K_COPY")]
+    K_COPY = 0x0007007C,
+    #[strum(to_string = "PSTE")]
+    #[strum(serialize = "K_PASTE")]
+    K_PASTE = 0x0007007D,
+    K_FIND = 0x0007007E,
+    K_MUTE = 0x0007007F,
+    #[strum(to_string = "K_VOL_UP")]
+    #[strum(serialize = "VOLU")]
+    #[strum(serialize = "K_VOLUME_UP")]
+    K_VOLUME_UP = 0x00070080,
+    #[strum(to_string = "K_VOL_DN")]
+    #[strum(serialize = "VOLD")]
+    #[strum(serialize = "K_VOLUME_DOWN")]
+    K_VOLUME_DOWN = 0x00070081,
+    #[strum(to_string = "LCAPS")]
+    #[strum(serialize = "LOCKING_CAPS")]
+    LOCKING_CAPS = 0x00070082,
+    #[strum(to_string = "LNLCK")]
+    #[strum(serialize = "LOCKING_NUM")]
+    LOCKING_NUM = 0x00070083,
+    #[strum(to_string = "LSLCK")]
+    #[strum(serialize = "LOCKING_SCROLL")]
+    LOCKING_SCROLL = 0x00070084,
+    KP_COMMA = 0x00070085,
+    KP_EQUAL_AS400 = 0x00070086,
+    #[strum(to_string = "INT_RO")]
+    #[strum(serialize = "INT1")]
+    #[strum(serialize = "INTERNATIONAL_1")]
+    INTERNATIONAL_1 = 0x00070087,
+    #[strum(to_string = "INTERNATIONAL_2")]
+    #[strum(serialize = "INT_KANA")]
+    #[strum(serialize = "INT2")]
+    #[strum(serialize = "INT_KATAKANAHIRAGANA")]
+    INT_KATAKANAHIRAGANA = 0x00070088,
+    #[strum(to_string = "INT_YEN")]
+    #[strum(serialize = "INT3")]
+    #[strum(serialize = "INTERNATIONAL_3")]
+    INTERNATIONAL_3 = 0x00070089,
+    #[strum(to_string = "INT_HENKAN")]
+    #[strum(serialize = "INT4")]
+    #[strum(serialize = "INTERNATIONAL_4")]
+    INTERNATIONAL_4 = 0x0007008A,
+    #[strum(to_string = "INT_MUHENKAN")]
+    #[strum(serialize = "INT5")]
+    #[strum(serialize = "INTERNATIONAL_5")]
+    INTERNATIONAL_5 = 0x0007008B,
+    #[strum(to_string = "INT_KPJPCOMMA")]
+    #[strum(serialize = "INT6")]
+    #[strum(serialize = "INTERNATIONAL_6")]
+    INTERNATIONAL_6 = 0x0007008C,
+    #[strum(to_string = "INT7")]
+    #[strum(serialize = "INTERNATIONAL_7")]
+    INTERNATIONAL_7 = 0x0007008D,
+    #[strum(to_string = "INT8")]
+    #[strum(serialize = "INTERNATIONAL_8")]
+    INTERNATIONAL_8 = 0x0007008E,
+    #[strum(to_string = "INT9")]
+    #[strum(serialize = "INTERNATIONAL_9")]
+    INTERNATIONAL_9 = 0x0007008F,
+    #[strum(to_string = "LANGUAGE_1")]
+    #[strum(serialize = "LANG1")]
+    #[strum(serialize = "LANG_HANGEUL")]
+    LANG_HANGEUL = 0x00070090,
+    #[strum(to_string = "LANGUAGE_2")]
+    #[strum(serialize = "LANG2")]
+    #[strum(serialize = "LANG_HANJA")]
+    LANG_HANJA = 0x00070091,
+    #[strum(to_string = "LANGUAGE_3")]
+    #[strum(serialize = "LANG3")]
+    #[strum(serialize = "LANG_KATAKANA")]
+    LANG_KATAKANA = 0x00070092,
+    #[strum(to_string = "LANGUAGE_4")]
+    #[strum(serialize = "LANG4")]
+    #[strum(serialize = "LANG_HIRAGANA")]
+    LANG_HIRAGANA = 0x00070093,
+    #[strum(to_string = "LANGUAGE_5")]
+    #[strum(serialize = "LANG5")]
+    #[strum(serialize = "LANG_ZENKAKUHANKAKU")]
+    LANG_ZENKAKUHANKAKU = 0x00070094,
+    #[strum(to_string = "LANG6")]
+    #[strum(serialize = "LANGUAGE_6")]
+    LANGUAGE_6 = 0x00070095,
+    #[strum(to_string = "LANG7")]
+    #[strum(serialize = "LANGUAGE_7")]
+    LANGUAGE_7 = 0x00070096,
+    #[strum(to_string = "LANG8")]
+    #[strum(serialize = "LANGUAGE_8")]
+    LANGUAGE_8 = 0x00070097,
+    #[strum(to_string = "LANG9")]
+    #[strum(serialize = "LANGUAGE_9")]
+    LANGUAGE_9 = 0x00070098,
+    ALT_ERASE = 0x00070099,
+    #[strum(to_string = "SYSREQ")]
+    #[strum(serialize = "ATTENTION")]
+    ATTENTION = 0x0007009A,
+    K_CANCEL = 0x0007009B,
+    CLEAR = 0x0007009C,
+    PRIOR = 0x0007009D,
+    #[strum(to_string = "RET2")]
+    #[strum(serialize = "RETURN2")]
+    RETURN2 = 0x0007009E,
+    SEPARATOR = 0x0007009F,
+    OUT = 0x000700A0,
+    OPER = 0x000700A1,
+    CLEAR_AGAIN = 0x000700A2,
+    CRSEL = 0x000700A3,
+    EXSEL = 0x000700A4,
+    #[strum(to_string = "KP_LPAR")]
+    #[strum(serialize = "KP_LEFT_PARENTHESIS")]
+    KP_LEFT_PARENTHESIS = 0x000700B6,
+    #[strum(to_string = "KP_RPAR")]
+    #[strum(serialize = "KP_RIGHT_PARENTHESIS")]
+    KP_RIGHT_PARENTHESIS = 0x000700B7,
+    KP_CLEAR = 0x000700D8,
+    #[strum(to_string = "LCTRL")]
+    #[strum(serialize = "LCTL")]
+    #[strum(serialize = "LEFT_CONTROL")]
+    LEFT_CONTROL = 0x000700E0,
+    #[strum(to_string = "LSHIFT")]
+    #[strum(serialize = "LSFT")]
+    #[strum(serialize = "LSHFT")]
+    #[strum(serialize = "LEFT_SHIFT")]
+    LEFT_SHIFT = 0x000700E1,
+    #[strum(to_string = "LALT")]
+    #[strum(serialize = "LEFT_ALT")]
+    LEFT_ALT = 0x000700E2,
+    #[strum(to_string = "LEFT_META")]
+    #[strum(serialize = "LCMD")]
+    #[strum(serialize = "LEFT_GUI")]
+    #[strum(serialize = "LEFT_WIN")]
+    #[strum(serialize = "LGUI")]
+    #[strum(serialize = "LMETA")]
+    #[strum(serialize = "LWIN")]
+    #[strum(serialize = "LEFT_COMMAND")]
+    LEFT_COMMAND = 0x000700E3,
+    #[strum(to_string = "RCTRL")]
+    #[strum(serialize = "RCTL")]
+    #[strum(serialize = "RIGHT_CONTROL")]
+    RIGHT_CONTROL = 0x000700E4,
+    #[strum(to_string = "RSHIFT")]
+    #[strum(serialize = "RSFT")]
+    #[strum(serialize = "RSHFT")]
+    #[strum(serialize = "RIGHT_SHIFT")]
+    RIGHT_SHIFT = 0x000700E5,
+    #[strum(to_string = "RALT")]
+    #[strum(serialize = "RIGHT_ALT")]
+    RIGHT_ALT = 0x000700E6,
+    #[strum(to_string = "RIGHT_META")]
+    #[strum(serialize = "RCMD")]
+    #[strum(serialize = "RGUI")]
+    #[strum(serialize = "RIGHT_GUI")]
+    #[strum(serialize = "RIGHT_WIN")]
+    #[strum(serialize = "RMETA")]
+    #[strum(serialize = "RWIN")]
+    #[strum(serialize = "RIGHT_COMMAND")]
+    RIGHT_COMMAND = 0x000700E7,
+    #[strum(to_string = "K_PP")]
+    #[strum(serialize = "K_PLAY_PAUSE")]
+    K_PLAY_PAUSE = 0x000700E8,
+    K_STOP2 = 0x000700E9,
+    #[strum(to_string = "K_PREV")]
+    #[strum(serialize = "K_PREVIOUS")]
+    K_PREVIOUS = 0x000700EA,
+    K_NEXT = 0x000700EB,
+    K_EJECT = 0x000700EC,
+    #[strum(to_string = "K_VOL_UP2")]
+    #[strum(serialize = "K_VOLUME_UP2")]
+    K_VOLUME_UP2 = 0x000700ED,
+    #[strum(to_string = "K_VOL_DN2")]
+    #[strum(serialize = "K_VOLUME_DOWN2")]
+    K_VOLUME_DOWN2 = 0x000700EE,
+    K_MUTE2 = 0x000700EF,
+    K_WWW = 0x000700F0,
+    K_BACK = 0x000700F1,
+    K_FORWARD = 0x000700F2,
+    K_STOP3 = 0x000700F3,
+    K_FIND2 = 0x000700F4,
+    K_SCROLL_UP = 0x000700F5,
+    K_SCROLL_DOWN = 0x000700F6,
+    K_EDIT = 0x000700F7,
+    K_SLEEP = 0x000700F8,
+    #[strum(to_string = "K_COFFEE")]
+    #[strum(serialize = "K_LOCK")]
+    #[strum(serialize = "K_SCREENSAVER")]
+    K_SCREENSAVER = 0x000700F9,
+    K_REFRESH = 0x000700FA,
+    #[strum(to_string = "K_CALC")]
+    #[strum(serialize = "K_CALCULATOR")]
+    K_CALCULATOR = 0x000700FB,
+    #[strum(to_string = "C_PWR")]
+    #[strum(serialize = "C_POWER")]
+    C_POWER = 0x000C0030,
+    C_RESET = 0x000C0031,
+    C_SLEEP = 0x000C0032,
+    C_SLEEP_MODE = 0x000C0034,
+    C_MENU = 0x000C0040,
+    #[strum(to_string = "C_MENU_PICK")]
+    #[strum(serialize = "C_MENU_SELECT")]
+    C_MENU_SELECT = 0x000C0041,
+    C_MENU_UP = 0x000C0042,
+    C_MENU_DOWN = 0x000C0043,
+    C_MENU_LEFT = 0x000C0044,
+    C_MENU_RIGHT = 0x000C0045,
+    #[strum(to_string = "C_MENU_ESC")]
+    #[strum(serialize = "C_MENU_ESCAPE")]
+    C_MENU_ESCAPE = 0x000C0046,
+    #[strum(to_string = "C_MENU_INC")]
+    #[strum(serialize = "C_MENU_INCREASE")]
+    C_MENU_INCREASE = 0x000C0047,
+    #[strum(to_string = "C_MENU_DEC")]
+    #[strum(serialize = "C_MENU_DECREASE")]
+    C_MENU_DECREASE = 0x000C0048,
+    C_DATA_ON_SCREEN = 0x000C0060,
+    #[strum(to_string = "C_CAPTIONS")]
+    #[strum(serialize = "C_SUBTITLES")]
+    C_SUBTITLES = 0x000C0061,
+    C_SNAPSHOT = 0x000C0065,
+    C_PIP = 0x000C0067,
+    #[strum(to_string = "C_RED")]
+    #[strum(serialize = "C_RED_BUTTON")]
+    C_RED_BUTTON = 0x000C0069,
+    #[strum(to_string = "C_GREEN")]
+    #[strum(serialize = "C_GREEN_BUTTON")]
+    C_GREEN_BUTTON = 0x000C006A,
+    #[strum(to_string = "C_BLUE")]
+    #[strum(serialize = "C_BLUE_BUTTON")]
+    C_BLUE_BUTTON = 0x000C006B,
+    #[strum(to_string = "C_YELLOW")]
+    #[strum(serialize = "C_YELLOW_BUTTON")]
+    C_YELLOW_BUTTON = 0x000C006C,
+    C_ASPECT = 0x000C006D,
+    #[strum(to_string = "C_MODE_STEP")]
+    #[strum(serialize = "C_MEDIA_STEP")]
+    C_MEDIA_STEP = 0x000C0082,
+    #[strum(to_string = "C_CHAN_LAST")]
+    #[strum(serialize = "C_RECALL_LAST")]
+    C_RECALL_LAST = 0x000C0083,
+    C_MEDIA_TV = 0x000C0089,
+    C_MEDIA_WWW = 0x000C008A,
+    C_MEDIA_DVD = 0x000C008B,
+    C_MEDIA_PHONE = 0x000C008C,
+    C_MEDIA_GAMES = 0x000C008F,
+    C_MEDIA_CD = 0x000C0091,
+    C_MEDIA_VCR = 0x000C0092,
+    C_MEDIA_TUNER = 0x000C0093,
+    C_QUIT = 0x000C0094,
+    C_HELP = 0x000C0095,
+    C_MEDIA_TAPE = 0x000C0096,
+    C_MEDIA_CABLE = 0x000C0097,
+    C_MEDIA_HOME = 0x000C009A,
+    #[strum(to_string = "C_CHAN_INC")]
+    #[strum(serialize = "C_CHANNEL_INC")]
+    C_CHANNEL_INC = 0x000C009C,
+    #[strum(to_string = "C_CHAN_DEC")]
+    #[strum(serialize = "C_CHANNEL_DEC")]
+    C_CHANNEL_DEC = 0x000C009D,
+    C_MEDIA_VCR_PLUS = 0x000C00A0,
+    C_PLAY = 0x000C00B0,
+    C_PAUSE = 0x000C00B1,
+    #[strum(to_string = "C_REC")]
+    #[strum(serialize = "C_RECORD")]
+    C_RECORD = 0x000C00B2,
+    #[strum(to_string = "C_FF")]
+    #[strum(serialize = "C_FAST_FORWARD")]
+    C_FAST_FORWARD = 0x000C00B3,
+    #[strum(to_string = "C_RW")]
+    #[strum(serialize = "C_REWIND")]
+    C_REWIND = 0x000C00B4,
+    #[strum(to_string = "M_NEXT")]
+    #[strum(serialize = "C_NEXT")]
+    C_NEXT = 0x000C00B5,
+    #[strum(to_string = "M_PREV")]
+    #[strum(serialize = "C_PREV")]
+    #[strum(serialize = "C_PREVIOUS")]
+    C_PREVIOUS = 0x000C00B6,
+    #[strum(to_string = "M_STOP")]
+    #[strum(serialize = "C_STOP")]
+    C_STOP = 0x000C00B7,
+    #[strum(to_string = "M_EJCT")]
+    #[strum(serialize = "C_EJECT")]
+    C_EJECT = 0x000C00B8,
+    #[strum(to_string = "C_SHUFFLE")]
+    #[strum(serialize = "C_RANDOM_PLAY")]
+    C_RANDOM_PLAY = 0x000C00B9,
+    C_REPEAT = 0x000C00BC,
+    #[strum(to_string = "C_SLOW2")]
+    #[strum(serialize = "C_SLOW_TRACKING")]
+    C_SLOW_TRACKING = 0x000C00BF,
+    C_STOP_EJECT = 0x000C00CC,
+    #[strum(to_string = "M_PLAY")]
+    #[strum(serialize = "C_PP")]
+    #[strum(serialize = "C_PLAY_PAUSE")]
+    C_PLAY_PAUSE = 0x000C00CD,
+    C_VOICE_COMMAND = 0x000C00CF,
+    #[strum(to_string = "M_MUTE")]
+    #[strum(serialize = "C_MUTE")]
+    C_MUTE = 0x000C00E2,
+    C_BASS_BOOST = 0x000C00E5,
+    #[strum(to_string = "C_VOL_UP")]
+    #[strum(serialize = "M_VOLU")]
+    #[strum(serialize = "C_VOLUME_UP")]
+    C_VOLUME_UP = 0x000C00E9,
+    #[strum(to_string = "C_VOL_DN")]
+    #[strum(serialize = "M_VOLD")]
+    #[strum(serialize = "C_VOLUME_DOWN")]
+    C_VOLUME_DOWN = 0x000C00EA,
+    C_SLOW = 0x000C00F5,
+    C_AL_WORD = 0x000C0184,
+    C_AL_TEXT_EDITOR = 0x000C0185,
+    #[strum(to_string = "C_AL_SHEET")]
+    #[strum(serialize = "C_AL_SPREADSHEET")]
+    C_AL_SPREADSHEET = 0x000C0186,
+    #[strum(to_string = "C_AL_DB")]
+    #[strum(serialize = "C_AL_DATABASE")]
+    C_AL_DATABASE = 0x000C0189,
+    #[strum(to_string = "C_AL_MAIL")]
+    #[strum(serialize = "C_AL_EMAIL")]
+    C_AL_EMAIL = 0x000C018A,
+    C_AL_NEWS = 0x000C018B,
+    C_AL_VOICEMAIL = 0x000C018C,
+    #[strum(to_string = "C_AL_CAL")]
+    #[strum(serialize = "C_AL_CALENDAR")]
+    C_AL_CALENDAR = 0x000C018E,
+    C_AL_JOURNAL = 0x000C0190,
+    C_AL_FINANCE = 0x000C0191,
+    #[strum(to_string = "C_AL_CALC")]
+    #[strum(serialize = "C_AL_CALCULATOR")]
+    C_AL_CALCULATOR = 0x000C0192,
+    C_AL_WWW = 0x000C0196,
+    #[strum(to_string = "C_AL_CHAT")]
+    #[strum(serialize = "C_AL_NETWORK_CHAT")]
+    C_AL_NETWORK_CHAT = 0x000C0199,
+    C_AL_LOGOFF = 0x000C019C,
+    C_AL_CONTROL_PANEL = 0x000C019F,
+    C_AL_HELP = 0x000C01A6,
+    #[strum(to_string = "C_AL_DOCS")]
+    #[strum(serialize = "C_AL_DOCUMENTS")]
+    C_AL_DOCUMENTS = 0x000C01A7,
+    #[strum(to_string = "C_AL_SPELL")]
+    #[strum(serialize = "C_AL_SPELLCHECK")]
+    C_AL_SPELLCHECK = 0x000C01AB,
+    C_AL_SCREEN_SAVER = 0x000C01B1,
+    #[strum(to_string = "C_AL_FILES")]
+    #[strum(serialize = "C_AL_FILE_BROWSER")]
+    C_AL_FILE_BROWSER = 0x000C01B4,
+    #[strum(to_string = "C_AL_IMAGES")]
+    #[strum(serialize = "C_AL_IMAGE_BROWSER")]
+    C_AL_IMAGE_BROWSER = 0x000C01B6,
+    #[strum(to_string = "C_AL_MUSIC")]
+    #[strum(serialize = "C_AL_AUDIO")]
+    #[strum(serialize = "C_AL_AUDIO_BROWSER")]
+    C_AL_AUDIO_BROWSER = 0x000C01B7,
+    #[strum(to_string = "C_AL_MOVIES")]
+    #[strum(serialize = "C_AL_MOVIE_BROWSER")]
+    C_AL_MOVIE_BROWSER = 0x000C01B8,
+    C_AC_NEW = 0x000C0201,
+    C_AC_OPEN = 0x000C0202,
+    C_AC_CLOSE = 0x000C0203,
+    C_AC_EXIT = 0x000C0204,
+    C_AC_SAVE = 0x000C0207,
+    C_AC_PRINT = 0x000C0208,
+    #[strum(to_string = "C_AC_PROPS")]
+    #[strum(serialize = "C_AC_PROPERTIES")]
+    C_AC_PROPERTIES = 0x000C0209,
+    C_AC_UNDO = 0x000C021A,
+    C_AC_COPY = 0x000C021B,
+    C_AC_CUT = 0x000C021C,
+    C_AC_PASTE = 0x000C021D,
+    C_AC_FIND = 0x000C021F,
+    C_AC_SEARCH = 0x000C0221,
+    C_AC_GOTO = 0x000C0222,
+    C_AC_HOME = 0x000C0223,
+    C_AC_BACK = 0x000C0224,
+    C_AC_FORWARD = 0x000C0225,
+    C_AC_STOP = 0x000C0226,
+    C_AC_REFRESH = 0x000C0227,
+    #[strum(to_string = "C_AC_FAVORITES")]
+    #[strum(serialize = "C_AC_BOOKMARKS")]
+    #[strum(serialize = "C_AC_FAVOURITES")]
+    C_AC_FAVOURITES = 0x000C022A,
+    C_AC_ZOOM_IN = 0x000C022D,
+    C_AC_ZOOM_OUT = 0x000C022E,
+    C_AC_ZOOM = 0x000C022F,
+    C_AC_VIEW_TOGGLE = 0x000C0232,
+    C_AC_SCROLL_UP = 0x000C0233,
+    C_AC_SCROLL_DOWN = 0x000C0234,
+    C_AC_EDIT = 0x000C023D,
+    C_AC_CANCEL = 0x000C025F,
+    #[strum(to_string = "C_AC_INS")]
+    #[strum(serialize = "C_AC_INSERT")]
+    C_AC_INSERT = 0x000C0269,
+    C_AC_DEL = 0x000C026A,
+    C_AC_REDO = 0x000C0279,
+    C_AC_REPLY = 0x000C0289,
+    C_AC_FORWARD_MAIL = 0x000C028B,
+    C_AC_SEND = 0x000C028C,
+    #[strum(to_string = "GLOBE")]
+    #[strum(serialize = "C_AC_NEXT_KEYBOARD_LAYOUT_SELECT")]
+    C_AC_NEXT_KEYBOARD_LAYOUT_SELECT = 0x000C029D,
+    #[strum(to_string = "EXCL")]
+    #[strum(serialize = "BANG")]
+    #[strum(serialize = "EXCLAMATION")]
+    EXCLAMATION = 0x0207001E,
+    #[strum(to_string = "ATSN")]
+    #[strum(serialize = "AT")]
+    #[strum(serialize = "AT_SIGN")]
+    AT_SIGN = 0x0207001F,
+    #[strum(to_string = "HASH")]
+    #[strum(serialize = "POUND")]
+    POUND = 0x02070020,
+    #[strum(to_string = "DLLR")]
+    #[strum(serialize = "DOLLAR")]
+    DOLLAR = 0x02070021,
+    #[strum(to_string = "PRCNT")]
+    #[strum(serialize = "PRCT")]
+    #[strum(serialize = "PERCENT")]
+    PERCENT = 0x02070022,
+    #[strum(to_string = "CRRT")]
+    #[strum(serialize = "CARET")]
+    CARET = 0x02070023,
+    #[strum(to_string = "AMPS")]
+    #[strum(serialize = "AMPERSAND")]
+    AMPERSAND = 0x02070024,
+    #[strum(to_string = "ASTRK")]
+    #[strum(serialize = "STAR")]
+    #[strum(serialize = "ASTERISK")]
+    ASTERISK = 0x02070025,
+    #[strum(to_string = "UNDER")]
+    #[strum(serialize = "UNDERSCORE")]
+    UNDERSCORE = 0x0207002D,
+    PLUS = 0x0207002E,
+    PIPE = 0x02070031,
+    TILDE2 = 0x02070032,
+    #[strum(to_string = "COLN")]
+    #[strum(serialize = "COLON")]
+    COLON = 0x02070033,
+    #[strum(to_string = "TILD")]
+    #[strum(serialize = "TILDE")]
+    TILDE = 0x02070035,
+    #[strum(to_string = "LABT")]
+    #[strum(serialize = "LT")]
+    #[strum(serialize = "LESS_THAN")]
+    LESS_THAN = 0x02070036,
+    #[strum(to_string = "QMARK")]
+    #[strum(serialize = "QUESTION")]
+    QUESTION = 0x02070038,
+    CLEAR2 = 0x02070053,
+    PIPE2 = 0x02070064,
+}
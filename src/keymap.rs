@@ -0,0 +1,635 @@
+use crate::binding::Behavior;
+use crate::privacy::fnv1a_64;
+use crate::proto::zmk;
+use crate::zmk_syntax::parse_bindings;
+
+pub use crate::zmk_syntax::ZmkSyntaxError;
+
+/// Keymap with every binding resolved to a typed [`Behavior`].
+///
+/// Obtained from a device with [`crate::StudioClient::get_typed_keymap`] or
+/// built in source with the [`keymap!`](crate::keymap!) macro.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypedKeymap {
+    pub layers: Vec<TypedLayer>,
+}
+
+/// A single layer within a [`TypedKeymap`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypedLayer {
+    pub id: u32,
+    pub name: String,
+    pub bindings: Vec<Behavior>,
+}
+
+impl TypedLayer {
+    /// Returns the binding at `position`, if the layer has that many keys.
+    pub fn binding_at(&self, position: usize) -> Option<&Behavior> {
+        self.bindings.get(position)
+    }
+}
+
+/// Which layers an operation such as [`crate::StudioClient::replace_bindings`] covers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LayerScope {
+    #[default]
+    All,
+    Layer(u32),
+    Layers(Vec<u32>),
+}
+
+impl LayerScope {
+    pub fn contains(&self, layer_id: u32) -> bool {
+        match self {
+            Self::All => true,
+            Self::Layer(id) => *id == layer_id,
+            Self::Layers(ids) => ids.contains(&layer_id),
+        }
+    }
+}
+
+impl TypedKeymap {
+    /// Returns the layer with the given ID, if present.
+    pub fn layer(&self, layer_id: u32) -> Option<&TypedLayer> {
+        self.layers.iter().find(|layer| layer.id == layer_id)
+    }
+
+    /// Returns the first layer named `name`.
+    pub fn layer_by_name(&self, name: &str) -> Option<&TypedLayer> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+
+    /// Returns the binding at `position` on the layer with the given ID.
+    pub fn binding_at(&self, layer_id: u32, position: usize) -> Option<&Behavior> {
+        self.layer(layer_id)?.binding_at(position)
+    }
+
+    /// Iterates over every binding as `(layer_id, position, behavior)`, layer by layer.
+    pub fn iter_positions(&self) -> impl Iterator<Item = (u32, usize, &Behavior)> {
+        self.layers.iter().flat_map(|layer| {
+            layer
+                .bindings
+                .iter()
+                .enumerate()
+                .map(|(position, behavior)| (layer.id, position, behavior))
+        })
+    }
+
+    /// Returns the `(layer_id, position)` of every binding equal to `behavior` within `scope`.
+    pub fn find_bindings(&self, behavior: &Behavior, scope: &LayerScope) -> Vec<(u32, usize)> {
+        self.layers
+            .iter()
+            .filter(|layer| scope.contains(layer.id))
+            .flat_map(|layer| {
+                layer
+                    .bindings
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, binding)| *binding == behavior)
+                    .map(|(position, _)| (layer.id, position))
+            })
+            .collect()
+    }
+
+    /// Builds a keymap from `(name, bindings)` pairs written in ZMK keymap syntax.
+    ///
+    /// Layers get IDs in declaration order, matching a freshly flashed keymap.
+    /// Layer parameters (`&mo NAV`) may reference layers by name or by index.
+    /// This is the function behind [`keymap!`](crate::keymap!).
+    pub fn from_zmk_layers(layers: &[(&str, &str)]) -> Result<Self, ZmkSyntaxError> {
+        let layer_id = |name: &str| {
+            layers
+                .iter()
+                .position(|(layer_name, _)| *layer_name == name)
+                .map(|index| index as u32)
+        };
+
+        let layers = layers
+            .iter()
+            .enumerate()
+            .map(|(index, (name, bindings))| {
+                let bindings =
+                    parse_bindings(bindings, &layer_id).map_err(|err| err.in_layer(name))?;
+                Ok(TypedLayer {
+                    id: index as u32,
+                    name: name.to_string(),
+                    bindings,
+                })
+            })
+            .collect::<Result<_, ZmkSyntaxError>>()?;
+
+        Ok(Self { layers })
+    }
+}
+
+/// Builds a [`TypedKeymap`] from ZMK keymap syntax.
+///
+/// Each layer is a name followed by its bindings in braces, written as in a
+/// `.keymap` file. Keys accept ZMK keycode names and modifier functions such
+/// as `LS(A)`; layer parameters accept layer names or indices.
+///
+/// ```
+/// use zmk_studio_api::{Behavior, keymap};
+///
+/// let keymap = keymap! {
+///     BASE {
+///         &kp ESC  &mt LSHIFT SPC    &lt NAV TAB  &kp LS(N1)
+///     }
+///     NAV {
+///         &kp LEFT &kp RIGHT         &trans       &to BASE
+///     }
+/// }
+/// .expect("keymap should parse");
+///
+/// assert_eq!(keymap.layers[1].bindings[3], Behavior::ToLayer { layer_id: 0 });
+/// ```
+///
+/// Syntax errors are reported when the expression is evaluated, as a
+/// [`ZmkSyntaxError`] naming the offending layer.
+#[macro_export]
+macro_rules! keymap {
+    ($($layer:ident { $($bindings:tt)* })*) => {
+        $crate::keymap::TypedKeymap::from_zmk_layers(&[
+            $((stringify!($layer), stringify!($($bindings)*))),*
+        ])
+    };
+}
+
+/// Composes a [`TypedKeymap`] offline, without a device connection.
+///
+/// Layers get IDs in the order they are added, and layer behaviors refer to
+/// layers by those IDs. Push the result with [`crate::StudioClient::apply_keymap`].
+#[derive(Debug, Clone, Default)]
+pub struct KeymapBuilder {
+    layers: Vec<TypedLayer>,
+}
+
+impl KeymapBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a layer and returns the builder.
+    pub fn layer(
+        mut self,
+        name: impl Into<String>,
+        bindings: impl IntoIterator<Item = Behavior>,
+    ) -> Self {
+        self.layers.push(TypedLayer {
+            id: self.layers.len() as u32,
+            name: name.into(),
+            bindings: bindings.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Returns the keymap without validating it.
+    pub fn build(self) -> TypedKeymap {
+        TypedKeymap {
+            layers: self.layers,
+        }
+    }
+
+    /// Returns the keymap after validating it against `layout`.
+    pub fn build_for_layout(
+        self,
+        layout: &zmk::keymap::PhysicalLayout,
+    ) -> Result<TypedKeymap, KeymapValidationError> {
+        let keymap = self.build();
+        keymap.validate(layout)?;
+        Ok(keymap)
+    }
+}
+
+/// Reason a [`TypedKeymap`] does not fit a keyboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapValidationError {
+    /// A layer does not have exactly one binding per physical key.
+    BindingCount {
+        layer_id: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// A layer behavior refers to a layer that is not part of the keymap.
+    UnknownLayerReference {
+        layer_id: u32,
+        position: usize,
+        referenced: u32,
+    },
+}
+
+impl std::fmt::Display for KeymapValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BindingCount {
+                layer_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Layer {layer_id} has {actual} bindings, expected {expected}"
+            ),
+            Self::UnknownLayerReference {
+                layer_id,
+                position,
+                referenced,
+            } => write!(
+                f,
+                "Binding {layer_id}/{position} refers to unknown layer {referenced}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeymapValidationError {}
+
+impl TypedKeymap {
+    /// Checks that every layer has one binding per key of `layout` and that
+    /// layer behaviors only refer to layers of this keymap.
+    pub fn validate(
+        &self,
+        layout: &zmk::keymap::PhysicalLayout,
+    ) -> Result<(), KeymapValidationError> {
+        self.validate_key_count(layout.keys.len())
+    }
+
+    pub(crate) fn validate_key_count(&self, key_count: usize) -> Result<(), KeymapValidationError> {
+        for layer in &self.layers {
+            if layer.bindings.len() != key_count {
+                return Err(KeymapValidationError::BindingCount {
+                    layer_id: layer.id,
+                    expected: key_count,
+                    actual: layer.bindings.len(),
+                });
+            }
+            for (position, behavior) in layer.bindings.iter().enumerate() {
+                if let Some(referenced) = referenced_layer(behavior)
+                    && self.layer(referenced).is_none()
+                {
+                    return Err(KeymapValidationError::UnknownLayerReference {
+                        layer_id: layer.id,
+                        position,
+                        referenced,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stable hash of a [`TypedKeymap`]'s layers, names and bindings.
+///
+/// Store it alongside caches or backups and compare it with
+/// [`crate::StudioClient::keymap_fingerprint`] to detect that another client
+/// changed the device in the meantime. Behaviors are hashed by meaning rather
+/// than firmware behavior ID, so the fingerprint survives firmware updates
+/// that renumber behaviors. The text form is 16 lowercase hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeymapFingerprint(pub u64);
+
+impl std::fmt::Display for KeymapFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl std::str::FromStr for KeymapFingerprint {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16).map(Self)
+    }
+}
+
+impl TypedKeymap {
+    pub fn fingerprint(&self) -> KeymapFingerprint {
+        KeymapFingerprint(fnv1a_64(self.to_snapshot_text().as_bytes()))
+    }
+}
+
+/// Summary of the changes staged by [`crate::StudioClient::apply_keymap`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyKeymapReport {
+    pub layers_added: usize,
+    pub layers_removed: usize,
+    pub layers_renamed: usize,
+    pub bindings_written: usize,
+}
+
+/// A binding joined with the geometry of the physical key it sits on.
+///
+/// Coordinates and sizes are in hundredths of a key unit, and `rotation` is
+/// in hundredths of a degree around (`rx`, `ry`), as reported by the firmware.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyView {
+    pub layer_id: u32,
+    pub position: usize,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub rotation: i32,
+    pub rx: i32,
+    pub ry: i32,
+    pub behavior: Behavior,
+    /// User note from [`KeymapView::apply_annotations`], if any.
+    pub annotation: Option<String>,
+}
+
+/// One layer of a [`KeymapView`], with one [`KeyView`] per physical key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerView {
+    pub id: u32,
+    pub name: String,
+    pub keys: Vec<KeyView>,
+}
+
+/// Keymap bindings aligned with a physical layout, ready for rendering.
+///
+/// Returned by [`crate::StudioClient::get_keymap_view`] for the active layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapView {
+    pub layout_index: u32,
+    pub layout_name: String,
+    pub layers: Vec<LayerView>,
+}
+
+impl KeymapView {
+    /// Joins `keymap` with `layout`, which must have one key per binding on every layer.
+    pub fn new(
+        keymap: &TypedKeymap,
+        layout_index: u32,
+        layout: &zmk::keymap::PhysicalLayout,
+    ) -> Result<Self, KeymapValidationError> {
+        let layers = keymap
+            .layers
+            .iter()
+            .map(|layer| {
+                if layer.bindings.len() != layout.keys.len() {
+                    return Err(KeymapValidationError::BindingCount {
+                        layer_id: layer.id,
+                        expected: layout.keys.len(),
+                        actual: layer.bindings.len(),
+                    });
+                }
+                let keys = layer
+                    .bindings
+                    .iter()
+                    .zip(&layout.keys)
+                    .enumerate()
+                    .map(|(position, (behavior, attrs))| KeyView {
+                        layer_id: layer.id,
+                        position,
+                        x: attrs.x,
+                        y: attrs.y,
+                        width: attrs.width,
+                        height: attrs.height,
+                        rotation: attrs.r,
+                        rx: attrs.rx,
+                        ry: attrs.ry,
+                        behavior: behavior.clone(),
+                        annotation: None,
+                    })
+                    .collect();
+                Ok(LayerView {
+                    id: layer.id,
+                    name: layer.name.clone(),
+                    keys,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            layout_index,
+            layout_name: layout.name.clone(),
+            layers,
+        })
+    }
+
+    pub fn layer(&self, layer_id: u32) -> Option<&LayerView> {
+        self.layers.iter().find(|layer| layer.id == layer_id)
+    }
+
+    /// Iterates over every key of every layer.
+    pub fn keys(&self) -> impl Iterator<Item = &KeyView> {
+        self.layers.iter().flat_map(|layer| &layer.keys)
+    }
+}
+
+pub(crate) fn referenced_layer(behavior: &Behavior) -> Option<u32> {
+    match behavior {
+        Behavior::LayerTap { layer_id, .. }
+        | Behavior::StickyLayer { layer_id }
+        | Behavior::MomentaryLayer { layer_id }
+        | Behavior::ToggleLayer { layer_id }
+        | Behavior::ToLayer { layer_id } => Some(*layer_id),
+        _ => None,
+    }
+}
+
+/// A layer-targeting binding rewritten after a layer move or removal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemappedBinding {
+    pub layer_id: u32,
+    pub position: usize,
+    pub before: Behavior,
+    pub after: Behavior,
+}
+
+/// Bindings touched by [`crate::LayerReferenceMode::Indices`] remapping.
+///
+/// Returned by [`crate::StudioClient::last_layer_remap_report`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerRemapReport {
+    pub rewritten: Vec<RemappedBinding>,
+    /// Bindings that targeted a removed layer and were left unchanged.
+    pub dangling: Vec<(u32, usize, Behavior)>,
+}
+
+/// New index of the layer at `index` after moving the layer at `start` to `dest`.
+pub(crate) fn index_after_move(index: u32, start: u32, dest: u32) -> u32 {
+    if index == start {
+        dest
+    } else if start < dest && (start..=dest).contains(&index) {
+        index - 1
+    } else if dest < start && (dest..start).contains(&index) {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// New index of the layer at `index` after removing the layer at `removed`,
+/// or `None` for the removed layer itself.
+pub(crate) fn index_after_removal(index: u32, removed: u32) -> Option<u32> {
+    match index.cmp(&removed) {
+        std::cmp::Ordering::Less => Some(index),
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some(index - 1),
+    }
+}
+
+/// Returns `behavior` with its layer reference (if any) replaced by `map(layer_id)`.
+pub(crate) fn remap_layer_reference(behavior: &Behavior, map: impl Fn(u32) -> u32) -> Behavior {
+    let mut behavior = behavior.clone();
+    match &mut behavior {
+        Behavior::LayerTap { layer_id, .. }
+        | Behavior::StickyLayer { layer_id }
+        | Behavior::MomentaryLayer { layer_id }
+        | Behavior::ToggleLayer { layer_id }
+        | Behavior::ToLayer { layer_id } => *layer_id = map(*layer_id),
+        _ => {}
+    }
+    behavior
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        KeymapBuilder, KeymapFingerprint, KeymapValidationError, KeymapView, LayerScope,
+        index_after_move, index_after_removal,
+    };
+    use crate::Behavior;
+    use crate::proto::zmk;
+
+    fn layout(keys: usize) -> zmk::keymap::PhysicalLayout {
+        zmk::keymap::PhysicalLayout {
+            name: "Default".to_string(),
+            keys: vec![zmk::keymap::KeyPhysicalAttrs::default(); keys],
+        }
+    }
+
+    #[test]
+    fn validates_against_layout() {
+        let keymap = KeymapBuilder::new()
+            .layer(
+                "Base",
+                [Behavior::MomentaryLayer { layer_id: 1 }, Behavior::None],
+            )
+            .layer(
+                "Nav",
+                [Behavior::Transparent, Behavior::ToLayer { layer_id: 0 }],
+            )
+            .build_for_layout(&layout(2))
+            .expect("keymap should fit");
+        assert_eq!(keymap.layers[1].id, 1);
+
+        let err = KeymapBuilder::new()
+            .layer("Base", [Behavior::None])
+            .build_for_layout(&layout(2))
+            .expect_err("binding count should be checked");
+        assert_eq!(
+            err,
+            KeymapValidationError::BindingCount {
+                layer_id: 0,
+                expected: 2,
+                actual: 1,
+            }
+        );
+
+        let err = KeymapBuilder::new()
+            .layer("Base", [Behavior::ToggleLayer { layer_id: 3 }])
+            .build_for_layout(&layout(1))
+            .expect_err("layer references should be checked");
+        assert_eq!(
+            err,
+            KeymapValidationError::UnknownLayerReference {
+                layer_id: 0,
+                position: 0,
+                referenced: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn fingerprint_tracks_content() {
+        let base = KeymapBuilder::new()
+            .layer("Base", [Behavior::None, Behavior::CapsWord])
+            .build();
+        let mut renamed = base.clone();
+        renamed.layers[0].name = "Default".to_string();
+
+        assert_eq!(base.fingerprint(), base.clone().fingerprint());
+        assert_ne!(base.fingerprint(), renamed.fingerprint());
+
+        let text = base.fingerprint().to_string();
+        assert_eq!(text.len(), 16);
+        assert_eq!(text.parse::<KeymapFingerprint>(), Ok(base.fingerprint()));
+    }
+
+    #[test]
+    fn joins_bindings_with_layout_geometry() {
+        let keymap = KeymapBuilder::new()
+            .layer("Base", [Behavior::None, Behavior::Transparent])
+            .build();
+        let mut geometry = layout(2);
+        geometry.keys[1].x = 100;
+        geometry.keys[1].r = 1500;
+
+        let view = KeymapView::new(&keymap, 0, &geometry).expect("layout should fit");
+        let key = &view.layer(0).expect("layer 0").keys[1];
+        assert_eq!((key.position, key.x, key.rotation), (1, 100, 1500));
+        assert_eq!(key.behavior, Behavior::Transparent);
+        assert_eq!(view.keys().count(), 2);
+
+        assert!(KeymapView::new(&keymap, 0, &layout(3)).is_err());
+    }
+
+    #[test]
+    fn tracks_layer_indices_across_moves_and_removals() {
+        // Moving layer 1 to index 3 in [0, 1, 2, 3] gives [0, 2, 3, 1].
+        let moved: Vec<u32> = (0..4).map(|index| index_after_move(index, 1, 3)).collect();
+        assert_eq!(moved, [0, 3, 1, 2]);
+        let moved_back: Vec<u32> = (0..4).map(|index| index_after_move(index, 3, 1)).collect();
+        assert_eq!(moved_back, [0, 2, 3, 1]);
+
+        let removed: Vec<Option<u32>> = (0..3).map(|index| index_after_removal(index, 1)).collect();
+        assert_eq!(removed, [Some(0), None, Some(1)]);
+    }
+
+    #[test]
+    fn finds_bindings_within_scope() {
+        let keymap = KeymapBuilder::new()
+            .layer("Base", [Behavior::Transparent, Behavior::None])
+            .layer("Nav", [Behavior::None, Behavior::Transparent])
+            .build();
+
+        assert_eq!(
+            keymap.find_bindings(&Behavior::Transparent, &LayerScope::All),
+            [(0, 0), (1, 1)]
+        );
+        assert_eq!(
+            keymap.find_bindings(&Behavior::Transparent, &LayerScope::Layer(1)),
+            [(1, 1)]
+        );
+        assert!(
+            keymap
+                .find_bindings(&Behavior::CapsWord, &LayerScope::Layers(vec![0, 1]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn looks_up_layers_and_bindings() {
+        let keymap = KeymapBuilder::new()
+            .layer("Base", [Behavior::Transparent, Behavior::None])
+            .layer("Nav", [Behavior::CapsWord])
+            .build();
+
+        assert_eq!(keymap.layer_by_name("Nav").map(|layer| layer.id), Some(1));
+        assert!(keymap.layer_by_name("nav").is_none());
+        assert_eq!(keymap.binding_at(0, 1), Some(&Behavior::None));
+        assert_eq!(keymap.binding_at(1, 1), None);
+        assert_eq!(
+            keymap.iter_positions().collect::<Vec<_>>(),
+            [
+                (0, 0, &Behavior::Transparent),
+                (0, 1, &Behavior::None),
+                (1, 0, &Behavior::CapsWord),
+            ]
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::binding::{BehaviorCatalog, BehaviorRole, role_from_display_name};
+use crate::keymap::{TypedKeymap, remap_layer_reference};
+use crate::proto::zmk;
+use crate::zmk_syntax::format_binding;
+
+/// Bindings per row when no physical layout is available.
+const DEVICETREE_ROW_LENGTH: usize = 12;
+
+/// Version written to [`KeymapDocument::version`] by this crate.
+pub const KEYMAP_DOCUMENT_VERSION: u32 = 1;
+
+/// Human-readable backup of a device keymap, written by
+/// [`crate::StudioClient::export_keymap`].
+///
+/// Its JSON form ([`KeymapDocument::to_json`]) is stable across releases:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "layers": [
+///     {
+///       "id": 0,
+///       "name": "Base",
+///       "bindings": [
+///         { "binding": "&kp EXCL", "behavior": "Key Press", "behavior_id": 1, "param1": 34013214, "param2": 0 }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeymapDocument {
+    pub version: u32,
+    pub layers: Vec<LayerDocument>,
+}
+
+/// A layer within a [`KeymapDocument`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerDocument {
+    /// Layer ID on the exporting device.
+    pub id: u32,
+    pub name: String,
+    pub bindings: Vec<BindingDocument>,
+}
+
+/// A single binding within a [`LayerDocument`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BindingDocument {
+    /// The binding in ZMK keymap syntax, with layer parameters written as layer IDs.
+    pub binding: String,
+    /// Firmware display name of the behavior, or `None` if the device did not report it.
+    pub behavior: Option<String>,
+    /// Behavior ID on the exporting device, or -1 if there was none, as for
+    /// documents read with [`crate::dts::parse_keymap`].
+    pub behavior_id: i32,
+    pub param1: u32,
+    pub param2: u32,
+}
+
+/// Options for [`crate::StudioClient::import_keymap`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Persist the imported keymap with [`crate::StudioClient::save_changes`].
+    pub save: bool,
+}
+
+/// A document binding whose behavior the device does not provide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedBinding {
+    /// Layer ID in the document.
+    pub layer_id: u32,
+    pub position: usize,
+    pub behavior: String,
+}
+
+/// Summary of the changes made by [`crate::StudioClient::import_keymap`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub layers_added: usize,
+    pub layers_removed: usize,
+    pub layers_moved: usize,
+    pub layers_renamed: usize,
+    pub bindings_written: usize,
+    /// Bindings left unchanged because the device lacks their behavior.
+    pub unsupported: Vec<UnsupportedBinding>,
+    /// Whether the changes were saved.
+    pub saved: bool,
+}
+
+/// Errors from reading a [`KeymapDocument`].
+#[derive(Debug)]
+pub enum KeymapDocumentError {
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for KeymapDocumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "Invalid keymap document JSON: {err}"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported keymap document version: {version}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapDocumentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(err) => Some(err),
+            Self::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for KeymapDocumentError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl KeymapDocument {
+    /// Renders the document as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("keymap documents always serialize")
+    }
+
+    /// Parses a document written by [`KeymapDocument::to_json`].
+    pub fn from_json(text: &str) -> Result<Self, KeymapDocumentError> {
+        let document: Self = serde_json::from_str(text)?;
+        if document.version != KEYMAP_DOCUMENT_VERSION {
+            return Err(KeymapDocumentError::UnsupportedVersion(document.version));
+        }
+        Ok(document)
+    }
+
+    /// Builds a document from a raw keymap and the device's behavior display names.
+    pub(crate) fn from_keymap(
+        keymap: &zmk::keymap::Keymap,
+        behavior_names: &HashMap<u32, String>,
+        catalog: &BehaviorCatalog,
+    ) -> Self {
+        let layers = keymap
+            .layers
+            .iter()
+            .map(|layer| LayerDocument {
+                id: layer.id,
+                name: layer.name.clone(),
+                bindings: layer
+                    .bindings
+                    .iter()
+                    .map(|binding| BindingDocument {
+                        binding: format_binding(&catalog.resolve(binding)),
+                        behavior: u32::try_from(binding.behavior_id)
+                            .ok()
+                            .and_then(|id| behavior_names.get(&id))
+                            .cloned(),
+                        behavior_id: binding.behavior_id,
+                        param1: binding.param1,
+                        param2: binding.param2,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            version: KEYMAP_DOCUMENT_VERSION,
+            layers,
+        }
+    }
+}
+
+impl BindingDocument {
+    /// Role of the behavior, recognized from its display name.
+    pub(crate) fn role(&self) -> Option<BehaviorRole> {
+        self.behavior.as_deref().and_then(role_from_display_name)
+    }
+
+    /// Whether `param1` is a layer ID.
+    pub(crate) fn references_layer(&self) -> bool {
+        matches!(
+            self.role(),
+            Some(
+                BehaviorRole::LayerTap
+                    | BehaviorRole::StickyLayer
+                    | BehaviorRole::MomentaryLayer
+                    | BehaviorRole::ToggleLayer
+                    | BehaviorRole::ToLayer
+            )
+        )
+    }
+}
+
+/// Renders `keymap` as a ZMK devicetree `keymap` node, ready to replace the
+/// one in a zmk-config `.keymap` file.
+///
+/// Layer parameters are written as layer indices, as firmware expects, and
+/// each layer node carries its name as `display-name`. When `layout` is given,
+/// bindings are broken into rows following the keys' vertical positions;
+/// otherwise they are written twelve to a row. Columns are aligned across rows.
+/// Bindings whose behavior has no devicetree form are written as `&none` with
+/// a comment, see [`crate::Behavior::to_zmk_string`].
+///
+/// ```text
+/// / {
+///     keymap {
+///         compatible = "zmk,keymap";
+///
+///         base_layer {
+///             display-name = "Base";
+///             bindings = <
+///                 &kp A  &kp B
+///                 &mo 1  &trans
+///             >;
+///         };
+///     };
+/// };
+/// ```
+pub fn to_devicetree(
+    keymap: &TypedKeymap,
+    catalog: &BehaviorCatalog,
+    layout: Option<&zmk::keymap::PhysicalLayout>,
+) -> String {
+    let index_of = |layer_id: u32| {
+        keymap
+            .layers
+            .iter()
+            .position(|layer| layer.id == layer_id)
+            .map_or(layer_id, |index| index as u32)
+    };
+
+    let mut node_names: Vec<String> = Vec::new();
+    let mut out = String::from("/ {\n    keymap {\n        compatible = \"zmk,keymap\";\n");
+    for layer in &keymap.layers {
+        let mut node = node_name(&layer.name);
+        if node_names.contains(&node) {
+            node = format!("{node}_{}", node_names.len());
+        }
+        node_names.push(node.clone());
+
+        let bindings: Vec<String> = layer
+            .bindings
+            .iter()
+            .map(|behavior| remap_layer_reference(behavior, index_of).to_zmk_string(catalog))
+            .collect();
+        let rows = row_lengths(bindings.len(), layout);
+        let mut widths = Vec::new();
+        let mut rest = bindings.as_slice();
+        let mut lines = Vec::new();
+        for length in rows {
+            let (row, tail) = rest.split_at(length.min(rest.len()));
+            rest = tail;
+            for (column, binding) in row.iter().enumerate() {
+                if widths.len() <= column {
+                    widths.push(0);
+                }
+                widths[column] = widths[column].max(binding.len());
+            }
+            lines.push(row);
+        }
+
+        out.push_str(&format!(
+            "\n        {node} {{\n            display-name = \"{}\";\n            bindings = <\n",
+            escape(&layer.name)
+        ));
+        for row in lines {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, binding)| format!("{binding:<width$}", width = widths[column]))
+                .collect();
+            out.push_str(&format!(
+                "                {}\n",
+                cells.join("  ").trim_end()
+            ));
+        }
+        out.push_str("            >;\n        };\n");
+    }
+    out.push_str("    };\n};\n");
+    out
+}
+
+/// Devicetree node name for a layer, such as `lower_layer` for "Lower".
+fn node_name(layer_name: &str) -> String {
+    let mut name = String::new();
+    for c in layer_name.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let mut name = name.trim_end_matches('_').to_string();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "layer_");
+    }
+    if !name.ends_with("layer") {
+        name.push_str("_layer");
+    }
+    name
+}
+
+/// Splits `count` bindings into rows of keys sharing a vertical position.
+fn row_lengths(count: usize, layout: Option<&zmk::keymap::PhysicalLayout>) -> Vec<usize> {
+    let Some(layout) = layout.filter(|layout| layout.keys.len() == count) else {
+        return (0..count)
+            .step_by(DEVICETREE_ROW_LENGTH)
+            .map(|start| DEVICETREE_ROW_LENGTH.min(count - start))
+            .collect();
+    };
+
+    // Key positions are in hundredths of a key; a new row starts half a key lower.
+    let mut rows = Vec::new();
+    let mut row_y: Option<i32> = None;
+    for key in &layout.keys {
+        match row_y {
+            Some(y) if (key.y - y).abs() < 50 => *rows.last_mut().expect("row started") += 1,
+            _ => {
+                rows.push(1);
+                row_y = Some(key.y);
+            }
+        }
+    }
+    rows
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::MockDevice;
+    use crate::{Behavior, StudioClient};
+
+    #[test]
+    fn exports_readable_bindings_and_round_trips() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let json = client.export_keymap_json().expect("export");
+        let document = KeymapDocument::from_json(&json).expect("parse");
+
+        let first = &document.layers[0].bindings[0];
+        assert_eq!(first.binding, "&kp A");
+        assert_eq!(first.behavior.as_deref(), Some("Key Press"));
+        assert_eq!(document.layers[1].name, "Lower");
+        assert_eq!(document.layers[1].bindings[0].binding, "&trans");
+        assert_eq!(
+            KeymapDocument::from_json(&document.to_json()).ok(),
+            Some(document)
+        );
+    }
+
+    #[test]
+    fn import_restores_an_exported_keymap() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client
+            .set_key_at(0, 3, Behavior::MomentaryLayer { layer_id: 1 })
+            .expect("set binding");
+        let document = client.export_keymap().expect("export");
+
+        client.move_layer(0, 1).expect("move layer");
+        client.set_layer_props(1, "Nav").expect("rename layer");
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        client.add_layer().expect("add layer");
+
+        let report = client
+            .import_keymap(&document, ImportOptions { save: true })
+            .expect("import");
+        assert_eq!(report.layers_removed, 1);
+        assert_eq!(report.layers_moved, 1);
+        assert_eq!(report.layers_renamed, 1);
+        assert_eq!(report.bindings_written, 1);
+        assert!(report.unsupported.is_empty());
+        assert!(report.saved);
+        assert_eq!(client.export_keymap().expect("export"), document);
+        assert!(!client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn renders_a_devicetree_keymap_node() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client
+            .set_key_at(0, 2, Behavior::MomentaryLayer { layer_id: 1 })
+            .expect("set binding");
+        client
+            .set_layer_props(1, "Nav \"2\"")
+            .expect("rename layer");
+
+        assert_eq!(
+            client.export_devicetree().expect("export"),
+            r#"/ {
+    keymap {
+        compatible = "zmk,keymap";
+
+        base_layer {
+            display-name = "Base";
+            bindings = <
+                &kp A  &kp B
+                &mo 1  &kp D
+            >;
+        };
+
+        nav_2_layer {
+            display-name = "Nav \"2\"";
+            bindings = <
+                &trans  &trans
+                &trans  &trans
+            >;
+        };
+    };
+};
+"#
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let json = r#"{ "version": 2, "layers": [] }"#;
+        assert!(matches!(
+            KeymapDocument::from_json(json),
+            Err(KeymapDocumentError::UnsupportedVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::hid_usage::{HidUsage, ModifierSet};
+use crate::keycode::Keycode;
+
+/// Language used for human-readable key labels.
+///
+/// This only affects names such as "Esc" or "Entf". Which character a key
+/// produces depends on the host's input layout and is not handled here.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LabelLocale {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl LabelLocale {
+    /// Picks a locale from a BCP 47 tag such as `de-AT` or `fr`, by primary language.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Self::English),
+            "de" => Some(Self::German),
+            "fr" => Some(Self::French),
+            "es" => Some(Self::Spanish),
+            _ => None,
+        }
+    }
+
+    fn column(self) -> usize {
+        self as usize
+    }
+}
+
+/// Translated labels, in [`LabelLocale`] order: English, German, French, Spanish.
+const LABELS: &[(Keycode, [&str; 4])] = &[
+    (Keycode::RETURN, ["Enter", "Eingabe", "Entrée", "Intro"]),
+    (Keycode::ESCAPE, ["Esc", "Esc", "Échap", "Esc"]),
+    (
+        Keycode::BACKSPACE,
+        ["Backspace", "Rücktaste", "Retour arrière", "Retroceso"],
+    ),
+    (Keycode::TAB, ["Tab", "Tab", "Tab", "Tab"]),
+    (Keycode::SPACE, ["Space", "Leertaste", "Espace", "Espacio"]),
+    (
+        Keycode::CAPSLOCK,
+        ["Caps Lock", "Feststell", "Verr. Maj", "Bloq Mayús"],
+    ),
+    (
+        Keycode::PRINTSCREEN,
+        ["Print Screen", "Druck", "Impr. écran", "Impr Pant"],
+    ),
+    (
+        Keycode::SCROLLLOCK,
+        ["Scroll Lock", "Rollen", "Arrêt défil", "Bloq Despl"],
+    ),
+    (Keycode::PAUSE_BREAK, ["Pause", "Pause", "Pause", "Pausa"]),
+    (Keycode::INSERT, ["Insert", "Einfg", "Inser", "Insert"]),
+    (Keycode::HOME, ["Home", "Pos1", "Origine", "Inicio"]),
+    (
+        Keycode::PAGE_UP,
+        ["Page Up", "Bild ↑", "Pg. préc.", "Re Pág"],
+    ),
+    (Keycode::DELETE, ["Delete", "Entf", "Suppr", "Supr"]),
+    (Keycode::END, ["End", "Ende", "Fin", "Fin"]),
+    (
+        Keycode::PAGE_DOWN,
+        ["Page Down", "Bild ↓", "Pg. suiv.", "Av Pág"],
+    ),
+    (Keycode::K_CONTEXT_MENU, ["Menu", "Menü", "Menu", "Menú"]),
+    (Keycode::LEFT_CONTROL, ["Ctrl", "Strg", "Ctrl", "Ctrl"]),
+    (Keycode::LEFT_SHIFT, ["Shift", "Umschalt", "Maj", "Mayús"]),
+    (Keycode::LEFT_ALT, ["Alt", "Alt", "Alt", "Alt"]),
+    (Keycode::LEFT_COMMAND, ["GUI", "GUI", "GUI", "GUI"]),
+    (
+        Keycode::RIGHT_CONTROL,
+        ["Right Ctrl", "Strg rechts", "Ctrl droit", "Ctrl der."],
+    ),
+    (
+        Keycode::RIGHT_SHIFT,
+        ["Right Shift", "Umschalt rechts", "Maj droite", "Mayús der."],
+    ),
+    (
+        Keycode::RIGHT_ALT,
+        ["Right Alt", "Alt Gr", "Alt Gr", "Alt Gr"],
+    ),
+    (
+        Keycode::RIGHT_COMMAND,
+        ["Right GUI", "GUI rechts", "GUI droit", "GUI der."],
+    ),
+    (Keycode::C_MUTE, ["Mute", "Stumm", "Muet", "Silencio"]),
+    (
+        Keycode::C_VOLUME_UP,
+        ["Volume Up", "Lauter", "Volume +", "Subir volumen"],
+    ),
+    (
+        Keycode::C_VOLUME_DOWN,
+        ["Volume Down", "Leiser", "Volume −", "Bajar volumen"],
+    ),
+    (
+        Keycode::C_PLAY_PAUSE,
+        [
+            "Play/Pause",
+            "Wiedergabe/Pause",
+            "Lecture/Pause",
+            "Reproducir/Pausa",
+        ],
+    ),
+    (
+        Keycode::C_NEXT,
+        [
+            "Next Track",
+            "Nächster Titel",
+            "Piste suivante",
+            "Pista siguiente",
+        ],
+    ),
+    (
+        Keycode::C_PREVIOUS,
+        [
+            "Previous Track",
+            "Vorheriger Titel",
+            "Piste précédente",
+            "Pista anterior",
+        ],
+    ),
+    (
+        Keycode::SYSTEM_POWER,
+        ["Power", "Ein/Aus", "Alimentation", "Encendido"],
+    ),
+    (
+        Keycode::SYSTEM_SLEEP,
+        ["Sleep", "Ruhezustand", "Veille", "Suspender"],
+    ),
+    (
+        Keycode::SYSTEM_WAKE_UP,
+        ["Wake", "Aufwecken", "Réveil", "Reactivar"],
+    ),
+];
+
+/// Labels that read the same in every supported language.
+const NEUTRAL_LABELS: &[(Keycode, &str)] = &[
+    (Keycode::NUMBER_1, "1"),
+    (Keycode::NUMBER_2, "2"),
+    (Keycode::NUMBER_3, "3"),
+    (Keycode::NUMBER_4, "4"),
+    (Keycode::NUMBER_5, "5"),
+    (Keycode::NUMBER_6, "6"),
+    (Keycode::NUMBER_7, "7"),
+    (Keycode::NUMBER_8, "8"),
+    (Keycode::NUMBER_9, "9"),
+    (Keycode::NUMBER_0, "0"),
+    (Keycode::RIGHT_ARROW, "→"),
+    (Keycode::LEFT_ARROW, "←"),
+    (Keycode::DOWN_ARROW, "↓"),
+    (Keycode::UP_ARROW, "↑"),
+];
+
+/// Modifier keys in [`ModifierSet`] bit order, for labelling modifier combinations.
+const MODIFIER_KEYS: [(ModifierSet, Keycode); 8] = [
+    (ModifierSet::LCTL, Keycode::LEFT_CONTROL),
+    (ModifierSet::LSFT, Keycode::LEFT_SHIFT),
+    (ModifierSet::LALT, Keycode::LEFT_ALT),
+    (ModifierSet::LGUI, Keycode::LEFT_COMMAND),
+    (ModifierSet::RCTL, Keycode::RIGHT_CONTROL),
+    (ModifierSet::RSFT, Keycode::RIGHT_SHIFT),
+    (ModifierSet::RALT, Keycode::RIGHT_ALT),
+    (ModifierSet::RGUI, Keycode::RIGHT_COMMAND),
+];
+
+impl Keycode {
+    /// Human-readable label in `locale`, such as "Entf" for [`Keycode::DELETE`] in German.
+    ///
+    /// Keys without a translation fall back to their ZMK name.
+    pub fn label(self, locale: LabelLocale) -> &'static str {
+        LABELS
+            .iter()
+            .find(|(keycode, _)| *keycode == self)
+            .map(|(_, labels)| labels[locale.column()])
+            .or_else(|| {
+                NEUTRAL_LABELS
+                    .iter()
+                    .find(|(keycode, _)| *keycode == self)
+                    .map(|(_, label)| *label)
+            })
+            .unwrap_or_else(|| self.to_name())
+    }
+}
+
+impl HidUsage {
+    /// Human-readable label in `locale`, with modifiers first: "Strg+Umschalt+Entf".
+    ///
+    /// Unknown base usages are shown as hex.
+    pub fn label(self, locale: LabelLocale) -> String {
+        let modifiers = self.modifier_set();
+        let mut parts: Vec<String> = MODIFIER_KEYS
+            .iter()
+            .filter(|(modifier, _)| modifiers.contains(*modifier))
+            .map(|(_, keycode)| keycode.label(locale).to_string())
+            .collect();
+        parts.push(match self.known_base_keycode() {
+            Some(keycode) => keycode.label(locale).to_string(),
+            None => format!("0x{:08X}", self.base().to_hid_usage()),
+        });
+        parts.join("+")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LabelLocale;
+    use crate::{HidUsage, Keycode, ModifierSet};
+
+    #[test]
+    fn labels_keys_by_locale() {
+        assert_eq!(Keycode::DELETE.label(LabelLocale::German), "Entf");
+        assert_eq!(Keycode::ESCAPE.label(LabelLocale::French), "Échap");
+        assert_eq!(Keycode::NUMBER_1.label(LabelLocale::Spanish), "1");
+        assert_eq!(Keycode::F5.label(LabelLocale::German), "F5");
+
+        let usage = HidUsage::from_encoded(Keycode::DELETE.to_hid_usage())
+            .with_modifier_set(ModifierSet::LCTL | ModifierSet::LSFT);
+        assert_eq!(usage.label(LabelLocale::German), "Strg+Umschalt+Entf");
+
+        assert_eq!(LabelLocale::from_tag("de-AT"), Some(LabelLocale::German));
+        assert_eq!(LabelLocale::from_tag("ja"), None);
+    }
+}
@@ -5,30 +5,105 @@
 //! - [`Behavior`] for typed key bindings
 //! - [`HidUsage`] and [`Keycode`] for ZMK key values
 //! - [`transport`] for BLE/serial I/O adapters
+//! - `dbus` (Linux, `dbus` feature) for exporting a keyboard on the session bus
+//! - [`prelude`] for glob-importing the common types and proto messages
 //!
 //! [`proto`] exposes raw generated protobuf types for advanced use cases.
 
+/// Host-side notes and labels for keys, merged into exports and views.
+pub mod annotations;
 mod binding;
+/// JSON export of the behaviors a firmware exposes, and diffs against older exports.
+#[cfg(feature = "json")]
+pub mod catalog;
+/// US-layout mapping between characters and the keys that type them.
+pub mod char_map;
 mod client;
+/// D-Bus service exposing a connected keyboard to desktop integrations.
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub mod dbus;
+/// Reading layers from ZMK `.keymap` devicetree files.
+#[cfg(feature = "json")]
+pub mod dts;
+/// Conversions between ZMK key values and Linux evdev `KEY_*` codes.
+#[cfg(feature = "evdev-codes")]
+pub mod evdev;
+/// Structured events for keymap mutations made through a client.
+pub mod events;
+/// C ABI for non-Rust hosts such as the .NET wrapper in `bindings/dotnet`.
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod framing;
 mod hid_usage;
+/// Hardware-in-the-loop helpers for integration tests against a real keyboard.
+#[cfg(feature = "hil")]
+pub mod hil;
+/// Opt-in local store of keymap snapshots taken on every save.
+pub mod history;
 mod keycode;
+/// Typed keymaps and the [`keymap!`] macro for writing them in ZMK syntax.
+pub mod keymap;
+/// Human-readable JSON backups of a device keymap.
+#[cfg(feature = "json")]
+pub mod keymap_export;
+/// Translated human-readable key labels.
+pub mod labels;
+/// Locating keymap positions from key presses observed on the host.
+#[cfg(feature = "locator")]
+pub mod locator;
+/// Classification and filtering of device notifications.
+pub mod notification;
+/// Glob import of the commonly used client, binding and protobuf types.
+pub mod prelude;
+/// Named binding presets such as `copy` or `screenshot`.
+pub mod presets;
+/// Redaction of device identifiers in logs, exports and diagnostics.
+pub mod privacy;
 /// Raw generated protobuf types used by the RPC protocol.
 pub mod proto;
-mod protocol;
+/// Frame encoding and decoding for both ends of a Studio RPC connection.
+pub mod protocol;
 #[cfg(feature = "python")]
 mod python;
+mod queue;
+/// Batch keymap operations loaded from JSON scripts.
+#[cfg(feature = "script")]
+pub mod script;
+#[cfg(feature = "serde")]
+mod serde_impls;
+/// Canonical text snapshots of a typed keymap.
+pub mod snapshot;
+/// Two-way synchronization between a snapshot file and the device.
+pub mod sync;
+/// Decoded RPC transcripts for bug reports.
+pub mod transcript;
+/// Copying and comparing keymaps between keyboards.
+pub mod transfer;
 /// Transport adapters for connecting to a ZMK Studio-capable device.
 pub mod transport;
+#[cfg(feature = "wasm")]
+mod wasm;
+/// Conversions between ZMK key values and W3C `KeyboardEvent.code` strings.
+#[cfg(feature = "web-codes")]
+pub mod web;
+/// Conversions between ZMK key values and Windows virtual-key and scan codes.
+#[cfg(feature = "windows-codes")]
+pub mod windows;
+mod zmk_syntax;
 
 /// Typed key binding value used by [`StudioClient::get_key_at`] and [`StudioClient::set_key_at`].
-pub use binding::Behavior;
+pub use binding::{Behavior, BehaviorCatalog, BehaviorRole};
 /// Errors returned by high-level client operations.
-pub use client::{ClientError, StudioClient};
+pub use client::{
+    ClientError, DeviceInfo, LayerReferenceMode, RawResponse, RemovedLayer, RetryPolicy,
+    StudioClient, UnsavedChangesPolicy,
+};
 /// Decoded ZMK HID usage values used in typed behavior APIs.
 pub use hid_usage::{
-    HID_USAGE_KEYBOARD, HidUsage, MOD_LALT, MOD_LCTL, MOD_LGUI, MOD_LSFT, MOD_RALT, MOD_RCTL,
-    MOD_RGUI, MOD_RSFT,
+    HID_USAGE_GENERIC_DESKTOP, HID_USAGE_KEYBOARD, HidUsage, MOD_LALT, MOD_LCTL, MOD_LGUI,
+    MOD_LSFT, MOD_RALT, MOD_RCTL, MOD_RGUI, MOD_RSFT, ModifierSet,
 };
 /// ZMK keycode enum used in typed behavior APIs.
 pub use keycode::Keycode;
+/// Overflow behavior for the client's notification and response queues.
+pub use queue::OverflowPolicy;
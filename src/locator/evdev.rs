@@ -0,0 +1,81 @@
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use evdev::{Device, EventType, KeyCode};
+
+use crate::hid_usage::{HID_USAGE_KEYBOARD, HidUsage};
+use crate::locator::{HostKeySource, LocatorError};
+
+const KEY_PRESSED: i32 = 1;
+
+/// [`HostKeySource`] reading key presses from Linux evdev keyboards.
+///
+/// Requires read access to `/dev/input/event*` (typically membership in the
+/// `input` group). Key presses are observed, not grabbed, so they still reach
+/// the focused application.
+pub struct EvdevKeySource {
+    rx: Receiver<Result<u16, std::io::Error>>,
+}
+
+impl EvdevKeySource {
+    /// Listens on every input device that reports letter keys.
+    pub fn open_all() -> Result<Self, LocatorError> {
+        let keyboards: Vec<Device> = evdev::enumerate()
+            .map(|(_, device)| device)
+            .filter(|device| {
+                device
+                    .supported_keys()
+                    .is_some_and(|keys| keys.contains(KeyCode::KEY_A))
+            })
+            .collect();
+        if keyboards.is_empty() {
+            return Err(LocatorError::NoKeyboards);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        for mut device in keyboards {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                loop {
+                    let events = match device.fetch_events() {
+                        Ok(events) => events,
+                        Err(err) => {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                    };
+                    for event in events {
+                        if event.event_type() == EventType::KEY
+                            && event.value() == KEY_PRESSED
+                            && tx.send(Ok(event.code())).is_err()
+                        {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+
+        Ok(Self { rx })
+    }
+}
+
+impl HostKeySource for EvdevKeySource {
+    fn next_key(&mut self, timeout: Duration) -> Result<Option<HidUsage>, LocatorError> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            let code = match self.rx.recv_timeout(remaining) {
+                Ok(code) => code?,
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => return Err(LocatorError::NoKeyboards),
+            };
+
+            // Skip keys without a keyboard-page usage (e.g. media keys) and keep waiting.
+            if let Some(usage_id) = crate::evdev::evdev_to_hid_keyboard(code) {
+                return Ok(Some(HidUsage::from_parts(HID_USAGE_KEYBOARD, usage_id, 0)));
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use crate::binding::Behavior;
+use crate::client::{ClientError, StudioClient};
+use crate::hid_usage::HidUsage;
+use crate::keymap::TypedKeymap;
+use crate::transport::Transport;
+
+#[cfg(all(feature = "locator-evdev", target_os = "linux"))]
+pub mod evdev;
+
+/// Errors from locating a key through host input.
+#[derive(Debug)]
+pub enum LocatorError {
+    Client(ClientError),
+    Io(std::io::Error),
+    /// No host keyboard input device could be opened.
+    NoKeyboards,
+}
+
+impl std::fmt::Display for LocatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Client(err) => write!(f, "Client error: {err}"),
+            Self::Io(err) => write!(f, "Host input error: {err}"),
+            Self::NoKeyboards => write!(f, "No host keyboard input devices available"),
+        }
+    }
+}
+
+impl std::error::Error for LocatorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Client(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::NoKeyboards => None,
+        }
+    }
+}
+
+impl From<ClientError> for LocatorError {
+    fn from(value: ClientError) -> Self {
+        Self::Client(value)
+    }
+}
+
+impl From<std::io::Error> for LocatorError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Source of key presses observed on the host.
+///
+/// The crate implements it only for Linux, as `evdev::EvdevKeySource` with
+/// the `locator-evdev` feature. On other platforms, implement it over the
+/// host's keyboard input API.
+pub trait HostKeySource {
+    /// Waits up to `timeout` for the next key press and returns its keyboard-page usage.
+    fn next_key(&mut self, timeout: Duration) -> Result<Option<HidUsage>, LocatorError>;
+}
+
+/// A keymap position whose binding produces an observed key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyMatch {
+    pub layer_id: u32,
+    /// Index of the layer in keymap order.
+    pub layer_index: usize,
+    pub position: usize,
+}
+
+/// Result of [`locate_key`]: the key the host saw and where it is bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedKey {
+    pub usage: HidUsage,
+    /// Matching positions, base layer first.
+    pub matches: Vec<KeyMatch>,
+}
+
+/// Waits for a key press on the host and finds the keymap positions that emit it.
+///
+/// The Studio protocol does not report the active layer, so every layer is
+/// searched; matches on lower layers come first. Returns `Ok(None)` if no key
+/// was pressed within `timeout`.
+///
+/// The host reports the key after its own layout processing, so this works
+/// best when the host uses the layout the keymap was written for (usually US).
+pub fn locate_key<T: Transport>(
+    client: &mut StudioClient<T>,
+    source: &mut impl HostKeySource,
+    timeout: Duration,
+) -> Result<Option<LocatedKey>, LocatorError> {
+    let keymap = client.get_typed_keymap()?;
+    let Some(usage) = source.next_key(timeout)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(LocatedKey {
+        usage,
+        matches: find_positions(&keymap, usage),
+    }))
+}
+
+/// Finds the positions whose binding emits `usage` when tapped.
+///
+/// Modifiers are ignored, since the host observes them as separate keys.
+pub fn find_positions(keymap: &TypedKeymap, usage: HidUsage) -> Vec<KeyMatch> {
+    let wanted = usage.base();
+    let mut matches = Vec::new();
+
+    for (layer_index, layer) in keymap.layers.iter().enumerate() {
+        for (position, behavior) in layer.bindings.iter().enumerate() {
+            if tapped_usage(behavior).is_some_and(|u| u.base() == wanted) {
+                matches.push(KeyMatch {
+                    layer_id: layer.id,
+                    layer_index,
+                    position,
+                });
+            }
+        }
+    }
+
+    matches
+}
+
+fn tapped_usage(behavior: &Behavior) -> Option<HidUsage> {
+    match behavior {
+        Behavior::KeyPress(key) | Behavior::KeyToggle(key) | Behavior::StickyKey(key) => Some(*key),
+        Behavior::LayerTap { tap, .. } | Behavior::ModTap { tap, .. } => Some(*tap),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyMatch, find_positions};
+    use crate::keymap::{TypedKeymap, TypedLayer};
+    use crate::{Behavior, HidUsage, Keycode};
+
+    fn key(keycode: Keycode) -> HidUsage {
+        HidUsage::from_encoded(keycode.to_hid_usage())
+    }
+
+    #[test]
+    fn finds_tap_positions_across_layers() {
+        let keymap = TypedKeymap {
+            layers: vec![
+                TypedLayer {
+                    id: 0,
+                    name: "Base".to_string(),
+                    bindings: vec![
+                        Behavior::KeyPress(key(Keycode::A)),
+                        Behavior::ModTap {
+                            hold: key(Keycode::LEFT_SHIFT),
+                            tap: key(Keycode::SPACE),
+                        },
+                    ],
+                },
+                TypedLayer {
+                    id: 3,
+                    name: "Sym".to_string(),
+                    bindings: vec![
+                        Behavior::KeyPress(HidUsage::from_parts(0x07, 0x2C, 0x02)),
+                        Behavior::Transparent,
+                    ],
+                },
+            ],
+        };
+
+        assert_eq!(
+            find_positions(&keymap, key(Keycode::SPACE)),
+            vec![
+                KeyMatch {
+                    layer_id: 0,
+                    layer_index: 0,
+                    position: 1,
+                },
+                KeyMatch {
+                    layer_id: 3,
+                    layer_index: 1,
+                    position: 0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use prost::Message;
+
+use crate::proto::zmk;
+use crate::proto::zmk::studio;
+
+/// A decoded device notification.
+///
+/// Delivered to listeners added with [`crate::StudioClient::add_notification_listener`]
+/// or [`crate::StudioClient::subscribe_notifications`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification {
+    /// The Studio lock state changed, for example after `&studio_unlock` was pressed.
+    LockStateChanged(zmk::core::LockState),
+    /// The device gained (`true`) or lost (`false`) unsaved keymap changes.
+    UnsavedChangesStatusChanged(bool),
+    /// A notification this crate does not recognize, e.g. from newer firmware.
+    Unknown(UnknownNotification),
+}
+
+/// A notification this crate does not recognize, with its encoded form so it
+/// can be decoded with newer protos.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownNotification {
+    /// The notification as far as this crate's protos decode it.
+    pub notification: studio::Notification,
+    /// The encoded `zmk.studio.Notification` as received, including fields
+    /// the protos do not know.
+    pub bytes: Vec<u8>,
+}
+
+impl UnknownNotification {
+    fn new(notification: studio::Notification) -> Self {
+        Self {
+            bytes: notification.encode_to_vec(),
+            notification,
+        }
+    }
+}
+
+impl Notification {
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            Self::LockStateChanged(_) => NotificationKind::LockStateChanged,
+            Self::UnsavedChangesStatusChanged(_) => NotificationKind::UnsavedChangesStatusChanged,
+            Self::Unknown(_) => NotificationKind::Unknown,
+        }
+    }
+
+    /// Decodes `notification`, keeping the bytes of `frame`, the response
+    /// frame it arrived in, if this crate does not recognize it.
+    pub(crate) fn from_frame(notification: studio::Notification, frame: &[u8]) -> Self {
+        match Self::from(notification) {
+            Self::Unknown(unknown) => Self::Unknown(UnknownNotification {
+                bytes: crate::protocol::notification_bytes(frame).unwrap_or(unknown.bytes),
+                ..unknown
+            }),
+            typed => typed,
+        }
+    }
+}
+
+impl From<studio::Notification> for Notification {
+    fn from(notification: studio::Notification) -> Self {
+        match &notification.subsystem {
+            Some(studio::notification::Subsystem::Core(zmk::core::Notification {
+                notification_type:
+                    Some(zmk::core::notification::NotificationType::LockStateChanged(raw)),
+            })) => match zmk::core::LockState::try_from(*raw) {
+                Ok(state) => Self::LockStateChanged(state),
+                Err(_) => Self::Unknown(UnknownNotification::new(notification)),
+            },
+            Some(studio::notification::Subsystem::Keymap(zmk::keymap::Notification {
+                notification_type:
+                    Some(zmk::keymap::notification::NotificationType::UnsavedChangesStatusChanged(
+                        unsaved,
+                    )),
+            })) => Self::UnsavedChangesStatusChanged(*unsaved),
+            _ => Self::Unknown(UnknownNotification::new(notification)),
+        }
+    }
+}
+
+/// Category of a device notification, used for filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationKind {
+    /// Core subsystem: the Studio lock state changed.
+    LockStateChanged,
+    /// Keymap subsystem: the device gained or lost unsaved changes.
+    UnsavedChangesStatusChanged,
+    /// A notification this crate does not recognize, e.g. from newer firmware.
+    Unknown,
+}
+
+impl NotificationKind {
+    const ALL: [Self; 3] = [
+        Self::LockStateChanged,
+        Self::UnsavedChangesStatusChanged,
+        Self::Unknown,
+    ];
+
+    pub fn of(notification: &studio::Notification) -> Self {
+        match &notification.subsystem {
+            Some(studio::notification::Subsystem::Core(core)) => match core.notification_type {
+                Some(zmk::core::notification::NotificationType::LockStateChanged(_)) => {
+                    Self::LockStateChanged
+                }
+                None => Self::Unknown,
+            },
+            Some(studio::notification::Subsystem::Keymap(keymap)) => {
+                match keymap.notification_type {
+                    Some(
+                        zmk::keymap::notification::NotificationType::UnsavedChangesStatusChanged(_),
+                    ) => Self::UnsavedChangesStatusChanged,
+                    None => Self::Unknown,
+                }
+            }
+            None => Self::Unknown,
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << Self::ALL.iter().position(|kind| *kind == self).unwrap_or(0)
+    }
+}
+
+/// Set of [`NotificationKind`]s a [`crate::StudioClient`] keeps.
+///
+/// Notifications of other kinds are discarded as soon as they are decoded,
+/// before they reach the notification queue. The default accepts everything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationFilter {
+    accepted: u8,
+}
+
+impl NotificationFilter {
+    pub fn all() -> Self {
+        Self { accepted: u8::MAX }
+    }
+
+    pub fn none() -> Self {
+        Self { accepted: 0 }
+    }
+
+    pub fn only(kinds: impl IntoIterator<Item = NotificationKind>) -> Self {
+        kinds.into_iter().fold(Self::none(), Self::with)
+    }
+
+    #[must_use]
+    pub fn with(self, kind: NotificationKind) -> Self {
+        Self {
+            accepted: self.accepted | kind.bit(),
+        }
+    }
+
+    #[must_use]
+    pub fn without(self, kind: NotificationKind) -> Self {
+        Self {
+            accepted: self.accepted & !kind.bit(),
+        }
+    }
+
+    pub fn accepts(self, kind: NotificationKind) -> bool {
+        self.accepted & kind.bit() != 0
+    }
+
+    pub(crate) fn accepts_notification(self, notification: &studio::Notification) -> bool {
+        self.accepts(NotificationKind::of(notification))
+    }
+}
+
+impl Default for NotificationFilter {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_state_changed() -> studio::Notification {
+        studio::Notification {
+            subsystem: Some(studio::notification::Subsystem::Core(
+                zmk::core::Notification {
+                    notification_type: Some(
+                        zmk::core::notification::NotificationType::LockStateChanged(0),
+                    ),
+                },
+            )),
+        }
+    }
+
+    #[test]
+    fn filters_by_kind() {
+        let notification = lock_state_changed();
+        assert_eq!(
+            NotificationKind::of(&notification),
+            NotificationKind::LockStateChanged
+        );
+
+        let filter = NotificationFilter::only([NotificationKind::LockStateChanged]);
+        assert!(filter.accepts_notification(&notification));
+        assert!(!filter.accepts(NotificationKind::UnsavedChangesStatusChanged));
+        assert!(!filter.accepts(NotificationKind::Unknown));
+
+        let filter = NotificationFilter::default().without(NotificationKind::LockStateChanged);
+        assert!(!filter.accepts_notification(&notification));
+        assert!(filter.accepts(NotificationKind::Unknown));
+    }
+
+    #[test]
+    fn decodes_typed_notifications() {
+        let notification = Notification::from(lock_state_changed());
+        assert_eq!(
+            notification,
+            Notification::LockStateChanged(zmk::core::LockState::ZmkStudioCoreLockStateLocked)
+        );
+        assert_eq!(notification.kind(), NotificationKind::LockStateChanged);
+
+        let empty = studio::Notification { subsystem: None };
+        assert_eq!(
+            Notification::from(empty),
+            Notification::Unknown(UnknownNotification {
+                notification: empty,
+                bytes: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn treats_unknown_enum_values_as_unknown_notifications() {
+        let notification = studio::Notification {
+            subsystem: Some(studio::notification::Subsystem::Core(
+                zmk::core::Notification {
+                    notification_type: Some(
+                        zmk::core::notification::NotificationType::LockStateChanged(99),
+                    ),
+                },
+            )),
+        };
+        let Notification::Unknown(unknown) = Notification::from(notification) else {
+            panic!("lock state 99 should not decode");
+        };
+        assert_eq!(unknown.notification, notification);
+        assert_eq!(
+            studio::Notification::decode(unknown.bytes.as_slice()),
+            Ok(notification)
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Glob-importable set of the types most programs need.
+//!
+//! ```
+//! use zmk_studio_api::prelude::*;
+//!
+//! let mut client = StudioClient::new(MockDevice::new());
+//! let keymap: Keymap = client.get_keymap()?;
+//! let binding: &BehaviorBinding = &keymap.layers[0].bindings[0];
+//! assert_eq!(
+//!     client.get_key_at(keymap.layers[0].id, 0)?,
+//!     Behavior::KeyPress(HidUsage::from_encoded(Keycode::A.to_hid_usage())),
+//! );
+//! # let _ = binding;
+//! # Ok::<(), ClientError>(())
+//! ```
+
+pub use crate::keymap::{TypedKeymap, TypedLayer};
+pub use crate::notification::Notification;
+/// Raw protobuf messages, for the calls that return them.
+pub use crate::proto::zmk;
+pub use crate::proto::zmk::keymap::{BehaviorBinding, Keymap, Layer, PhysicalLayouts};
+#[cfg(feature = "ble")]
+pub use crate::transport::ble::{BleTransport, BleTransportError};
+pub use crate::transport::mock::MockDevice;
+#[cfg(feature = "serial")]
+pub use crate::transport::serial::{SerialTransport, SerialTransportError};
+pub use crate::{Behavior, ClientError, DeviceInfo, HidUsage, Keycode, ModifierSet, StudioClient};
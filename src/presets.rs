@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::binding::Behavior;
+use crate::zmk_syntax::{ZmkSyntaxError, format_binding, parse_binding};
+
+/// Prefix marking a preset reference where a binding is expected.
+pub const PRESET_PREFIX: char = '@';
+
+const DEFAULT_PRESETS: &[(&str, &str)] = &[
+    ("copy", "&kp LC(C)"),
+    ("cut", "&kp LC(X)"),
+    ("paste", "&kp LC(V)"),
+    ("undo", "&kp LC(Z)"),
+    ("redo", "&kp LC(Y)"),
+    ("select_all", "&kp LC(A)"),
+    ("find", "&kp LC(F)"),
+    ("save", "&kp LC(S)"),
+    ("screenshot", "&kp LG(LS(S))"),
+];
+
+/// Errors from loading presets or resolving a preset reference.
+#[derive(Debug)]
+pub enum PresetError {
+    Io(std::io::Error),
+    /// A preset file line is not of the form `name = binding`.
+    InvalidLine {
+        line: usize,
+    },
+    Syntax {
+        line: usize,
+        error: ZmkSyntaxError,
+    },
+    UnknownPreset(String),
+}
+
+impl std::fmt::Display for PresetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Preset I/O error: {err}"),
+            Self::InvalidLine { line } => {
+                write!(f, "Preset line {line}: expected `name = binding`")
+            }
+            Self::Syntax { line, error } => write!(f, "Preset line {line}: {error}"),
+            Self::UnknownPreset(name) => write!(f, "Unknown preset {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Syntax { error, .. } => Some(error),
+            Self::InvalidLine { .. } | Self::UnknownPreset(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PresetError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// User-extensible registry of named bindings such as `copy` or `screenshot`.
+///
+/// Presets are stored as text, one `name = binding` per line in ZMK keymap
+/// syntax, with `#` starting a comment:
+///
+/// ```text
+/// copy = &kp LC(C)
+/// screenshot = &kp LG(LS(S))
+/// ```
+///
+/// Wherever a binding is accepted from users (scripts, CLI commands), a
+/// preset can be referenced as `@name`; see [`PresetRegistry::resolve`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PresetRegistry {
+    presets: BTreeMap<String, Behavior>,
+}
+
+impl PresetRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with common editing shortcuts (`copy`, `paste`, `undo`, ...).
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        for (name, binding) in DEFAULT_PRESETS {
+            let behavior = parse_binding(binding, &|_| None).expect("default presets are valid");
+            registry.insert(*name, behavior);
+        }
+        registry
+    }
+
+    /// Adds or replaces a preset, returning the previous binding.
+    pub fn insert(&mut self, name: impl Into<String>, behavior: Behavior) -> Option<Behavior> {
+        self.presets.insert(name.into(), behavior)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Behavior> {
+        self.presets.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Behavior> {
+        self.presets.get(name)
+    }
+
+    /// Iterates presets in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Behavior)> {
+        self.presets
+            .iter()
+            .map(|(name, behavior)| (name.as_str(), behavior))
+    }
+
+    /// Adds every preset of `other`, replacing presets with the same name.
+    pub fn extend(&mut self, other: PresetRegistry) {
+        self.presets.extend(other.presets);
+    }
+
+    /// Resolves `@name` to a preset, or parses `text` as a ZMK binding.
+    pub fn resolve(&self, text: &str) -> Result<Behavior, PresetError> {
+        let text = text.trim();
+        if let Some(name) = text.strip_prefix(PRESET_PREFIX) {
+            return self
+                .get(name)
+                .cloned()
+                .ok_or_else(|| PresetError::UnknownPreset(name.to_string()));
+        }
+        parse_binding(text, &|_| None).map_err(|error| PresetError::Syntax { line: 1, error })
+    }
+
+    /// Parses presets from their text form.
+    pub fn parse(text: &str) -> Result<Self, PresetError> {
+        let mut registry = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (name, binding) = line
+                .split_once('=')
+                .map(|(name, binding)| (name.trim(), binding.trim()))
+                .filter(|(name, _)| !name.is_empty())
+                .ok_or(PresetError::InvalidLine { line: line_number })?;
+            let behavior =
+                parse_binding(binding, &|_| None).map_err(|error| PresetError::Syntax {
+                    line: line_number,
+                    error,
+                })?;
+            registry.insert(name, behavior);
+        }
+        Ok(registry)
+    }
+
+    /// Renders presets in their text form, sorted by name.
+    pub fn to_text(&self) -> String {
+        self.iter()
+            .map(|(name, behavior)| format!("{name} = {}\n", format_binding(behavior)))
+            .collect()
+    }
+
+    /// Loads presets from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PresetError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Writes presets to a file, replacing it.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PresetError> {
+        std::fs::write(path, self.to_text())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PresetError, PresetRegistry};
+    use crate::{Behavior, HidUsage, Keycode, MOD_LCTL};
+
+    #[test]
+    fn parses_resolves_and_round_trips() {
+        let mut registry = PresetRegistry::parse(
+            "# shortcuts\n\
+             copy = &kp LC(C)\n\
+             nav = &mo 2  # hold for navigation\n",
+        )
+        .expect("presets should parse");
+
+        let copy = Behavior::KeyPress(HidUsage::from_parts(
+            0x07,
+            Keycode::C as u32 as u16,
+            MOD_LCTL,
+        ));
+        assert_eq!(registry.resolve("@copy").ok(), Some(copy));
+        assert_eq!(
+            registry.resolve("&kp ESC").ok(),
+            Some(Behavior::KeyPress(HidUsage::from_encoded(
+                Keycode::ESCAPE.to_hid_usage()
+            )))
+        );
+        assert!(matches!(
+            registry.resolve("@paste"),
+            Err(PresetError::UnknownPreset(_))
+        ));
+
+        registry.insert("trans", Behavior::Transparent);
+        assert_eq!(
+            registry.to_text(),
+            "copy = &kp LC(C)\nnav = &mo 2\ntrans = &trans\n"
+        );
+        assert_eq!(
+            PresetRegistry::parse(&registry.to_text()).ok(),
+            Some(registry)
+        );
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(PresetRegistry::with_defaults().get("screenshot").is_some());
+    }
+}
@@ -0,0 +1,101 @@
+/// How device serial numbers are rendered in user-visible output.
+///
+/// Applies wherever this crate formats a serial number for humans or exports
+/// (for example [`crate::StudioClient::device_serial`] and the D-Bus service);
+/// raw protobuf responses are never modified. `Debug` output and logs never
+/// show serial numbers, whatever the mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerialRedaction {
+    /// Full lowercase hex string.
+    #[default]
+    None,
+    /// Keeps only the last `visible` hex digits, masking the rest with `*`.
+    Truncate { visible: usize },
+    /// Stable, non-reversible 64-bit FNV-1a hash, prefixed with `fnv:`.
+    Hash,
+}
+
+impl SerialRedaction {
+    /// Renders `serial` according to this redaction mode.
+    pub fn apply(self, serial: &[u8]) -> String {
+        let hex = serial_hex(serial);
+        match self {
+            Self::None => hex,
+            Self::Truncate { visible } => {
+                let hidden = hex.len().saturating_sub(visible);
+                let mut out = "*".repeat(hidden);
+                out.push_str(&hex[hidden..]);
+                out
+            }
+            Self::Hash => format!("fnv:{:016x}", fnv1a_64(serial)),
+        }
+    }
+}
+
+/// Stands in for a serial number of `.0` bytes or characters in `Debug` output.
+pub(crate) struct RedactedSerial(pub usize);
+
+impl std::fmt::Debug for RedactedSerial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{} redacted>", self.0)
+    }
+}
+
+/// Formats serial number bytes as a lowercase hex string.
+pub fn serial_hex(serial: &[u8]) -> String {
+    serial.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SerialRedaction;
+    use crate::proto::zmk::{core, studio};
+
+    const SERIAL: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+
+    #[test]
+    fn applies_redaction_modes() {
+        assert_eq!(SerialRedaction::None.apply(&SERIAL), "deadbeef");
+        assert_eq!(
+            SerialRedaction::Truncate { visible: 3 }.apply(&SERIAL),
+            "*****eef"
+        );
+        assert_eq!(
+            SerialRedaction::Truncate { visible: 20 }.apply(&SERIAL),
+            "deadbeef"
+        );
+
+        let hashed = SerialRedaction::Hash.apply(&SERIAL);
+        assert!(hashed.starts_with("fnv:"));
+        assert_eq!(hashed, SerialRedaction::Hash.apply(&SERIAL));
+        assert_ne!(hashed, SerialRedaction::Hash.apply(&[0xde, 0xad]));
+    }
+
+    #[test]
+    fn keeps_serials_out_of_debug_output() {
+        let info = core::GetDeviceInfoResponse {
+            name: "Corne".to_string(),
+            serial_number: SERIAL.to_vec(),
+        };
+        let response = studio::RequestResponse {
+            request_id: 1,
+            subsystem: Some(studio::request_response::Subsystem::Core(core::Response {
+                response_type: Some(core::response::ResponseType::GetDeviceInfo(info)),
+            })),
+        };
+        let debug = format!("{response:?}");
+        assert!(debug.contains("Corne"));
+        assert!(debug.contains("serial_number: <4 redacted>"));
+        assert!(!debug.contains("222, 173, 190, 239"));
+        assert!(!debug.contains("deadbeef"));
+    }
+}
@@ -1 +1,13 @@
 include!(concat!(env!("OUT_DIR"), "/proto_mod.rs"));
+
+impl std::fmt::Debug for zmk::core::GetDeviceInfoResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GetDeviceInfoResponse")
+            .field("name", &self.name)
+            .field(
+                "serial_number",
+                &crate::privacy::RedactedSerial(self.serial_number.len()),
+            )
+            .finish()
+    }
+}
@@ -1,7 +1,12 @@
+use std::io::Write;
+
 use prost::Message;
 
-use crate::framing::{FrameDecoder, FramingError, encode_frame};
-use crate::proto::zmk::studio::{Request, Response};
+use crate::framing::encode_frame;
+pub use crate::framing::{FrameDecoder, FramingError};
+use crate::proto::zmk::studio::{
+    Notification, Request, RequestResponse, Response, notification, request_response, response,
+};
 
 #[derive(Debug)]
 pub enum ProtocolError {
@@ -39,10 +44,42 @@ impl From<prost::DecodeError> for ProtocolError {
     }
 }
 
+/// Encodes a request as a complete frame, ready to write to a transport.
 pub fn encode_request(request: &Request) -> Vec<u8> {
     encode_frame(&request.encode_to_vec())
 }
 
+/// Encodes a response as a complete frame, ready to write to a transport.
+pub fn encode_response(response: &Response) -> Vec<u8> {
+    encode_frame(&response.encode_to_vec())
+}
+
+/// Wraps a request response in a [`Response`] and encodes it as a frame.
+pub fn encode_request_response(request_response: RequestResponse) -> Vec<u8> {
+    encode_response(&Response {
+        r#type: Some(response::Type::RequestResponse(request_response)),
+    })
+}
+
+/// Wraps a notification in a [`Response`] and encodes it as a frame.
+pub fn encode_notification(notification: Notification) -> Vec<u8> {
+    encode_response(&Response {
+        r#type: Some(response::Type::Notification(notification)),
+    })
+}
+
+/// Decodes the requests completed by `chunk`, for the device side of a connection.
+pub fn decode_requests(
+    decoder: &mut FrameDecoder,
+    chunk: &[u8],
+) -> Result<Vec<Request>, ProtocolError> {
+    decoder
+        .push(chunk)?
+        .into_iter()
+        .map(|frame| Request::decode(frame.as_slice()).map_err(ProtocolError::from))
+        .collect()
+}
+
 pub fn decode_responses(
     decoder: &mut FrameDecoder,
     chunk: &[u8],
@@ -53,3 +90,167 @@ pub fn decode_responses(
         .map(|frame| Response::decode(frame.as_slice()).map_err(ProtocolError::from))
         .collect()
 }
+
+/// Like [`decode_responses`], but also returns the frame each response was
+/// decoded from, which keeps fields this crate's protos do not know.
+pub fn decode_responses_with_frames(
+    decoder: &mut FrameDecoder,
+    chunk: &[u8],
+) -> Result<Vec<(Response, Vec<u8>)>, ProtocolError> {
+    decoder
+        .push(chunk)?
+        .into_iter()
+        .map(|frame| Ok((Response::decode(frame.as_slice())?, frame)))
+        .collect()
+}
+
+/// [`Response`] with its variants left encoded.
+#[derive(Clone, PartialEq, Message)]
+struct EncodedResponse {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    request_response: Option<Vec<u8>>,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    notification: Option<Vec<u8>>,
+}
+
+/// The encoded [`RequestResponse`] inside a response frame, if it holds one.
+pub(crate) fn request_response_bytes(frame: &[u8]) -> Option<Vec<u8>> {
+    EncodedResponse::decode(frame).ok()?.request_response
+}
+
+/// The encoded [`Notification`] inside a response frame, if it holds one.
+pub(crate) fn notification_bytes(frame: &[u8]) -> Option<Vec<u8>> {
+    EncodedResponse::decode(frame).ok()?.notification
+}
+
+/// Writes framed responses and notifications, for device emulators and bridges.
+///
+/// Every write is flushed so the peer sees complete frames immediately.
+#[derive(Debug)]
+pub struct ResponseWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> ResponseWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Answers the request with ID `request_id`.
+    pub fn respond(
+        &mut self,
+        request_id: u32,
+        subsystem: request_response::Subsystem,
+    ) -> std::io::Result<()> {
+        self.write_frame(&encode_request_response(RequestResponse {
+            request_id,
+            subsystem: Some(subsystem),
+        }))
+    }
+
+    /// Sends an unsolicited notification.
+    pub fn notify(&mut self, subsystem: notification::Subsystem) -> std::io::Result<()> {
+        self.write_frame(&encode_notification(Notification {
+            subsystem: Some(subsystem),
+        }))
+    }
+
+    /// Sends an already-built response.
+    pub fn write_response(&mut self, response: &Response) -> std::io::Result<()> {
+        self.write_frame(&encode_response(response))
+    }
+
+    fn write_frame(&mut self, frame: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(frame)?;
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::zmk::{core, studio::request};
+
+    #[test]
+    fn server_frames_round_trip_through_client_decoding() {
+        let mut writer = ResponseWriter::new(Vec::new());
+        writer
+            .respond(
+                7,
+                request_response::Subsystem::Core(core::Response {
+                    response_type: Some(core::response::ResponseType::GetLockState(
+                        core::LockState::ZmkStudioCoreLockStateUnlocked.into(),
+                    )),
+                }),
+            )
+            .expect("writing to a Vec succeeds");
+        writer
+            .notify(notification::Subsystem::Core(core::Notification {
+                notification_type: Some(core::notification::NotificationType::LockStateChanged(
+                    core::LockState::ZmkStudioCoreLockStateLocked.into(),
+                )),
+            }))
+            .expect("writing to a Vec succeeds");
+
+        let responses = decode_responses(&mut FrameDecoder::new(), &writer.into_inner())
+            .expect("frames decode");
+        assert_eq!(responses.len(), 2);
+        assert!(matches!(
+            &responses[0].r#type,
+            Some(response::Type::RequestResponse(rr)) if rr.request_id == 7
+        ));
+        assert!(matches!(
+            responses[1].r#type,
+            Some(response::Type::Notification(_))
+        ));
+
+        let request = Request {
+            request_id: 3,
+            subsystem: Some(request::Subsystem::Core(core::Request {
+                request_type: Some(core::request::RequestType::GetLockState(true)),
+            })),
+        };
+        let requests = decode_requests(&mut FrameDecoder::new(), &encode_request(&request))
+            .expect("frames decode");
+        assert_eq!(requests, vec![request]);
+    }
+
+    #[test]
+    fn keeps_fields_unknown_to_the_protos() {
+        // A core response with a response type added by newer firmware (field 15).
+        let core = [0x7a, 0x01, 0x2a];
+        let mut request_response = vec![0x08, 0x05, 0x1a, core.len() as u8];
+        request_response.extend(core);
+        let mut frame = vec![0x0a, request_response.len() as u8];
+        frame.extend(&request_response);
+
+        let decoded = decode_responses_with_frames(&mut FrameDecoder::new(), &encode_frame(&frame))
+            .expect("frames decode");
+        let [(response, raw)] = decoded.as_slice() else {
+            panic!("expected one response, got {decoded:?}");
+        };
+        assert_eq!(
+            response.r#type,
+            Some(response::Type::RequestResponse(RequestResponse {
+                request_id: 5,
+                subsystem: Some(request_response::Subsystem::Core(core::Response {
+                    response_type: None
+                })),
+            }))
+        );
+        assert_eq!(request_response_bytes(raw), Some(request_response));
+        assert_eq!(notification_bytes(raw), None);
+    }
+}
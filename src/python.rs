@@ -1,22 +1,35 @@
-use std::io::{Read, Write};
+use std::ffi::CString;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Mutex;
 
 use prost::Message;
+use pyo3::create_exception;
 use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyBytes, PyDict, PyModule};
 use strum::IntoEnumIterator;
 
+use crate::binding::role_from_display_name;
+use crate::keymap::{TypedKeymap, TypedLayer};
+use crate::proto::zmk::behaviors::{
+    BehaviorParameterValueDescription, GetBehaviorDetailsResponse,
+    behavior_parameter_value_description::ValueType,
+};
+use crate::proto::zmk::keymap::PhysicalLayout;
+use crate::sync::{SyncDifference, diff_snapshots};
+use crate::transport::Transport;
 #[cfg(feature = "ble")]
-use crate::transport::ble::BleTransport;
+use crate::transport::ble::{BleScanOptions, BleTransport};
+use crate::transport::mock::MockDevice;
 #[cfg(feature = "serial")]
-use crate::transport::serial::SerialTransport;
+use crate::transport::serial::{SerialTransport, is_zmk_device};
+use crate::zmk_syntax::{format_binding, parse_binding};
 use crate::{Behavior, ClientError, HidUsage, Keycode, StudioClient};
 
-trait ReadWriteSend: Read + Write + Send {}
-impl<T: Read + Write + Send> ReadWriteSend for T {}
+trait TransportSend: Transport + Send {}
+impl<T: Transport + Send> TransportSend for T {}
 
-type DynClient = StudioClient<Box<dyn ReadWriteSend>>;
+type DynClient = StudioClient<Box<dyn TransportSend>>;
 
 #[pyclass(name = "Behavior")]
 #[derive(Clone)]
@@ -65,11 +78,667 @@ impl PyBehavior {
         }
     }
 
+    /// Encoded HID usage of a `KeyPress`, `KeyToggle` or `StickyKey`.
+    #[getter]
+    pub fn key(&self) -> Option<u32> {
+        match self.inner {
+            Behavior::KeyPress(key) | Behavior::KeyToggle(key) | Behavior::StickyKey(key) => {
+                Some(key.to_hid_usage())
+            }
+            _ => None,
+        }
+    }
+
+    #[getter]
+    pub fn layer_id(&self) -> Option<u32> {
+        match self.inner {
+            Behavior::LayerTap { layer_id, .. }
+            | Behavior::StickyLayer { layer_id }
+            | Behavior::MomentaryLayer { layer_id }
+            | Behavior::ToggleLayer { layer_id }
+            | Behavior::ToLayer { layer_id } => Some(layer_id),
+            _ => None,
+        }
+    }
+
+    /// Encoded HID usage held by a `ModTap`.
+    #[getter]
+    pub fn hold(&self) -> Option<u32> {
+        match self.inner {
+            Behavior::ModTap { hold, .. } => Some(hold.to_hid_usage()),
+            _ => None,
+        }
+    }
+
+    /// Encoded HID usage tapped by a `LayerTap` or `ModTap`.
+    #[getter]
+    pub fn tap(&self) -> Option<u32> {
+        match self.inner {
+            Behavior::LayerTap { tap, .. } | Behavior::ModTap { tap, .. } => {
+                Some(tap.to_hid_usage())
+            }
+            _ => None,
+        }
+    }
+
+    #[getter]
+    pub fn command(&self) -> Option<u32> {
+        match self.inner {
+            Behavior::Bluetooth { command, .. }
+            | Behavior::Backlight { command, .. }
+            | Behavior::Underglow { command, .. } => Some(command),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    pub fn value(&self) -> Option<u32> {
+        match self.inner {
+            Behavior::Bluetooth { value, .. }
+            | Behavior::Backlight { value, .. }
+            | Behavior::Underglow { value, .. }
+            | Behavior::ExternalPower { value }
+            | Behavior::OutputSelection { value }
+            | Behavior::MouseKeyPress { value }
+            | Behavior::MouseMove { value }
+            | Behavior::MouseScroll { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Firmware behavior ID of an `Unknown` binding.
+    #[getter]
+    pub fn behavior_id(&self) -> Option<i32> {
+        match self.inner {
+            Behavior::Unknown { behavior_id, .. } => Some(behavior_id),
+            _ => None,
+        }
+    }
+
+    /// Raw first parameter, as sent to the firmware.
+    #[getter]
+    pub fn param1(&self) -> u32 {
+        self.inner.params().0
+    }
+
+    /// Raw second parameter, as sent to the firmware.
+    #[getter]
+    pub fn param2(&self) -> u32 {
+        self.inner.params().1
+    }
+
+    /// Returns `{"kind": ...}` plus the fields set for this kind, e.g.
+    /// `{"kind": "ModTap", "hold": 0x700E1, "tap": 0x70029}`.
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("kind", self.kind())?;
+        if let Behavior::Unknown {
+            behavior_id,
+            param1,
+            param2,
+        } = self.inner
+        {
+            dict.set_item("behavior_id", behavior_id)?;
+            dict.set_item("param1", param1)?;
+            dict.set_item("param2", param2)?;
+            return Ok(dict);
+        }
+        let fields = [
+            ("key", self.key()),
+            ("layer_id", self.layer_id()),
+            ("hold", self.hold()),
+            ("tap", self.tap()),
+            ("command", self.command()),
+            ("value", self.value()),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                dict.set_item(name, value)?;
+            }
+        }
+        Ok(dict)
+    }
+
+    /// Builds a behavior from a dict in the shape returned by `to_dict`.
+    ///
+    /// Key fields also accept keycode names.
+    #[staticmethod]
+    pub fn from_dict<'py>(data: &Bound<'py, PyDict>) -> PyResult<Self> {
+        let field = |name: &str| -> PyResult<Bound<'py, PyAny>> {
+            data.get_item(name)?
+                .ok_or_else(|| PyValueError::new_err(format!("behavior dict has no {name:?}")))
+        };
+        let key = |name: &str| parse_hid_usage(&field(name)?);
+        let number = |name: &str| field(name)?.extract::<u32>();
+
+        let kind: String = field("kind")?.extract()?;
+        let inner = match kind.as_str() {
+            "KeyPress" => Behavior::KeyPress(key("key")?),
+            "KeyToggle" => Behavior::KeyToggle(key("key")?),
+            "LayerTap" => Behavior::LayerTap {
+                layer_id: number("layer_id")?,
+                tap: key("tap")?,
+            },
+            "ModTap" => Behavior::ModTap {
+                hold: key("hold")?,
+                tap: key("tap")?,
+            },
+            "StickyKey" => Behavior::StickyKey(key("key")?),
+            "StickyLayer" => Behavior::StickyLayer {
+                layer_id: number("layer_id")?,
+            },
+            "MomentaryLayer" => Behavior::MomentaryLayer {
+                layer_id: number("layer_id")?,
+            },
+            "ToggleLayer" => Behavior::ToggleLayer {
+                layer_id: number("layer_id")?,
+            },
+            "ToLayer" => Behavior::ToLayer {
+                layer_id: number("layer_id")?,
+            },
+            "Bluetooth" => Behavior::Bluetooth {
+                command: number("command")?,
+                value: number("value")?,
+            },
+            "ExternalPower" => Behavior::ExternalPower {
+                value: number("value")?,
+            },
+            "OutputSelection" => Behavior::OutputSelection {
+                value: number("value")?,
+            },
+            "Backlight" => Behavior::Backlight {
+                command: number("command")?,
+                value: number("value")?,
+            },
+            "Underglow" => Behavior::Underglow {
+                command: number("command")?,
+                value: number("value")?,
+            },
+            "MouseKeyPress" => Behavior::MouseKeyPress {
+                value: number("value")?,
+            },
+            "MouseMove" => Behavior::MouseMove {
+                value: number("value")?,
+            },
+            "MouseScroll" => Behavior::MouseScroll {
+                value: number("value")?,
+            },
+            "CapsWord" => Behavior::CapsWord,
+            "KeyRepeat" => Behavior::KeyRepeat,
+            "Reset" => Behavior::Reset,
+            "Bootloader" => Behavior::Bootloader,
+            "SoftOff" => Behavior::SoftOff,
+            "StudioUnlock" => Behavior::StudioUnlock,
+            "GraveEscape" => Behavior::GraveEscape,
+            "Transparent" => Behavior::Transparent,
+            "None" => Behavior::None,
+            "Unknown" => Behavior::Unknown {
+                behavior_id: field("behavior_id")?.extract()?,
+                param1: number("param1")?,
+                param2: number("param2")?,
+            },
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown behavior kind: {other}"
+                )));
+            }
+        };
+        Ok(Self::new(inner))
+    }
+
+    /// Parses one binding in ZMK keymap syntax, such as `&mt LSHIFT ESC`.
+    ///
+    /// Layer parameters are layer IDs; layer names are not resolved.
+    #[staticmethod]
+    pub fn parse(text: &str) -> PyResult<Self> {
+        let inner =
+            parse_binding(text, &|_| None).map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(Self::new(inner))
+    }
+
+    /// Formats the binding in ZMK keymap syntax.
+    pub fn to_zmk(&self) -> String {
+        format_binding(&self.inner)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inner.hash(&mut hasher);
+        hasher.finish()
+    }
+
     fn __repr__(&self) -> String {
         format!("Behavior({:?})", self.inner)
     }
 }
 
+/// A typed keymap, convertible to and from snapshot text.
+#[pyclass(name = "Keymap", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyKeymap {
+    inner: TypedKeymap,
+}
+
+#[pymethods]
+impl PyKeymap {
+    #[staticmethod]
+    pub fn from_snapshot_text(text: &str) -> PyResult<Self> {
+        let inner = TypedKeymap::from_snapshot_text(text)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(Self { inner })
+    }
+
+    pub fn to_snapshot_text(&self) -> String {
+        self.inner.to_snapshot_text()
+    }
+
+    /// Layer IDs in keymap order.
+    #[getter]
+    pub fn layer_ids(&self) -> Vec<u32> {
+        self.inner.layers.iter().map(|layer| layer.id).collect()
+    }
+
+    #[getter]
+    pub fn layers(&self) -> Vec<PyLayer> {
+        self.inner.layers.iter().map(PyLayer::from).collect()
+    }
+
+    pub fn get_key(&self, layer_id: u32, position: usize) -> Option<PyBehavior> {
+        let behavior = self.inner.layer(layer_id)?.bindings.get(position)?;
+        Some(PyBehavior::new(behavior.clone()))
+    }
+
+    pub fn fingerprint(&self) -> String {
+        self.inner.fingerprint().to_string()
+    }
+}
+
+/// One layer of a `Keymap`.
+#[pyclass(name = "Layer", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyLayer {
+    id: u32,
+    name: String,
+    bindings: Vec<PyBehavior>,
+}
+
+impl From<&TypedLayer> for PyLayer {
+    fn from(layer: &TypedLayer) -> Self {
+        Self {
+            id: layer.id,
+            name: layer.name.clone(),
+            bindings: layer
+                .bindings
+                .iter()
+                .cloned()
+                .map(PyBehavior::new)
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyLayer {
+    fn __len__(&self) -> usize {
+        self.bindings.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Layer(id={}, name={:?}, bindings={})",
+            self.id,
+            self.name,
+            self.bindings.len()
+        )
+    }
+}
+
+/// The keyboard name and serial number reported by the firmware.
+#[pyclass(name = "DeviceInfo", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyDeviceInfo {
+    name: String,
+    serial_number: Vec<u8>,
+}
+
+#[pymethods]
+impl PyDeviceInfo {
+    fn __repr__(&self) -> String {
+        format!("DeviceInfo(name={:?})", self.name)
+    }
+}
+
+/// Position and size of one key, in hundredths of a key unit; rotation is in
+/// hundredths of a degree around `(rx, ry)`.
+#[pyclass(name = "KeyPhysicalAttrs", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyKeyPhysicalAttrs {
+    width: i32,
+    height: i32,
+    x: i32,
+    y: i32,
+    r: i32,
+    rx: i32,
+    ry: i32,
+}
+
+#[pymethods]
+impl PyKeyPhysicalAttrs {
+    fn __repr__(&self) -> String {
+        format!(
+            "KeyPhysicalAttrs(x={}, y={}, width={}, height={})",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
+
+/// A named physical layout: key positions in key-position order.
+#[pyclass(name = "PhysicalLayout", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyPhysicalLayout {
+    name: String,
+    keys: Vec<PyKeyPhysicalAttrs>,
+}
+
+impl From<&PhysicalLayout> for PyPhysicalLayout {
+    fn from(layout: &PhysicalLayout) -> Self {
+        Self {
+            name: layout.name.clone(),
+            keys: layout
+                .keys
+                .iter()
+                .map(|key| PyKeyPhysicalAttrs {
+                    width: key.width,
+                    height: key.height,
+                    x: key.x,
+                    y: key.y,
+                    r: key.r,
+                    rx: key.rx,
+                    ry: key.ry,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyPhysicalLayout {
+    fn __repr__(&self) -> String {
+        format!(
+            "PhysicalLayout(name={:?}, keys={})",
+            self.name,
+            self.keys.len()
+        )
+    }
+}
+
+/// The physical layouts a keyboard supports and which one is active.
+#[pyclass(name = "PhysicalLayouts", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyPhysicalLayouts {
+    active_layout_index: u32,
+    layouts: Vec<PyPhysicalLayout>,
+}
+
+#[pymethods]
+impl PyPhysicalLayouts {
+    /// The active layout, if its index is in range.
+    #[getter]
+    pub fn active(&self) -> Option<PyPhysicalLayout> {
+        self.layouts.get(self.active_layout_index as usize).cloned()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PhysicalLayouts(active_layout_index={}, layouts={})",
+            self.active_layout_index,
+            self.layouts.len()
+        )
+    }
+}
+
+/// One change needed to turn keymap `a` into keymap `b`, from [`diff_keymaps`].
+#[pyclass(name = "KeymapDifference", from_py_object)]
+#[derive(Clone)]
+pub struct PyKeymapDifference {
+    inner: SyncDifference,
+}
+
+#[pymethods]
+impl PyKeymapDifference {
+    /// One of `"LayerName"`, `"Binding"`, `"LayerOnlyInTarget"` or `"LayerOnlyInSource"`.
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        match self.inner {
+            SyncDifference::LayerName { .. } => "LayerName",
+            SyncDifference::Binding { .. } => "Binding",
+            SyncDifference::LayerOnlyLocal { .. } => "LayerOnlyInTarget",
+            SyncDifference::LayerOnlyOnDevice { .. } => "LayerOnlyInSource",
+        }
+    }
+
+    #[getter]
+    pub fn layer_id(&self) -> u32 {
+        match self.inner {
+            SyncDifference::LayerName { layer_id, .. }
+            | SyncDifference::Binding { layer_id, .. }
+            | SyncDifference::LayerOnlyLocal { layer_id }
+            | SyncDifference::LayerOnlyOnDevice { layer_id } => layer_id,
+        }
+    }
+
+    #[getter]
+    pub fn position(&self) -> Option<usize> {
+        match self.inner {
+            SyncDifference::Binding { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+
+    /// The value in keymap `a`: a layer name, a `Behavior`, or `None`.
+    #[getter(old)]
+    pub fn old_value<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        match &self.inner {
+            SyncDifference::LayerName { device, .. } => {
+                Ok(Some(device.clone().into_pyobject(py)?.into_any()))
+            }
+            SyncDifference::Binding { device, .. } => behavior_object(py, device.as_ref()),
+            _ => Ok(None),
+        }
+    }
+
+    /// The value in keymap `b`: a layer name, a `Behavior`, or `None`.
+    #[getter(new)]
+    pub fn new_value<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        match &self.inner {
+            SyncDifference::LayerName { local, .. } => {
+                Ok(Some(local.clone().into_pyobject(py)?.into_any()))
+            }
+            SyncDifference::Binding { local, .. } => behavior_object(py, local.as_ref()),
+            _ => Ok(None),
+        }
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("KeymapDifference({})", self.inner)
+    }
+}
+
+fn behavior_object<'py>(
+    py: Python<'py>,
+    behavior: Option<&Behavior>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    behavior
+        .map(|behavior| Ok(Bound::new(py, PyBehavior::new(behavior.clone()))?.into_any()))
+        .transpose()
+}
+
+/// Lists the changes that turn keymap `a` into keymap `b`.
+///
+/// Layers are matched by ID. Pass the result to `StudioClient.apply_diff` to
+/// stage them on a device whose keymap is `a`.
+#[pyfunction]
+fn diff_keymaps(a: &PyKeymap, b: &PyKeymap) -> Vec<PyKeymapDifference> {
+    diff_snapshots(&b.inner, &a.inner)
+        .into_iter()
+        .map(|inner| PyKeymapDifference { inner })
+        .collect()
+}
+
+/// One allowed value (or value range) for a behavior parameter.
+#[pyclass(name = "ParameterDescription", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyParameterDescription {
+    name: String,
+    /// One of `"nil"`, `"constant"`, `"range"`, `"hid_usage"`, `"layer_id"` or `"unknown"`.
+    kind: &'static str,
+    constant: Option<u32>,
+    min: Option<i32>,
+    max: Option<i32>,
+    keyboard_max: Option<u32>,
+    consumer_max: Option<u32>,
+}
+
+impl From<&BehaviorParameterValueDescription> for PyParameterDescription {
+    fn from(value: &BehaviorParameterValueDescription) -> Self {
+        let mut description = Self {
+            name: value.name.clone(),
+            kind: "unknown",
+            constant: None,
+            min: None,
+            max: None,
+            keyboard_max: None,
+            consumer_max: None,
+        };
+        match &value.value_type {
+            Some(ValueType::Nil(_)) => description.kind = "nil",
+            Some(ValueType::Constant(constant)) => {
+                description.kind = "constant";
+                description.constant = Some(*constant);
+            }
+            Some(ValueType::Range(range)) => {
+                description.kind = "range";
+                description.min = Some(range.min);
+                description.max = Some(range.max);
+            }
+            Some(ValueType::HidUsage(usage)) => {
+                description.kind = "hid_usage";
+                description.keyboard_max = Some(usage.keyboard_max);
+                description.consumer_max = Some(usage.consumer_max);
+            }
+            Some(ValueType::LayerId(_)) => description.kind = "layer_id",
+            None => {}
+        }
+        description
+    }
+}
+
+#[pymethods]
+impl PyParameterDescription {
+    fn __repr__(&self) -> String {
+        format!(
+            "ParameterDescription(name={:?}, kind={:?})",
+            self.name, self.kind
+        )
+    }
+}
+
+/// A behavior exposed by the firmware, with its detected role and parameter metadata.
+#[pyclass(name = "BehaviorInfo", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyBehaviorInfo {
+    id: u32,
+    display_name: String,
+    /// Behavior kind this crate recognizes (for example `"KeyPress"`), if any.
+    role: Option<String>,
+    /// Accepted parameter combinations, each a `(param1, param2)` pair of description lists.
+    metadata: Vec<(Vec<PyParameterDescription>, Vec<PyParameterDescription>)>,
+}
+
+impl From<&GetBehaviorDetailsResponse> for PyBehaviorInfo {
+    fn from(details: &GetBehaviorDetailsResponse) -> Self {
+        let descriptions = |values: &[BehaviorParameterValueDescription]| {
+            values.iter().map(PyParameterDescription::from).collect()
+        };
+        Self {
+            id: details.id,
+            display_name: details.display_name.clone(),
+            role: role_from_display_name(&details.display_name).map(|role| format!("{role:?}")),
+            metadata: details
+                .metadata
+                .iter()
+                .map(|set| (descriptions(&set.param1), descriptions(&set.param2)))
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyBehaviorInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "BehaviorInfo(id={}, display_name={:?}, role={:?})",
+            self.id, self.display_name, self.role
+        )
+    }
+}
+
+/// A ZMK Studio keyboard seen advertising over BLE.
+#[pyclass(name = "BleDevice", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyBleDevice {
+    /// Pass to `StudioClient.connect_ble(device_id=...)`.
+    device_id: String,
+    name: Option<String>,
+    /// Bluetooth adapter that saw the device.
+    adapter: String,
+    /// Signal strength in dBm.
+    rssi: Option<i16>,
+    is_connected: bool,
+    /// Battery level in percent, if advertised.
+    battery_level: Option<u8>,
+}
+
+#[pymethods]
+impl PyBleDevice {
+    fn __repr__(&self) -> String {
+        format!(
+            "BleDevice(device_id={:?}, name={:?})",
+            self.device_id, self.name
+        )
+    }
+}
+
+/// A serial port, with USB details when the port is a USB device.
+#[pyclass(name = "SerialPort", get_all, skip_from_py_object)]
+#[derive(Clone)]
+pub struct PySerialPort {
+    /// Pass to `StudioClient.open_serial(path)`.
+    path: String,
+    /// Whether the port has ZMK's default USB vendor and product IDs.
+    is_zmk: bool,
+    vid: Option<u16>,
+    pid: Option<u16>,
+    manufacturer: Option<String>,
+    product: Option<String>,
+    serial_number: Option<String>,
+}
+
+#[pymethods]
+impl PySerialPort {
+    fn __repr__(&self) -> String {
+        format!("SerialPort(path={:?}, is_zmk={})", self.path, self.is_zmk)
+    }
+}
+
 #[pyclass(name = "StudioClient")]
 pub struct PyStudioClient {
     inner: Mutex<DynClient>,
@@ -79,9 +748,9 @@ pub struct PyStudioClient {
 impl PyStudioClient {
     #[staticmethod]
     #[cfg(feature = "serial")]
-    pub fn open_serial(path: &str) -> PyResult<Self> {
-        let transport = SerialTransport::open(path).map_err(|err| {
-            PyRuntimeError::new_err(format!("failed to open serial transport: {err}"))
+    pub fn open_serial(py: Python<'_>, path: &str) -> PyResult<Self> {
+        let transport = py.detach(|| SerialTransport::open(path)).map_err(|err| {
+            ZmkConnectionError::new_err(format!("failed to open serial transport: {err}"))
         })?;
         Ok(Self {
             inner: Mutex::new(StudioClient::new(Box::new(transport))),
@@ -98,10 +767,12 @@ impl PyStudioClient {
 
     #[staticmethod]
     #[cfg(feature = "ble")]
-    pub fn open_ble(device_id: &str) -> PyResult<Self> {
-        let transport = BleTransport::connect_device(device_id).map_err(|err| {
-            PyRuntimeError::new_err(format!("failed to connect BLE transport: {err}"))
-        })?;
+    pub fn open_ble(py: Python<'_>, device_id: &str) -> PyResult<Self> {
+        let transport = py
+            .detach(|| BleTransport::connect_device(device_id))
+            .map_err(|err| {
+                ZmkConnectionError::new_err(format!("failed to connect BLE transport: {err}"))
+            })?;
         Ok(Self {
             inner: Mutex::new(StudioClient::new(Box::new(transport))),
         })
@@ -115,6 +786,52 @@ impl PyStudioClient {
         ))
     }
 
+    /// Connects to the BLE keyboard `device_id` from `discover_ble_devices()`,
+    /// or to the first one discovered when no ID is given.
+    #[staticmethod]
+    #[pyo3(signature = (device_id=None))]
+    #[cfg(feature = "ble")]
+    pub fn connect_ble(py: Python<'_>, device_id: Option<&str>) -> PyResult<Self> {
+        let device_id = match device_id {
+            Some(device_id) => device_id.to_string(),
+            None => {
+                let options = BleScanOptions {
+                    max_devices: Some(1),
+                    ..Default::default()
+                };
+                py.detach(|| crate::transport::ble::discover_devices_with_options(options))
+                    .map_err(|err| {
+                        ZmkConnectionError::new_err(format!(
+                            "failed to scan for BLE devices: {err}"
+                        ))
+                    })?
+                    .into_iter()
+                    .next()
+                    .map(|device| device.device_id)
+                    .ok_or_else(|| ZmkConnectionError::new_err("no ZMK Studio BLE device found"))?
+            }
+        };
+        Self::open_ble(py, &device_id)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (device_id=None))]
+    #[cfg(not(feature = "ble"))]
+    pub fn connect_ble(device_id: Option<&str>) -> PyResult<Self> {
+        let _ = device_id;
+        Err(PyRuntimeError::new_err(
+            "ble support is disabled for this build",
+        ))
+    }
+
+    /// Connects to a fresh in-process mock keyboard, for tests without hardware.
+    #[staticmethod]
+    pub fn open_mock() -> Self {
+        Self {
+            inner: Mutex::new(StudioClient::new(Box::new(MockDevice::new()))),
+        }
+    }
+
     pub fn get_lock_state(&self) -> PyResult<String> {
         let state = self.with_client(|client| client.get_lock_state())?;
         Ok(state.as_str_name().to_string())
@@ -128,6 +845,25 @@ impl PyStudioClient {
         self.with_client(|client| client.list_all_behaviors())
     }
 
+    /// Lists every behavior with its display name, role and parameter metadata.
+    pub fn list_behaviors(&self) -> PyResult<Vec<PyBehaviorInfo>> {
+        self.with_client(|client| {
+            client
+                .list_all_behaviors()?
+                .into_iter()
+                .map(|id| Ok(PyBehaviorInfo::from(&client.get_behavior_details(id)?)))
+                .collect()
+        })
+    }
+
+    pub fn get_device_info(&self) -> PyResult<PyDeviceInfo> {
+        let info = self.with_client(|client| client.get_device_info())?;
+        Ok(PyDeviceInfo {
+            name: info.name,
+            serial_number: info.serial_number,
+        })
+    }
+
     pub fn get_device_info_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
         let info = self.with_client(|client| client.get_device_info())?;
         Ok(PyBytes::new(py, &info.encode_to_vec()))
@@ -155,6 +891,34 @@ impl PyStudioClient {
         Ok(PyBytes::new(py, &layouts.encode_to_vec()))
     }
 
+    pub fn get_physical_layouts(&self) -> PyResult<PyPhysicalLayouts> {
+        let layouts = self.with_client(|client| client.get_physical_layouts())?;
+        Ok(PyPhysicalLayouts {
+            active_layout_index: layouts.active_layout_index,
+            layouts: layouts.layouts.iter().map(PyPhysicalLayout::from).collect(),
+        })
+    }
+
+    /// Reads the keymap with layers, names and typed `Behavior` bindings.
+    pub fn get_keymap(&self) -> PyResult<PyKeymap> {
+        self.get_typed_keymap()
+    }
+
+    pub fn get_typed_keymap(&self) -> PyResult<PyKeymap> {
+        let inner = self.with_client(|client| client.get_typed_keymap())?;
+        Ok(PyKeymap { inner })
+    }
+
+    /// Stages the changes from `diff_keymaps(device_keymap, target)` on the device.
+    ///
+    /// Returns the number of changes applied; layer additions and removals are
+    /// skipped. Persist with `save_changes()`.
+    pub fn apply_diff(&self, diff: Vec<PyKeymapDifference>) -> PyResult<usize> {
+        let differences: Vec<_> = diff.into_iter().map(|d| d.inner).collect();
+        let report = self.with_client(|client| client.apply_differences(&differences))?;
+        Ok(report.pushed)
+    }
+
     pub fn get_key_at(&self, layer_id: u32, key_position: i32) -> PyResult<PyBehavior> {
         let behavior = self.with_client(|client| client.get_key_at(layer_id, key_position))?;
         Ok(PyBehavior::new(behavior))
@@ -180,18 +944,120 @@ impl PyStudioClient {
     pub fn discard_changes(&self) -> PyResult<bool> {
         self.with_client(|client| client.discard_changes())
     }
+
+    /// Whether the device is still attached; false after it was unplugged.
+    pub fn is_connected(&self) -> PyResult<bool> {
+        self.with_client(|client| Ok(client.is_connected()))
+    }
+
+    /// Reopens the device after it was unplugged and plugged back in.
+    pub fn reconnect(&self) -> PyResult<()> {
+        self.with_client(|client| client.reconnect())
+    }
 }
 
 impl PyStudioClient {
-    fn with_client<R>(
+    /// Runs `f` on the client with the GIL released, so other Python threads
+    /// keep running while it waits on the device.
+    fn with_client<R: Send>(
         &self,
-        f: impl FnOnce(&mut DynClient) -> Result<R, ClientError>,
+        f: impl FnOnce(&mut DynClient) -> Result<R, ClientError> + Send,
     ) -> PyResult<R> {
-        let mut client = self
-            .inner
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("client mutex is poisoned"))?;
-        f(&mut client).map_err(|err| PyRuntimeError::new_err(err.to_string()))
+        Python::attach(|py| {
+            py.detach(|| {
+                let mut client = self
+                    .inner
+                    .lock()
+                    .map_err(|_| ZmkError::new_err("client mutex is poisoned"))?;
+                f(&mut client).map_err(client_error)
+            })
+        })
+    }
+}
+
+create_exception!(
+    zmk_studio_api,
+    ZmkError,
+    PyRuntimeError,
+    "Base class for errors raised by zmk_studio_api."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkIoError,
+    ZmkError,
+    "Reading from or writing to the device failed."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkConnectionError,
+    ZmkIoError,
+    "The device could not be found or connected."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkTimeoutError,
+    ZmkIoError,
+    "The device did not respond within the request timeout."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkProtocolError,
+    ZmkError,
+    "The device sent a malformed or unexpected response."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkLockedError,
+    ZmkError,
+    "The device must be unlocked in ZMK Studio before this request."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkKeymapError,
+    ZmkError,
+    "The device rejected a keymap change, or the change was invalid."
+);
+create_exception!(
+    zmk_studio_api,
+    ZmkSaveFailed,
+    ZmkKeymapError,
+    "The device could not save the staged changes."
+);
+
+/// Maps a `ClientError` to the matching `ZmkError` subclass.
+fn client_error(err: ClientError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        ClientError::Io(_) => ZmkIoError::new_err(message),
+        ClientError::Timeout => ZmkTimeoutError::new_err(message),
+        ClientError::Disconnected(_) => ZmkConnectionError::new_err(message),
+        err if err.is_locked() => ZmkLockedError::new_err(message),
+        ClientError::Protocol(_)
+        | ClientError::Meta(_)
+        | ClientError::NoResponse
+        | ClientError::MissingResponseType
+        | ClientError::MissingSubsystem
+        | ClientError::UnexpectedSubsystem(_)
+        | ClientError::UnexpectedRequestId { .. }
+        | ClientError::UnknownEnumValue { .. } => ZmkProtocolError::new_err(message),
+        ClientError::SaveChangesFailed(_) => ZmkSaveFailed::new_err(message),
+        ClientError::SetLayerBindingFailed(_)
+        | ClientError::SetActivePhysicalLayoutFailed(_)
+        | ClientError::MoveLayerFailed(_)
+        | ClientError::AddLayerFailed(_)
+        | ClientError::RemoveLayerFailed(_)
+        | ClientError::RestoreLayerFailed(_)
+        | ClientError::SetLayerPropsFailed(_)
+        | ClientError::InvalidLayerOrPosition { .. }
+        | ClientError::UnknownLayerName(_)
+        | ClientError::MissingBehaviorRole(_)
+        | ClientError::BehaviorIdOutOfRange { .. }
+        | ClientError::InvalidBehaviorParam { .. }
+        | ClientError::InvalidKeymap(_) => ZmkKeymapError::new_err(message),
+        ClientError::History(_)
+        | ClientError::Annotation(_)
+        | ClientError::SnapshotParse(_)
+        | ClientError::QueueFull(_) => ZmkError::new_err(message),
     }
 }
 
@@ -356,10 +1222,145 @@ fn raw(behavior_id: i32, param1: u32, param2: u32) -> PyBehavior {
     })
 }
 
+/// Returns a `StudioClient` connected to a fresh in-process mock keyboard.
+#[pyfunction]
+fn mock_client() -> PyStudioClient {
+    PyStudioClient::open_mock()
+}
+
+/// Lists serial ports as `(port_name, is_zmk_device)` pairs.
+#[pyfunction]
+#[cfg(feature = "serial")]
+fn list_serial_ports() -> PyResult<Vec<(String, bool)>> {
+    let ports = serialport::available_ports().map_err(|err| {
+        ZmkConnectionError::new_err(format!("failed to list serial ports: {err}"))
+    })?;
+    Ok(ports
+        .iter()
+        .map(|port| (port.port_name.clone(), is_zmk_device(port)))
+        .collect())
+}
+
+#[pyfunction]
+#[cfg(not(feature = "serial"))]
+fn list_serial_ports() -> PyResult<Vec<(String, bool)>> {
+    Err(PyRuntimeError::new_err(
+        "serial support is disabled for this build",
+    ))
+}
+
+/// Scans for ZMK Studio keyboards advertising over BLE.
+#[pyfunction]
+#[cfg(feature = "ble")]
+fn discover_ble_devices(py: Python<'_>) -> PyResult<Vec<PyBleDevice>> {
+    let devices = py
+        .detach(crate::transport::ble::discover_devices)
+        .map_err(|err| {
+            ZmkConnectionError::new_err(format!("failed to scan for BLE devices: {err}"))
+        })?;
+    Ok(devices
+        .into_iter()
+        .map(|device| PyBleDevice {
+            device_id: device.device_id,
+            name: device.local_name,
+            adapter: device.adapter,
+            rssi: device.rssi,
+            is_connected: device.is_connected,
+            battery_level: device.battery_level,
+        })
+        .collect())
+}
+
+#[pyfunction]
+#[cfg(not(feature = "ble"))]
+fn discover_ble_devices() -> PyResult<Vec<PyBleDevice>> {
+    Err(PyRuntimeError::new_err(
+        "ble support is disabled for this build",
+    ))
+}
+
+/// Lists serial ports, ZMK keyboards first.
+#[pyfunction]
+#[cfg(feature = "serial")]
+fn discover_serial_ports() -> PyResult<Vec<PySerialPort>> {
+    let ports = serialport::available_ports().map_err(|err| {
+        ZmkConnectionError::new_err(format!("failed to list serial ports: {err}"))
+    })?;
+    let mut ports: Vec<_> = ports
+        .iter()
+        .map(|port| {
+            let usb = match &port.port_type {
+                serialport::SerialPortType::UsbPort(usb) => Some(usb),
+                _ => None,
+            };
+            PySerialPort {
+                path: port.port_name.clone(),
+                is_zmk: is_zmk_device(port),
+                vid: usb.map(|usb| usb.vid),
+                pid: usb.map(|usb| usb.pid),
+                manufacturer: usb.and_then(|usb| usb.manufacturer.clone()),
+                product: usb.and_then(|usb| usb.product.clone()),
+                serial_number: usb.and_then(|usb| usb.serial_number.clone()),
+            }
+        })
+        .collect();
+    ports.sort_by_key(|port| !port.is_zmk);
+    Ok(ports)
+}
+
+#[pyfunction]
+#[cfg(not(feature = "serial"))]
+fn discover_serial_ports() -> PyResult<Vec<PySerialPort>> {
+    Err(PyRuntimeError::new_err(
+        "serial support is disabled for this build",
+    ))
+}
+
+/// Builds the `zmk_studio_api.cli` submodule behind the `zmk-studio` console script.
+fn cli_module<'py>(py: Python<'py>) -> PyResult<Bound<'py, PyModule>> {
+    let source = CString::new(include_str!("python_cli.py"))
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    PyModule::from_code(py, &source, c"zmk_studio_api/cli.py", c"zmk_studio_api.cli")
+}
+
+/// Builds the `zmk_studio_api.testing` submodule from `python_testing.py`.
+fn testing_module<'py>(py: Python<'py>) -> PyResult<Bound<'py, PyModule>> {
+    let source = CString::new(include_str!("python_testing.py"))
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    let module = PyModule::from_code(
+        py,
+        &source,
+        c"zmk_studio_api/testing.py",
+        c"zmk_studio_api.testing",
+    )?;
+    module.add_function(wrap_pyfunction!(mock_client, &module)?)?;
+    Ok(module)
+}
+
 #[pymodule]
 fn zmk_studio_api(py: Python<'_>, module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<PyStudioClient>()?;
     module.add_class::<PyBehavior>()?;
+    module.add_class::<PyBehaviorInfo>()?;
+    module.add_class::<PyKeymap>()?;
+    module.add_class::<PyKeymapDifference>()?;
+    module.add_class::<PyLayer>()?;
+    module.add_class::<PyDeviceInfo>()?;
+    module.add_class::<PyKeyPhysicalAttrs>()?;
+    module.add_class::<PyPhysicalLayout>()?;
+    module.add_class::<PyPhysicalLayouts>()?;
+    module.add_class::<PyBleDevice>()?;
+    module.add_class::<PySerialPort>()?;
+
+    module.add("ZmkError", py.get_type::<ZmkError>())?;
+    module.add("ZmkIoError", py.get_type::<ZmkIoError>())?;
+    module.add("ZmkConnectionError", py.get_type::<ZmkConnectionError>())?;
+    module.add("ZmkTimeoutError", py.get_type::<ZmkTimeoutError>())?;
+    module.add("ZmkProtocolError", py.get_type::<ZmkProtocolError>())?;
+    module.add("ZmkLockedError", py.get_type::<ZmkLockedError>())?;
+    module.add("ZmkKeymapError", py.get_type::<ZmkKeymapError>())?;
+    module.add("ZmkSaveFailed", py.get_type::<ZmkSaveFailed>())?;
+    module.add_class::<PyParameterDescription>()?;
 
     let enum_module = py.import("enum")?;
     let int_enum = enum_module.getattr("IntEnum")?;
@@ -397,6 +1398,20 @@ fn zmk_studio_api(py: Python<'_>, module: &Bound<'_, PyModule>) -> PyResult<()>
     module.add_function(wrap_pyfunction!(transparent, module)?)?;
     module.add_function(wrap_pyfunction!(no_behavior, module)?)?;
     module.add_function(wrap_pyfunction!(raw, module)?)?;
+    module.add_function(wrap_pyfunction!(diff_keymaps, module)?)?;
+    module.add_function(wrap_pyfunction!(list_serial_ports, module)?)?;
+    module.add_function(wrap_pyfunction!(discover_serial_ports, module)?)?;
+    module.add_function(wrap_pyfunction!(discover_ble_devices, module)?)?;
+
+    // Register the submodules so `import zmk_studio_api.testing` and the
+    // `zmk_studio_api.cli:main` entry point resolve.
+    let sys_modules = py.import("sys")?.getattr("modules")?;
+    let testing = testing_module(py)?;
+    module.add_submodule(&testing)?;
+    sys_modules.set_item("zmk_studio_api.testing", &testing)?;
+    let cli = cli_module(py)?;
+    module.add_submodule(&cli)?;
+    sys_modules.set_item("zmk_studio_api.cli", &cli)?;
 
     Ok(())
 }
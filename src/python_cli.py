@@ -0,0 +1,108 @@
+"""The ``zmk-studio`` console script shipped with the Python wheel.
+
+Mirrors the Rust CLI for pip-only users::
+
+    zmk-studio devices
+    zmk-studio dump --port COM5
+    zmk-studio set-key --port COM5 --save 0 12 "&mt LSHIFT ESC"
+    zmk-studio backup --port COM5 keymap.txt
+    zmk-studio restore --port COM5 keymap.txt
+"""
+
+import argparse
+import sys
+
+
+def _open(args):
+    import zmk_studio_api as zmk
+
+    return zmk.StudioClient.open_serial(args.port)
+
+
+def _devices(args):
+    import zmk_studio_api as zmk
+
+    for port, is_zmk in zmk.list_serial_ports():
+        print(f"{port}\t{'ZMK' if is_zmk else '-'}")
+
+
+def _dump(args):
+    print(_open(args).get_typed_keymap().to_snapshot_text(), end="")
+
+
+def _set_key(args):
+    import zmk_studio_api as zmk
+
+    client = _open(args)
+    client.set_key_at(args.layer, args.position, zmk.Behavior.parse(args.binding))
+    if args.save:
+        client.save_changes()
+    else:
+        print("Change is staged but not saved; pass --save to persist it")
+
+
+def _backup(args):
+    text = _open(args).get_typed_keymap().to_snapshot_text()
+    with open(args.file, "w", encoding="utf-8") as file:
+        file.write(text)
+    print(f"Saved keymap to {args.file}")
+
+
+def _restore(args):
+    import zmk_studio_api as zmk
+
+    with open(args.file, encoding="utf-8") as file:
+        target = zmk.Keymap.from_snapshot_text(file.read())
+    client = _open(args)
+    diff = zmk.diff_keymaps(client.get_typed_keymap(), target)
+    for difference in diff:
+        print(difference)
+    if args.dry_run:
+        print("Dry run: no changes were made")
+        return
+    applied = client.apply_diff(diff)
+    if applied:
+        client.save_changes()
+    print(f"Restored {applied} change(s)")
+
+
+def _parser():
+    parser = argparse.ArgumentParser(prog="zmk-studio", description=__doc__.splitlines()[0])
+    commands = parser.add_subparsers(dest="command", required=True)
+
+    commands.add_parser("devices", help="list serial ports").set_defaults(run=_devices)
+
+    dump = commands.add_parser("dump", help="print the keymap as snapshot text")
+    dump.add_argument("--port", required=True)
+    dump.set_defaults(run=_dump)
+
+    set_key = commands.add_parser("set-key", help="bind one key position")
+    set_key.add_argument("--port", required=True)
+    set_key.add_argument("--save", action="store_true", help="persist the change")
+    set_key.add_argument("layer", type=int, help="layer ID")
+    set_key.add_argument("position", type=int, help="key position")
+    set_key.add_argument("binding", help='binding in ZMK syntax, e.g. "&kp A"')
+    set_key.set_defaults(run=_set_key)
+
+    backup = commands.add_parser("backup", help="write the keymap to a snapshot file")
+    backup.add_argument("--port", required=True)
+    backup.add_argument("file")
+    backup.set_defaults(run=_backup)
+
+    restore = commands.add_parser("restore", help="apply a snapshot file and save")
+    restore.add_argument("--port", required=True)
+    restore.add_argument("--dry-run", action="store_true")
+    restore.add_argument("file")
+    restore.set_defaults(run=_restore)
+
+    return parser
+
+
+def main(argv=None):
+    args = _parser().parse_args(argv)
+    try:
+        args.run(args)
+    except (OSError, RuntimeError, ValueError) as err:
+        print(f"error: {err}", file=sys.stderr)
+        return 1
+    return 0
@@ -0,0 +1,21 @@
+"""Helpers for testing scripts against an in-process mock keyboard.
+
+``mock_client()`` returns a ``StudioClient`` connected to a fresh emulated
+device with two layers of four keys. With pytest installed, the
+``studio_client`` fixture yields such a client; re-export it from your
+``conftest.py`` to use it::
+
+    from zmk_studio_api.testing import studio_client  # noqa: F401
+"""
+
+try:
+    import pytest
+except ImportError:
+    pytest = None
+
+if pytest is not None:
+
+    @pytest.fixture
+    def studio_client():
+        """A ``StudioClient`` connected to a fresh mock keyboard."""
+        yield mock_client()  # noqa: F821 - added by the extension module
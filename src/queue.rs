@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+/// What a full client queue does with a newly arriving item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Evict the oldest queued item to make room.
+    #[default]
+    DropOldest,
+    /// Discard the arriving item.
+    DropNew,
+    /// Discard the arriving item and fail the operation that received it.
+    Error,
+}
+
+/// FIFO queue with an optional capacity and a count of items lost to overflow.
+#[derive(Debug)]
+pub(crate) struct BoundedQueue<T> {
+    items: VecDeque<T>,
+    capacity: Option<usize>,
+    policy: OverflowPolicy,
+    dropped: u64,
+}
+
+/// Returned by [`BoundedQueue::push`] when the policy is [`OverflowPolicy::Error`],
+/// handing back the refused item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueueFull<T>(pub(crate) T);
+
+impl<T> BoundedQueue<T> {
+    pub(crate) fn unbounded() -> Self {
+        Self {
+            items: VecDeque::new(),
+            capacity: None,
+            policy: OverflowPolicy::default(),
+            dropped: 0,
+        }
+    }
+
+    /// Changes the limit, trimming the oldest items if the queue is already over it.
+    pub(crate) fn set_limit(&mut self, capacity: Option<usize>, policy: OverflowPolicy) {
+        self.capacity = capacity;
+        self.policy = policy;
+        if let Some(capacity) = capacity {
+            while self.items.len() > capacity {
+                self.items.pop_front();
+                self.dropped += 1;
+            }
+        }
+    }
+
+    pub(crate) fn push(&mut self, item: T) -> Result<(), QueueFull<T>> {
+        let full = self
+            .capacity
+            .is_some_and(|capacity| self.items.len() >= capacity);
+        if !full {
+            self.items.push_back(item);
+            return Ok(());
+        }
+
+        self.dropped += 1;
+        match self.policy {
+            OverflowPolicy::DropOldest => {
+                if self.items.pop_front().is_some() {
+                    self.items.push_back(item);
+                }
+                Ok(())
+            }
+            OverflowPolicy::DropNew => Ok(()),
+            OverflowPolicy::Error => Err(QueueFull(item)),
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        self.items.pop_front()
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(policy: OverflowPolicy) -> (BoundedQueue<u8>, Result<(), QueueFull<u8>>) {
+        let mut queue = BoundedQueue::unbounded();
+        queue.set_limit(Some(2), policy);
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+        let result = queue.push(3);
+        (queue, result)
+    }
+
+    #[test]
+    fn applies_overflow_policies() {
+        let (mut queue, result) = filled(OverflowPolicy::DropOldest);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            (queue.pop(), queue.pop(), queue.dropped()),
+            (Some(2), Some(3), 1)
+        );
+
+        let (mut queue, result) = filled(OverflowPolicy::DropNew);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            (queue.pop(), queue.pop(), queue.dropped()),
+            (Some(1), Some(2), 1)
+        );
+
+        let (mut queue, result) = filled(OverflowPolicy::Error);
+        assert_eq!(result, Err(QueueFull(3)));
+        assert_eq!((queue.pop(), queue.dropped()), (Some(1), 1));
+
+        let mut queue = BoundedQueue::unbounded();
+        queue.set_limit(Some(0), OverflowPolicy::DropOldest);
+        assert_eq!(queue.push(1), Ok(()));
+        assert_eq!((queue.pop(), queue.dropped()), (None, 1));
+    }
+}
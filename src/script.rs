@@ -0,0 +1,451 @@
+use serde::Deserialize;
+
+use crate::binding::Behavior;
+use crate::client::{ClientError, StudioClient};
+use crate::presets::{PRESET_PREFIX, PresetRegistry};
+use crate::proto::zmk;
+use crate::snapshot::{behavior_from_text, behavior_to_text};
+use crate::transport::Transport;
+
+/// A batch of keymap operations, usually loaded from a JSON file.
+///
+/// ```json
+/// {
+///   "operations": [
+///     { "op": "set_key", "layer_id": 0, "position": 12, "binding": "KeyPress ESC" },
+///     { "op": "rename_layer", "layer_id": 1, "name": "Nav" },
+///     { "op": "move_layer", "from": 1, "to": 2 },
+///     { "op": "save" }
+///   ]
+/// }
+/// ```
+///
+/// Bindings use the behavior syntax of [`crate::keymap::TypedKeymap::to_snapshot_text`],
+/// or reference a preset as `@name` when [`ApplyOptions::presets`] is set.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Script {
+    pub operations: Vec<Operation>,
+}
+
+/// A single scripted operation.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    SetKey {
+        layer_id: u32,
+        position: i32,
+        binding: String,
+    },
+    RenameLayer {
+        layer_id: u32,
+        name: String,
+    },
+    /// Moves the layer at index `from` to index `to`.
+    MoveLayer {
+        from: u32,
+        to: u32,
+    },
+    Save,
+}
+
+/// Errors from loading or applying a [`Script`].
+#[derive(Debug)]
+pub enum ScriptError {
+    Parse(serde_json::Error),
+    /// Reading the device state before the first operation failed.
+    Setup(ClientError),
+    /// The device already had unsaved changes before the script started.
+    UnsavedChanges,
+    InvalidBinding {
+        index: usize,
+        binding: String,
+    },
+    InvalidLayer {
+        index: usize,
+        layer_id: u32,
+    },
+    InvalidPosition {
+        index: usize,
+        position: i32,
+    },
+    InvalidLayerIndex {
+        index: usize,
+        layer_index: u32,
+    },
+    /// An operation failed on the device; unsaved changes were discarded.
+    Client {
+        index: usize,
+        error: ClientError,
+    },
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "Failed to parse script: {err}"),
+            Self::Setup(err) => write!(f, "Failed to read the device state: {err}"),
+            Self::UnsavedChanges => write!(
+                f,
+                "Device has unsaved changes; save or discard them before applying a script"
+            ),
+            Self::InvalidBinding { index, binding } => {
+                write!(f, "Operation {index}: invalid binding {binding:?}")
+            }
+            Self::InvalidLayer { index, layer_id } => {
+                write!(f, "Operation {index}: no layer with id {layer_id}")
+            }
+            Self::InvalidPosition { index, position } => {
+                write!(
+                    f,
+                    "Operation {index}: key position {position} is out of range"
+                )
+            }
+            Self::InvalidLayerIndex { index, layer_index } => {
+                write!(
+                    f,
+                    "Operation {index}: layer index {layer_index} is out of range"
+                )
+            }
+            Self::Client { index, error } => write!(f, "Operation {index} failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            Self::Setup(err) => Some(err),
+            Self::Client { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Options for [`apply`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApplyOptions<'a> {
+    /// Validate and describe the operations without touching the device.
+    pub dry_run: bool,
+    /// Presets available to `@name` binding references.
+    pub presets: Option<&'a PresetRegistry>,
+}
+
+/// Outcome of [`apply`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyReport {
+    /// Human-readable description of each operation, in order.
+    pub steps: Vec<String>,
+    /// Whether a `save` operation persisted changes.
+    pub saved: bool,
+}
+
+impl Script {
+    /// Parses a script from JSON.
+    pub fn from_json(text: &str) -> Result<Self, ScriptError> {
+        serde_json::from_str(text).map_err(ScriptError::Parse)
+    }
+}
+
+/// Validates and executes `script` against the device.
+///
+/// All operations are validated against the current keymap before anything is
+/// written. If an operation fails on the device, changes since the last
+/// `save` operation are discarded. Changes left unsaved at the end of the
+/// script are kept staged.
+pub fn apply<T: Transport>(
+    client: &mut StudioClient<T>,
+    script: &Script,
+    options: ApplyOptions<'_>,
+) -> Result<ApplyReport, ScriptError> {
+    if client.check_unsaved_changes().map_err(ScriptError::Setup)? {
+        return Err(ScriptError::UnsavedChanges);
+    }
+    let keymap = client.get_keymap().map_err(ScriptError::Setup)?;
+    let planned = plan(script, &keymap, options.presets)?;
+
+    let mut report = ApplyReport {
+        steps: planned.iter().map(Step::describe).collect(),
+        saved: false,
+    };
+    if options.dry_run {
+        return Ok(report);
+    }
+
+    for (index, step) in planned.into_iter().enumerate() {
+        let is_save = matches!(step, Step::Save);
+        let result = match step {
+            Step::SetKey {
+                layer_id,
+                position,
+                behavior,
+            } => client.set_key_at(layer_id, position, behavior),
+            Step::RenameLayer { layer_id, name } => client.set_layer_props(layer_id, name),
+            Step::MoveLayer { from, to } => client.move_layer(from, to).map(|_| ()),
+            Step::Save => client.save_changes(),
+        };
+
+        if let Err(error) = result {
+            let _ = client.discard_changes();
+            return Err(ScriptError::Client { index, error });
+        }
+        report.saved |= is_save;
+    }
+
+    Ok(report)
+}
+
+enum Step {
+    SetKey {
+        layer_id: u32,
+        position: i32,
+        behavior: Behavior,
+    },
+    RenameLayer {
+        layer_id: u32,
+        name: String,
+    },
+    MoveLayer {
+        from: u32,
+        to: u32,
+    },
+    Save,
+}
+
+impl Step {
+    fn describe(&self) -> String {
+        match self {
+            Self::SetKey {
+                layer_id,
+                position,
+                behavior,
+            } => format!(
+                "set {layer_id}/{position} to {}",
+                behavior_to_text(behavior)
+            ),
+            Self::RenameLayer { layer_id, name } => format!("rename layer {layer_id} to {name:?}"),
+            Self::MoveLayer { from, to } => format!("move layer at index {from} to index {to}"),
+            Self::Save => "save changes".to_string(),
+        }
+    }
+}
+
+/// Validates every operation against a simulated copy of the keymap.
+fn plan(
+    script: &Script,
+    keymap: &zmk::keymap::Keymap,
+    presets: Option<&PresetRegistry>,
+) -> Result<Vec<Step>, ScriptError> {
+    let mut layers: Vec<(u32, usize)> = keymap
+        .layers
+        .iter()
+        .map(|layer| (layer.id, layer.bindings.len()))
+        .collect();
+
+    script
+        .operations
+        .iter()
+        .enumerate()
+        .map(|(index, operation)| {
+            let step = match operation {
+                Operation::SetKey {
+                    layer_id,
+                    position,
+                    binding,
+                } => {
+                    let (_, len) = layers.iter().find(|(id, _)| id == layer_id).ok_or(
+                        ScriptError::InvalidLayer {
+                            index,
+                            layer_id: *layer_id,
+                        },
+                    )?;
+                    if !usize::try_from(*position).is_ok_and(|pos| pos < *len) {
+                        return Err(ScriptError::InvalidPosition {
+                            index,
+                            position: *position,
+                        });
+                    }
+                    let behavior = resolve_binding(binding, presets).ok_or_else(|| {
+                        ScriptError::InvalidBinding {
+                            index,
+                            binding: binding.clone(),
+                        }
+                    })?;
+                    Step::SetKey {
+                        layer_id: *layer_id,
+                        position: *position,
+                        behavior,
+                    }
+                }
+                Operation::RenameLayer { layer_id, name } => {
+                    if !layers.iter().any(|(id, _)| id == layer_id) {
+                        return Err(ScriptError::InvalidLayer {
+                            index,
+                            layer_id: *layer_id,
+                        });
+                    }
+                    Step::RenameLayer {
+                        layer_id: *layer_id,
+                        name: name.clone(),
+                    }
+                }
+                Operation::MoveLayer { from, to } => {
+                    for layer_index in [*from, *to] {
+                        if layer_index as usize >= layers.len() {
+                            return Err(ScriptError::InvalidLayerIndex { index, layer_index });
+                        }
+                    }
+                    let layer = layers.remove(*from as usize);
+                    layers.insert(*to as usize, layer);
+                    Step::MoveLayer {
+                        from: *from,
+                        to: *to,
+                    }
+                }
+                Operation::Save => Step::Save,
+            };
+            Ok(step)
+        })
+        .collect()
+}
+
+fn resolve_binding(binding: &str, presets: Option<&PresetRegistry>) -> Option<Behavior> {
+    match (binding.strip_prefix(PRESET_PREFIX), presets) {
+        (Some(name), Some(presets)) => presets.get(name).cloned(),
+        _ => behavior_from_text(binding),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApplyOptions, Script, ScriptError, apply};
+    use crate::transport::mock::MockDevice;
+    use crate::{Behavior, Keycode, StudioClient};
+
+    fn script(operations: &str) -> Script {
+        Script::from_json(&format!(r#"{{ "operations": [{operations}] }}"#)).expect("script")
+    }
+
+    #[test]
+    fn validates_every_operation_before_writing() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let original = client.get_keymap().expect("keymap");
+
+        let cases = [
+            (
+                r#"{ "op": "set_key", "layer_id": 0, "position": 0, "binding": "KeyPress Q" },
+                   { "op": "set_key", "layer_id": 7, "position": 0, "binding": "KeyPress Q" }"#,
+                "InvalidLayer { index: 1, layer_id: 7 }",
+            ),
+            (
+                r#"{ "op": "set_key", "layer_id": 0, "position": 4, "binding": "KeyPress Q" }"#,
+                "InvalidPosition { index: 0, position: 4 }",
+            ),
+            (
+                r#"{ "op": "set_key", "layer_id": 0, "position": 0, "binding": "Nonsense" }"#,
+                "InvalidBinding { index: 0, binding: \"Nonsense\" }",
+            ),
+            (
+                r#"{ "op": "move_layer", "from": 0, "to": 2 }"#,
+                "InvalidLayerIndex { index: 0, layer_index: 2 }",
+            ),
+        ];
+        for (operations, expected) in cases {
+            let err = apply(&mut client, &script(operations), ApplyOptions::default())
+                .expect_err(operations);
+            assert_eq!(format!("{err:?}"), expected);
+        }
+        assert_eq!(client.get_keymap().expect("keymap"), original);
+        assert!(!client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn applies_and_saves_a_script() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let script = script(
+            r#"{ "op": "set_key", "layer_id": 1, "position": 3, "binding": "KeyPress ESC" },
+               { "op": "rename_layer", "layer_id": 1, "name": "Nav" },
+               { "op": "move_layer", "from": 1, "to": 0 },
+               { "op": "save" }"#,
+        );
+
+        let report = apply(&mut client, &script, ApplyOptions::default()).expect("apply");
+        assert_eq!(
+            report.steps,
+            [
+                "set 1/3 to KeyPress ESC",
+                "rename layer 1 to \"Nav\"",
+                "move layer at index 1 to index 0",
+                "save changes",
+            ]
+        );
+        assert!(report.saved);
+        assert!(!client.check_unsaved_changes().expect("status"));
+
+        let keymap = client.get_keymap().expect("keymap");
+        assert_eq!(
+            (keymap.layers[0].id, keymap.layers[0].name.as_str()),
+            (1, "Nav")
+        );
+        assert_eq!(
+            client.get_key_at(1, 3).expect("get binding"),
+            Behavior::KeyPress(Keycode::ESCAPE.into())
+        );
+    }
+
+    #[test]
+    fn describes_a_dry_run_without_touching_the_device() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let original = client.get_keymap().expect("keymap");
+        let script = script(
+            r#"{ "op": "set_key", "layer_id": 0, "position": 0, "binding": "KeyPress Q" },
+               { "op": "save" }"#,
+        );
+        let options = ApplyOptions {
+            dry_run: true,
+            ..ApplyOptions::default()
+        };
+
+        let report = apply(&mut client, &script, options).expect("dry run");
+        assert_eq!(report.steps.len(), 2);
+        assert!(!report.saved);
+        assert_eq!(client.get_keymap().expect("keymap"), original);
+        assert!(!client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn discards_changes_after_a_failed_operation() {
+        // The device lacks `&caps_word`, which only shows once it is written.
+        let mut client = StudioClient::new(MockDevice::new());
+        let original = client.get_keymap().expect("keymap");
+        let script = script(
+            r#"{ "op": "set_key", "layer_id": 0, "position": 0, "binding": "KeyPress Q" },
+               { "op": "set_key", "layer_id": 0, "position": 1, "binding": "CapsWord" }"#,
+        );
+
+        let err = apply(&mut client, &script, ApplyOptions::default()).expect_err("fails");
+        assert!(matches!(err, ScriptError::Client { index: 1, .. }));
+        assert_eq!(client.get_keymap().expect("keymap"), original);
+        assert!(!client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn reports_setup_failures_separately() {
+        let mut device = MockDevice::new();
+        device.set_locked(true);
+        let mut client = StudioClient::new(device);
+
+        let err = apply(&mut client, &script(""), ApplyOptions::default()).expect_err("locked");
+        assert!(matches!(
+            err,
+            ScriptError::Setup(ref error) if error.is_locked()
+        ));
+
+        let mut client = StudioClient::new(MockDevice::new());
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        let err = apply(&mut client, &script(""), ApplyOptions::default()).expect_err("staged");
+        assert!(matches!(err, ScriptError::UnsavedChanges));
+    }
+}
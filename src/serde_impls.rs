@@ -0,0 +1,102 @@
+//! Human-readable serde representations of key values and bindings.
+//!
+//! [`Keycode`] serializes as its ZMK name (`"ESC"`), [`HidUsage`] as a
+//! binding parameter (`"LS(A)"`), and [`Behavior`] as a binding in ZMK keymap
+//! syntax (`"&mt LSHIFT ESC"`), so config files read like `.keymap` files.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::binding::Behavior;
+use crate::hid_usage::HidUsage;
+use crate::keycode::Keycode;
+use crate::zmk_syntax::{format_binding, format_usage, parse_binding, parse_usage};
+
+impl Serialize for Keycode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Keycode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Keycode::from_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("unknown keycode `{name}`")))
+    }
+}
+
+impl Serialize for HidUsage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_usage(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for HidUsage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_usage(&text).map_err(D::Error::custom)
+    }
+}
+
+/// Layer parameters are written as layer IDs, and [`Behavior::Unknown`] as
+/// `&unknown <id> <param1> <param2>`, so every value round-trips.
+impl Serialize for Behavior {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_binding(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Behavior {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_binding(&text, &|_| None).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use crate::keymap::KeymapBuilder;
+    use crate::{Behavior, HidUsage, Keycode, MOD_LSFT};
+
+    #[test]
+    fn serializes_as_zmk_syntax_and_round_trips() {
+        let shifted = HidUsage::from_parts(0x07, 0x04, MOD_LSFT);
+        assert_eq!(serde_json::to_string(&Keycode::A).unwrap(), r#""A""#);
+        assert_eq!(serde_json::to_string(&shifted).unwrap(), r#""LS(A)""#);
+        assert_eq!(
+            serde_json::from_str::<HidUsage>(r#""LS(A)""#).unwrap(),
+            shifted
+        );
+
+        let keymap = KeymapBuilder::new()
+            .layer(
+                "Base",
+                [
+                    Behavior::KeyPress(shifted),
+                    Behavior::LayerTap {
+                        layer_id: 1,
+                        tap: HidUsage::from_encoded(Keycode::SPACE.to_hid_usage()),
+                    },
+                    Behavior::Unknown {
+                        behavior_id: 42,
+                        param1: 1,
+                        param2: 2,
+                    },
+                ],
+            )
+            .build();
+        let json = serde_json::to_value(&keymap).unwrap();
+        assert_eq!(
+            json["layers"][0]["bindings"],
+            serde_json::json!(["&kp LS(A)", "&lt 1 SPC", "&unknown 42 0x1 0x2"])
+        );
+        assert_eq!(
+            serde_json::from_value::<crate::keymap::TypedKeymap>(json).unwrap(),
+            keymap
+        );
+
+        assert!(serde_json::from_str::<Keycode>(r#""NOPE""#).is_err());
+        assert!(serde_json::from_str::<Behavior>(r#""&kp A &kp B""#).is_err());
+    }
+}
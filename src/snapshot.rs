@@ -0,0 +1,415 @@
+use crate::binding::Behavior;
+use crate::hid_usage::HidUsage;
+use crate::keycode::Keycode;
+use crate::keymap::{TypedKeymap, TypedLayer};
+
+const SNAPSHOT_HEADER: &str = "zmk-studio-snapshot 1";
+
+/// Error returned when parsing snapshot text fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SnapshotParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Snapshot parse error on line {}: {}",
+            self.line, self.message
+        )
+    }
+}
+
+impl std::error::Error for SnapshotParseError {}
+
+/// Canonical, diff-friendly text representation of a keymap.
+///
+/// The text form lists layers in device order, followed by one binding per
+/// line prefixed with `layer_id/position`, so a changed key shows up as a
+/// single changed line in `git diff`:
+///
+/// ```text
+/// zmk-studio-snapshot 1
+/// layer 0 Base
+/// 0/0 KeyPress ESC
+/// 0/1 LayerTap 1 SPC
+/// ```
+///
+/// Behaviors are written by variant name rather than firmware behavior ID,
+/// and keys by keycode name (or `0x`-prefixed encoded usage when unnamed).
+/// Layer names that would not survive as plain text (surrounding whitespace,
+/// line breaks, or a leading `"`) are written in double quotes with
+/// backslash escapes.
+impl TypedKeymap {
+    /// Renders the canonical snapshot text form.
+    pub fn to_snapshot_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(SNAPSHOT_HEADER);
+        out.push('\n');
+
+        for layer in &self.layers {
+            out.push_str(&format!("layer {}", layer.id));
+            if !layer.name.is_empty() {
+                out.push(' ');
+                out.push_str(&layer_name_to_text(&layer.name));
+            }
+            out.push('\n');
+            for (position, behavior) in layer.bindings.iter().enumerate() {
+                out.push_str(&format!(
+                    "{}/{} {}\n",
+                    layer.id,
+                    position,
+                    behavior_to_text(behavior)
+                ));
+            }
+        }
+
+        out
+    }
+
+    /// Parses the canonical text form produced by [`TypedKeymap::to_snapshot_text`].
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn from_snapshot_text(text: &str) -> Result<Self, SnapshotParseError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        match lines.next() {
+            Some((_, SNAPSHOT_HEADER)) => {}
+            Some((line, _)) => return Err(parse_error(line, "missing snapshot header")),
+            None => return Err(parse_error(1, "empty snapshot")),
+        }
+
+        let mut keymap = TypedKeymap::default();
+        for (line_number, line) in lines {
+            if let Some(rest) = line.strip_prefix("layer ") {
+                let (id, name) = rest.split_once(' ').unwrap_or((rest, ""));
+                let id = id
+                    .parse()
+                    .map_err(|_| parse_error(line_number, "invalid layer id"))?;
+                let name = layer_name_from_text(name)
+                    .ok_or_else(|| parse_error(line_number, "invalid quoted layer name"))?;
+                keymap.layers.push(TypedLayer {
+                    id,
+                    name,
+                    bindings: Vec::new(),
+                });
+                continue;
+            }
+
+            let (location, behavior) = line
+                .split_once(' ')
+                .ok_or_else(|| parse_error(line_number, "expected `layer/position behavior`"))?;
+            let (layer_id, position) = location
+                .split_once('/')
+                .and_then(|(layer, pos)| {
+                    Some((layer.parse::<u32>().ok()?, pos.parse::<usize>().ok()?))
+                })
+                .ok_or_else(|| parse_error(line_number, "invalid binding location"))?;
+
+            let layer = keymap
+                .layers
+                .last_mut()
+                .filter(|layer| layer.id == layer_id)
+                .ok_or_else(|| parse_error(line_number, "binding outside of its layer block"))?;
+            if position != layer.bindings.len() {
+                return Err(parse_error(
+                    line_number,
+                    "binding positions must be consecutive",
+                ));
+            }
+
+            let behavior = behavior_from_text(behavior)
+                .ok_or_else(|| parse_error(line_number, "invalid behavior"))?;
+            layer.bindings.push(behavior);
+        }
+
+        Ok(keymap)
+    }
+}
+
+fn layer_name_to_text(name: &str) -> String {
+    let plain = name.trim() == name && !name.starts_with('"') && !name.contains(['\r', '\n']);
+    if plain {
+        return name.to_string();
+    }
+
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('"');
+    for c in name.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn layer_name_from_text(text: &str) -> Option<String> {
+    let Some(quoted) = text.strip_prefix('"') else {
+        return Some(text.to_string());
+    };
+    let inner = quoted.strip_suffix('"')?;
+
+    let mut name = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => name.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                _ => return None,
+            }),
+            '"' => return None,
+            c => name.push(c),
+        }
+    }
+    Some(name)
+}
+
+fn parse_error(line: usize, message: &str) -> SnapshotParseError {
+    SnapshotParseError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+pub(crate) fn behavior_to_text(behavior: &Behavior) -> String {
+    match behavior {
+        Behavior::KeyPress(key) => format!("KeyPress {}", usage_to_text(*key)),
+        Behavior::KeyToggle(key) => format!("KeyToggle {}", usage_to_text(*key)),
+        Behavior::LayerTap { layer_id, tap } => {
+            format!("LayerTap {layer_id} {}", usage_to_text(*tap))
+        }
+        Behavior::ModTap { hold, tap } => {
+            format!("ModTap {} {}", usage_to_text(*hold), usage_to_text(*tap))
+        }
+        Behavior::StickyKey(key) => format!("StickyKey {}", usage_to_text(*key)),
+        Behavior::StickyLayer { layer_id } => format!("StickyLayer {layer_id}"),
+        Behavior::MomentaryLayer { layer_id } => format!("MomentaryLayer {layer_id}"),
+        Behavior::ToggleLayer { layer_id } => format!("ToggleLayer {layer_id}"),
+        Behavior::ToLayer { layer_id } => format!("ToLayer {layer_id}"),
+        Behavior::Bluetooth { command, value } => format!("Bluetooth {command} {value}"),
+        Behavior::ExternalPower { value } => format!("ExternalPower {value}"),
+        Behavior::OutputSelection { value } => format!("OutputSelection {value}"),
+        Behavior::Backlight { command, value } => format!("Backlight {command} {value}"),
+        Behavior::Underglow { command, value } => format!("Underglow {command} {value}"),
+        Behavior::MouseKeyPress { value } => format!("MouseKeyPress {value}"),
+        Behavior::MouseMove { value } => format!("MouseMove {value}"),
+        Behavior::MouseScroll { value } => format!("MouseScroll {value}"),
+        Behavior::CapsWord => "CapsWord".to_string(),
+        Behavior::KeyRepeat => "KeyRepeat".to_string(),
+        Behavior::Reset => "Reset".to_string(),
+        Behavior::Bootloader => "Bootloader".to_string(),
+        Behavior::SoftOff => "SoftOff".to_string(),
+        Behavior::StudioUnlock => "StudioUnlock".to_string(),
+        Behavior::GraveEscape => "GraveEscape".to_string(),
+        Behavior::Transparent => "Transparent".to_string(),
+        Behavior::None => "None".to_string(),
+        Behavior::Unknown {
+            behavior_id,
+            param1,
+            param2,
+        } => format!("Unknown {behavior_id} {param1} {param2}"),
+    }
+}
+
+pub(crate) fn behavior_from_text(text: &str) -> Option<Behavior> {
+    let mut parts = text.split_whitespace();
+    let kind = parts.next()?;
+    let args: Vec<&str> = parts.collect();
+
+    let behavior = match (kind, args.as_slice()) {
+        ("KeyPress", [key]) => Behavior::KeyPress(usage_from_text(key)?),
+        ("KeyToggle", [key]) => Behavior::KeyToggle(usage_from_text(key)?),
+        ("LayerTap", [layer_id, tap]) => Behavior::LayerTap {
+            layer_id: layer_id.parse().ok()?,
+            tap: usage_from_text(tap)?,
+        },
+        ("ModTap", [hold, tap]) => Behavior::ModTap {
+            hold: usage_from_text(hold)?,
+            tap: usage_from_text(tap)?,
+        },
+        ("StickyKey", [key]) => Behavior::StickyKey(usage_from_text(key)?),
+        ("StickyLayer", [layer_id]) => Behavior::StickyLayer {
+            layer_id: layer_id.parse().ok()?,
+        },
+        ("MomentaryLayer", [layer_id]) => Behavior::MomentaryLayer {
+            layer_id: layer_id.parse().ok()?,
+        },
+        ("ToggleLayer", [layer_id]) => Behavior::ToggleLayer {
+            layer_id: layer_id.parse().ok()?,
+        },
+        ("ToLayer", [layer_id]) => Behavior::ToLayer {
+            layer_id: layer_id.parse().ok()?,
+        },
+        ("Bluetooth", [command, value]) => Behavior::Bluetooth {
+            command: command.parse().ok()?,
+            value: value.parse().ok()?,
+        },
+        ("ExternalPower", [value]) => Behavior::ExternalPower {
+            value: value.parse().ok()?,
+        },
+        ("OutputSelection", [value]) => Behavior::OutputSelection {
+            value: value.parse().ok()?,
+        },
+        ("Backlight", [command, value]) => Behavior::Backlight {
+            command: command.parse().ok()?,
+            value: value.parse().ok()?,
+        },
+        ("Underglow", [command, value]) => Behavior::Underglow {
+            command: command.parse().ok()?,
+            value: value.parse().ok()?,
+        },
+        ("MouseKeyPress", [value]) => Behavior::MouseKeyPress {
+            value: value.parse().ok()?,
+        },
+        ("MouseMove", [value]) => Behavior::MouseMove {
+            value: value.parse().ok()?,
+        },
+        ("MouseScroll", [value]) => Behavior::MouseScroll {
+            value: value.parse().ok()?,
+        },
+        ("CapsWord", []) => Behavior::CapsWord,
+        ("KeyRepeat", []) => Behavior::KeyRepeat,
+        ("Reset", []) => Behavior::Reset,
+        ("Bootloader", []) => Behavior::Bootloader,
+        ("SoftOff", []) => Behavior::SoftOff,
+        ("StudioUnlock", []) => Behavior::StudioUnlock,
+        ("GraveEscape", []) => Behavior::GraveEscape,
+        ("Transparent", []) => Behavior::Transparent,
+        ("None", []) => Behavior::None,
+        ("Unknown", [behavior_id, param1, param2]) => Behavior::Unknown {
+            behavior_id: behavior_id.parse().ok()?,
+            param1: param1.parse().ok()?,
+            param2: param2.parse().ok()?,
+        },
+        _ => return None,
+    };
+
+    Some(behavior)
+}
+
+fn usage_to_text(usage: HidUsage) -> String {
+    match usage.known_keycode() {
+        Some(keycode) => keycode.to_name().to_string(),
+        None => format!("0x{:08X}", usage.to_hid_usage()),
+    }
+}
+
+fn usage_from_text(text: &str) -> Option<HidUsage> {
+    if let Some(hex) = text.strip_prefix("0x") {
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .map(HidUsage::from_encoded);
+    }
+
+    Keycode::from_name(text).map(|keycode| HidUsage::from_encoded(keycode.to_hid_usage()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keymap::{TypedKeymap, TypedLayer};
+    use crate::{Behavior, HidUsage, Keycode};
+
+    fn key(keycode: Keycode) -> HidUsage {
+        HidUsage::from_encoded(keycode.to_hid_usage())
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let keymap = TypedKeymap {
+            layers: vec![
+                TypedLayer {
+                    id: 0,
+                    name: "Base Layer".to_string(),
+                    bindings: vec![
+                        Behavior::KeyPress(key(Keycode::ESCAPE)),
+                        Behavior::LayerTap {
+                            layer_id: 1,
+                            tap: key(Keycode::SPACE),
+                        },
+                        Behavior::KeyPress(HidUsage::from_parts(0x07, 0x04, 0x03)),
+                    ],
+                },
+                TypedLayer {
+                    id: 1,
+                    name: String::new(),
+                    bindings: vec![
+                        Behavior::Transparent,
+                        Behavior::Unknown {
+                            behavior_id: 42,
+                            param1: 1,
+                            param2: 2,
+                        },
+                    ],
+                },
+            ],
+        };
+
+        let text = keymap.to_snapshot_text();
+        assert_eq!(
+            text,
+            "zmk-studio-snapshot 1\n\
+             layer 0 Base Layer\n\
+             0/0 KeyPress ESC\n\
+             0/1 LayerTap 1 SPC\n\
+             0/2 KeyPress 0x03070004\n\
+             layer 1\n\
+             1/0 Transparent\n\
+             1/1 Unknown 42 1 2\n"
+        );
+        assert_eq!(TypedKeymap::from_snapshot_text(&text), Ok(keymap));
+    }
+
+    #[test]
+    fn quotes_layer_names_that_plain_text_would_alter() {
+        let names = [" Nav ", "\"Sym\"", "Two\nLines", "Tab\t", "C:\\ \""];
+        let keymap = TypedKeymap {
+            layers: names
+                .iter()
+                .zip(0..)
+                .map(|(name, id)| TypedLayer {
+                    id,
+                    name: name.to_string(),
+                    bindings: vec![Behavior::Transparent],
+                })
+                .collect(),
+        };
+
+        let text = keymap.to_snapshot_text();
+        assert!(text.contains("layer 0 \" Nav \"\n"));
+        assert!(text.contains("layer 1 \"\\\"Sym\\\"\"\n"));
+        assert!(text.contains("layer 2 \"Two\\nLines\"\n"));
+        assert!(text.contains("layer 3 \"Tab\\t\"\n"));
+        // Quotes and backslashes inside a name need no quoting.
+        assert!(text.contains("layer 4 C:\\ \"\n"));
+        assert_eq!(TypedKeymap::from_snapshot_text(&text), Ok(keymap));
+
+        for bad in ["layer 0 \"Nav", "layer 0 \"a\"b\"", "layer 0 \"\\x\""] {
+            let text = format!("zmk-studio-snapshot 1\n{bad}\n");
+            let err = TypedKeymap::from_snapshot_text(&text).expect_err(bad);
+            assert_eq!(err.line, 2);
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_order_positions() {
+        let text = "zmk-studio-snapshot 1\nlayer 0 Base\n0/1 Transparent\n";
+        let err = TypedKeymap::from_snapshot_text(text).expect_err("parse should fail");
+        assert_eq!(err.line, 3);
+    }
+}
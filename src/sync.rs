@@ -0,0 +1,236 @@
+use std::path::Path;
+
+use crate::binding::Behavior;
+use crate::keymap::TypedKeymap;
+use crate::snapshot::behavior_to_text;
+
+/// A single difference between a local snapshot file and the device.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncDifference {
+    LayerName {
+        layer_id: u32,
+        local: String,
+        device: String,
+    },
+    /// A binding that differs, or exists on only one side (`None`).
+    Binding {
+        layer_id: u32,
+        position: usize,
+        local: Option<Behavior>,
+        device: Option<Behavior>,
+    },
+    /// A layer present in the file but not on the device. Cannot be pushed.
+    LayerOnlyLocal { layer_id: u32 },
+    /// A layer present on the device but not in the file. Cannot be pushed.
+    LayerOnlyOnDevice { layer_id: u32 },
+}
+
+impl std::fmt::Display for SyncDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LayerName {
+                layer_id,
+                local,
+                device,
+            } => write!(
+                f,
+                "layer {layer_id} name: file {local:?}, device {device:?}"
+            ),
+            Self::Binding {
+                layer_id,
+                position,
+                local,
+                device,
+            } => write!(
+                f,
+                "{layer_id}/{position}: file {}, device {}",
+                describe(local.as_ref()),
+                describe(device.as_ref())
+            ),
+            Self::LayerOnlyLocal { layer_id } => {
+                write!(f, "layer {layer_id}: only in file")
+            }
+            Self::LayerOnlyOnDevice { layer_id } => {
+                write!(f, "layer {layer_id}: only on device")
+            }
+        }
+    }
+}
+
+fn describe(behavior: Option<&Behavior>) -> String {
+    behavior.map_or_else(|| "<missing>".to_string(), behavior_to_text)
+}
+
+/// Resolution chosen by the caller for a [`SyncDifference`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// Write the file's value to the device.
+    Push,
+    /// Write the device's value to the file.
+    Pull,
+    /// Leave both sides unchanged.
+    Skip,
+}
+
+/// Summary of a [`crate::StudioClient::sync`] run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    /// Differences skipped by the caller or not representable in the chosen direction.
+    pub skipped: usize,
+}
+
+/// Computes the differences between a local snapshot and the device snapshot.
+///
+/// Layers are matched by ID. Differences are ordered by the device layer
+/// order, followed by layers only present locally.
+pub fn diff_snapshots(local: &TypedKeymap, device: &TypedKeymap) -> Vec<SyncDifference> {
+    let mut differences = Vec::new();
+
+    for device_layer in &device.layers {
+        let Some(local_layer) = local.layer(device_layer.id) else {
+            differences.push(SyncDifference::LayerOnlyOnDevice {
+                layer_id: device_layer.id,
+            });
+            continue;
+        };
+
+        if local_layer.name != device_layer.name {
+            differences.push(SyncDifference::LayerName {
+                layer_id: device_layer.id,
+                local: local_layer.name.clone(),
+                device: device_layer.name.clone(),
+            });
+        }
+
+        let len = local_layer.bindings.len().max(device_layer.bindings.len());
+        for position in 0..len {
+            let local = local_layer.bindings.get(position);
+            let device = device_layer.bindings.get(position);
+            if local != device {
+                differences.push(SyncDifference::Binding {
+                    layer_id: device_layer.id,
+                    position,
+                    local: local.cloned(),
+                    device: device.cloned(),
+                });
+            }
+        }
+    }
+
+    for local_layer in &local.layers {
+        if device.layer(local_layer.id).is_none() {
+            differences.push(SyncDifference::LayerOnlyLocal {
+                layer_id: local_layer.id,
+            });
+        }
+    }
+
+    differences
+}
+
+/// Applies a pulled difference to the local snapshot.
+pub(crate) fn pull_difference(
+    local: &mut TypedKeymap,
+    device: &TypedKeymap,
+    diff: &SyncDifference,
+) {
+    match diff {
+        SyncDifference::LayerName {
+            layer_id, device, ..
+        } => {
+            if let Some(layer) = local.layers.iter_mut().find(|l| l.id == *layer_id) {
+                layer.name = device.clone();
+            }
+        }
+        SyncDifference::Binding {
+            layer_id,
+            position,
+            device,
+            ..
+        } => {
+            let Some(layer) = local.layers.iter_mut().find(|l| l.id == *layer_id) else {
+                return;
+            };
+            match device {
+                Some(behavior) if *position < layer.bindings.len() => {
+                    layer.bindings[*position] = behavior.clone();
+                }
+                // Differences are ordered by position, so missing tail entries arrive in order.
+                Some(behavior) => layer.bindings.push(behavior.clone()),
+                None => layer.bindings.truncate(*position),
+            }
+        }
+        SyncDifference::LayerOnlyOnDevice { layer_id } => {
+            if let Some(layer) = device.layer(*layer_id) {
+                local.layers.push(layer.clone());
+            }
+        }
+        SyncDifference::LayerOnlyLocal { layer_id } => {
+            local.layers.retain(|l| l.id != *layer_id);
+        }
+    }
+}
+
+pub(crate) fn read_snapshot_file(path: &Path) -> Result<Option<String>, std::io::Error> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SyncDifference, diff_snapshots};
+    use crate::Behavior;
+    use crate::keymap::{TypedKeymap, TypedLayer};
+
+    fn layer(id: u32, name: &str, bindings: Vec<Behavior>) -> TypedLayer {
+        TypedLayer {
+            id,
+            name: name.to_string(),
+            bindings,
+        }
+    }
+
+    #[test]
+    fn diffs_names_bindings_and_layers() {
+        let local = TypedKeymap {
+            layers: vec![
+                layer(0, "Base", vec![Behavior::Transparent, Behavior::None]),
+                layer(2, "Old", vec![]),
+            ],
+        };
+        let device = TypedKeymap {
+            layers: vec![
+                layer(
+                    0,
+                    "Default",
+                    vec![Behavior::Transparent, Behavior::CapsWord],
+                ),
+                layer(1, "Nav", vec![]),
+            ],
+        };
+
+        assert_eq!(
+            diff_snapshots(&local, &device),
+            vec![
+                SyncDifference::LayerName {
+                    layer_id: 0,
+                    local: "Base".to_string(),
+                    device: "Default".to_string(),
+                },
+                SyncDifference::Binding {
+                    layer_id: 0,
+                    position: 1,
+                    local: Some(Behavior::None),
+                    device: Some(Behavior::CapsWord),
+                },
+                SyncDifference::LayerOnlyOnDevice { layer_id: 1 },
+                SyncDifference::LayerOnlyLocal { layer_id: 2 },
+            ]
+        );
+    }
+}
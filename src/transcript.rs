@@ -0,0 +1,163 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::privacy::{RedactedSerial, SerialRedaction};
+use crate::proto::zmk;
+use crate::proto::zmk::studio;
+
+/// Kind of message recorded in a [`Transcript`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptDirection {
+    Request,
+    Response,
+    Notification,
+}
+
+impl TranscriptDirection {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Request => "request",
+            Self::Response => "response",
+            Self::Notification => "notification",
+        }
+    }
+}
+
+/// A single decoded message in a [`Transcript`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    /// Time since the transcript was started.
+    pub elapsed: Duration,
+    pub direction: TranscriptDirection,
+    /// Decoded message in Rust debug notation.
+    pub text: String,
+}
+
+/// Decoded log of the RPC traffic of a client session, for attaching to bug reports.
+///
+/// Start one with [`crate::StudioClient::start_transcript`]. Device serial
+/// numbers are rendered through the transcript's [`SerialRedaction`].
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    started: Instant,
+    redaction: SerialRedaction,
+    entries: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    pub fn new(redaction: SerialRedaction) -> Self {
+        Self {
+            started: Instant::now(),
+            redaction,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Renders one line per message, prefixed with the time since the transcript started.
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "[+{:.3}s] {} {}\n",
+                    entry.elapsed.as_secs_f64(),
+                    entry.direction.label(),
+                    entry.text
+                )
+            })
+            .collect()
+    }
+
+    /// Writes [`Transcript::to_text`] to `path`, replacing the file.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_text())
+    }
+
+    pub(crate) fn record_request(&mut self, request: &studio::Request) {
+        self.push(TranscriptDirection::Request, format!("{request:?}"));
+    }
+
+    pub(crate) fn record_response(&mut self, response: &studio::Response) {
+        let direction = match response.r#type {
+            Some(studio::response::Type::Notification(_)) => TranscriptDirection::Notification,
+            _ => TranscriptDirection::Response,
+        };
+        let text = format!("{response:?}");
+        // Debug output hides the serial; show it as the redaction mode allows.
+        let text = match device_serial(response) {
+            Some(serial) => text.replacen(
+                &format!("serial_number: {:?}", RedactedSerial(serial.len())),
+                &format!("serial_number: {:?}", self.redaction.apply(serial)),
+                1,
+            ),
+            None => text,
+        };
+        self.push(direction, text);
+    }
+
+    fn push(&mut self, direction: TranscriptDirection, text: String) {
+        self.entries.push(TranscriptEntry {
+            elapsed: self.started.elapsed(),
+            direction,
+            text,
+        });
+    }
+}
+
+fn device_serial(response: &studio::Response) -> Option<&[u8]> {
+    match &response.r#type {
+        Some(studio::response::Type::RequestResponse(rr)) => match &rr.subsystem {
+            Some(studio::request_response::Subsystem::Core(core)) => match &core.response_type {
+                Some(zmk::core::response::ResponseType::GetDeviceInfo(info)) => {
+                    Some(&info.serial_number)
+                }
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Transcript, TranscriptDirection};
+    use crate::privacy::SerialRedaction;
+    use crate::proto::zmk;
+    use crate::proto::zmk::studio;
+
+    #[test]
+    fn redacts_serial_numbers() {
+        let mut transcript = Transcript::new(SerialRedaction::Truncate { visible: 2 });
+        transcript.record_response(&studio::Response {
+            r#type: Some(studio::response::Type::RequestResponse(
+                studio::RequestResponse {
+                    request_id: 0,
+                    subsystem: Some(studio::request_response::Subsystem::Core(
+                        zmk::core::Response {
+                            response_type: Some(zmk::core::response::ResponseType::GetDeviceInfo(
+                                zmk::core::GetDeviceInfoResponse {
+                                    name: "Corne".to_string(),
+                                    serial_number: vec![0xDE, 0xAD, 0xBE, 0xEF],
+                                },
+                            )),
+                        },
+                    )),
+                },
+            )),
+        });
+
+        let entry = &transcript.entries()[0];
+        assert_eq!(entry.direction, TranscriptDirection::Response);
+        assert!(entry.text.contains("name: \"Corne\""));
+        assert!(entry.text.contains("serial_number: \"******ef\""));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::binding::Behavior;
+use crate::client::{ClientError, StudioClient};
+use crate::keymap::{
+    ApplyKeymapReport, TypedKeymap, TypedLayer, referenced_layer, remap_layer_reference,
+};
+use crate::proto::zmk;
+use crate::transport::Transport;
+
+/// Why a binding could not be copied to the target keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UntransferredReason {
+    /// The target firmware does not provide the behavior.
+    UnsupportedBehavior,
+    /// The target has fewer keys than the source.
+    NoSuchPosition,
+    /// The binding refers to a layer that did not fit on the target.
+    MissingLayer,
+}
+
+/// A source binding that was not copied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UntransferredBinding {
+    /// Source layer ID.
+    pub layer_id: u32,
+    pub position: usize,
+    pub behavior: Behavior,
+    pub reason: UntransferredReason,
+}
+
+/// Outcome of [`clone_keymap`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CloneReport {
+    pub source_key_count: usize,
+    pub target_key_count: usize,
+    /// Changes staged on the target.
+    pub applied: ApplyKeymapReport,
+    /// Source layer IDs that exceeded the target's layer capacity.
+    pub skipped_layers: Vec<u32>,
+    /// Bindings left out of the copy. Their target positions are set to [`Behavior::None`].
+    pub untransferred: Vec<UntransferredBinding>,
+}
+
+/// Copies the layers and bindings of `source` to `target`.
+///
+/// The target keeps its own key count: surplus source positions are dropped
+/// and missing ones are filled with [`Behavior::None`]. Bindings whose
+/// behavior the target firmware lacks are replaced with [`Behavior::None`].
+/// Everything left out is listed in the report. Changes are staged on the
+/// target; persist them with [`StudioClient::save_changes`].
+pub fn clone_keymap<S: Transport, T: Transport>(
+    source: &mut StudioClient<S>,
+    target: &mut StudioClient<T>,
+) -> Result<CloneReport, ClientError> {
+    let source_keymap = source.get_typed_keymap()?;
+    let target_raw = target.get_keymap()?;
+
+    let source_key_count = source_keymap
+        .layers
+        .first()
+        .map_or(0, |layer| layer.bindings.len());
+    let target_key_count = target_raw
+        .layers
+        .first()
+        .map_or(0, |layer| layer.bindings.len());
+    let layer_capacity = target_raw.layers.len() + target_raw.available_layers as usize;
+
+    let mut report = CloneReport {
+        source_key_count,
+        target_key_count,
+        ..CloneReport::default()
+    };
+    let (kept_layers, skipped_layers) = source_keymap
+        .layers
+        .split_at(source_keymap.layers.len().min(layer_capacity));
+    report.skipped_layers = skipped_layers.iter().map(|layer| layer.id).collect();
+
+    let mut layers = Vec::with_capacity(kept_layers.len());
+    for layer in kept_layers {
+        let mut bindings = Vec::with_capacity(target_key_count);
+        for (position, behavior) in layer.bindings.iter().enumerate() {
+            let reason = if position >= target_key_count {
+                Some(UntransferredReason::NoSuchPosition)
+            } else if referenced_layer(behavior)
+                .is_some_and(|id| report.skipped_layers.contains(&id))
+            {
+                Some(UntransferredReason::MissingLayer)
+            } else if !target.supports_behavior(behavior)? {
+                Some(UntransferredReason::UnsupportedBehavior)
+            } else {
+                None
+            };
+
+            match reason {
+                Some(reason) => {
+                    report.untransferred.push(UntransferredBinding {
+                        layer_id: layer.id,
+                        position,
+                        behavior: behavior.clone(),
+                        reason,
+                    });
+                    if position < target_key_count {
+                        bindings.push(Behavior::None);
+                    }
+                }
+                None => bindings.push(behavior.clone()),
+            }
+        }
+        bindings.resize(target_key_count, Behavior::None);
+
+        layers.push(TypedLayer {
+            id: layer.id,
+            name: layer.name.clone(),
+            bindings,
+        });
+    }
+
+    report.applied = target.apply_keymap(&TypedKeymap { layers })?;
+    Ok(report)
+}
+
+/// A single difference found by [`compare`].
+///
+/// Layers are matched by index, and layer references in bindings are compared
+/// by layer index, since layer IDs are assigned per device.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceDifference {
+    LayerName {
+        layer_index: usize,
+        a: String,
+        b: String,
+    },
+    /// A binding that differs, or exists on only one device (`None`).
+    Binding {
+        layer_index: usize,
+        position: usize,
+        a: Option<Behavior>,
+        b: Option<Behavior>,
+    },
+    /// A layer present on only one device.
+    Layer {
+        layer_index: usize,
+        a: Option<String>,
+        b: Option<String>,
+    },
+    /// A physical layout that differs in name or key geometry, or exists on only one device.
+    PhysicalLayout {
+        layout_index: usize,
+        a: Option<zmk::keymap::PhysicalLayout>,
+        b: Option<zmk::keymap::PhysicalLayout>,
+    },
+    ActiveLayout {
+        a: u32,
+        b: u32,
+    },
+}
+
+/// Outcome of [`compare`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComparisonReport {
+    pub differences: Vec<DeviceDifference>,
+}
+
+impl ComparisonReport {
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// Compares the keymaps and physical layouts of two keyboards.
+pub fn compare<A: Transport, B: Transport>(
+    a: &mut StudioClient<A>,
+    b: &mut StudioClient<B>,
+) -> Result<ComparisonReport, ClientError> {
+    let keymap_a = a.get_typed_keymap()?;
+    let keymap_b = b.get_typed_keymap()?;
+    let layouts_a = a.get_physical_layouts()?;
+    let layouts_b = b.get_physical_layouts()?;
+
+    let mut differences = compare_keymaps(&keymap_a, &keymap_b);
+
+    let layout_count = layouts_a.layouts.len().max(layouts_b.layouts.len());
+    for layout_index in 0..layout_count {
+        let layout_a = layouts_a.layouts.get(layout_index);
+        let layout_b = layouts_b.layouts.get(layout_index);
+        if layout_a != layout_b {
+            differences.push(DeviceDifference::PhysicalLayout {
+                layout_index,
+                a: layout_a.cloned(),
+                b: layout_b.cloned(),
+            });
+        }
+    }
+    if layouts_a.active_layout_index != layouts_b.active_layout_index {
+        differences.push(DeviceDifference::ActiveLayout {
+            a: layouts_a.active_layout_index,
+            b: layouts_b.active_layout_index,
+        });
+    }
+
+    Ok(ComparisonReport { differences })
+}
+
+/// Compares two keymaps layer by layer, ignoring differences in layer IDs.
+pub fn compare_keymaps(a: &TypedKeymap, b: &TypedKeymap) -> Vec<DeviceDifference> {
+    let mut differences = Vec::new();
+
+    let layer_count = a.layers.len().max(b.layers.len());
+    for layer_index in 0..layer_count {
+        let (layer_a, layer_b) = match (a.layers.get(layer_index), b.layers.get(layer_index)) {
+            (Some(layer_a), Some(layer_b)) => (layer_a, layer_b),
+            (layer_a, layer_b) => {
+                differences.push(DeviceDifference::Layer {
+                    layer_index,
+                    a: layer_a.map(|layer| layer.name.clone()),
+                    b: layer_b.map(|layer| layer.name.clone()),
+                });
+                continue;
+            }
+        };
+
+        if layer_a.name != layer_b.name {
+            differences.push(DeviceDifference::LayerName {
+                layer_index,
+                a: layer_a.name.clone(),
+                b: layer_b.name.clone(),
+            });
+        }
+
+        let len = layer_a.bindings.len().max(layer_b.bindings.len());
+        for position in 0..len {
+            let binding_a = layer_a
+                .bindings
+                .get(position)
+                .map(|behavior| by_layer_index(a, behavior));
+            let binding_b = layer_b
+                .bindings
+                .get(position)
+                .map(|behavior| by_layer_index(b, behavior));
+            if binding_a != binding_b {
+                differences.push(DeviceDifference::Binding {
+                    layer_index,
+                    position,
+                    a: binding_a,
+                    b: binding_b,
+                });
+            }
+        }
+    }
+
+    differences
+}
+
+fn by_layer_index(keymap: &TypedKeymap, behavior: &Behavior) -> Behavior {
+    remap_layer_reference(behavior, |layer_id| {
+        keymap
+            .layers
+            .iter()
+            .position(|layer| layer.id == layer_id)
+            .map_or(layer_id, |index| index as u32)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceDifference, UntransferredReason, clone_keymap, compare, compare_keymaps};
+    use crate::keymap::{TypedKeymap, TypedLayer};
+    use crate::proto::zmk;
+    use crate::transport::mock::MockDevice;
+    use crate::{Behavior, Keycode, StudioClient};
+
+    fn layer(id: u32, name: &str, bindings: Vec<Behavior>) -> TypedLayer {
+        TypedLayer {
+            id,
+            name: name.to_string(),
+            bindings,
+        }
+    }
+
+    #[test]
+    fn compares_layers_by_index() {
+        let a = TypedKeymap {
+            layers: vec![
+                layer(
+                    0,
+                    "Base",
+                    vec![Behavior::MomentaryLayer { layer_id: 1 }, Behavior::None],
+                ),
+                layer(1, "Nav", vec![]),
+            ],
+        };
+        let b = TypedKeymap {
+            layers: vec![
+                layer(
+                    4,
+                    "Base",
+                    vec![Behavior::MomentaryLayer { layer_id: 7 }, Behavior::CapsWord],
+                ),
+                layer(7, "Navigation", vec![]),
+                layer(9, "Extra", vec![]),
+            ],
+        };
+
+        assert_eq!(
+            compare_keymaps(&a, &b),
+            vec![
+                DeviceDifference::Binding {
+                    layer_index: 0,
+                    position: 1,
+                    a: Some(Behavior::None),
+                    b: Some(Behavior::CapsWord),
+                },
+                DeviceDifference::LayerName {
+                    layer_index: 1,
+                    a: "Nav".to_string(),
+                    b: "Navigation".to_string(),
+                },
+                DeviceDifference::Layer {
+                    layer_index: 2,
+                    a: None,
+                    b: Some("Extra".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn clones_a_keymap_between_matching_keyboards() {
+        let mut source = StudioClient::new(MockDevice::new());
+        source.add_layer().expect("add layer");
+        source.set_layer_props(2, "Fn").expect("rename");
+        source
+            .set_key_at(0, 1, Behavior::MomentaryLayer { layer_id: 2 })
+            .expect("set binding");
+        for position in 0..4 {
+            source
+                .set_key_at(2, position, Behavior::KeyPress(Keycode::F1.into()))
+                .expect("set binding");
+        }
+        let mut target = StudioClient::new(MockDevice::new());
+
+        let report = clone_keymap(&mut source, &mut target).expect("clone");
+        assert_eq!((report.source_key_count, report.target_key_count), (4, 4));
+        assert_eq!(report.applied.layers_added, 1);
+        assert!(report.skipped_layers.is_empty());
+        assert!(report.untransferred.is_empty());
+        assert!(
+            compare(&mut source, &mut target)
+                .expect("compare")
+                .is_identical()
+        );
+    }
+
+    #[test]
+    fn reports_what_does_not_fit_a_different_keyboard() {
+        let mut source = StudioClient::new(MockDevice::new());
+        source.add_layer().expect("add layer");
+        source
+            .set_key_at(0, 0, Behavior::MomentaryLayer { layer_id: 2 })
+            .expect("set binding");
+
+        // Two keys, room for two layers, and no `&trans`.
+        let key_press = |usage: u32| zmk::keymap::BehaviorBinding {
+            behavior_id: 1,
+            param1: usage,
+            param2: 0,
+        };
+        let small = MockDevice::new()
+            .with_behaviors([(1, "Key Press"), (3, "None"), (4, "Momentary Layer")])
+            .with_keymap(zmk::keymap::Keymap {
+                layers: vec![
+                    zmk::keymap::Layer {
+                        id: 0,
+                        name: "Base".to_string(),
+                        bindings: vec![key_press(0x0007_0004), key_press(0x0007_0005)],
+                    },
+                    zmk::keymap::Layer {
+                        id: 1,
+                        name: "Lower".to_string(),
+                        bindings: vec![key_press(0x0007_0006), key_press(0x0007_0007)],
+                    },
+                ],
+                available_layers: 0,
+                max_layer_name_length: 20,
+            });
+        let mut target = StudioClient::new(small);
+
+        let report = clone_keymap(&mut source, &mut target).expect("clone");
+        assert_eq!((report.source_key_count, report.target_key_count), (4, 2));
+        assert_eq!(report.skipped_layers, vec![2]);
+
+        let reasons = |reason| {
+            report
+                .untransferred
+                .iter()
+                .filter(|binding| binding.reason == reason)
+                .map(|binding| (binding.layer_id, binding.position))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(reasons(UntransferredReason::MissingLayer), vec![(0, 0)]);
+        assert_eq!(
+            reasons(UntransferredReason::NoSuchPosition),
+            vec![(0, 2), (0, 3), (1, 2), (1, 3)]
+        );
+        assert_eq!(
+            reasons(UntransferredReason::UnsupportedBehavior),
+            vec![(1, 0), (1, 1)]
+        );
+
+        let copied = target.get_typed_keymap().expect("keymap");
+        assert_eq!(copied.layers.len(), 2);
+        assert_eq!(
+            copied.layers[0].bindings,
+            vec![Behavior::None, Behavior::KeyPress(Keycode::B.into())]
+        );
+        assert_eq!(copied.layers[1].bindings, vec![Behavior::None; 2]);
+    }
+}
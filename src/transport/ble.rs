@@ -1,59 +1,214 @@
 use std::collections::VecDeque;
 use std::io::{Read, Write};
+use std::pin::Pin;
+use std::sync::OnceLock;
 use std::sync::mpsc::{self, Receiver};
-use std::thread;
 use std::time::Duration;
 
 use btleplug::api::{
-    Central, CharPropFlags, Characteristic, Manager as _, Peripheral as _, ScanFilter, WriteType,
+    AddressType, BDAddr, Central, CentralEvent, CharPropFlags, Characteristic, Manager as _,
+    Peripheral as _, PeripheralProperties, ScanFilter, ValueNotification, WriteType,
 };
 use btleplug::platform::{Adapter, Manager, Peripheral};
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
+use super::Transport;
+use crate::privacy::RedactedSerial;
+
+/// Worker threads of the runtime shared by all BLE transports and scans;
+/// the work is I/O-bound, so a couple suffice for any number of keyboards.
+const RUNTIME_WORKER_THREADS: usize = 2;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
 const DEFAULT_SCAN_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Minimum ATT MTU every stack supports, assumed when the negotiated one is unknown.
+const DEFAULT_ATT_MTU: u16 = 23;
+/// Bytes of each ATT write taken by the opcode and attribute handle.
+const ATT_WRITE_HEADER_LEN: usize = 3;
+
 const BLE_SERVICE_UUID: &str = "00000000-0196-6107-c967-c5cfb1c2482a";
 const BLE_RPC_CHARACTERISTIC_UUID: &str = "00000001-0196-6107-c967-c5cfb1c2482a";
+const BATTERY_SERVICE_UUID: Uuid = Uuid::from_u128(0x0000_180f_0000_1000_8000_0080_5f9b_34fb);
 
+/// How long [`discover_devices_with_options`] scans and when it stops early.
 #[derive(Debug, Clone)]
-struct BleScanOptions {
-    scan_timeout: Duration,
+#[non_exhaustive]
+pub struct BleScanOptions {
+    /// Upper bound on the scan; it ends earlier once `max_devices` are found.
+    pub scan_timeout: Duration,
+    /// Stops scanning as soon as this many devices have been found.
+    pub max_devices: Option<usize>,
+    /// Scans only the adapter whose description contains this text, as
+    /// listed by [`list_adapters`]; all adapters when `None`.
+    pub adapter: Option<String>,
 }
 
 impl Default for BleScanOptions {
     fn default() -> Self {
         Self {
             scan_timeout: DEFAULT_SCAN_TIMEOUT,
+            max_devices: None,
+            adapter: None,
         }
     }
 }
 
+/// Which keyboard [`BleTransport::connect`] looks for, and how it connects.
+///
+/// A device advertising the peripheral ID `device_id` is always accepted.
+/// Otherwise, if `name_equals` or `name_contains` is set, a device whose
+/// advertised local name matches is accepted; when several do, connecting
+/// fails with [`BleTransportError::AmbiguousName`] listing them.
 #[derive(Debug, Clone)]
-struct BleConnectOptions {
-    scan_timeout: Duration,
-    read_timeout: Duration,
-    device_id: String,
+#[non_exhaustive]
+pub struct BleConnectOptions {
+    pub scan_timeout: Duration,
+    pub read_timeout: Duration,
+    /// Platform-specific peripheral ID, or empty to select by name only.
+    pub device_id: String,
+    /// Local name the device must advertise exactly.
+    pub name_equals: Option<String>,
+    /// Text the advertised local name must contain, ignoring case.
+    pub name_contains: Option<String>,
+    pub reconnect: Option<ReconnectPolicy>,
+    /// Connects through the adapter whose description contains this text, as
+    /// listed by [`list_adapters`]; the first adapter when `None`.
+    pub adapter: Option<String>,
+    /// ATT MTU negotiated with the keyboard; outgoing frames are split into
+    /// writes that fit it. btleplug does not report the negotiated MTU, so
+    /// `None` assumes the 23-byte minimum.
+    pub mtu: Option<u16>,
+    /// Advertised identity accepted when `device_id` is not seen.
+    fallback: Option<SavedBleDevice>,
 }
 
 impl BleConnectOptions {
-    fn new(device_id: &str) -> Self {
+    /// Options for the peripheral with `device_id`.
+    pub fn new(device_id: &str) -> Self {
         Self {
             scan_timeout: DEFAULT_SCAN_TIMEOUT,
             read_timeout: DEFAULT_READ_TIMEOUT,
             device_id: device_id.to_string(),
+            name_equals: None,
+            name_contains: None,
+            reconnect: None,
+            adapter: None,
+            mtu: None,
+            fallback: None,
+        }
+    }
+
+    /// Options for the device whose advertised name contains `name`, ignoring case.
+    pub fn with_name(name: &str) -> Self {
+        Self {
+            name_contains: Some(name.to_string()),
+            ..Self::new("")
+        }
+    }
+
+    fn selects_by_name(&self) -> bool {
+        self.name_equals.is_some() || self.name_contains.is_some()
+    }
+
+    fn matches_name(&self, local_name: Option<&str>) -> bool {
+        if !self.selects_by_name() {
+            return false;
+        }
+        let Some(local_name) = local_name else {
+            return false;
+        };
+        self.name_equals
+            .as_deref()
+            .is_none_or(|expected| local_name == expected)
+            && self
+                .name_contains
+                .as_deref()
+                .is_none_or(|text| local_name.to_lowercase().contains(&text.to_lowercase()))
+    }
+
+    /// What the scan is looking for, for error messages.
+    fn target(&self) -> String {
+        if !self.device_id.is_empty() {
+            return self.device_id.clone();
+        }
+        match (&self.name_equals, &self.name_contains) {
+            (Some(name), _) => format!("name {name:?}"),
+            (None, Some(text)) => format!("name containing {text:?}"),
+            (None, None) => String::from("any device"),
+        }
+    }
+}
+
+/// How a [`BleTransport`] reconnects after the keyboard drops the connection,
+/// for example when it goes to sleep or out of range.
+///
+/// Attempts are spaced by a backoff that starts at `initial_backoff` and
+/// doubles after every failure, up to `max_backoff`. Reads time out while
+/// reconnecting, and writes are held until the connection is back. Responses
+/// to requests in flight during the disconnect are lost, so pair this with
+/// [`crate::StudioClient::set_request_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Attempts per disconnect before the transport gives up and reports EOF.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
         }
     }
 }
 
+impl ReconnectPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+/// Kind of Bluetooth device address a peripheral advertises with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BleAddressType {
+    Public,
+    /// A random address, which ZMK uses for each of its profiles.
+    Random,
+}
+
 /// A discoverable ZMK Studio BLE device.
+///
+/// There is no `is_paired` flag, since btleplug does not expose bonding state.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BleDeviceInfo {
     pub device_id: String,
     pub local_name: Option<String>,
+    /// Description of the Bluetooth adapter that saw the device.
+    pub adapter: String,
+    /// Service data advertised for the ZMK Studio service, if any.
+    pub service_data: Option<Vec<u8>>,
+    /// Signal strength of the latest advertisement in dBm.
+    pub rssi: Option<i16>,
+    /// Bluetooth address, where the platform exposes it (not on macOS, which
+    /// identifies peripherals by a per-host UUID instead).
+    pub address: Option<String>,
+    pub address_type: Option<BleAddressType>,
+    /// Whether this host is currently connected to the device.
+    pub is_connected: bool,
+    /// Battery level in percent, if advertised as Battery Service data.
+    pub battery_level: Option<u8>,
 }
 
 impl BleDeviceInfo {
@@ -65,6 +220,66 @@ impl BleDeviceInfo {
     }
 }
 
+/// A BLE keyboard remembered across sessions.
+///
+/// Peripheral IDs are not stable on every platform: CoreBluetooth on macOS
+/// hands out per-host UUIDs that change after a Bluetooth cache reset. When
+/// the saved ID is not seen, [`crate::StudioClient::open_ble_saved`] falls back
+/// to a device advertising the same name and service data, then verifies the
+/// serial number after connecting before trusting it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SavedBleDevice {
+    pub device_id: String,
+    pub local_name: Option<String>,
+    pub service_data: Option<Vec<u8>>,
+    /// Serial number reported over RPC, recorded after the first verified connection.
+    pub serial_number: Option<Vec<u8>>,
+}
+
+impl std::fmt::Debug for SavedBleDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SavedBleDevice")
+            .field("device_id", &self.device_id)
+            .field("local_name", &self.local_name)
+            .field("service_data", &self.service_data)
+            .field(
+                "serial_number",
+                &self
+                    .serial_number
+                    .as_ref()
+                    .map(|serial| RedactedSerial(serial.len())),
+            )
+            .finish()
+    }
+}
+
+impl From<&BleDeviceInfo> for SavedBleDevice {
+    fn from(info: &BleDeviceInfo) -> Self {
+        Self {
+            device_id: info.device_id.clone(),
+            local_name: info.local_name.clone(),
+            service_data: info.service_data.clone(),
+            serial_number: None,
+        }
+    }
+}
+
+impl SavedBleDevice {
+    /// Whether an advertisement matches this device's name and service data.
+    fn matches_advertisement(&self, local_name: Option<&str>, service_data: Option<&[u8]>) -> bool {
+        let Some(name) = self.local_name.as_deref() else {
+            return false;
+        };
+        if local_name != Some(name) {
+            return false;
+        }
+        match &self.service_data {
+            Some(expected) => service_data == Some(expected.as_slice()),
+            None => true,
+        }
+    }
+}
+
 /// Errors from BLE transport setup/operation.
 #[derive(Debug)]
 pub enum BleTransportError {
@@ -72,9 +287,28 @@ pub enum BleTransportError {
     Btleplug(btleplug::Error),
     Uuid(uuid::Error),
     NoAdapter,
+    /// No adapter's description contains the requested text.
+    AdapterNotFound(String),
+    /// No device advertised the requested peripheral ID or name.
     DeviceNotFound(String),
+    /// Several devices matched the requested name; connect by device ID instead.
+    AmbiguousName {
+        query: String,
+        candidates: Vec<BleDeviceInfo>,
+    },
     MissingRpcCharacteristic,
+    /// The keyboard refused the Studio characteristic because it is not
+    /// paired (bonded) with this computer. btleplug cannot pair, so pair it
+    /// in the system Bluetooth settings and connect again.
+    PairingRequired {
+        device_id: String,
+        source: btleplug::Error,
+    },
     SetupChannelClosed,
+    /// The connected keyboard's identity could not be checked over RPC.
+    Verification(Box<crate::ClientError>),
+    /// The connected keyboard reported a different serial number than the saved one.
+    DeviceMismatch(String),
 }
 
 impl std::fmt::Display for BleTransportError {
@@ -84,11 +318,26 @@ impl std::fmt::Display for BleTransportError {
             Self::Btleplug(err) => write!(f, "BLE error: {err}"),
             Self::Uuid(err) => write!(f, "UUID parse error: {err}"),
             Self::NoAdapter => write!(f, "No Bluetooth adapter available"),
-            Self::DeviceNotFound(device_id) => {
-                write!(f, "BLE device not found for id: {device_id}")
+            Self::AdapterNotFound(adapter) => write!(f, "No Bluetooth adapter matches {adapter:?}"),
+            Self::DeviceNotFound(target) => write!(f, "BLE device not found: {target}"),
+            Self::AmbiguousName { query, candidates } => {
+                let names: Vec<_> = candidates.iter().map(BleDeviceInfo::display_name).collect();
+                write!(
+                    f,
+                    "Several BLE devices match {query}: {}; connect by device ID instead",
+                    names.join(", ")
+                )
             }
             Self::MissingRpcCharacteristic => write!(f, "ZMK Studio RPC characteristic not found"),
+            Self::PairingRequired { device_id, .. } => write!(
+                f,
+                "BLE device {device_id} must be paired first; pair it in the system Bluetooth settings"
+            ),
             Self::SetupChannelClosed => write!(f, "BLE worker initialization channel closed"),
+            Self::Verification(err) => write!(f, "Failed to verify BLE device identity: {err}"),
+            Self::DeviceMismatch(device_id) => {
+                write!(f, "BLE device {device_id} is not the saved keyboard")
+            }
         }
     }
 }
@@ -99,10 +348,15 @@ impl std::error::Error for BleTransportError {
             Self::RuntimeInit(err) => Some(err),
             Self::Btleplug(err) => Some(err),
             Self::Uuid(err) => Some(err),
+            Self::Verification(err) => Some(err.as_ref()),
+            Self::PairingRequired { source, .. } => Some(source),
             Self::NoAdapter
+            | Self::AdapterNotFound(_)
             | Self::DeviceNotFound(_)
+            | Self::AmbiguousName { .. }
             | Self::MissingRpcCharacteristic
-            | Self::SetupChannelClosed => None,
+            | Self::SetupChannelClosed
+            | Self::DeviceMismatch(_) => None,
         }
     }
 }
@@ -120,53 +374,212 @@ impl From<uuid::Error> for BleTransportError {
 }
 
 /// Discover ZMK Studio-capable BLE peripherals.
+///
+/// All Bluetooth adapters are scanned concurrently and their results merged.
+/// Scans for the full default scan timeout; use
+/// [`discover_devices_with_options`] to stop as soon as a device is found.
 pub fn discover_devices() -> Result<Vec<BleDeviceInfo>, BleTransportError> {
     discover_devices_with_options(BleScanOptions::default())
 }
 
+/// Returns the runtime that drives every BLE scan and connection, starting it
+/// on first use.
+fn shared_runtime() -> Result<&'static Runtime, BleTransportError> {
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(RUNTIME_WORKER_THREADS)
+        .thread_name("zmk-studio-ble")
+        .enable_all()
+        .build()
+        .map_err(BleTransportError::RuntimeInit)?;
+    // If another thread won the race, its runtime is used and ours is dropped.
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
+
+/// Lists the descriptions of the available Bluetooth adapters, such as
+/// `hci0 (usb:v1D6Bp0246d0537)` on Linux.
+///
+/// Pass part of one, such as `hci1`, as the `adapter` of
+/// [`BleConnectOptions`] or [`BleScanOptions`] to use only that adapter.
+pub fn list_adapters() -> Result<Vec<String>, BleTransportError> {
+    shared_runtime()?.block_on(async {
+        let manager = Manager::new().await?;
+        let mut descriptions = Vec::new();
+        for adapter in manager.adapters().await? {
+            descriptions.push(adapter.adapter_info().await?);
+        }
+        Ok(descriptions)
+    })
+}
+
+/// Devices from [`discover_devices_stream`], in the order they are first seen.
+///
+/// Yields an error instead if scanning could not start. Iteration ends when
+/// the scan timeout elapses or `max_devices` have been yielded; dropping the
+/// stream stops the scan at the next advertisement.
+pub struct BleDeviceStream {
+    devices: Receiver<Result<BleDeviceInfo, BleTransportError>>,
+}
+
+impl Iterator for BleDeviceStream {
+    type Item = Result<BleDeviceInfo, BleTransportError>;
+
+    /// Blocks until the next device is found or the scan ends.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.devices.recv().ok()
+    }
+}
+
+/// Like [`discover_devices_with_options`], but yields each device as soon as
+/// it is first seen, for pickers that update while the scan runs.
+pub fn discover_devices_stream(options: BleScanOptions) -> BleDeviceStream {
+    let (tx, rx) = mpsc::channel();
+    let runtime = match shared_runtime() {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            let _ = tx.send(Err(err));
+            return BleDeviceStream { devices: rx };
+        }
+    };
+    runtime.spawn(async move {
+        let mut seen = Vec::new();
+        let scan = scan_devices(&options, |device| {
+            if seen.contains(&device.device_id) {
+                return true;
+            }
+            seen.push(device.device_id.clone());
+            tx.send(Ok(device)).is_ok() && options.max_devices.is_none_or(|max| seen.len() < max)
+        });
+        if let Err(err) = scan.await {
+            let _ = tx.send(Err(err));
+        }
+    });
+    BleDeviceStream { devices: rx }
+}
+
 /// Blocking BLE transport adapter for [`crate::StudioClient`].
 ///
-/// Internally this runs an async worker thread and exposes a blocking
-/// [`Read`] + [`Write`] interface.
+/// Internally this runs an async worker task and exposes a blocking
+/// [`Read`] + [`Write`] interface. All transports and scans share one
+/// two-thread tokio runtime, started on first use, so connecting several
+/// keyboards does not add threads. Do not call the blocking functions of
+/// this module from inside an async runtime.
 pub struct BleTransport {
     write_tx: UnboundedSender<Vec<u8>>,
+    /// Asks the worker to disconnect; taken by the first disconnect or drop.
+    shutdown_tx: Option<oneshot::Sender<ShutdownRequest>>,
     read_rx: Receiver<Vec<u8>>,
     read_queue: VecDeque<u8>,
     read_timeout: Duration,
+    /// Options that established the connection, pinned to the connected device ID.
+    options: BleConnectOptions,
 }
 
 impl BleTransport {
     /// Connects to a specific BLE peripheral using a deterministic device ID.
+    ///
+    /// Connects as soon as the device is seen advertising, scanning for at
+    /// most the scan timeout.
     pub fn connect_device(device_id: &str) -> Result<Self, BleTransportError> {
-        Self::connect_with_options(BleConnectOptions::new(device_id))
+        Self::connect(BleConnectOptions::new(device_id))
+    }
+
+    /// Connects to the keyboard whose advertised name contains `name`, ignoring case.
+    ///
+    /// Unlike peripheral IDs, the name is the same on every host. Connects as
+    /// soon as a match is seen; if several matching devices have been seen by
+    /// then, fails with [`BleTransportError::AmbiguousName`] listing them,
+    /// unless exactly one of them is named `name` exactly.
+    pub fn connect_by_name(name: &str) -> Result<Self, BleTransportError> {
+        Self::connect(BleConnectOptions::with_name(name))
+    }
+
+    /// Connects according to `options`.
+    pub fn connect(options: BleConnectOptions) -> Result<Self, BleTransportError> {
+        Self::connect_with_options(options).map(|(transport, _)| transport)
+    }
+
+    /// Peripheral ID of the connected device.
+    pub fn device_id(&self) -> &str {
+        &self.options.device_id
+    }
+
+    /// Unsubscribes from the keyboard and disconnects it, waiting until done.
+    ///
+    /// Afterwards reads and writes fail and [`Transport::is_connected`] is
+    /// `false`; [`Transport::reconnect`] connects again. Dropping the
+    /// transport disconnects the same way without waiting. Calling this
+    /// again, or after the connection was lost, does nothing.
+    pub fn disconnect(&mut self) -> Result<(), BleTransportError> {
+        let Some(shutdown_tx) = self.shutdown_tx.take() else {
+            return Ok(());
+        };
+        let (reply_tx, reply_rx) = mpsc::channel();
+        if shutdown_tx.send(Some(reply_tx)).is_err() {
+            // The worker has already stopped.
+            return Ok(());
+        }
+        reply_rx.recv().unwrap_or(Ok(()))
     }
 
-    fn connect_with_options(options: BleConnectOptions) -> Result<Self, BleTransportError> {
+    /// Like [`BleTransport::connect_device`], reconnecting according to `policy`
+    /// whenever the connection drops.
+    pub fn connect_device_with_reconnect(
+        device_id: &str,
+        policy: ReconnectPolicy,
+    ) -> Result<Self, BleTransportError> {
+        let mut options = BleConnectOptions::new(device_id);
+        options.reconnect = Some(policy);
+        Self::connect_with_options(options).map(|(transport, _)| transport)
+    }
+
+    /// Connects to a saved device by ID, or by its advertised identity if the ID changed.
+    ///
+    /// Returns the transport together with the device ID that was actually
+    /// connected. The match is not verified; use
+    /// [`crate::StudioClient::open_ble_saved`] to also check the serial number.
+    pub fn connect_saved(saved: &SavedBleDevice) -> Result<(Self, String), BleTransportError> {
+        let mut options = BleConnectOptions::new(&saved.device_id);
+        options.fallback = Some(saved.clone());
+        Self::connect_with_options(options)
+    }
+
+    fn connect_with_options(
+        options: BleConnectOptions,
+    ) -> Result<(Self, String), BleTransportError> {
         let read_timeout = options.read_timeout;
         let worker_options = options.clone();
+        let mut options = options;
         let (write_tx, write_rx) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
         let (read_tx, read_rx) = mpsc::channel::<Vec<u8>>();
-        let (setup_tx, setup_rx) = mpsc::channel::<Result<(), BleTransportError>>();
-
-        thread::spawn(move || {
-            let runtime = match Runtime::new() {
-                Ok(rt) => rt,
-                Err(err) => {
-                    let _ = setup_tx.send(Err(BleTransportError::RuntimeInit(err)));
-                    return;
-                }
-            };
+        let (setup_tx, setup_rx) = mpsc::channel::<Result<String, BleTransportError>>();
 
-            let _ = runtime.block_on(run_ble_worker(write_rx, read_tx, setup_tx, worker_options));
-        });
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        shared_runtime()?.spawn(run_ble_worker(
+            write_rx,
+            read_tx,
+            setup_tx,
+            shutdown_rx,
+            worker_options,
+        ));
 
         match setup_rx.recv() {
-            Ok(Ok(())) => Ok(Self {
-                write_tx,
-                read_rx,
-                read_queue: VecDeque::new(),
-                read_timeout,
-            }),
+            Ok(Ok(device_id)) => {
+                options.device_id = device_id.clone();
+                Ok((
+                    Self {
+                        write_tx,
+                        shutdown_tx: Some(shutdown_tx),
+                        read_rx,
+                        read_queue: VecDeque::new(),
+                        read_timeout,
+                        options,
+                    },
+                    device_id,
+                ))
+            }
             Ok(Err(err)) => Err(err),
             Err(_) => Err(BleTransportError::SetupChannelClosed),
         }
@@ -209,6 +622,40 @@ impl Read for BleTransport {
     }
 }
 
+impl Transport for BleTransport {
+    fn description(&self) -> String {
+        format!("BLE {}", self.options.device_id)
+    }
+
+    /// `false` once the worker has given up on the connection, including
+    /// after a [`ReconnectPolicy`] runs out of attempts.
+    fn is_connected(&self) -> bool {
+        !self.write_tx.is_closed()
+    }
+
+    /// Scans for and connects to the same device again with a new worker.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        let (reconnected, _) =
+            Self::connect_with_options(self.options.clone()).map_err(std::io::Error::other)?;
+        *self = reconnected;
+        Ok(())
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        self.read_timeout = timeout;
+        self.options.read_timeout = timeout;
+        Ok(())
+    }
+}
+
+impl Drop for BleTransport {
+    fn drop(&mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(None);
+        }
+    }
+}
+
 impl Write for BleTransport {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         self.write_tx.send(buf.to_vec()).map_err(|_| {
@@ -225,129 +672,458 @@ impl Write for BleTransport {
     }
 }
 
-fn discover_devices_with_options(
+/// Like [`discover_devices`], ending the scan as `options` allow.
+///
+/// Devices are collected as their advertisements arrive, so with
+/// `max_devices` set this returns as soon as enough keyboards are seen.
+pub fn discover_devices_with_options(
     options: BleScanOptions,
 ) -> Result<Vec<BleDeviceInfo>, BleTransportError> {
-    let runtime = Runtime::new().map_err(BleTransportError::RuntimeInit)?;
-    runtime.block_on(discover_devices_async(options))
+    shared_runtime()?.block_on(discover_devices_async(options))
 }
 
 async fn discover_devices_async(
     options: BleScanOptions,
 ) -> Result<Vec<BleDeviceInfo>, BleTransportError> {
-    let service_uuid = Uuid::parse_str(BLE_SERVICE_UUID)?;
+    let mut devices: Vec<BleDeviceInfo> = Vec::new();
+    scan_devices(&options, |device| {
+        match devices.iter_mut().find(|d| d.device_id == device.device_id) {
+            // Later advertisements may carry a name the first one lacked.
+            Some(known) => *known = device,
+            None => devices.push(device),
+        }
+        options.max_devices.is_none_or(|max| devices.len() < max)
+    })
+    .await?;
+    Ok(devices)
+}
 
+/// Returns every adapter, or only those whose description contains `wanted`.
+async fn find_adapters(wanted: Option<&str>) -> Result<Vec<Adapter>, BleTransportError> {
     let manager = Manager::new().await?;
     let adapters = manager.adapters().await?;
-    let adapter = adapters
-        .into_iter()
-        .next()
-        .ok_or(BleTransportError::NoAdapter)?;
+    if adapters.is_empty() {
+        return Err(BleTransportError::NoAdapter);
+    }
+    let Some(wanted) = wanted else {
+        return Ok(adapters);
+    };
+
+    let mut matching = Vec::new();
+    for adapter in adapters {
+        if adapter.adapter_info().await?.contains(wanted) {
+            matching.push(adapter);
+        }
+    }
+    if matching.is_empty() {
+        return Err(BleTransportError::AdapterNotFound(wanted.to_string()));
+    }
+    Ok(matching)
+}
+
+/// Scans the selected adapters concurrently, passing each advertisement of a
+/// Studio device to `on_device` until it returns `false` or the scan times out.
+async fn scan_devices(
+    options: &BleScanOptions,
+    mut on_device: impl FnMut(BleDeviceInfo) -> bool,
+) -> Result<(), BleTransportError> {
+    let service_uuid = Uuid::parse_str(BLE_SERVICE_UUID)?;
+    let adapters = find_adapters(options.adapter.as_deref()).await?;
+
+    // Scan every adapter concurrently so a second radio does not add to the scan time.
+    let (found_tx, mut found_rx) = tokio::sync::mpsc::unbounded_channel();
+    let scans = futures::future::join_all(
+        adapters
+            .iter()
+            .map(|adapter| scan_adapter(adapter, service_uuid, found_tx.clone())),
+    );
+    drop(found_tx);
+
+    let mut any_found = false;
+    let mut first_error = None;
+    let collect = async {
+        while let Some(result) = found_rx.recv().await {
+            match result {
+                Ok(device) => {
+                    any_found = true;
+                    if !on_device(device) {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+    };
+    let _ = tokio::time::timeout(
+        options.scan_timeout,
+        futures::future::select(Box::pin(scans), Box::pin(collect)),
+    )
+    .await;
+    for adapter in &adapters {
+        let _ = adapter.stop_scan().await;
+    }
+
+    // A failing adapter only matters if no other adapter could scan.
+    match first_error {
+        Some(err) if !any_found => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Reports Studio devices seen by `adapter` to `found` until the scan is
+/// dropped or the adapter fails.
+async fn scan_adapter(
+    adapter: &Adapter,
+    service_uuid: Uuid,
+    found: UnboundedSender<Result<BleDeviceInfo, BleTransportError>>,
+) {
+    if let Err(err) = watch_adapter(adapter, service_uuid, &found).await {
+        let _ = found.send(Err(err));
+    }
+}
 
+async fn watch_adapter(
+    adapter: &Adapter,
+    service_uuid: Uuid,
+    found: &UnboundedSender<Result<BleDeviceInfo, BleTransportError>>,
+) -> Result<(), BleTransportError> {
+    let adapter_name = adapter.adapter_info().await?;
+    let mut events = adapter.events().await?;
     adapter
         .start_scan(ScanFilter {
             services: vec![service_uuid],
         })
         .await?;
-    tokio::time::sleep(options.scan_timeout).await;
 
-    let peripherals = adapter.peripherals().await?;
-    let mut devices = Vec::new();
+    // Devices may already be known from an earlier scan.
+    for peripheral in adapter.peripherals().await? {
+        if let Some(device) = device_info(&peripheral, &adapter_name, service_uuid).await?
+            && found.send(Ok(device)).is_err()
+        {
+            return Ok(());
+        }
+    }
 
-    for peripheral in peripherals {
-        let Some(props) = peripheral.properties().await? else {
-            continue;
+    while let Some(event) = events.next().await {
+        let id = match event {
+            CentralEvent::DeviceDiscovered(id)
+            | CentralEvent::DeviceUpdated(id)
+            | CentralEvent::ServicesAdvertisement { id, .. }
+            | CentralEvent::ServiceDataAdvertisement { id, .. } => id,
+            _ => continue,
         };
-
-        if !props.services.contains(&service_uuid) {
+        let Ok(peripheral) = adapter.peripheral(&id).await else {
             continue;
+        };
+        if let Some(device) = device_info(&peripheral, &adapter_name, service_uuid).await?
+            && found.send(Ok(device)).is_err()
+        {
+            return Ok(());
         }
+    }
+    Ok(())
+}
 
-        devices.push(BleDeviceInfo {
+/// Describes `peripheral` if it advertises the Studio service.
+async fn device_info(
+    peripheral: &Peripheral,
+    adapter_name: &str,
+    service_uuid: Uuid,
+) -> Result<Option<BleDeviceInfo>, BleTransportError> {
+    let Some(props) = peripheral.properties().await? else {
+        return Ok(None);
+    };
+    if !props.services.contains(&service_uuid) {
+        return Ok(None);
+    }
+    let is_connected = peripheral.is_connected().await?;
+    Ok(Some(BleDeviceInfo::from_properties(
+        peripheral,
+        props,
+        adapter_name,
+        service_uuid,
+        is_connected,
+    )))
+}
+
+impl BleDeviceInfo {
+    fn from_properties(
+        peripheral: &Peripheral,
+        props: PeripheralProperties,
+        adapter_name: &str,
+        service_uuid: Uuid,
+        is_connected: bool,
+    ) -> Self {
+        let address = (props.address != BDAddr::default()).then(|| props.address.to_string());
+        Self {
             device_id: peripheral.id().to_string(),
             local_name: props.local_name,
-        });
+            adapter: adapter_name.to_string(),
+            service_data: props.service_data.get(&service_uuid).cloned(),
+            rssi: props.rssi,
+            address,
+            address_type: props.address_type.map(|kind| match kind {
+                AddressType::Public => BleAddressType::Public,
+                AddressType::Random => BleAddressType::Random,
+            }),
+            is_connected,
+            battery_level: props
+                .service_data
+                .get(&BATTERY_SERVICE_UUID)
+                .and_then(|data| data.first().copied()),
+        }
     }
+}
 
-    Ok(devices)
+/// Sent to the worker to close the connection, with a channel for the
+/// outcome when the caller waits for it.
+type ShutdownRequest = Option<mpsc::Sender<Result<(), BleTransportError>>>;
+
+type NotificationStream = Pin<Box<dyn Stream<Item = ValueNotification> + Send>>;
+
+/// A connected peripheral subscribed to the RPC characteristic.
+struct BleConnection {
+    peripheral: Peripheral,
+    characteristic: Characteristic,
+    write_type: WriteType,
+    /// Largest payload of a single write.
+    chunk_size: usize,
+    notifications: NotificationStream,
+}
+
+impl BleConnection {
+    /// Writes `data` as MTU-sized chunks, in order. Takes `&mut self` because
+    /// the notification stream is not `Sync`.
+    async fn write_frame(&mut self, data: &[u8]) -> Result<(), btleplug::Error> {
+        for chunk in data.chunks(self.chunk_size) {
+            self.peripheral
+                .write(&self.characteristic, chunk, self.write_type)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Payload size of a write that fits in one ATT packet of `mtu` bytes.
+fn write_chunk_size(mtu: u16) -> usize {
+    usize::from(mtu.max(DEFAULT_ATT_MTU)) - ATT_WRITE_HEADER_LEN
 }
 
 async fn run_ble_worker(
     mut write_rx: UnboundedReceiver<Vec<u8>>,
     read_tx: mpsc::Sender<Vec<u8>>,
-    setup_tx: mpsc::Sender<Result<(), BleTransportError>>,
+    setup_tx: mpsc::Sender<Result<String, BleTransportError>>,
+    mut shutdown_rx: oneshot::Receiver<ShutdownRequest>,
     options: BleConnectOptions,
 ) -> Result<(), BleTransportError> {
     let service_uuid = Uuid::parse_str(BLE_SERVICE_UUID)?;
     let rpc_uuid = Uuid::parse_str(BLE_RPC_CHARACTERISTIC_UUID)?;
 
-    let (peripheral, characteristic, write_type) =
-        match connect_peripheral(service_uuid, rpc_uuid, &options).await {
-            Ok(v) => v,
-            Err(err) => {
-                let _ = setup_tx.send(Err(err));
-                return Ok(());
-            }
-        };
-
-    if let Err(err) = peripheral.subscribe(&characteristic).await {
-        let _ = setup_tx.send(Err(err.into()));
-        return Ok(());
-    }
-    let mut notifications = match peripheral.notifications().await {
-        Ok(stream) => stream,
+    let mut connection = match connect_and_subscribe(service_uuid, rpc_uuid, &options).await {
+        Ok(connection) => connection,
         Err(err) => {
-            let _ = setup_tx.send(Err(err.into()));
+            let _ = setup_tx.send(Err(err));
             return Ok(());
         }
     };
-    let _ = setup_tx.send(Ok(()));
+    let device_id = connection.peripheral.id().to_string();
+    #[cfg(feature = "tracing")]
+    tracing::info!(device_id, "connected");
+    let _ = setup_tx.send(Ok(device_id.clone()));
+
+    // Reconnect to the peripheral actually connected, even if it was found by
+    // its saved identity.
+    let reconnect_options = BleConnectOptions {
+        device_id,
+        ..options.clone()
+    };
+    let mut unsent = None;
 
     loop {
-        tokio::select! {
-            maybe_notification = notifications.next() => {
-                let Some(notification) = maybe_notification else {
-                    break;
-                };
-                if notification.uuid == characteristic.uuid && read_tx.send(notification.value).is_err() {
-                    break;
+        let disconnected = tokio::select! {
+            request = &mut shutdown_rx => {
+                let result = close(&connection.peripheral, &connection.characteristic).await;
+                // A dropped transport has no one waiting for the result.
+                if let Ok(Some(reply)) = request {
+                    let _ = reply.send(result);
                 }
+                return Ok(());
             }
+            maybe_notification = connection.notifications.next() => match maybe_notification {
+                Some(notification) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        characteristic = %notification.uuid,
+                        len = notification.value.len(),
+                        "notified"
+                    );
+                    if notification.uuid == connection.characteristic.uuid
+                        && read_tx.send(notification.value).is_err()
+                    {
+                        break;
+                    }
+                    false
+                }
+                None => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("notification stream ended");
+                    true
+                }
+            },
             maybe_write = write_rx.recv() => {
                 let Some(data) = maybe_write else {
                     break;
                 };
-                if let Err(err) = peripheral.write(&characteristic, &data, write_type).await {
-                    return Err(err.into());
+                #[cfg(feature = "tracing")]
+                tracing::debug!(write_type = ?connection.write_type, len = data.len(), "writing");
+                match connection.write_frame(&data).await {
+                    Ok(()) => false,
+                    Err(err) if options.reconnect.is_none() => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %err, "write failed");
+                        return Err(err.into());
+                    }
+                    Err(_err) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_err, "write failed; reconnecting");
+                        unsent = Some(data);
+                        true
+                    }
+                }
+            }
+        };
+        if !disconnected {
+            continue;
+        }
+
+        let Some(policy) = options.reconnect else {
+            break;
+        };
+        let _ = connection.peripheral.disconnect().await;
+        let reconnected = tokio::select! {
+            reconnected = reconnect(service_uuid, rpc_uuid, &reconnect_options, &policy) => {
+                reconnected
+            }
+            request = &mut shutdown_rx => {
+                if let Ok(Some(reply)) = request {
+                    let _ = reply.send(Ok(()));
                 }
+                return Ok(());
             }
+        };
+        match reconnected {
+            Some(reconnected) => connection = reconnected,
+            None => return Ok(()),
+        }
+        if let Some(data) = unsent.take() {
+            connection.write_frame(&data).await?;
         }
     }
 
-    let _ = peripheral.disconnect().await;
+    let _ = close(&connection.peripheral, &connection.characteristic).await;
     Ok(())
 }
 
+/// Unsubscribes from the RPC characteristic and disconnects the peripheral.
+async fn close(
+    peripheral: &Peripheral,
+    characteristic: &Characteristic,
+) -> Result<(), BleTransportError> {
+    #[cfg(feature = "tracing")]
+    tracing::info!("disconnecting");
+    let unsubscribed = peripheral.unsubscribe(characteristic).await;
+    peripheral.disconnect().await?;
+    Ok(unsubscribed?)
+}
+
+/// Retries [`connect_and_subscribe`] with backoff, giving up after `policy.max_attempts`.
+async fn reconnect(
+    service_uuid: Uuid,
+    rpc_uuid: Uuid,
+    options: &BleConnectOptions,
+    policy: &ReconnectPolicy,
+) -> Option<BleConnection> {
+    for attempt in 0..policy.max_attempts {
+        tokio::time::sleep(policy.backoff(attempt)).await;
+        match connect_and_subscribe(service_uuid, rpc_uuid, options).await {
+            Ok(connection) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!(attempt, "reconnected");
+                return Some(connection);
+            }
+            #[cfg(feature = "tracing")]
+            Err(err) => tracing::warn!(attempt, error = %err, "reconnect attempt failed"),
+            #[cfg(not(feature = "tracing"))]
+            Err(_) => {}
+        }
+    }
+    #[cfg(feature = "tracing")]
+    tracing::warn!(attempts = policy.max_attempts, "giving up on reconnecting");
+    None
+}
+
+async fn connect_and_subscribe(
+    service_uuid: Uuid,
+    rpc_uuid: Uuid,
+    options: &BleConnectOptions,
+) -> Result<BleConnection, BleTransportError> {
+    let (peripheral, characteristic, write_type) =
+        connect_peripheral(service_uuid, rpc_uuid, options).await?;
+    // ZMK only lets bonded hosts use the Studio characteristic, so this is
+    // where an unpaired keyboard fails.
+    if let Err(err) = peripheral.subscribe(&characteristic).await {
+        if requires_pairing(&err) {
+            return Err(BleTransportError::PairingRequired {
+                device_id: peripheral.id().to_string(),
+                source: err,
+            });
+        }
+        return Err(err.into());
+    }
+    let notifications = peripheral.notifications().await?;
+    let mtu = options.mtu.unwrap_or(DEFAULT_ATT_MTU);
+    #[cfg(feature = "tracing")]
+    tracing::debug!(mtu, "write chunking");
+    Ok(BleConnection {
+        peripheral,
+        characteristic,
+        write_type,
+        chunk_size: write_chunk_size(mtu),
+        notifications,
+    })
+}
+
 async fn connect_peripheral(
     service_uuid: Uuid,
     rpc_uuid: Uuid,
     options: &BleConnectOptions,
 ) -> Result<(Peripheral, Characteristic, WriteType), BleTransportError> {
-    let manager = Manager::new().await?;
-    let adapters = manager.adapters().await?;
-    let adapter = adapters
+    let adapter = find_adapters(options.adapter.as_deref())
+        .await?
         .into_iter()
         .next()
         .ok_or(BleTransportError::NoAdapter)?;
 
+    let mut events = adapter.events().await?;
     adapter
         .start_scan(ScanFilter {
             services: vec![service_uuid],
         })
         .await?;
-    tokio::time::sleep(options.scan_timeout).await;
 
-    let peripheral = select_peripheral(&adapter, service_uuid, &options.device_id).await?;
+    let found = tokio::time::timeout(
+        options.scan_timeout,
+        wait_for_peripheral(&adapter, &mut events, service_uuid, options),
+    )
+    .await;
+    let _ = adapter.stop_scan().await;
+    let peripheral = match found {
+        Ok(result) => result?,
+        Err(_) => return Err(BleTransportError::DeviceNotFound(options.target())),
+    };
+
     peripheral.connect().await?;
     peripheral.discover_services().await?;
 
@@ -369,17 +1145,77 @@ async fn connect_peripheral(
     Ok((peripheral, characteristic, write_type))
 }
 
-async fn select_peripheral(
+/// Whether `err` is a GATT authentication or encryption failure, which
+/// btleplug reports differently on each platform.
+fn requires_pairing(err: &btleplug::Error) -> bool {
+    const HINTS: [&str; 6] = [
+        "authenticat",
+        "encrypt",
+        "pair",
+        "bond",
+        "notpermitted",
+        // Windows reports refused CCCD writes as a bare protocol error.
+        "protocolerror",
+    ];
+    let message = match err {
+        btleplug::Error::PermissionDenied => return true,
+        btleplug::Error::NotSupported(message) | btleplug::Error::RuntimeError(message) => {
+            message.clone()
+        }
+        btleplug::Error::Other(err) => err.to_string(),
+        _ => return false,
+    };
+    let message = message.to_lowercase().replace([' ', '_'], "");
+    HINTS.iter().any(|hint| message.contains(hint))
+}
+
+/// Returns the target peripheral as soon as the adapter reports it with the Studio service.
+async fn wait_for_peripheral(
     adapter: &Adapter,
+    events: &mut (impl Stream<Item = CentralEvent> + Unpin),
     service_uuid: Uuid,
-    device_id: &str,
+    options: &BleConnectOptions,
 ) -> Result<Peripheral, BleTransportError> {
-    let peripherals = adapter.peripherals().await?;
-    for peripheral in peripherals {
-        if peripheral.id().to_string() != device_id {
+    // The device may already be known from an earlier scan.
+    if let Ok(peripheral) = select_peripheral(adapter, service_uuid, options).await {
+        return Ok(peripheral);
+    }
+
+    while let Some(event) = events.next().await {
+        let id = match event {
+            CentralEvent::DeviceDiscovered(id)
+            | CentralEvent::DeviceUpdated(id)
+            | CentralEvent::ServicesAdvertisement { id, .. }
+            | CentralEvent::ServiceDataAdvertisement { id, .. } => id,
+            _ => continue,
+        };
+        if options.fallback.is_none()
+            && !options.selects_by_name()
+            && id.to_string() != options.device_id
+        {
             continue;
         }
+        match select_peripheral(adapter, service_uuid, options).await {
+            Ok(peripheral) => return Ok(peripheral),
+            Err(err @ BleTransportError::AmbiguousName { .. }) => return Err(err),
+            Err(_) => {}
+        }
+    }
 
+    Err(BleTransportError::DeviceNotFound(options.target()))
+}
+
+/// Picks the peripheral with the requested ID, or else the one matching the
+/// requested name or saved identity.
+async fn select_peripheral(
+    adapter: &Adapter,
+    service_uuid: Uuid,
+    options: &BleConnectOptions,
+) -> Result<Peripheral, BleTransportError> {
+    let peripherals = adapter.peripherals().await?;
+    let mut fallback = None;
+    let mut named = Vec::new();
+    for peripheral in peripherals {
         let Some(props) = peripheral.properties().await? else {
             continue;
         };
@@ -388,8 +1224,101 @@ async fn select_peripheral(
             continue;
         }
 
+        if peripheral.id().to_string() == options.device_id {
+            return Ok(peripheral);
+        }
+        if options.matches_name(props.local_name.as_deref()) {
+            named.push((peripheral, props));
+            continue;
+        }
+        let matches_saved = options.fallback.as_ref().is_some_and(|saved| {
+            saved.matches_advertisement(
+                props.local_name.as_deref(),
+                props.service_data.get(&service_uuid).map(Vec::as_slice),
+            )
+        });
+        if matches_saved && fallback.is_none() {
+            fallback = Some(peripheral);
+        }
+    }
+
+    if named.len() > 1 {
+        // A name that matches exactly wins over names that merely contain it.
+        let exact = options.name_contains.as_deref().map(|text| {
+            named
+                .iter()
+                .filter(|(_, props)| props.local_name.as_deref() == Some(text))
+                .count()
+        });
+        if exact == Some(1) {
+            let text = options.name_contains.as_deref();
+            named.retain(|(_, props)| props.local_name.as_deref() == text);
+        } else {
+            let adapter_name = adapter.adapter_info().await?;
+            let mut candidates = Vec::with_capacity(named.len());
+            for (peripheral, props) in named {
+                let is_connected = peripheral.is_connected().await.unwrap_or(false);
+                candidates.push(BleDeviceInfo::from_properties(
+                    &peripheral,
+                    props,
+                    &adapter_name,
+                    service_uuid,
+                    is_connected,
+                ));
+            }
+            return Err(BleTransportError::AmbiguousName {
+                query: options.target(),
+                candidates,
+            });
+        }
+    }
+    if let Some((peripheral, _)) = named.pop() {
         return Ok(peripheral);
     }
 
-    Err(BleTransportError::DeviceNotFound(device_id.to_string()))
+    fallback.ok_or_else(|| BleTransportError::DeviceNotFound(options.target()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BleConnectOptions, requires_pairing, write_chunk_size};
+
+    #[test]
+    fn matches_advertised_names() {
+        let options = BleConnectOptions::with_name("corne");
+        assert!(options.matches_name(Some("Corne Left")));
+        assert!(!options.matches_name(Some("Sofle")));
+        assert!(!options.matches_name(None));
+        assert_eq!(options.target(), "name containing \"corne\"");
+
+        let mut exact = BleConnectOptions::new("");
+        exact.name_equals = Some("Corne".to_string());
+        assert!(exact.matches_name(Some("Corne")));
+        assert!(!exact.matches_name(Some("Corne Left")));
+
+        assert!(!BleConnectOptions::new("id").matches_name(Some("Corne")));
+    }
+
+    #[test]
+    fn chunks_writes_to_the_att_payload() {
+        assert_eq!(write_chunk_size(23), 20);
+        assert_eq!(write_chunk_size(247), 244);
+        // Below the ATT minimum the MTU must be wrong; keep the minimum.
+        assert_eq!(write_chunk_size(0), 20);
+    }
+
+    #[test]
+    fn recognizes_pairing_failures() {
+        assert!(requires_pairing(&btleplug::Error::PermissionDenied));
+        assert!(requires_pairing(&btleplug::Error::NotSupported(
+            "ProtocolError".to_string()
+        )));
+        assert!(requires_pairing(&btleplug::Error::Other(
+            "org.bluez.Error.Failed: Insufficient Authentication".into()
+        )));
+        assert!(!requires_pairing(&btleplug::Error::NotConnected));
+        assert!(!requires_pairing(&btleplug::Error::Other(
+            "Communication Error:".into()
+        )));
+    }
 }
@@ -0,0 +1,185 @@
+use std::io::{Read, Write};
+
+use super::Transport;
+
+const DEFAULT_WRITE_CAPACITY: usize = 512;
+const DEFAULT_READ_CAPACITY: usize = 4096;
+
+/// Transport wrapper that coalesces writes and reads in large chunks.
+///
+/// Writes are collected until the next read, an explicit [`Write::flush`], or
+/// until the buffer would exceed its capacity, so consecutive frames reach the
+/// inner transport in as few calls as possible. Reads fill an internal buffer
+/// with as much as the inner transport returns and serve smaller reads from it.
+///
+/// On BLE each inner write becomes one characteristic write, so keep the write
+/// capacity at or below the negotiated MTU payload size there.
+pub struct BufferedTransport<T: Write> {
+    inner: T,
+    write_buffer: Vec<u8>,
+    write_capacity: usize,
+    read_buffer: Box<[u8]>,
+    read_pos: usize,
+    read_len: usize,
+}
+
+impl<T: Write> BufferedTransport<T> {
+    pub fn new(inner: T) -> Self {
+        Self::with_capacity(inner, DEFAULT_WRITE_CAPACITY, DEFAULT_READ_CAPACITY)
+    }
+
+    pub fn with_capacity(inner: T, write_capacity: usize, read_capacity: usize) -> Self {
+        Self {
+            inner,
+            write_buffer: Vec::with_capacity(write_capacity),
+            write_capacity: write_capacity.max(1),
+            read_buffer: vec![0; read_capacity.max(1)].into_boxed_slice(),
+            read_pos: 0,
+            read_len: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    fn flush_writes(&mut self) -> std::io::Result<()> {
+        if !self.write_buffer.is_empty() {
+            self.inner.write_all(&self.write_buffer)?;
+            self.write_buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+impl<T: Transport> Transport for BufferedTransport<T> {
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Drops any buffered bytes and reconnects the inner transport.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        self.write_buffer.clear();
+        self.read_pos = 0;
+        self.read_len = 0;
+        self.inner.reconnect()
+    }
+
+    fn read_chunk_size(&self) -> usize {
+        self.read_buffer.len()
+    }
+}
+
+impl<T: Read + Write> Read for BufferedTransport<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // The device will not answer requests that are still sitting in our buffer.
+        self.flush_writes()?;
+
+        if self.read_pos == self.read_len {
+            // Large reads bypass the buffer entirely.
+            if buf.len() >= self.read_buffer.len() {
+                return self.inner.read(buf);
+            }
+            self.read_len = self.inner.read(&mut self.read_buffer)?;
+            self.read_pos = 0;
+        }
+
+        let available = &self.read_buffer[self.read_pos..self.read_len];
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.read_pos += count;
+        Ok(count)
+    }
+}
+
+impl<T: Write> Write for BufferedTransport<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.write_buffer.len() + buf.len() > self.write_capacity {
+            self.flush_writes()?;
+        }
+        if buf.len() >= self.write_capacity {
+            return self.inner.write(buf);
+        }
+        self.write_buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flush_writes()?;
+        self.inner.flush()
+    }
+}
+
+impl<T: Write> Drop for BufferedTransport<T> {
+    fn drop(&mut self) {
+        let _ = self.flush_writes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Write};
+
+    use super::BufferedTransport;
+
+    /// Records every write call separately and serves reads from a fixed input.
+    struct Recorder {
+        input: Cursor<Vec<u8>>,
+        writes: Vec<Vec<u8>>,
+    }
+
+    impl Read for Recorder {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes.push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn coalesces_writes_until_read() {
+        let recorder = Recorder {
+            input: Cursor::new(vec![1, 2, 3, 4, 5]),
+            writes: Vec::new(),
+        };
+        let mut transport = BufferedTransport::with_capacity(recorder, 8, 4);
+
+        transport.write_all(&[0xAB, 1, 0xAD]).unwrap();
+        transport.write_all(&[0xAB, 2, 0xAD]).unwrap();
+        assert!(transport.get_ref().writes.is_empty());
+
+        let mut byte = [0; 1];
+        transport.read_exact(&mut byte).unwrap();
+        assert_eq!(byte, [1]);
+        assert_eq!(
+            transport.get_ref().writes,
+            vec![vec![0xAB, 1, 0xAD, 0xAB, 2, 0xAD]]
+        );
+        assert_eq!(transport.get_ref().input.position(), 4);
+
+        let mut rest = Vec::new();
+        transport.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, [2, 3, 4, 5]);
+
+        transport.write_all(&[0xAB, 3, 0xAD]).unwrap();
+        transport.flush().unwrap();
+        assert_eq!(transport.get_ref().writes.len(), 2);
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::{Read, Write};
+
+use embedded_io::ErrorKind;
+
+use super::Transport;
+
+/// Adapts an [`embedded_io`] UART (or any other byte stream) for use with
+/// [`StudioClient`](crate::StudioClient).
+///
+/// This lets a host with `std` available (for example an ESP-IDF dongle or an
+/// embedded Linux board) configure a keyboard over UART using the HAL's
+/// blocking `embedded-io` implementation. Reads should return after a short
+/// timeout rather than block forever, like the serial transport does.
+pub struct EmbeddedIoTransport<T> {
+    inner: T,
+}
+
+impl<T> EmbeddedIoTransport<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: embedded_io::Read + embedded_io::Write> Transport for EmbeddedIoTransport<T> {
+    fn description(&self) -> String {
+        String::from("embedded-io stream")
+    }
+}
+
+impl<T: embedded_io::Read> Read for EmbeddedIoTransport<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf).map_err(to_io_error)
+    }
+}
+
+impl<T: embedded_io::Write> Write for EmbeddedIoTransport<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf).map_err(to_io_error)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush().map_err(to_io_error)
+    }
+}
+
+fn to_io_error(err: impl embedded_io::Error) -> std::io::Error {
+    let kind = match err.kind() {
+        ErrorKind::TimedOut => std::io::ErrorKind::TimedOut,
+        ErrorKind::Interrupted => std::io::ErrorKind::Interrupted,
+        ErrorKind::InvalidInput => std::io::ErrorKind::InvalidInput,
+        ErrorKind::InvalidData => std::io::ErrorKind::InvalidData,
+        ErrorKind::BrokenPipe => std::io::ErrorKind::BrokenPipe,
+        ErrorKind::NotConnected => std::io::ErrorKind::NotConnected,
+        ErrorKind::WriteZero => std::io::ErrorKind::WriteZero,
+        ErrorKind::Unsupported => std::io::ErrorKind::Unsupported,
+        ErrorKind::OutOfMemory => std::io::ErrorKind::OutOfMemory,
+        _ => std::io::ErrorKind::Other,
+    };
+    std::io::Error::new(kind, format!("{err:?}"))
+}
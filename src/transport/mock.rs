@@ -0,0 +1,1223 @@
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+
+use crate::proto::zmk::studio::{
+    Notification, Request, RequestResponse, notification, request, request_response,
+};
+use crate::proto::zmk::{behaviors, core, keymap, meta};
+use crate::protocol::{
+    FrameDecoder, decode_requests, encode_notification, encode_request_response,
+};
+use crate::transport::Transport;
+
+/// Behaviors exposed by a default [`MockDevice`], as `(id, display_name)`.
+const DEFAULT_BEHAVIORS: &[(u32, &str)] = &[
+    (1, "Key Press"),
+    (2, "Transparent"),
+    (3, "None"),
+    (4, "Momentary Layer"),
+    (5, "Layer-Tap"),
+    (6, "Mod-Tap"),
+    (7, "Toggle Layer"),
+];
+
+const DEFAULT_KEY_COUNT: usize = 4;
+const DEFAULT_AVAILABLE_LAYERS: u32 = 4;
+const MAX_LAYER_NAME_LENGTH: u32 = 20;
+
+/// In-memory keyboard that answers Studio RPC requests, for tests without hardware.
+///
+/// Requests written to the device are answered immediately; the framed
+/// responses (and any notifications) are returned by subsequent reads. A read
+/// with nothing pending fails with [`std::io::ErrorKind::TimedOut`], like a
+/// serial port whose read timeout expired.
+///
+/// The default device is unlocked and has two layers of four keys bound to
+/// `&kp A`..`&kp D` and `&trans`, on a single 2x2 physical layout.
+#[derive(Debug)]
+pub struct MockDevice {
+    decoder: FrameDecoder,
+    outgoing: VecDeque<u8>,
+    name: String,
+    serial_number: Vec<u8>,
+    locked: bool,
+    behaviors: Vec<behaviors::GetBehaviorDetailsResponse>,
+    keymap: keymap::Keymap,
+    saved_keymap: keymap::Keymap,
+    layouts: keymap::PhysicalLayouts,
+    removed_layers: Vec<keymap::Layer>,
+    next_layer_id: u32,
+    dropped_responses: usize,
+    delayed_responses: usize,
+    /// Delayed responses, sent after the next read times out.
+    late: Vec<u8>,
+    notify_first: bool,
+    fail_write_after: Option<usize>,
+    unplug_after: Option<usize>,
+    unplugged: bool,
+}
+
+impl Default for MockDevice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockDevice {
+    pub fn new() -> Self {
+        let key_press = |usage: u32| keymap::BehaviorBinding {
+            behavior_id: 1,
+            param1: usage,
+            param2: 0,
+        };
+        let transparent = keymap::BehaviorBinding {
+            behavior_id: 2,
+            param1: 0,
+            param2: 0,
+        };
+        let keymap = keymap::Keymap {
+            layers: vec![
+                keymap::Layer {
+                    id: 0,
+                    name: "Base".to_string(),
+                    bindings: (0..DEFAULT_KEY_COUNT as u32)
+                        .map(|offset| key_press(0x0007_0004 + offset))
+                        .collect(),
+                },
+                keymap::Layer {
+                    id: 1,
+                    name: "Lower".to_string(),
+                    bindings: vec![transparent; DEFAULT_KEY_COUNT],
+                },
+            ],
+            available_layers: DEFAULT_AVAILABLE_LAYERS,
+            max_layer_name_length: MAX_LAYER_NAME_LENGTH,
+        };
+        let key = |x: i32, y: i32| keymap::KeyPhysicalAttrs {
+            width: 100,
+            height: 100,
+            x,
+            y,
+            r: 0,
+            rx: 0,
+            ry: 0,
+        };
+
+        Self {
+            decoder: FrameDecoder::new(),
+            outgoing: VecDeque::new(),
+            name: "Mock Keyboard".to_string(),
+            serial_number: vec![0x4d, 0x4f, 0x43, 0x4b],
+            locked: false,
+            behaviors: DEFAULT_BEHAVIORS
+                .iter()
+                .map(
+                    |&(id, display_name)| behaviors::GetBehaviorDetailsResponse {
+                        id,
+                        display_name: display_name.to_string(),
+                        metadata: Vec::new(),
+                    },
+                )
+                .collect(),
+            saved_keymap: keymap.clone(),
+            removed_layers: Vec::new(),
+            next_layer_id: keymap.layers.len() as u32,
+            dropped_responses: 0,
+            delayed_responses: 0,
+            late: Vec::new(),
+            notify_first: false,
+            fail_write_after: None,
+            unplug_after: None,
+            unplugged: false,
+            keymap,
+            layouts: keymap::PhysicalLayouts {
+                active_layout_index: 0,
+                layouts: vec![keymap::PhysicalLayout {
+                    name: "Default".to_string(),
+                    keys: vec![key(0, 0), key(100, 0), key(0, 100), key(100, 100)],
+                }],
+            },
+        }
+    }
+
+    /// Replaces the behavior catalog with `behaviors`, given as `(id, display_name)`.
+    ///
+    /// Existing bindings are kept as they are, even if they refer to behavior
+    /// IDs that no longer exist.
+    pub fn with_behaviors<'a>(
+        mut self,
+        behaviors: impl IntoIterator<Item = (u32, &'a str)>,
+    ) -> Self {
+        self.behaviors = behaviors
+            .into_iter()
+            .map(|(id, display_name)| behaviors::GetBehaviorDetailsResponse {
+                id,
+                display_name: display_name.to_string(),
+                metadata: Vec::new(),
+            })
+            .collect();
+        self
+    }
+
+    /// Replaces the behavior catalog with full behavior details, including
+    /// parameter metadata.
+    pub fn with_behavior_details(
+        mut self,
+        behaviors: impl IntoIterator<Item = behaviors::GetBehaviorDetailsResponse>,
+    ) -> Self {
+        self.behaviors = behaviors.into_iter().collect();
+        self
+    }
+
+    /// Replaces the keymap, as if it had just been saved.
+    ///
+    /// Each layer should have one binding per key of the physical layouts.
+    pub fn with_keymap(mut self, keymap: keymap::Keymap) -> Self {
+        self.next_layer_id = keymap
+            .layers
+            .iter()
+            .map(|layer| layer.id + 1)
+            .max()
+            .unwrap_or(0);
+        self.saved_keymap = keymap.clone();
+        self.keymap = keymap;
+        self
+    }
+
+    /// Replaces the physical layouts.
+    pub fn with_physical_layouts(mut self, layouts: keymap::PhysicalLayouts) -> Self {
+        self.layouts = layouts;
+        self
+    }
+
+    /// Whether Studio is locked; locked devices reject keymap and behavior requests.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Locks or unlocks the device, as pressing `&studio_unlock` would, and notifies the client.
+    pub fn set_locked(&mut self, locked: bool) {
+        if self.locked != locked {
+            self.locked = locked;
+            self.notify_lock_state();
+        }
+    }
+
+    /// Handles the next `count` requests without sending their responses, like
+    /// frames lost in transit.
+    pub fn drop_next_responses(&mut self, count: usize) {
+        self.dropped_responses = count;
+    }
+
+    /// Holds back the responses to the next `count` requests until a read
+    /// has timed out, like a device that answers too slowly.
+    pub fn delay_next_responses(&mut self, count: usize) {
+        self.delayed_responses = count;
+    }
+
+    /// Sends the unsaved-changes notification caused by a request ahead of
+    /// its response instead of after it.
+    pub fn notify_before_responding(&mut self) {
+        self.notify_first = true;
+    }
+
+    /// Handles the next `count` requests, then fails the write carrying the
+    /// one after, once, without otherwise disturbing the device.
+    pub fn fail_write_after(&mut self, count: usize) {
+        self.fail_write_after = Some(count);
+    }
+
+    /// Handles the next `count` requests, then behaves like an unplugged
+    /// device until [`Transport::reconnect`]: reads return EOF and writes fail.
+    pub fn unplug_after(&mut self, count: usize) {
+        self.unplug_after = Some(count);
+    }
+
+    /// The live (possibly unsaved) keymap.
+    pub fn keymap(&self) -> &keymap::Keymap {
+        &self.keymap
+    }
+
+    /// The keymap as of the last save.
+    pub fn saved_keymap(&self) -> &keymap::Keymap {
+        &self.saved_keymap
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.keymap != self.saved_keymap
+    }
+
+    fn lock_state(&self) -> core::LockState {
+        if self.locked {
+            core::LockState::ZmkStudioCoreLockStateLocked
+        } else {
+            core::LockState::ZmkStudioCoreLockStateUnlocked
+        }
+    }
+
+    fn notify_lock_state(&mut self) {
+        let frame = encode_notification(Notification {
+            subsystem: Some(notification::Subsystem::Core(core::Notification {
+                notification_type: Some(core::notification::NotificationType::LockStateChanged(
+                    self.lock_state().into(),
+                )),
+            })),
+        });
+        self.outgoing.extend(frame);
+    }
+
+    fn notify_unsaved_changes(&mut self, had_unsaved_changes: bool) {
+        let unsaved = self.has_unsaved_changes();
+        if unsaved == had_unsaved_changes {
+            return;
+        }
+        let frame = encode_notification(Notification {
+            subsystem: Some(notification::Subsystem::Keymap(keymap::Notification {
+                notification_type: Some(
+                    keymap::notification::NotificationType::UnsavedChangesStatusChanged(unsaved),
+                ),
+            })),
+        });
+        self.outgoing.extend(frame);
+    }
+
+    fn handle(&mut self, request: Request) {
+        let had_unsaved_changes = self.has_unsaved_changes();
+        let subsystem = match request.subsystem {
+            Some(request::Subsystem::Core(core)) => self.handle_core(core),
+            Some(request::Subsystem::Behaviors(_) | request::Subsystem::Keymap(_))
+                if self.locked =>
+            {
+                simple_error(meta::ErrorConditions::UnlockRequired)
+            }
+            Some(request::Subsystem::Behaviors(behaviors)) => self.handle_behaviors(behaviors),
+            Some(request::Subsystem::Keymap(keymap)) => self.handle_keymap(keymap),
+            None => simple_error(meta::ErrorConditions::RpcNotFound),
+        };
+        if self.notify_first {
+            self.notify_unsaved_changes(had_unsaved_changes);
+        }
+        if self.dropped_responses > 0 {
+            self.dropped_responses -= 1;
+        } else {
+            let frame = encode_request_response(RequestResponse {
+                request_id: request.request_id,
+                subsystem: Some(subsystem),
+            });
+            if self.delayed_responses > 0 {
+                self.delayed_responses -= 1;
+                self.late.extend(frame);
+            } else {
+                self.outgoing.extend(frame);
+            }
+        }
+        if !self.notify_first {
+            self.notify_unsaved_changes(had_unsaved_changes);
+        }
+    }
+
+    fn handle_core(&mut self, request: core::Request) -> request_response::Subsystem {
+        use core::request::RequestType;
+        use core::response::ResponseType;
+
+        let response = match request.request_type {
+            Some(RequestType::GetDeviceInfo(_)) => {
+                ResponseType::GetDeviceInfo(core::GetDeviceInfoResponse {
+                    name: self.name.clone(),
+                    serial_number: self.serial_number.clone(),
+                })
+            }
+            Some(RequestType::GetLockState(_)) => {
+                ResponseType::GetLockState(self.lock_state().into())
+            }
+            Some(RequestType::Lock(_)) => {
+                self.set_locked(true);
+                ResponseType::GetLockState(self.lock_state().into())
+            }
+            Some(RequestType::ResetSettings(_)) => {
+                self.keymap = self.saved_keymap.clone();
+                ResponseType::ResetSettings(true)
+            }
+            None => return simple_error(meta::ErrorConditions::RpcNotFound),
+        };
+        request_response::Subsystem::Core(core::Response {
+            response_type: Some(response),
+        })
+    }
+
+    fn handle_behaviors(&mut self, request: behaviors::Request) -> request_response::Subsystem {
+        use behaviors::request::RequestType;
+        use behaviors::response::ResponseType;
+
+        let response = match request.request_type {
+            Some(RequestType::ListAllBehaviors(_)) => {
+                ResponseType::ListAllBehaviors(behaviors::ListAllBehaviorsResponse {
+                    behaviors: self.behaviors.iter().map(|b| b.id).collect(),
+                })
+            }
+            Some(RequestType::GetBehaviorDetails(details)) => {
+                match self.behaviors.iter().find(|b| b.id == details.behavior_id) {
+                    Some(behavior) => ResponseType::GetBehaviorDetails(behavior.clone()),
+                    None => return simple_error(meta::ErrorConditions::Generic),
+                }
+            }
+            None => return simple_error(meta::ErrorConditions::RpcNotFound),
+        };
+        request_response::Subsystem::Behaviors(behaviors::Response {
+            response_type: Some(response),
+        })
+    }
+
+    fn handle_keymap(&mut self, request: keymap::Request) -> request_response::Subsystem {
+        use keymap::request::RequestType;
+        use keymap::response::ResponseType;
+
+        let response = match request.request_type {
+            Some(RequestType::GetKeymap(_)) => ResponseType::GetKeymap(self.keymap.clone()),
+            Some(RequestType::SetLayerBinding(set)) => {
+                ResponseType::SetLayerBinding(self.set_layer_binding(set).into())
+            }
+            Some(RequestType::CheckUnsavedChanges(_)) => {
+                ResponseType::CheckUnsavedChanges(self.has_unsaved_changes())
+            }
+            Some(RequestType::SaveChanges(_)) => {
+                self.saved_keymap = self.keymap.clone();
+                ResponseType::SaveChanges(keymap::SaveChangesResponse {
+                    result: Some(keymap::save_changes_response::Result::Ok(true)),
+                })
+            }
+            Some(RequestType::DiscardChanges(_)) => {
+                let discarded = self.has_unsaved_changes();
+                self.keymap = self.saved_keymap.clone();
+                ResponseType::DiscardChanges(discarded)
+            }
+            Some(RequestType::GetPhysicalLayouts(_)) => {
+                ResponseType::GetPhysicalLayouts(self.layouts.clone())
+            }
+            Some(RequestType::SetActivePhysicalLayout(index)) => {
+                use keymap::set_active_physical_layout_response::Result;
+                let result = if (index as usize) < self.layouts.layouts.len() {
+                    self.layouts.active_layout_index = index;
+                    Result::Ok(self.keymap.clone())
+                } else {
+                    Result::Err(
+                        keymap::SetActivePhysicalLayoutErrorCode::SetActivePhysicalLayoutErrInvalidLayoutIndex
+                            .into(),
+                    )
+                };
+                ResponseType::SetActivePhysicalLayout(keymap::SetActivePhysicalLayoutResponse {
+                    result: Some(result),
+                })
+            }
+            Some(RequestType::MoveLayer(move_layer)) => {
+                ResponseType::MoveLayer(self.move_layer(move_layer))
+            }
+            Some(RequestType::AddLayer(_)) => ResponseType::AddLayer(self.add_layer()),
+            Some(RequestType::RemoveLayer(remove)) => {
+                ResponseType::RemoveLayer(self.remove_layer(remove))
+            }
+            Some(RequestType::RestoreLayer(restore)) => {
+                ResponseType::RestoreLayer(self.restore_layer(restore))
+            }
+            Some(RequestType::SetLayerProps(props)) => {
+                ResponseType::SetLayerProps(self.set_layer_props(props).into())
+            }
+            None => return simple_error(meta::ErrorConditions::RpcNotFound),
+        };
+        request_response::Subsystem::Keymap(keymap::Response {
+            response_type: Some(response),
+        })
+    }
+
+    fn set_layer_binding(
+        &mut self,
+        request: keymap::SetLayerBindingRequest,
+    ) -> keymap::SetLayerBindingResponse {
+        let Some(binding) = request.binding else {
+            return keymap::SetLayerBindingResponse::SetLayerBindingRespInvalidParameters;
+        };
+        if !self
+            .behaviors
+            .iter()
+            .any(|b| i32::try_from(b.id).is_ok_and(|id| id == binding.behavior_id))
+        {
+            return keymap::SetLayerBindingResponse::SetLayerBindingRespInvalidBehavior;
+        }
+        let slot = usize::try_from(request.key_position)
+            .ok()
+            .and_then(|position| {
+                self.keymap
+                    .layers
+                    .iter_mut()
+                    .find(|layer| layer.id == request.layer_id)?
+                    .bindings
+                    .get_mut(position)
+            });
+        match slot {
+            Some(slot) => {
+                *slot = binding;
+                keymap::SetLayerBindingResponse::SetLayerBindingRespOk
+            }
+            None => keymap::SetLayerBindingResponse::SetLayerBindingRespInvalidLocation,
+        }
+    }
+
+    fn move_layer(&mut self, request: keymap::MoveLayerRequest) -> keymap::MoveLayerResponse {
+        use keymap::move_layer_response::Result;
+
+        let len = self.keymap.layers.len();
+        let (start, dest) = (request.start_index as usize, request.dest_index as usize);
+        let result = if start >= len {
+            Result::Err(keymap::MoveLayerErrorCode::MoveLayerErrInvalidLayer.into())
+        } else if dest >= len {
+            Result::Err(keymap::MoveLayerErrorCode::MoveLayerErrInvalidDestination.into())
+        } else {
+            let layer = self.keymap.layers.remove(start);
+            self.keymap.layers.insert(dest, layer);
+            Result::Ok(self.keymap.clone())
+        };
+        keymap::MoveLayerResponse {
+            result: Some(result),
+        }
+    }
+
+    fn add_layer(&mut self) -> keymap::AddLayerResponse {
+        use keymap::add_layer_response::Result;
+
+        let result = if self.keymap.layers.len() >= self.keymap.available_layers as usize {
+            Result::Err(keymap::AddLayerErrorCode::AddLayerErrNoSpace.into())
+        } else {
+            let layer = keymap::Layer {
+                id: self.next_layer_id,
+                name: String::new(),
+                bindings: vec![keymap::BehaviorBinding::default(); self.key_count()],
+            };
+            self.next_layer_id += 1;
+            self.keymap.layers.push(layer.clone());
+            Result::Ok(keymap::AddLayerResponseDetails {
+                index: (self.keymap.layers.len() - 1) as u32,
+                layer: Some(layer),
+            })
+        };
+        keymap::AddLayerResponse {
+            result: Some(result),
+        }
+    }
+
+    fn remove_layer(&mut self, request: keymap::RemoveLayerRequest) -> keymap::RemoveLayerResponse {
+        use keymap::remove_layer_response::Result;
+
+        let index = request.layer_index as usize;
+        let result = if index < self.keymap.layers.len() {
+            let layer = self.keymap.layers.remove(index);
+            self.removed_layers.push(layer);
+            Result::Ok(keymap::RemoveLayerOk {})
+        } else {
+            Result::Err(keymap::RemoveLayerErrorCode::RemoveLayerErrInvalidIndex.into())
+        };
+        keymap::RemoveLayerResponse {
+            result: Some(result),
+        }
+    }
+
+    fn restore_layer(
+        &mut self,
+        request: keymap::RestoreLayerRequest,
+    ) -> keymap::RestoreLayerResponse {
+        use keymap::restore_layer_response::Result;
+
+        let index = request.at_index as usize;
+        let removed = self
+            .removed_layers
+            .iter()
+            .position(|layer| layer.id == request.layer_id);
+        let result = match removed {
+            None => Result::Err(keymap::RestoreLayerErrorCode::RestoreLayerErrInvalidId.into()),
+            Some(_) if index > self.keymap.layers.len() => {
+                Result::Err(keymap::RestoreLayerErrorCode::RestoreLayerErrInvalidIndex.into())
+            }
+            Some(removed) => {
+                let layer = self.removed_layers.remove(removed);
+                self.keymap.layers.insert(index, layer.clone());
+                Result::Ok(layer)
+            }
+        };
+        keymap::RestoreLayerResponse {
+            result: Some(result),
+        }
+    }
+
+    fn set_layer_props(
+        &mut self,
+        request: keymap::SetLayerPropsRequest,
+    ) -> keymap::SetLayerPropsResponse {
+        match self
+            .keymap
+            .layers
+            .iter_mut()
+            .find(|layer| layer.id == request.layer_id)
+        {
+            Some(layer) => {
+                layer.name = request.name;
+                keymap::SetLayerPropsResponse::SetLayerPropsRespOk
+            }
+            None => keymap::SetLayerPropsResponse::SetLayerPropsRespErrInvalidId,
+        }
+    }
+
+    fn key_count(&self) -> usize {
+        let index = self.layouts.active_layout_index as usize;
+        self.layouts
+            .layouts
+            .get(index)
+            .map_or(0, |layout| layout.keys.len())
+    }
+}
+
+fn simple_error(condition: meta::ErrorConditions) -> request_response::Subsystem {
+    request_response::Subsystem::Meta(meta::Response {
+        response_type: Some(meta::response::ResponseType::SimpleError(condition.into())),
+    })
+}
+
+impl Transport for MockDevice {
+    fn description(&self) -> String {
+        format!("mock device {:?}", self.name)
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.unplugged
+    }
+
+    /// Plugs the device back in, keeping its keymap and lock state.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        self.unplugged = false;
+        self.decoder = FrameDecoder::new();
+        self.outgoing.clear();
+        Ok(())
+    }
+
+    /// Accepted and ignored: reads never wait.
+    fn set_read_timeout(&mut self, _timeout: std::time::Duration) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for MockDevice {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.unplugged {
+            return Ok(0);
+        }
+        if self.outgoing.is_empty() {
+            self.outgoing.extend(self.late.drain(..));
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "Timed out waiting for data",
+            ));
+        }
+        let len = buf.len().min(self.outgoing.len());
+        for (slot, byte) in buf.iter_mut().zip(self.outgoing.drain(..len)) {
+            *slot = byte;
+        }
+        Ok(len)
+    }
+}
+
+impl Write for MockDevice {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let requests = decode_requests(&mut self.decoder, buf)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        for request in requests {
+            if self.unplug_after == Some(0) {
+                self.unplug_after = None;
+                self.unplugged = true;
+                self.outgoing.clear();
+            }
+            if self.unplugged {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "Device was unplugged",
+                ));
+            }
+            if let Some(count) = &mut self.unplug_after {
+                *count -= 1;
+            }
+            match &mut self.fail_write_after {
+                Some(0) => {
+                    self.fail_write_after = None;
+                    return Err(std::io::Error::other("Write failed"));
+                }
+                Some(count) => *count -= 1,
+                None => {}
+            }
+            self.handle(request);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::MockDevice;
+    use crate::framing::encode_frame;
+    use crate::notification::Notification;
+    use crate::notification::UnknownNotification;
+    use crate::privacy::SerialRedaction;
+    use crate::proto::zmk::{core, studio};
+    use crate::protocol::{encode_notification, encode_request};
+    use crate::transport::recording::ReplayTransport;
+    use crate::{
+        Behavior, ClientError, HidUsage, Keycode, LayerReferenceMode, RetryPolicy, StudioClient,
+    };
+
+    /// A device that answers the `n`th core request with the `n`th frame of
+    /// `replies`, each given as its encoded payload.
+    fn newer_firmware(replies: &[(core::request::RequestType, Vec<u8>)]) -> ReplayTransport {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        };
+        let mut capture = String::new();
+        for (request_id, (request_type, reply)) in (0..).zip(replies) {
+            let request = encode_request(&studio::Request {
+                request_id,
+                subsystem: Some(studio::request::Subsystem::Core(core::Request {
+                    request_type: Some(*request_type),
+                })),
+            });
+            capture += &format!("write {}\nread {}\n", hex(&request), hex(reply));
+        }
+        ReplayTransport::from_capture(&capture).expect("valid capture")
+    }
+
+    /// Encodes a length-delimited protobuf field holding `value`.
+    fn field(tag: u8, value: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag << 3 | 2, value.len() as u8];
+        encoded.extend(value);
+        encoded
+    }
+
+    #[test]
+    fn serves_a_studio_client() {
+        let mut client = StudioClient::new(MockDevice::new());
+        assert_eq!(
+            client.get_device_info().expect("info").name,
+            "Mock Keyboard"
+        );
+        assert_eq!(
+            client.get_key_at(0, 0).expect("binding"),
+            Behavior::KeyPress(HidUsage::from_encoded(Keycode::A.to_hid_usage()))
+        );
+
+        client
+            .set_key_at(1, 2, Behavior::MomentaryLayer { layer_id: 0 })
+            .expect("set binding");
+        assert_eq!(
+            client.get_key_at(1, 2).expect("binding"),
+            Behavior::MomentaryLayer { layer_id: 0 }
+        );
+        assert!(client.check_unsaved_changes().expect("status"));
+        client.save_changes().expect("save");
+        assert!(!client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn sets_keys_in_a_batch_with_per_key_results() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let results = client
+            .set_keys(&[
+                (1, 0, Behavior::Transparent),
+                (1, 9, Behavior::None),
+                (
+                    1,
+                    1,
+                    Behavior::Bluetooth {
+                        command: 0,
+                        value: 0,
+                    },
+                ),
+                (1, 2, Behavior::MomentaryLayer { layer_id: 0 }),
+            ])
+            .expect("batch");
+
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(ClientError::SetLayerBindingFailed(_))
+        ));
+        assert!(matches!(
+            results[2],
+            Err(ClientError::MissingBehaviorRole("Bluetooth"))
+        ));
+        assert!(results[3].is_ok());
+        assert_eq!(
+            client.get_key_at(1, 2).expect("binding"),
+            Behavior::MomentaryLayer { layer_id: 0 }
+        );
+        assert!(client.check_unsaved_changes().expect("status"));
+    }
+
+    #[test]
+    fn decodes_device_info() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let info = client.device_info().expect("info");
+        assert_eq!(info.name, "Mock Keyboard");
+        assert_eq!(info.serial_number, "4d4f434b");
+
+        client.set_serial_redaction(SerialRedaction::Truncate { visible: 2 });
+        assert_eq!(
+            client.device_info().expect("info").serial_number,
+            "******4b"
+        );
+    }
+
+    #[test]
+    fn uses_a_configured_behavior_catalog() {
+        let device = MockDevice::new().with_behaviors([(10, "Key Press"), (11, "Transparent")]);
+        let mut client = StudioClient::new(device);
+
+        client
+            .set_key_at(0, 0, Behavior::Transparent)
+            .expect("set binding");
+        assert_eq!(
+            client.get_keymap().expect("keymap").layers[0].bindings[0].behavior_id,
+            11
+        );
+        assert!(matches!(
+            client.set_key_at(0, 1, Behavior::None),
+            Err(ClientError::MissingBehaviorRole("None"))
+        ));
+    }
+
+    #[test]
+    fn delivers_typed_notifications_to_subscribers() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let notifications = client.subscribe_notifications();
+
+        client
+            .set_key_at(0, 0, Behavior::Transparent)
+            .expect("set binding");
+        // The device sends the notification after the response.
+        assert_eq!(client.poll_notifications().expect("poll"), 1);
+        assert_eq!(
+            notifications.try_recv(),
+            Ok(Notification::UnsavedChangesStatusChanged(true))
+        );
+        assert_eq!(client.poll_notifications().expect("poll"), 0);
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[test]
+    fn passes_core_responses_through() {
+        let mut client = StudioClient::new(MockDevice::new());
+        let response = client
+            .call_core(core::request::RequestType::GetLockState(true))
+            .expect("core call");
+        assert_eq!(
+            response.response_type,
+            Some(core::response::ResponseType::GetLockState(
+                core::LockState::ZmkStudioCoreLockStateUnlocked.into()
+            ))
+        );
+    }
+
+    #[test]
+    fn tolerates_unknown_enum_values_from_newer_firmware() {
+        let lock_state = |state| core::response::ResponseType::GetLockState(state);
+        let response = |request_id, response_type| {
+            crate::protocol::encode_request_response(studio::RequestResponse {
+                request_id,
+                subsystem: Some(studio::request_response::Subsystem::Core(core::Response {
+                    response_type: Some(response_type),
+                })),
+            })
+        };
+        let mut unknown_then_known = encode_notification(studio::Notification {
+            subsystem: Some(studio::notification::Subsystem::Core(core::Notification {
+                notification_type: Some(core::notification::NotificationType::LockStateChanged(7)),
+            })),
+        });
+        unknown_then_known.extend(response(1, lock_state(0)));
+        let replies = [
+            (
+                core::request::RequestType::GetLockState(true),
+                response(0, lock_state(7)),
+            ),
+            (
+                core::request::RequestType::GetLockState(true),
+                unknown_then_known,
+            ),
+        ];
+        let mut device = newer_firmware(&replies);
+        let mut client = StudioClient::new(&mut device);
+        let notifications = client.subscribe_notifications();
+
+        assert!(matches!(
+            client.get_lock_state(),
+            Err(ClientError::UnknownEnumValue {
+                field: "core.get_lock_state",
+                value: 7
+            })
+        ));
+        assert_eq!(
+            client.get_lock_state().expect("lock state"),
+            core::LockState::ZmkStudioCoreLockStateLocked
+        );
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(Notification::Unknown(UnknownNotification { .. }))
+        ));
+    }
+
+    #[test]
+    fn keeps_the_bytes_of_unknown_responses_and_notifications() {
+        // A new core notification type (field 9) and a response from a new
+        // subsystem (field 12), neither of which the bundled protos know.
+        let notification = field(2, &field(2, &field(9, &[0x01])));
+        let request_response = [vec![0x08, 0x00], field(12, &[0x08, 0x01])].concat();
+        let mut reply = encode_frame(&notification);
+        reply.extend(encode_frame(&field(1, &request_response)));
+        let mut device =
+            newer_firmware(&[(core::request::RequestType::GetDeviceInfo(true), reply)]);
+        let mut client = StudioClient::new(&mut device);
+        let notifications = client.subscribe_notifications();
+
+        let raw = client
+            .call_raw(studio::request::Subsystem::Core(core::Request {
+                request_type: Some(core::request::RequestType::GetDeviceInfo(true)),
+            }))
+            .expect("raw call");
+        assert_eq!(raw.response.subsystem, None);
+        assert_eq!(raw.bytes, request_response);
+        match notifications.try_recv() {
+            Ok(Notification::Unknown(unknown)) => assert_eq!(unknown.bytes, &notification[2..]),
+            other => panic!("expected an unknown notification, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn classifies_client_errors() {
+        let mut device = MockDevice::new();
+        device.set_locked(true);
+        let mut client = StudioClient::new(device);
+        let err = client.get_keymap().expect_err("locked");
+        assert!(err.is_locked());
+        assert!(!err.is_transient());
+        assert_eq!(err.meta_condition(), Some(1));
+
+        assert!(ClientError::Timeout.is_timeout());
+        assert!(ClientError::Timeout.is_transient());
+        assert!(!ClientError::Disconnected("serial COM3".to_string()).is_transient());
+        assert!(ClientError::MissingBehaviorRole("KeyPress").is_unsupported());
+        let unknown = ClientError::UnknownEnumValue {
+            field: "meta.simple_error",
+            value: 9,
+        };
+        assert_eq!(unknown.meta_condition(), Some(9));
+        assert!(!unknown.is_locked());
+    }
+
+    #[test]
+    fn times_out_on_a_lost_response_and_recovers() {
+        let mut device = MockDevice::new();
+        device.drop_next_responses(1);
+        let mut client = StudioClient::new(device);
+        client.set_request_timeout(Some(Duration::from_millis(20)));
+
+        assert!(matches!(
+            client.get_device_info(),
+            Err(ClientError::Timeout)
+        ));
+        assert_eq!(
+            client.get_device_info().expect("info").name,
+            "Mock Keyboard"
+        );
+    }
+
+    #[test]
+    fn retries_idempotent_requests_after_a_timeout() {
+        let client_dropping_one_response = || {
+            let mut device = MockDevice::new();
+            device.drop_next_responses(1);
+            let mut client = StudioClient::new(device);
+            client.set_request_timeout(Some(Duration::from_millis(20)));
+            client.set_retry_policy(Some(RetryPolicy {
+                initial_backoff: Duration::ZERO,
+                ..RetryPolicy::default()
+            }));
+            client
+        };
+        assert_eq!(
+            client_dropping_one_response()
+                .get_device_info()
+                .expect("retried")
+                .name,
+            "Mock Keyboard"
+        );
+
+        // A repeated discard reports that nothing was left to discard, so it
+        // is not retried.
+        assert!(matches!(
+            client_dropping_one_response().discard_changes(),
+            Err(ClientError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn retries_after_a_read_timeout_without_mistaking_the_late_response() {
+        let mut device = MockDevice::new();
+        device.delay_next_responses(1);
+        let mut client = StudioClient::new(device);
+        client.set_retry_policy(Some(RetryPolicy {
+            initial_backoff: Duration::ZERO,
+            max_attempts: 2,
+            ..RetryPolicy::default()
+        }));
+
+        // Without a request timeout the first read timeout fails the attempt;
+        // its response arrives just before the retry's.
+        assert_eq!(
+            client.get_device_info().expect("retried").name,
+            "Mock Keyboard"
+        );
+        assert_eq!(
+            client.get_device_info().expect("in sync").name,
+            "Mock Keyboard"
+        );
+    }
+
+    #[test]
+    fn reports_an_unplugged_device_and_reconnects() {
+        let mut device = MockDevice::new();
+        device.unplug_after(1);
+        let mut client = StudioClient::new(device);
+        client.set_request_timeout(Some(Duration::from_millis(20)));
+
+        assert!(client.get_device_info().is_ok());
+        assert!(client.is_connected());
+        match client.get_device_info() {
+            Err(ClientError::Disconnected(device)) => {
+                assert_eq!(device, "mock device \"Mock Keyboard\"");
+            }
+            other => panic!("expected a disconnect, got {other:?}"),
+        }
+        assert!(!client.is_connected());
+
+        client.reconnect().expect("reconnect");
+        assert!(client.is_connected());
+        assert_eq!(
+            client.get_device_info().expect("info").name,
+            "Mock Keyboard"
+        );
+    }
+
+    #[test]
+    fn caches_physical_layouts_until_a_new_layout_is_selected() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.start_transcript(SerialRedaction::None);
+        let layouts_requests = |client: &StudioClient<MockDevice>| {
+            client
+                .transcript()
+                .expect("transcript")
+                .to_text()
+                .matches("GetPhysicalLayouts(true)")
+                .count()
+        };
+
+        assert_eq!(
+            client
+                .get_physical_layouts()
+                .expect("layouts")
+                .active_layout_index,
+            0
+        );
+        client.get_physical_layouts().expect("layouts");
+        assert_eq!(layouts_requests(&client), 1);
+
+        client.set_active_physical_layout(0).expect("select layout");
+        client.get_physical_layouts().expect("layouts");
+        assert_eq!(layouts_requests(&client), 2);
+    }
+
+    #[test]
+    fn caches_the_keymap_across_reads_and_binding_writes() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.start_transcript(SerialRedaction::None);
+        let keymap_requests = |client: &StudioClient<MockDevice>| {
+            client
+                .transcript()
+                .expect("transcript")
+                .to_text()
+                .matches("GetKeymap(true)")
+                .count()
+        };
+
+        for position in 0..4 {
+            client.get_key_at(0, position).expect("get binding");
+        }
+        assert_eq!(keymap_requests(&client), 1);
+
+        client
+            .set_key_at(0, 2, Behavior::None)
+            .expect("set binding");
+        assert_eq!(
+            client.get_key_at(0, 2).expect("get binding"),
+            Behavior::None
+        );
+        assert_eq!(keymap_requests(&client), 1);
+
+        client.discard_changes().expect("discard");
+        assert_ne!(
+            client.get_key_at(0, 2).expect("get binding"),
+            Behavior::None
+        );
+        assert_eq!(keymap_requests(&client), 2);
+
+        client.refresh_keymap().expect("refresh");
+        assert_eq!(keymap_requests(&client), 3);
+    }
+
+    #[test]
+    fn keeps_the_cached_keymap_when_notified_before_the_response() {
+        let mut device = MockDevice::new();
+        device.notify_before_responding();
+        let mut client = StudioClient::new(device);
+        client.start_transcript(SerialRedaction::None);
+        let keymap_requests = |client: &StudioClient<MockDevice>| {
+            client
+                .transcript()
+                .expect("transcript")
+                .to_text()
+                .matches("GetKeymap(true)")
+                .count()
+        };
+        client.get_keymap().expect("keymap");
+
+        // The change notification precedes the response and must not be
+        // mistaken for someone else's edit.
+        client
+            .set_key_at(0, 0, Behavior::None)
+            .expect("set binding");
+        assert!(client.next_notification().is_some());
+        assert_eq!(
+            client.get_key_at(0, 0).expect("get binding"),
+            Behavior::None
+        );
+        assert_eq!(keymap_requests(&client), 1);
+
+        client.save_changes().expect("save");
+        assert!(client.next_notification().is_some());
+        client.get_keymap().expect("keymap");
+        assert_eq!(keymap_requests(&client), 1);
+    }
+
+    #[test]
+    fn addresses_layers_by_name() {
+        let mut client = StudioClient::new(MockDevice::new());
+        assert_eq!(client.layer_id_by_name("Lower").expect("lookup"), 1);
+        assert!(matches!(
+            client.layer_id_by_name("Nav"),
+            Err(ClientError::UnknownLayerName(name)) if name == "Nav"
+        ));
+
+        client
+            .set_key_in_layer("Lower", 0, Behavior::None)
+            .expect("set binding");
+        assert_eq!(
+            client.get_key_in_layer("Lower", 0).expect("get binding"),
+            Behavior::None
+        );
+        assert_eq!(
+            client.get_key_at(1, 0).expect("get binding"),
+            Behavior::None
+        );
+    }
+
+    #[test]
+    fn rejects_params_outside_the_behavior_metadata() {
+        use crate::proto::zmk::behaviors::{
+            BehaviorBindingParametersSet, BehaviorParameterHidUsage,
+            BehaviorParameterValueDescription, GetBehaviorDetailsResponse,
+            behavior_parameter_value_description::ValueType,
+        };
+
+        let key_press = GetBehaviorDetailsResponse {
+            id: 1,
+            display_name: "Key Press".to_string(),
+            metadata: vec![BehaviorBindingParametersSet {
+                param1: vec![BehaviorParameterValueDescription {
+                    name: "Key".to_string(),
+                    value_type: Some(ValueType::HidUsage(BehaviorParameterHidUsage {
+                        keyboard_max: 0xFF,
+                        consumer_max: 0,
+                    })),
+                }],
+                param2: Vec::new(),
+            }],
+        };
+        let mut client = StudioClient::new(MockDevice::new().with_behavior_details([key_press]));
+        client.start_transcript(SerialRedaction::None);
+
+        let err = client
+            .set_key_at(
+                0,
+                0,
+                Behavior::KeyPress(HidUsage::from_parts(0x07, 0x1FF, 0)),
+            )
+            .expect_err("out of range");
+        assert!(matches!(
+            &err,
+            ClientError::InvalidBehaviorParam { behavior, param: 1, value: 0x0007_01FF, allowed }
+                if behavior == "Key Press" && allowed.len() == 1
+        ));
+        assert!(
+            !client
+                .transcript()
+                .expect("transcript")
+                .to_text()
+                .contains("SetLayerBinding")
+        );
+
+        client
+            .set_key_at(
+                0,
+                0,
+                Behavior::KeyPress(HidUsage::from_parts(0x07, 0x1F, 0)),
+            )
+            .expect("in range");
+    }
+
+    #[test]
+    fn rewrites_layer_indices_after_a_move() {
+        let mut client = StudioClient::new(MockDevice::new());
+        client.set_layer_reference_mode(LayerReferenceMode::Indices);
+        client.add_layer().expect("add layer");
+        client
+            .set_key_at(0, 0, Behavior::MomentaryLayer { layer_id: 1 })
+            .expect("set binding");
+        client
+            .set_key_at(0, 1, Behavior::ToggleLayer { layer_id: 2 })
+            .expect("set binding");
+
+        client.move_layer(2, 1).expect("move layer");
+        assert_eq!(
+            client.get_key_at(0, 0).expect("binding"),
+            Behavior::MomentaryLayer { layer_id: 2 }
+        );
+        assert_eq!(
+            client.get_key_at(0, 1).expect("binding"),
+            Behavior::ToggleLayer { layer_id: 1 }
+        );
+        let report = client.last_layer_remap_report().expect("report");
+        assert_eq!(report.rewritten.len(), 2);
+        assert!(report.dangling.is_empty());
+    }
+}
@@ -1,4 +1,140 @@
+use std::io::{Read, Write};
+use std::time::Duration;
+
 #[cfg(feature = "ble")]
 pub mod ble;
+/// Write-coalescing and read-ahead wrapper for any transport.
+pub mod buffered;
+/// `std::io` adapter for `embedded-io` byte streams such as a UART.
+#[cfg(feature = "embedded")]
+pub mod embedded;
+/// In-memory device emulator for testing without hardware.
+pub mod mock;
+/// Capture and playback of transport traffic for deterministic tests.
+pub mod recording;
 #[cfg(feature = "serial")]
 pub mod serial;
+#[cfg(feature = "sim")]
+pub mod sim;
+/// Studio RPC over TCP, for keyboards bridged to the network.
+#[cfg(feature = "tcp")]
+pub mod tcp;
+/// Raw HID transport for keyboards without a CDC serial port.
+#[cfg(feature = "usb")]
+pub mod usb;
+/// Arrival and removal events for serial and BLE devices.
+pub mod watch;
+
+/// USB vendor ID ZMK firmware uses unless overridden by the board.
+pub const ZMK_USB_VID: u16 = 0x1D50;
+/// USB product ID ZMK firmware uses unless overridden by the board.
+pub const ZMK_USB_PID: u16 = 0x615E;
+
+/// Bytes [`crate::StudioClient`] reads at a time unless the transport asks otherwise.
+pub const DEFAULT_READ_CHUNK_SIZE: usize = 256;
+
+/// A byte stream to a ZMK Studio device, with metadata about the connection.
+///
+/// [`crate::StudioClient`] uses this to tell a slow device (a read timeout
+/// while [`Transport::is_connected`] still holds) from one that is gone, and
+/// to name the device in errors. Every method has a default, so any other
+/// [`Read`] + [`Write`] stream only needs an empty `impl Transport for MyStream {}`.
+pub trait Transport: Read + Write {
+    /// Human-readable description of the device or endpoint, such as
+    /// `serial /dev/ttyACM0`.
+    fn description(&self) -> String {
+        String::from("custom transport")
+    }
+
+    /// Whether the connection is believed to be up.
+    ///
+    /// Transports that can detect a lost connection return `false` after it
+    /// drops, until [`Transport::reconnect`] succeeds.
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    /// Re-establishes the connection to the same device.
+    ///
+    /// Fails with [`std::io::ErrorKind::Unsupported`] unless the transport
+    /// knows how to reopen itself.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "transport cannot reconnect",
+        ))
+    }
+
+    /// Preferred number of bytes per read, for example one report or packet.
+    fn read_chunk_size(&self) -> usize {
+        DEFAULT_READ_CHUNK_SIZE
+    }
+
+    /// Changes how long a read waits for data before failing with
+    /// [`std::io::ErrorKind::TimedOut`].
+    ///
+    /// Fails with [`std::io::ErrorKind::Unsupported`] unless the transport's
+    /// read timeout is adjustable.
+    fn set_read_timeout(&mut self, _timeout: Duration) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "transport has a fixed read timeout",
+        ))
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn description(&self) -> String {
+        (**self).description()
+    }
+
+    fn is_connected(&self) -> bool {
+        (**self).is_connected()
+    }
+
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        (**self).reconnect()
+    }
+
+    fn read_chunk_size(&self) -> usize {
+        (**self).read_chunk_size()
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for &mut T {
+    fn description(&self) -> String {
+        (**self).description()
+    }
+
+    fn is_connected(&self) -> bool {
+        (**self).is_connected()
+    }
+
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        (**self).reconnect()
+    }
+
+    fn read_chunk_size(&self) -> usize {
+        (**self).read_chunk_size()
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+/// Whether an I/O error leaves the connection usable: a timeout or an
+/// interrupted call rather than a lost device.
+#[cfg(any(feature = "serial", feature = "tcp"))]
+pub(crate) fn is_transient(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::TimedOut
+            | std::io::ErrorKind::WouldBlock
+            | std::io::ErrorKind::Interrupted
+    )
+}
@@ -0,0 +1,434 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use super::Transport;
+
+const CAPTURE_HEADER: &str = "# zmk-studio-api capture v1";
+
+/// Errors from loading a capture for [`ReplayTransport`].
+#[derive(Debug)]
+pub enum CaptureError {
+    Io(std::io::Error),
+    /// A line is neither a comment nor a recognized event.
+    InvalidLine {
+        line: usize,
+        text: String,
+    },
+}
+
+impl std::fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to read capture: {err}"),
+            Self::InvalidLine { line, text } => {
+                write!(f, "Invalid capture event on line {line}: {text:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::InvalidLine { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CaptureError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CaptureEvent {
+    Write(Vec<u8>),
+    /// Bytes returned by one read; empty for end of stream.
+    Read(Vec<u8>),
+    Timeout,
+    Reconnect,
+}
+
+impl CaptureEvent {
+    fn parse(text: &str) -> Option<Self> {
+        let (kind, data) = text.split_once(' ').unwrap_or((text, ""));
+        match kind {
+            "write" => decode_hex(data).map(Self::Write),
+            "read" => decode_hex(data).map(Self::Read),
+            "timeout" if data.is_empty() => Some(Self::Timeout),
+            "reconnect" if data.is_empty() => Some(Self::Reconnect),
+            _ => None,
+        }
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.is_ascii() || !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn is_timeout(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+    )
+}
+
+/// Transport wrapper that logs every byte exchanged with the inner transport.
+///
+/// The capture is a text file with one event per line: bytes written, bytes
+/// returned by a read, read timeouts and reconnects, each prefixed with the
+/// time since recording started. Play it back with [`ReplayTransport`] to
+/// reproduce a session without the device, for example in a regression test
+/// or from a trace attached to a bug report. Other I/O errors are passed
+/// through but not recorded.
+///
+/// The capture contains the raw RPC traffic, including the device serial
+/// number; review it before sharing.
+pub struct RecordingTransport<T, W: Write = File> {
+    inner: T,
+    capture: W,
+    started: Instant,
+}
+
+impl<T> RecordingTransport<T> {
+    /// Records traffic of `inner` to a new file at `path`, replacing any existing file.
+    pub fn create(inner: T, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::new(inner, File::create(path)?)
+    }
+}
+
+impl<T, W: Write> RecordingTransport<T, W> {
+    /// Records traffic of `inner` to `capture`, starting with the capture header.
+    pub fn new(inner: T, mut capture: W) -> std::io::Result<Self> {
+        writeln!(capture, "{CAPTURE_HEADER}")?;
+        capture.flush()?;
+        Ok(Self {
+            inner,
+            capture,
+            started: Instant::now(),
+        })
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the inner transport and the capture sink.
+    pub fn into_parts(self) -> (T, W) {
+        (self.inner, self.capture)
+    }
+
+    /// Appends one event line, flushed so a crash does not lose the tail of the trace.
+    fn record(&mut self, event: &str) -> std::io::Result<()> {
+        let line = format!("[+{:.3}s] {event}\n", self.started.elapsed().as_secs_f64());
+        self.capture.write_all(line.as_bytes())?;
+        self.capture.flush()
+    }
+}
+
+impl<T: Read, W: Write> Read for RecordingTransport<T, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.inner.read(buf) {
+            Ok(read) => {
+                self.record(&format!("read {}", encode_hex(&buf[..read])))?;
+                Ok(read)
+            }
+            Err(err) => {
+                if is_timeout(&err) {
+                    self.record("timeout")?;
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<T: Write, W: Write> Write for RecordingTransport<T, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.record(&format!("write {}", encode_hex(&buf[..written])))?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: Transport, W: Write> Transport for RecordingTransport<T, W> {
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        self.inner.reconnect()?;
+        self.record("reconnect")
+    }
+
+    fn read_chunk_size(&self) -> usize {
+        self.inner.read_chunk_size()
+    }
+
+    fn set_read_timeout(&mut self, timeout: std::time::Duration) -> std::io::Result<()> {
+        self.inner.set_read_timeout(timeout)
+    }
+}
+
+/// Plays back a capture written by [`RecordingTransport`].
+///
+/// Reads return the recorded bytes and timeouts in order. Writes are checked
+/// against the recorded writes as one byte stream, so a client that sends
+/// anything else fails with [`std::io::ErrorKind::InvalidData`]. A read while
+/// the capture expects a write times out, like a device waiting for a request.
+/// Past the end of the capture, reads and writes fail with
+/// [`std::io::ErrorKind::UnexpectedEof`].
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    events: VecDeque<CaptureEvent>,
+    /// Bytes of the front event already read or matched.
+    offset: usize,
+    name: String,
+    connected: bool,
+}
+
+impl ReplayTransport {
+    /// Loads the capture at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CaptureError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let mut replay = Self::from_capture(&text)?;
+        replay.name = path.display().to_string();
+        Ok(replay)
+    }
+
+    /// Parses a capture from its text.
+    pub fn from_capture(text: &str) -> Result<Self, CaptureError> {
+        let mut events = VecDeque::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // The elapsed-time prefix is informational only.
+            let event = match line.strip_prefix("[+") {
+                Some(rest) => rest.split_once("s] ").map(|(_, event)| event),
+                None => Some(line),
+            };
+            let event =
+                event
+                    .and_then(CaptureEvent::parse)
+                    .ok_or_else(|| CaptureError::InvalidLine {
+                        line: index + 1,
+                        text: line.to_string(),
+                    })?;
+            events.push_back(event);
+        }
+        Ok(Self {
+            events,
+            offset: 0,
+            name: String::from("capture"),
+            connected: true,
+        })
+    }
+
+    /// Whether every recorded event has been played back.
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    fn advance(&mut self) {
+        self.events.pop_front();
+        self.offset = 0;
+    }
+
+    fn diverged(&self, action: &str) -> std::io::Error {
+        let expected = match self.events.front() {
+            Some(CaptureEvent::Write(_)) => "a write",
+            Some(CaptureEvent::Read(_)) | Some(CaptureEvent::Timeout) => "a read",
+            Some(CaptureEvent::Reconnect) => "a reconnect",
+            None => "the end of the capture",
+        };
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Replay diverged from the capture: {action} where it recorded {expected}"),
+        )
+    }
+}
+
+fn end_of_capture() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "End of capture")
+}
+
+impl Read for ReplayTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.events.front() {
+            None => Err(end_of_capture()),
+            Some(CaptureEvent::Read(data)) if data.is_empty() => {
+                self.advance();
+                self.connected = false;
+                Ok(0)
+            }
+            Some(CaptureEvent::Read(data)) => {
+                let available = &data[self.offset..];
+                let count = available.len().min(buf.len());
+                buf[..count].copy_from_slice(&available[..count]);
+                self.offset += count;
+                if self.offset == data.len() {
+                    self.advance();
+                }
+                Ok(count)
+            }
+            Some(CaptureEvent::Timeout) => {
+                self.advance();
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "Timed out waiting for data",
+                ))
+            }
+            Some(CaptureEvent::Write(_)) => Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "Timed out waiting for data",
+            )),
+            Some(CaptureEvent::Reconnect) => Err(self.diverged("a read")),
+        }
+    }
+}
+
+impl Write for ReplayTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut matched = 0;
+        while matched < buf.len() {
+            let Some(CaptureEvent::Write(data)) = self.events.front() else {
+                if self.events.is_empty() {
+                    return Err(end_of_capture());
+                }
+                return Err(self.diverged("a write"));
+            };
+            let expected = &data[self.offset..];
+            let count = expected.len().min(buf.len() - matched);
+            if expected[..count] != buf[matched..matched + count] {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "Replay diverged from the capture: wrote {}, recorded {}",
+                        encode_hex(&buf[matched..matched + count]),
+                        encode_hex(&expected[..count])
+                    ),
+                ));
+            }
+            matched += count;
+            self.offset += count;
+            if self.offset == data.len() {
+                self.advance();
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn description(&self) -> String {
+        format!("replay of {}", self.name)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Succeeds only where the capture recorded a reconnect.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        if self.events.front() != Some(&CaptureEvent::Reconnect) {
+            return Err(self.diverged("a reconnect"));
+        }
+        self.advance();
+        self.connected = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::{CaptureError, RecordingTransport, ReplayTransport};
+    use crate::transport::mock::MockDevice;
+    use crate::{Behavior, ClientError, Keycode, StudioClient};
+
+    fn record(
+        session: impl FnOnce(&mut StudioClient<&mut RecordingTransport<MockDevice, Vec<u8>>>),
+    ) -> String {
+        let mut recorder = RecordingTransport::new(MockDevice::new(), Vec::new()).unwrap();
+        session(&mut StudioClient::new(&mut recorder));
+        String::from_utf8(recorder.into_parts().1).unwrap()
+    }
+
+    #[test]
+    fn replays_a_recorded_session() {
+        let mut recorded = None;
+        let capture = record(|client| {
+            let name = client.get_device_info().expect("info").name;
+            recorded = Some((name, client.get_key_at(0, 1).expect("binding")));
+        });
+        assert!(capture.starts_with("# zmk-studio-api capture v1\n[+"));
+        let (name, binding) = recorded.unwrap();
+
+        let mut replay = ReplayTransport::from_capture(&capture).unwrap();
+        let mut client = StudioClient::new(&mut replay);
+        assert_eq!(client.get_device_info().expect("info").name, name);
+        assert_eq!(client.get_key_at(0, 1).expect("binding"), binding);
+        assert!(matches!(
+            client.get_device_info(),
+            Err(ClientError::Io(err)) if err.kind() == ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn rejects_writes_that_differ_from_the_capture() {
+        let capture = record(|client| {
+            client.get_device_info().expect("info");
+        });
+
+        let mut replay = ReplayTransport::from_capture(&capture).unwrap();
+        let mut client = StudioClient::new(&mut replay);
+        assert!(matches!(
+            client.set_key_at(0, 0, Behavior::KeyPress(Keycode::B.into())),
+            Err(ClientError::Io(err)) if err.kind() == ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            ReplayTransport::from_capture("write ab\nsend 01\n"),
+            Err(CaptureError::InvalidLine { line: 2, .. })
+        ));
+    }
+}
@@ -1,12 +1,22 @@
 use std::io::{Read, Write};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serialport::{SerialPortInfo, SerialPortType, UsbPortInfo};
+
+use super::{Transport, is_transient};
 
 const DEFAULT_BAUD_RATE: u32 = 12_500;
 const DEFAULT_TIMEOUT: Duration = Duration::from_millis(500);
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub use super::{ZMK_USB_PID, ZMK_USB_VID};
+pub use serialport::FlowControl;
 
 #[derive(Debug)]
 pub enum SerialTransportError {
     Open(serialport::Error),
+    Enumerate(serialport::Error),
     NoMatchingPort,
 }
 
@@ -14,6 +24,7 @@ impl std::fmt::Display for SerialTransportError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Open(err) => write!(f, "Failed to open serial port: {err}"),
+            Self::Enumerate(err) => write!(f, "Failed to list serial ports: {err}"),
             Self::NoMatchingPort => write!(f, "No matching serial port found"),
         }
     }
@@ -22,7 +33,7 @@ impl std::fmt::Display for SerialTransportError {
 impl std::error::Error for SerialTransportError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Open(err) => Some(err),
+            Self::Open(err) | Self::Enumerate(err) => Some(err),
             Self::NoMatchingPort => None,
         }
     }
@@ -34,37 +45,265 @@ impl From<serialport::Error> for SerialTransportError {
     }
 }
 
+/// Port settings for [`SerialTransport::open_with_options`].
+///
+/// The defaults suit ZMK's CDC ACM port, which ignores the baud rate; some
+/// boards only answer once DTR is asserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialOptions {
+    baud_rate: u32,
+    timeout: Duration,
+    dtr: Option<bool>,
+    rts: Option<bool>,
+    flow_control: FlowControl,
+}
+
+impl Default for SerialOptions {
+    fn default() -> Self {
+        Self {
+            baud_rate: DEFAULT_BAUD_RATE,
+            timeout: DEFAULT_TIMEOUT,
+            dtr: None,
+            rts: None,
+            flow_control: FlowControl::None,
+        }
+    }
+}
+
+impl SerialOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn baud_rate(mut self, baud_rate: u32) -> Self {
+        self.baud_rate = baud_rate;
+        self
+    }
+
+    /// How long a read or write waits before failing with `TimedOut`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the DTR line while opening; left as the OS default otherwise.
+    pub fn dtr(mut self, asserted: bool) -> Self {
+        self.dtr = Some(asserted);
+        self
+    }
+
+    /// Sets the RTS line right after opening; left as the OS default otherwise.
+    pub fn rts(mut self, asserted: bool) -> Self {
+        self.rts = Some(asserted);
+        self
+    }
+
+    pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+        self.flow_control = flow_control;
+        self
+    }
+}
+
 pub struct SerialTransport {
     inner: Box<dyn serialport::SerialPort>,
+    path: String,
+    options: SerialOptions,
+    /// USB identity of the port when opened, to find it again under a new
+    /// path after the keyboard is replugged.
+    usb: Option<UsbPortInfo>,
+    connected: bool,
 }
 
 impl SerialTransport {
     pub fn open(path: &str) -> Result<Self, SerialTransportError> {
-        Self::open_with(path, DEFAULT_BAUD_RATE, DEFAULT_TIMEOUT)
+        Self::open_with_options(path, SerialOptions::default())
     }
 
-    fn open_with(
+    /// Opens `path` with explicit port settings.
+    pub fn open_with_options(
         path: &str,
-        baud_rate: u32,
+        options: SerialOptions,
+    ) -> Result<Self, SerialTransportError> {
+        let mut builder = serialport::new(path, options.baud_rate)
+            .timeout(options.timeout)
+            .flow_control(options.flow_control);
+        if let Some(dtr) = options.dtr {
+            builder = builder.dtr_on_open(dtr);
+        }
+        let mut port = builder.open()?;
+        if let Some(rts) = options.rts {
+            port.write_request_to_send(rts)?;
+        }
+        Ok(Self {
+            inner: port,
+            path: path.to_string(),
+            options,
+            usb: usb_port_info(path),
+            connected: true,
+        })
+    }
+
+    /// Settings the port was opened with.
+    pub fn options(&self) -> &SerialOptions {
+        &self.options
+    }
+
+    /// Path of the open port; [`SerialTransport::reopen`] may change it.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Reopens the port after the keyboard was unplugged and plugged back in.
+    ///
+    /// Tries the same path first. The OS may assign the replugged keyboard a
+    /// different path (`/dev/ttyACM1`, another COM number), so if that fails
+    /// the port with the same USB vendor ID, product ID and serial number is
+    /// opened instead. Returns the error for the original path when neither
+    /// works, for example because the keyboard is still unplugged.
+    pub fn reopen(&mut self) -> Result<(), SerialTransportError> {
+        let err = match Self::open_with_options(&self.path, self.options.clone()) {
+            Ok(reopened) => {
+                *self = reopened;
+                return Ok(());
+            }
+            Err(err) => err,
+        };
+        let Some(usb) = &self.usb else {
+            return Err(err);
+        };
+        let ports = serialport::available_ports().map_err(SerialTransportError::Enumerate)?;
+        let Some(port) = ports
+            .iter()
+            .find(|port| matches!(&port.port_type, SerialPortType::UsbPort(info) if same_usb_device(info, usb)))
+        else {
+            return Err(err);
+        };
+        #[cfg(feature = "tracing")]
+        tracing::info!(old = %self.path, new = %port.port_name, "serial port moved");
+        *self = Self::open_with_options(&port.port_name, self.options.clone())?;
+        Ok(())
+    }
+
+    /// Polls port enumeration until a port accepted by `matcher` appears, then opens it.
+    ///
+    /// Useful right after plugging the keyboard in or after it reboots. A
+    /// matching port that cannot be opened yet (for example while udev is
+    /// still applying permissions) is retried until `timeout` elapses, after
+    /// which the last open error or [`SerialTransportError::NoMatchingPort`]
+    /// is returned. [`is_zmk_device`] is a suitable default matcher.
+    pub fn wait_for_device(
+        mut matcher: impl FnMut(&SerialPortInfo) -> bool,
         timeout: Duration,
     ) -> Result<Self, SerialTransportError> {
-        let port = serialport::new(path, baud_rate).timeout(timeout).open()?;
-        Ok(Self { inner: port })
+        let deadline = Instant::now() + timeout;
+        let mut last_error = SerialTransportError::NoMatchingPort;
+
+        loop {
+            let ports = serialport::available_ports().map_err(SerialTransportError::Enumerate)?;
+            for port in ports.iter().filter(|port| matcher(port)) {
+                match Self::open(&port.port_name) {
+                    Ok(transport) => return Ok(transport),
+                    Err(err) => last_error = err,
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Err(last_error);
+            }
+            thread::sleep(
+                DEVICE_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())),
+            );
+        }
     }
+
+    /// Records a failed or empty read or write: anything but a timeout means
+    /// the port is gone, typically because the keyboard was unplugged.
+    fn track<R>(&mut self, result: std::io::Result<R>) -> std::io::Result<R> {
+        if let Err(err) = &result
+            && !is_transient(err)
+        {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(port = %self.path, error = %err, "serial port lost");
+            self.connected = false;
+        }
+        result
+    }
+}
+
+/// USB details of the port at `path`, if it is a USB port.
+fn usb_port_info(path: &str) -> Option<UsbPortInfo> {
+    serialport::available_ports()
+        .ok()?
+        .into_iter()
+        .find(|port| port.port_name == path)
+        .and_then(|port| match port.port_type {
+            SerialPortType::UsbPort(usb) => Some(usb),
+            _ => None,
+        })
+}
+
+/// Whether two USB ports belong to the same device. Without a serial number
+/// any keyboard with the same IDs matches.
+fn same_usb_device(a: &UsbPortInfo, b: &UsbPortInfo) -> bool {
+    a.vid == b.vid && a.pid == b.pid && a.serial_number == b.serial_number
+}
+
+/// Returns whether `info` describes a USB port with ZMK's default vendor and product IDs.
+pub fn is_zmk_device(info: &SerialPortInfo) -> bool {
+    matches!(
+        &info.port_type,
+        SerialPortType::UsbPort(usb) if usb.vid == ZMK_USB_VID && usb.pid == ZMK_USB_PID
+    )
 }
 
 impl Read for SerialTransport {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.inner.read(buf)
+        let read = self.inner.read(buf);
+        #[cfg(feature = "tracing")]
+        if let Ok(len) = &read {
+            tracing::debug!(port = %self.path, len, "read");
+        }
+        if matches!(read, Ok(0)) && !buf.is_empty() {
+            self.connected = false;
+        }
+        self.track(read)
     }
 }
 
 impl Write for SerialTransport {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.inner.write(buf)
+        let written = self.inner.write(buf);
+        #[cfg(feature = "tracing")]
+        if let Ok(len) = &written {
+            tracing::debug!(port = %self.path, len, "wrote");
+        }
+        self.track(written)
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        self.inner.flush()
+        let flushed = self.inner.flush();
+        self.track(flushed)
+    }
+}
+
+impl Transport for SerialTransport {
+    fn description(&self) -> String {
+        format!("serial {}", self.path)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Reopens the port with the same settings; see [`SerialTransport::reopen`].
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        self.reopen().map_err(std::io::Error::other)
+    }
+
+    /// Also applies to the port after [`SerialTransport::reopen`].
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        self.inner.set_timeout(timeout)?;
+        self.options.timeout = timeout;
+        Ok(())
     }
 }
@@ -0,0 +1,208 @@
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use super::Transport;
+#[cfg(feature = "serial")]
+use crate::transport::serial::{SerialTransport, SerialTransportError};
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+const PTY_MARKER: &str = "connected to pseudotty:";
+
+/// Errors from connecting to a `native_sim`/`native_posix` firmware build.
+#[derive(Debug)]
+pub enum SimTransportError {
+    Tcp(std::io::Error),
+    #[cfg(feature = "serial")]
+    Pty(SerialTransportError),
+    /// A PTY endpoint was given, but PTYs need the `serial` feature.
+    #[cfg(not(feature = "serial"))]
+    PtyUnsupported,
+}
+
+impl std::fmt::Display for SimTransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(err) => write!(f, "Failed to connect to simulator socket: {err}"),
+            #[cfg(feature = "serial")]
+            Self::Pty(err) => write!(f, "Failed to open simulator PTY: {err}"),
+            #[cfg(not(feature = "serial"))]
+            Self::PtyUnsupported => {
+                write!(f, "PTY endpoints require the `serial` feature")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimTransportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Tcp(err) => Some(err),
+            #[cfg(feature = "serial")]
+            Self::Pty(err) => Some(err),
+            #[cfg(not(feature = "serial"))]
+            Self::PtyUnsupported => None,
+        }
+    }
+}
+
+/// Transport to a ZMK firmware running as a host process (`native_sim`/`native_posix`).
+///
+/// The simulator exposes the Studio UART either as a pseudo terminal or, when
+/// bridged, as a TCP socket.
+pub enum SimTransport {
+    Tcp(TcpStream),
+    #[cfg(feature = "serial")]
+    Pty(SerialTransport),
+}
+
+/// Connects to a simulator endpoint.
+///
+/// Endpoints starting with `/` are treated as PTY paths (for example
+/// `/dev/pts/3`); anything else is treated as a TCP `host:port` address.
+pub fn connect(endpoint: &str) -> Result<SimTransport, SimTransportError> {
+    if is_pty_endpoint(endpoint) {
+        connect_pty(endpoint)
+    } else {
+        connect_tcp(endpoint)
+    }
+}
+
+/// Connects to a simulator UART bridged to a TCP socket.
+pub fn connect_tcp(addr: impl ToSocketAddrs) -> Result<SimTransport, SimTransportError> {
+    let stream = TcpStream::connect(addr).map_err(SimTransportError::Tcp)?;
+    stream
+        .set_read_timeout(Some(DEFAULT_READ_TIMEOUT))
+        .map_err(SimTransportError::Tcp)?;
+    stream.set_nodelay(true).map_err(SimTransportError::Tcp)?;
+    Ok(SimTransport::Tcp(stream))
+}
+
+/// Opens the pseudo terminal the simulator attached its UART to.
+#[cfg(feature = "serial")]
+pub fn connect_pty(path: &str) -> Result<SimTransport, SimTransportError> {
+    let transport = SerialTransport::open(path).map_err(SimTransportError::Pty)?;
+    Ok(SimTransport::Pty(transport))
+}
+
+/// Opens the pseudo terminal the simulator attached its UART to.
+#[cfg(not(feature = "serial"))]
+pub fn connect_pty(_path: &str) -> Result<SimTransport, SimTransportError> {
+    Err(SimTransportError::PtyUnsupported)
+}
+
+/// Extracts the PTY path from a simulator console line.
+///
+/// Zephyr prints lines like `uart connected to pseudotty: /dev/pts/3` on
+/// startup; this returns `/dev/pts/3` for such a line.
+pub fn find_pty_path(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once(PTY_MARKER)?;
+    let path = rest.split_whitespace().next()?;
+    is_pty_endpoint(path).then_some(path)
+}
+
+fn is_pty_endpoint(endpoint: &str) -> bool {
+    endpoint.starts_with('/')
+}
+
+impl Read for SimTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => match stream.read(buf) {
+                // Normalize socket timeouts so callers see the same error as on serial.
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Err(
+                    std::io::Error::new(std::io::ErrorKind::TimedOut, "Timed out waiting for data"),
+                ),
+                #[cfg(feature = "tracing")]
+                Ok(len) => {
+                    tracing::debug!(len, "read");
+                    Ok(len)
+                }
+                other => other,
+            },
+            #[cfg(feature = "serial")]
+            Self::Pty(transport) => transport.read(buf),
+        }
+    }
+}
+
+impl Transport for SimTransport {
+    fn description(&self) -> String {
+        match self {
+            Self::Tcp(stream) => match stream.peer_addr() {
+                Ok(addr) => format!("simulator {addr}"),
+                Err(_) => String::from("simulator socket"),
+            },
+            #[cfg(feature = "serial")]
+            Self::Pty(transport) => format!("simulator {}", transport.description()),
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        match self {
+            // The raw socket keeps no state; the client treats EOF as a disconnect.
+            Self::Tcp(_) => true,
+            #[cfg(feature = "serial")]
+            Self::Pty(transport) => transport.is_connected(),
+        }
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.set_read_timeout(Some(timeout)),
+            #[cfg(feature = "serial")]
+            Self::Pty(transport) => transport.set_read_timeout(timeout),
+        }
+    }
+}
+
+impl Write for SimTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => {
+                let written = stream.write(buf);
+                #[cfg(feature = "tracing")]
+                if let Ok(len) = &written {
+                    tracing::debug!(len, "wrote");
+                }
+                written
+            }
+            #[cfg(feature = "serial")]
+            Self::Pty(transport) => transport.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(feature = "serial")]
+            Self::Pty(transport) => transport.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_pty_path, is_pty_endpoint};
+
+    #[test]
+    fn finds_pty_path_in_console_line() {
+        assert_eq!(
+            find_pty_path("uart connected to pseudotty: /dev/pts/3"),
+            Some("/dev/pts/3")
+        );
+        assert_eq!(
+            find_pty_path("uart_1 connected to pseudotty: /dev/pts/12\r"),
+            Some("/dev/pts/12")
+        );
+        assert_eq!(find_pty_path("*** Booting Zephyr OS ***"), None);
+    }
+
+    #[test]
+    fn classifies_endpoints() {
+        assert!(is_pty_endpoint("/dev/pts/3"));
+        assert!(!is_pty_endpoint("127.0.0.1:3000"));
+        assert!(!is_pty_endpoint("localhost:3000"));
+    }
+}
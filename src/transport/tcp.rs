@@ -0,0 +1,212 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use super::{Transport, is_transient};
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_millis(500);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors from connecting to a TCP bridge.
+#[derive(Debug)]
+pub enum TcpTransportError {
+    Resolve(std::io::Error),
+    NoAddress,
+    Connect(std::io::Error),
+}
+
+impl std::fmt::Display for TcpTransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Resolve(err) => write!(f, "Failed to resolve bridge address: {err}"),
+            Self::NoAddress => write!(f, "Bridge address did not resolve to any socket address"),
+            Self::Connect(err) => write!(f, "Failed to connect to bridge: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TcpTransportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Resolve(err) | Self::Connect(err) => Some(err),
+            Self::NoAddress => None,
+        }
+    }
+}
+
+/// Studio RPC over a TCP connection to a bridge that forwards the byte
+/// stream to a keyboard, for example a small daemon on a machine the
+/// keyboard is plugged into.
+///
+/// The stream is the same framed protocol as over serial.
+pub struct TcpTransport {
+    stream: TcpStream,
+    peer: SocketAddr,
+    read_timeout: Duration,
+    connected: bool,
+}
+
+impl TcpTransport {
+    /// Connects to `addr` (for example `"raspberrypi.local:4242"`), trying
+    /// each resolved address for up to five seconds.
+    pub fn connect(addr: impl ToSocketAddrs) -> Result<Self, TcpTransportError> {
+        Self::connect_timeout(addr, DEFAULT_CONNECT_TIMEOUT)
+    }
+
+    /// Like [`TcpTransport::connect`], with a custom per-address connect timeout.
+    pub fn connect_timeout(
+        addr: impl ToSocketAddrs,
+        timeout: Duration,
+    ) -> Result<Self, TcpTransportError> {
+        let mut last_error = None;
+        for addr in addr.to_socket_addrs().map_err(TcpTransportError::Resolve)? {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => return Self::from_stream(stream).map_err(TcpTransportError::Connect),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.map_or(TcpTransportError::NoAddress, TcpTransportError::Connect))
+    }
+
+    /// Wraps an already connected stream, applying the default read timeout.
+    pub fn from_stream(stream: TcpStream) -> std::io::Result<Self> {
+        Self::configure(stream, DEFAULT_READ_TIMEOUT)
+    }
+
+    fn configure(stream: TcpStream, read_timeout: Duration) -> std::io::Result<Self> {
+        stream.set_read_timeout(Some(read_timeout))?;
+        stream.set_nodelay(true)?;
+        let peer = stream.peer_addr()?;
+        Ok(Self {
+            stream,
+            peer,
+            read_timeout,
+            connected: true,
+        })
+    }
+
+    /// Sets how long a read waits for the bridge before timing out.
+    pub fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        self.stream.set_read_timeout(Some(timeout))?;
+        self.read_timeout = timeout;
+        Ok(())
+    }
+
+    fn track<R>(&mut self, result: std::io::Result<R>) -> std::io::Result<R> {
+        if let Err(err) = &result
+            && !is_transient(err)
+        {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(peer = %self.peer, error = %err, "bridge connection lost");
+            self.connected = false;
+        }
+        result
+    }
+
+    /// Address of the bridge this transport is connected to.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer
+    }
+}
+
+impl Read for TcpTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = match self.stream.read(buf) {
+            // Normalize socket timeouts so callers see the same error as on serial.
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "Timed out waiting for data",
+            )),
+            Ok(0) if !buf.is_empty() => {
+                self.connected = false;
+                Ok(0)
+            }
+            other => other,
+        };
+        #[cfg(feature = "tracing")]
+        if let Ok(len) = &read {
+            tracing::debug!(peer = %self.peer, len, "read");
+        }
+        self.track(read)
+    }
+}
+
+impl Write for TcpTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.stream.write(buf);
+        #[cfg(feature = "tracing")]
+        if let Ok(len) = &written {
+            tracing::debug!(peer = %self.peer, len, "wrote");
+        }
+        self.track(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let flushed = self.stream.flush();
+        self.track(flushed)
+    }
+}
+
+impl Transport for TcpTransport {
+    fn description(&self) -> String {
+        format!("TCP bridge {}", self.peer)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Connects to the same bridge address again, keeping the read timeout.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        let stream = TcpStream::connect_timeout(&self.peer, DEFAULT_CONNECT_TIMEOUT)?;
+        *self = Self::configure(stream, self.read_timeout)?;
+        Ok(())
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        TcpTransport::set_read_timeout(self, timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ErrorKind, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::TcpTransport;
+    use crate::transport::Transport;
+
+    #[test]
+    fn exchanges_bytes_and_reports_timeouts() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let bridge = thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut request = [0u8; 3];
+            socket.read_exact(&mut request).unwrap();
+            socket.write_all(&request.map(|byte| byte + 1)).unwrap();
+        });
+
+        let mut transport = TcpTransport::connect(addr).unwrap();
+        assert_eq!(transport.peer_addr(), addr);
+        transport.write_all(&[1, 2, 3]).unwrap();
+        let mut response = [0u8; 3];
+        transport.read_exact(&mut response).unwrap();
+        assert_eq!(response, [2, 3, 4]);
+        bridge.join().unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut idle = TcpTransport::connect(listener.local_addr().unwrap()).unwrap();
+        idle.set_read_timeout(Duration::from_millis(20)).unwrap();
+        let err = idle.read(&mut response).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert!(idle.is_connected(), "a timeout is not a disconnect");
+
+        drop(listener.accept().unwrap());
+        assert_eq!(idle.read(&mut response).unwrap(), 0);
+        assert!(!idle.is_connected());
+        assert!(idle.description().starts_with("TCP bridge 127.0.0.1:"));
+    }
+}
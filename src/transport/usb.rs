@@ -0,0 +1,308 @@
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use hidapi::{DeviceInfo, HidApi, HidDevice};
+
+use super::Transport;
+use crate::privacy::RedactedSerial;
+
+pub use super::{ZMK_USB_PID, ZMK_USB_VID};
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Vendor-defined HID usage page of the ZMK Studio raw HID interface.
+pub const STUDIO_USAGE_PAGE: u16 = 0xFF5A;
+/// Usage of the ZMK Studio collection within [`STUDIO_USAGE_PAGE`].
+pub const STUDIO_USAGE: u16 = 0x01;
+
+/// Bytes in each input and output report, excluding the report ID.
+const REPORT_SIZE: usize = 64;
+/// Stream bytes carried per report, after the length byte.
+const REPORT_PAYLOAD: usize = REPORT_SIZE - 1;
+
+/// A raw HID interface that may speak ZMK Studio RPC.
+#[derive(Clone, PartialEq, Eq)]
+pub struct UsbDeviceInfo {
+    /// Platform path of the HID interface; pass to [`UsbTransport::open`].
+    pub path: String,
+    pub vid: u16,
+    pub pid: u16,
+    pub usage_page: u16,
+    pub usage: u16,
+    pub product: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+impl std::fmt::Debug for UsbDeviceInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsbDeviceInfo")
+            .field("path", &self.path)
+            .field("vid", &self.vid)
+            .field("pid", &self.pid)
+            .field("usage_page", &self.usage_page)
+            .field("usage", &self.usage)
+            .field("product", &self.product)
+            .field(
+                "serial_number",
+                &self
+                    .serial_number
+                    .as_ref()
+                    .map(|serial| RedactedSerial(serial.len())),
+            )
+            .finish()
+    }
+}
+
+impl UsbDeviceInfo {
+    fn from_hid(info: &DeviceInfo) -> Self {
+        Self {
+            path: info.path().to_string_lossy().into_owned(),
+            vid: info.vendor_id(),
+            pid: info.product_id(),
+            usage_page: info.usage_page(),
+            usage: info.usage(),
+            product: info.product_string().map(str::to_string),
+            serial_number: info.serial_number().map(str::to_string),
+        }
+    }
+
+    /// Returns whether this is the Studio interface of a keyboard with ZMK's
+    /// default vendor and product IDs.
+    pub fn is_zmk_device(&self) -> bool {
+        self.vid == ZMK_USB_VID && self.pid == ZMK_USB_PID && self.is_studio_interface()
+    }
+
+    /// Returns whether the interface has the ZMK Studio usage page and usage,
+    /// whatever the board's vendor and product IDs.
+    pub fn is_studio_interface(&self) -> bool {
+        self.usage_page == STUDIO_USAGE_PAGE && self.usage == STUDIO_USAGE
+    }
+
+    pub fn display_name(&self) -> String {
+        match &self.product {
+            Some(name) if !name.is_empty() => format!("{} [{}]", name, self.path),
+            _ => self.path.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum UsbTransportError {
+    Hid(hidapi::HidError),
+    InvalidPath(String),
+    NoMatchingDevice,
+}
+
+impl std::fmt::Display for UsbTransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hid(err) => write!(f, "USB HID error: {err}"),
+            Self::InvalidPath(path) => write!(f, "Invalid HID device path: {path:?}"),
+            Self::NoMatchingDevice => write!(f, "No ZMK Studio USB HID device found"),
+        }
+    }
+}
+
+impl std::error::Error for UsbTransportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Hid(err) => Some(err),
+            Self::InvalidPath(_) | Self::NoMatchingDevice => None,
+        }
+    }
+}
+
+impl From<hidapi::HidError> for UsbTransportError {
+    fn from(value: hidapi::HidError) -> Self {
+        Self::Hid(value)
+    }
+}
+
+/// Lists HID interfaces with the ZMK Studio usage page, from any vendor.
+///
+/// Use [`UsbDeviceInfo::is_zmk_device`] to keep only keyboards with ZMK's
+/// default vendor and product IDs.
+pub fn discover_devices() -> Result<Vec<UsbDeviceInfo>, UsbTransportError> {
+    let api = HidApi::new()?;
+    Ok(api
+        .device_list()
+        .map(UsbDeviceInfo::from_hid)
+        .filter(UsbDeviceInfo::is_studio_interface)
+        .collect())
+}
+
+/// ZMK Studio over a raw HID interface, for setups without a CDC serial port.
+///
+/// The RPC byte stream is the same as over serial, split across fixed-size
+/// reports: each report starts with the number of stream bytes it carries
+/// (at most 63), followed by those bytes and zero padding.
+pub struct UsbTransport {
+    device: HidDevice,
+    path: String,
+    read_queue: VecDeque<u8>,
+    read_timeout: Duration,
+    connected: bool,
+}
+
+impl UsbTransport {
+    /// Opens the HID interface at `path`, as listed by [`discover_devices`].
+    pub fn open(path: &str) -> Result<Self, UsbTransportError> {
+        let api = HidApi::new()?;
+        let c_path =
+            CString::new(path).map_err(|_| UsbTransportError::InvalidPath(path.to_string()))?;
+        Ok(Self::from_device(api.open_path(&c_path)?, path))
+    }
+
+    /// Opens the first interface accepted by `matcher`.
+    /// [`UsbDeviceInfo::is_zmk_device`] is a suitable default matcher.
+    pub fn open_first(
+        mut matcher: impl FnMut(&UsbDeviceInfo) -> bool,
+    ) -> Result<Self, UsbTransportError> {
+        let device = discover_devices()?
+            .into_iter()
+            .find(|device| matcher(device))
+            .ok_or(UsbTransportError::NoMatchingDevice)?;
+        Self::open(&device.path)
+    }
+
+    fn from_device(device: HidDevice, path: &str) -> Self {
+        Self {
+            device,
+            path: path.to_string(),
+            read_queue: VecDeque::new(),
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            connected: true,
+        }
+    }
+
+    /// Converts a HID error, which hidapi only reports once the device is gone.
+    fn hid_io_error(&mut self, err: hidapi::HidError) -> std::io::Error {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(path = %self.path, error = %err, "HID device lost");
+        self.connected = false;
+        std::io::Error::new(std::io::ErrorKind::NotConnected, err)
+    }
+}
+
+/// Builds the output report carrying `chunk`, with report ID 0 in front.
+fn encode_report(chunk: &[u8]) -> [u8; REPORT_SIZE + 1] {
+    debug_assert!(chunk.len() <= REPORT_PAYLOAD);
+    let mut report = [0u8; REPORT_SIZE + 1];
+    report[1] = chunk.len() as u8;
+    report[2..2 + chunk.len()].copy_from_slice(chunk);
+    report
+}
+
+/// Returns the stream bytes carried by an input report.
+fn decode_report(report: &[u8]) -> &[u8] {
+    let Some((&len, payload)) = report.split_first() else {
+        return &[];
+    };
+    &payload[..usize::from(len).min(payload.len())]
+}
+
+impl Read for UsbTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.read_queue.is_empty() {
+            let mut report = [0u8; REPORT_SIZE];
+            let timeout_ms = i32::try_from(self.read_timeout.as_millis()).unwrap_or(i32::MAX);
+            let len = match self.device.read_timeout(&mut report, timeout_ms) {
+                Ok(len) => len,
+                Err(err) => return Err(self.hid_io_error(err)),
+            };
+            if len == 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "timed out waiting for USB HID report",
+                ));
+            }
+            let payload = decode_report(&report[..len]);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(path = %self.path, len = payload.len(), "read report");
+            self.read_queue.extend(payload);
+        }
+
+        let n = buf.len().min(self.read_queue.len());
+        for (slot, byte) in buf.iter_mut().zip(self.read_queue.drain(..n)) {
+            *slot = byte;
+        }
+        Ok(n)
+    }
+}
+
+impl Write for UsbTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for chunk in buf.chunks(REPORT_PAYLOAD) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(path = %self.path, len = chunk.len(), "wrote report");
+            if let Err(err) = self.device.write(&encode_report(chunk)) {
+                return Err(self.hid_io_error(err));
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Transport for UsbTransport {
+    fn description(&self) -> String {
+        format!("USB HID {}", self.path)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Reopens the same HID interface path.
+    fn reconnect(&mut self) -> std::io::Result<()> {
+        let reopened = Self::open(&self.path).map_err(std::io::Error::other)?;
+        *self = Self {
+            read_timeout: self.read_timeout,
+            ..reopened
+        };
+        Ok(())
+    }
+
+    fn read_chunk_size(&self) -> usize {
+        REPORT_PAYLOAD
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        self.read_timeout = timeout;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_the_stream_across_length_prefixed_reports() {
+        let stream: Vec<u8> = (0..100).collect();
+        let reports: Vec<_> = stream.chunks(REPORT_PAYLOAD).map(encode_report).collect();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0][0], 0, "report ID");
+        assert_eq!(reports[0][1], 63);
+        assert_eq!(reports[1][1], 37);
+        assert!(reports[1][39..].iter().all(|&byte| byte == 0));
+
+        // Input reports arrive without the report ID.
+        let decoded: Vec<u8> = reports
+            .iter()
+            .flat_map(|report| decode_report(&report[1..]).to_vec())
+            .collect();
+        assert_eq!(decoded, stream);
+        assert_eq!(decode_report(&[200, 1, 2]), &[1, 2]);
+        assert_eq!(decode_report(&[]), &[] as &[u8]);
+    }
+}
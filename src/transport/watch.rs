@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+#[cfg(feature = "ble")]
+use crate::transport::ble::{self, BleDeviceInfo};
+#[cfg(feature = "serial")]
+use crate::transport::serial::is_zmk_device;
+
+#[cfg(feature = "serial")]
+const SERIAL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+// BLE discovery already scans for several seconds per round.
+#[cfg(feature = "ble")]
+const BLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A ZMK Studio device seen by a [`DeviceWatcher`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchedDevice {
+    /// A USB serial port with ZMK's vendor and product IDs.
+    Serial { port_name: String },
+    /// A BLE peripheral advertising the ZMK Studio service.
+    #[cfg(feature = "ble")]
+    Ble(BleDeviceInfo),
+}
+
+/// Arrival or removal of a device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceEvent {
+    Arrived(WatchedDevice),
+    Removed(WatchedDevice),
+}
+
+/// Background watcher emitting [`DeviceEvent`]s for every enabled transport.
+///
+/// Serial ports are polled twice a second; BLE is scanned continuously, so a
+/// BLE keyboard is reported as removed once it stops advertising (including
+/// when another host connects to it). Devices present when watching starts
+/// are reported as arrivals. Polling stops when the watcher is dropped.
+pub struct DeviceWatcher {
+    events: Receiver<DeviceEvent>,
+    stop: Arc<AtomicBool>,
+}
+
+/// Starts watching for ZMK Studio devices.
+pub fn watch() -> DeviceWatcher {
+    let (tx, rx) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    #[cfg(feature = "serial")]
+    spawn_poller(
+        tx.clone(),
+        Arc::clone(&stop),
+        SERIAL_POLL_INTERVAL,
+        list_serial_devices,
+    );
+    #[cfg(feature = "ble")]
+    spawn_poller(
+        tx.clone(),
+        Arc::clone(&stop),
+        BLE_POLL_INTERVAL,
+        list_ble_devices,
+    );
+    drop(tx);
+
+    DeviceWatcher { events: rx, stop }
+}
+
+impl DeviceWatcher {
+    /// Returns the next event if one is already queued.
+    pub fn try_next(&self) -> Option<DeviceEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next event.
+    pub fn next_timeout(&self, timeout: Duration) -> Option<DeviceEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+}
+
+impl Iterator for DeviceWatcher {
+    type Item = DeviceEvent;
+
+    /// Blocks until the next event.
+    fn next(&mut self) -> Option<DeviceEvent> {
+        self.events.recv().ok()
+    }
+}
+
+impl Drop for DeviceWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[allow(dead_code)] // Unused when no transport feature is enabled.
+fn spawn_poller(
+    tx: Sender<DeviceEvent>,
+    stop: Arc<AtomicBool>,
+    interval: Duration,
+    mut list: impl FnMut() -> Option<Vec<WatchedDevice>> + Send + 'static,
+) {
+    thread::spawn(move || {
+        let mut known = Vec::new();
+        while !stop.load(Ordering::Relaxed) {
+            // Enumeration errors are transient (for example an adapter being reset); retry.
+            if let Some(current) = list() {
+                for event in diff_devices(&known, &current) {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+                known = current;
+            }
+            thread::sleep(interval);
+        }
+    });
+}
+
+#[cfg(feature = "serial")]
+fn list_serial_devices() -> Option<Vec<WatchedDevice>> {
+    let ports = serialport::available_ports().ok()?;
+    Some(
+        ports
+            .into_iter()
+            .filter(is_zmk_device)
+            .map(|port| WatchedDevice::Serial {
+                port_name: port.port_name,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(feature = "ble")]
+fn list_ble_devices() -> Option<Vec<WatchedDevice>> {
+    let devices = ble::discover_devices().ok()?;
+    Some(devices.into_iter().map(WatchedDevice::Ble).collect())
+}
+
+impl WatchedDevice {
+    /// Whether both describe the same device, ignoring details such as
+    /// signal strength that change between scans.
+    fn is_same_device(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Serial { port_name: a }, Self::Serial { port_name: b }) => a == b,
+            #[cfg(feature = "ble")]
+            (Self::Ble(a), Self::Ble(b)) => a.device_id == b.device_id,
+            #[cfg(feature = "ble")]
+            _ => false,
+        }
+    }
+}
+
+fn diff_devices(previous: &[WatchedDevice], current: &[WatchedDevice]) -> Vec<DeviceEvent> {
+    let contains = |devices: &[WatchedDevice], device: &WatchedDevice| {
+        devices.iter().any(|other| other.is_same_device(device))
+    };
+    let removed = previous
+        .iter()
+        .filter(|device| !contains(current, device))
+        .map(|device| DeviceEvent::Removed(device.clone()));
+    let arrived = current
+        .iter()
+        .filter(|device| !contains(previous, device))
+        .map(|device| DeviceEvent::Arrived(device.clone()));
+    removed.chain(arrived).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceEvent, WatchedDevice, diff_devices};
+
+    fn serial(port_name: &str) -> WatchedDevice {
+        WatchedDevice::Serial {
+            port_name: port_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_removals_then_arrivals() {
+        assert_eq!(
+            diff_devices(
+                &[serial("/dev/ttyACM0"), serial("/dev/ttyACM1")],
+                &[serial("/dev/ttyACM1"), serial("/dev/ttyACM2")],
+            ),
+            vec![
+                DeviceEvent::Removed(serial("/dev/ttyACM0")),
+                DeviceEvent::Arrived(serial("/dev/ttyACM2")),
+            ]
+        );
+    }
+}
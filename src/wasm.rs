@@ -0,0 +1,142 @@
+use serde_json::{Value, json};
+use strum::IntoEnumIterator;
+use wasm_bindgen::prelude::*;
+
+use crate::keymap::{TypedKeymap, TypedLayer};
+use crate::zmk_syntax::{format_binding, parse_binding};
+use crate::{Behavior, HidUsage, Keycode};
+
+/// Returns the canonical name of every known keycode.
+#[wasm_bindgen(js_name = keycodeNames)]
+pub fn keycode_names() -> Vec<String> {
+    Keycode::iter()
+        .map(|keycode| keycode.to_name().to_owned())
+        .collect()
+}
+
+/// Resolves a ZMK keycode name or alias to its encoded HID usage.
+#[wasm_bindgen(js_name = keycodeFromName)]
+pub fn keycode_from_name(name: &str) -> Option<u32> {
+    Keycode::from_name(name).map(Keycode::to_hid_usage)
+}
+
+/// Returns the canonical keycode name for an encoded HID usage, if known.
+#[wasm_bindgen(js_name = keycodeName)]
+pub fn keycode_name(usage: u32) -> Option<String> {
+    Keycode::from_hid_usage(usage).map(|keycode| keycode.to_name().to_owned())
+}
+
+/// Formats an encoded HID usage the way this crate displays it, including modifiers.
+#[wasm_bindgen(js_name = formatHidUsage)]
+pub fn format_hid_usage(usage: u32) -> String {
+    HidUsage::from_encoded(usage).to_string()
+}
+
+/// A key binding, parsed from and formatted to ZMK keymap syntax.
+///
+/// Layer parameters are layer IDs; layer names are not resolved.
+#[wasm_bindgen(js_name = Binding)]
+pub struct WasmBinding {
+    inner: Behavior,
+}
+
+#[wasm_bindgen(js_class = Binding)]
+impl WasmBinding {
+    /// Parses exactly one binding such as `&kp LC(C)` or `&mt LSHIFT ESC`.
+    pub fn parse(text: &str) -> Result<WasmBinding, JsError> {
+        Ok(Self {
+            inner: parse_binding(text, &|_| None)?,
+        })
+    }
+
+    /// Name of the behavior variant (for example `"KeyPress"`), or `"Unknown"`.
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        self.inner
+            .role()
+            .map_or_else(|| "Unknown".to_owned(), |role| format!("{role:?}"))
+    }
+
+    /// Formats the binding in ZMK keymap syntax.
+    #[wasm_bindgen(js_name = toZmk)]
+    pub fn to_zmk(&self) -> String {
+        format_binding(&self.inner)
+    }
+}
+
+/// A typed keymap that can be converted between JSON and snapshot text.
+///
+/// The JSON form is `{"layers": [{"id": 0, "name": "Base", "bindings": ["&kp A", ...]}]}`,
+/// with each binding in ZMK keymap syntax.
+#[wasm_bindgen(js_name = Keymap)]
+pub struct WasmKeymap {
+    inner: TypedKeymap,
+}
+
+#[wasm_bindgen(js_class = Keymap)]
+impl WasmKeymap {
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(text: &str) -> Result<WasmKeymap, JsError> {
+        let value: Value = serde_json::from_str(text)?;
+        let layers = value["layers"]
+            .as_array()
+            .ok_or_else(|| JsError::new("expected a `layers` array"))?;
+
+        let mut keymap = TypedKeymap::default();
+        for layer in layers {
+            let id = layer["id"]
+                .as_u64()
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(|| JsError::new("layer is missing a numeric `id`"))?;
+            let name = layer["name"].as_str().unwrap_or_default().to_owned();
+            let bindings = layer["bindings"]
+                .as_array()
+                .ok_or_else(|| JsError::new("layer is missing a `bindings` array"))?
+                .iter()
+                .map(|binding| -> Result<Behavior, JsError> {
+                    let text = binding
+                        .as_str()
+                        .ok_or_else(|| JsError::new("bindings must be strings"))?;
+                    Ok(parse_binding(text, &|_| None)?)
+                })
+                .collect::<Result<_, _>>()?;
+            keymap.layers.push(TypedLayer { id, name, bindings });
+        }
+
+        Ok(Self { inner: keymap })
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        let layers: Vec<Value> = self
+            .inner
+            .layers
+            .iter()
+            .map(|layer| {
+                json!({
+                    "id": layer.id,
+                    "name": layer.name,
+                    "bindings": layer.bindings.iter().map(format_binding).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&json!({ "layers": layers }))
+            .expect("JSON values always serialize")
+    }
+
+    #[wasm_bindgen(js_name = fromSnapshotText)]
+    pub fn from_snapshot_text(text: &str) -> Result<WasmKeymap, JsError> {
+        let inner = TypedKeymap::from_snapshot_text(text)?;
+        Ok(Self { inner })
+    }
+
+    #[wasm_bindgen(js_name = toSnapshotText)]
+    pub fn to_snapshot_text(&self) -> String {
+        self.inner.to_snapshot_text()
+    }
+
+    /// Hex-encoded content fingerprint, matching `StudioClient::keymap_fingerprint`.
+    pub fn fingerprint(&self) -> String {
+        self.inner.fingerprint().to_string()
+    }
+}
@@ -0,0 +1,228 @@
+use crate::hid_usage::{
+    HID_USAGE_CONSUMER, HID_USAGE_GENERIC_DESKTOP, HID_USAGE_KEYBOARD, HidUsage,
+};
+use crate::keycode::Keycode;
+
+/// `(usage page, usage ID, KeyboardEvent.code)` for each mapped key.
+///
+/// Consumer-page entries come first: browsers report media keys with the same
+/// codes as the rarely used keyboard-page volume keys, and ZMK keymaps bind
+/// the consumer-page variants (`C_VOL_UP` and friends). Generic Desktop entries
+/// come last, so `"Power"` resolves to the keyboard-page key.
+const WEB_CODES: &[(u16, u16, &str)] = &[
+    (HID_USAGE_CONSUMER, 0xB5, "MediaTrackNext"),
+    (HID_USAGE_CONSUMER, 0xB6, "MediaTrackPrevious"),
+    (HID_USAGE_CONSUMER, 0xB7, "MediaStop"),
+    (HID_USAGE_CONSUMER, 0xB8, "Eject"),
+    (HID_USAGE_CONSUMER, 0xCD, "MediaPlayPause"),
+    (HID_USAGE_CONSUMER, 0xE2, "AudioVolumeMute"),
+    (HID_USAGE_CONSUMER, 0xE9, "AudioVolumeUp"),
+    (HID_USAGE_CONSUMER, 0xEA, "AudioVolumeDown"),
+    (HID_USAGE_CONSUMER, 0x183, "MediaSelect"),
+    (HID_USAGE_CONSUMER, 0x18A, "LaunchMail"),
+    (HID_USAGE_CONSUMER, 0x192, "LaunchApp2"),
+    (HID_USAGE_CONSUMER, 0x194, "LaunchApp1"),
+    (HID_USAGE_CONSUMER, 0x221, "BrowserSearch"),
+    (HID_USAGE_CONSUMER, 0x223, "BrowserHome"),
+    (HID_USAGE_CONSUMER, 0x224, "BrowserBack"),
+    (HID_USAGE_CONSUMER, 0x225, "BrowserForward"),
+    (HID_USAGE_CONSUMER, 0x226, "BrowserStop"),
+    (HID_USAGE_CONSUMER, 0x227, "BrowserRefresh"),
+    (HID_USAGE_CONSUMER, 0x22A, "BrowserFavorites"),
+    (HID_USAGE_KEYBOARD, 0x04, "KeyA"),
+    (HID_USAGE_KEYBOARD, 0x05, "KeyB"),
+    (HID_USAGE_KEYBOARD, 0x06, "KeyC"),
+    (HID_USAGE_KEYBOARD, 0x07, "KeyD"),
+    (HID_USAGE_KEYBOARD, 0x08, "KeyE"),
+    (HID_USAGE_KEYBOARD, 0x09, "KeyF"),
+    (HID_USAGE_KEYBOARD, 0x0A, "KeyG"),
+    (HID_USAGE_KEYBOARD, 0x0B, "KeyH"),
+    (HID_USAGE_KEYBOARD, 0x0C, "KeyI"),
+    (HID_USAGE_KEYBOARD, 0x0D, "KeyJ"),
+    (HID_USAGE_KEYBOARD, 0x0E, "KeyK"),
+    (HID_USAGE_KEYBOARD, 0x0F, "KeyL"),
+    (HID_USAGE_KEYBOARD, 0x10, "KeyM"),
+    (HID_USAGE_KEYBOARD, 0x11, "KeyN"),
+    (HID_USAGE_KEYBOARD, 0x12, "KeyO"),
+    (HID_USAGE_KEYBOARD, 0x13, "KeyP"),
+    (HID_USAGE_KEYBOARD, 0x14, "KeyQ"),
+    (HID_USAGE_KEYBOARD, 0x15, "KeyR"),
+    (HID_USAGE_KEYBOARD, 0x16, "KeyS"),
+    (HID_USAGE_KEYBOARD, 0x17, "KeyT"),
+    (HID_USAGE_KEYBOARD, 0x18, "KeyU"),
+    (HID_USAGE_KEYBOARD, 0x19, "KeyV"),
+    (HID_USAGE_KEYBOARD, 0x1A, "KeyW"),
+    (HID_USAGE_KEYBOARD, 0x1B, "KeyX"),
+    (HID_USAGE_KEYBOARD, 0x1C, "KeyY"),
+    (HID_USAGE_KEYBOARD, 0x1D, "KeyZ"),
+    (HID_USAGE_KEYBOARD, 0x1E, "Digit1"),
+    (HID_USAGE_KEYBOARD, 0x1F, "Digit2"),
+    (HID_USAGE_KEYBOARD, 0x20, "Digit3"),
+    (HID_USAGE_KEYBOARD, 0x21, "Digit4"),
+    (HID_USAGE_KEYBOARD, 0x22, "Digit5"),
+    (HID_USAGE_KEYBOARD, 0x23, "Digit6"),
+    (HID_USAGE_KEYBOARD, 0x24, "Digit7"),
+    (HID_USAGE_KEYBOARD, 0x25, "Digit8"),
+    (HID_USAGE_KEYBOARD, 0x26, "Digit9"),
+    (HID_USAGE_KEYBOARD, 0x27, "Digit0"),
+    (HID_USAGE_KEYBOARD, 0x28, "Enter"),
+    (HID_USAGE_KEYBOARD, 0x29, "Escape"),
+    (HID_USAGE_KEYBOARD, 0x2A, "Backspace"),
+    (HID_USAGE_KEYBOARD, 0x2B, "Tab"),
+    (HID_USAGE_KEYBOARD, 0x2C, "Space"),
+    (HID_USAGE_KEYBOARD, 0x2D, "Minus"),
+    (HID_USAGE_KEYBOARD, 0x2E, "Equal"),
+    (HID_USAGE_KEYBOARD, 0x2F, "BracketLeft"),
+    (HID_USAGE_KEYBOARD, 0x30, "BracketRight"),
+    (HID_USAGE_KEYBOARD, 0x31, "Backslash"),
+    (HID_USAGE_KEYBOARD, 0x33, "Semicolon"),
+    (HID_USAGE_KEYBOARD, 0x34, "Quote"),
+    (HID_USAGE_KEYBOARD, 0x35, "Backquote"),
+    (HID_USAGE_KEYBOARD, 0x36, "Comma"),
+    (HID_USAGE_KEYBOARD, 0x37, "Period"),
+    (HID_USAGE_KEYBOARD, 0x38, "Slash"),
+    (HID_USAGE_KEYBOARD, 0x39, "CapsLock"),
+    (HID_USAGE_KEYBOARD, 0x3A, "F1"),
+    (HID_USAGE_KEYBOARD, 0x3B, "F2"),
+    (HID_USAGE_KEYBOARD, 0x3C, "F3"),
+    (HID_USAGE_KEYBOARD, 0x3D, "F4"),
+    (HID_USAGE_KEYBOARD, 0x3E, "F5"),
+    (HID_USAGE_KEYBOARD, 0x3F, "F6"),
+    (HID_USAGE_KEYBOARD, 0x40, "F7"),
+    (HID_USAGE_KEYBOARD, 0x41, "F8"),
+    (HID_USAGE_KEYBOARD, 0x42, "F9"),
+    (HID_USAGE_KEYBOARD, 0x43, "F10"),
+    (HID_USAGE_KEYBOARD, 0x44, "F11"),
+    (HID_USAGE_KEYBOARD, 0x45, "F12"),
+    (HID_USAGE_KEYBOARD, 0x46, "PrintScreen"),
+    (HID_USAGE_KEYBOARD, 0x47, "ScrollLock"),
+    (HID_USAGE_KEYBOARD, 0x48, "Pause"),
+    (HID_USAGE_KEYBOARD, 0x49, "Insert"),
+    (HID_USAGE_KEYBOARD, 0x4A, "Home"),
+    (HID_USAGE_KEYBOARD, 0x4B, "PageUp"),
+    (HID_USAGE_KEYBOARD, 0x4C, "Delete"),
+    (HID_USAGE_KEYBOARD, 0x4D, "End"),
+    (HID_USAGE_KEYBOARD, 0x4E, "PageDown"),
+    (HID_USAGE_KEYBOARD, 0x4F, "ArrowRight"),
+    (HID_USAGE_KEYBOARD, 0x50, "ArrowLeft"),
+    (HID_USAGE_KEYBOARD, 0x51, "ArrowDown"),
+    (HID_USAGE_KEYBOARD, 0x52, "ArrowUp"),
+    (HID_USAGE_KEYBOARD, 0x53, "NumLock"),
+    (HID_USAGE_KEYBOARD, 0x54, "NumpadDivide"),
+    (HID_USAGE_KEYBOARD, 0x55, "NumpadMultiply"),
+    (HID_USAGE_KEYBOARD, 0x56, "NumpadSubtract"),
+    (HID_USAGE_KEYBOARD, 0x57, "NumpadAdd"),
+    (HID_USAGE_KEYBOARD, 0x58, "NumpadEnter"),
+    (HID_USAGE_KEYBOARD, 0x59, "Numpad1"),
+    (HID_USAGE_KEYBOARD, 0x5A, "Numpad2"),
+    (HID_USAGE_KEYBOARD, 0x5B, "Numpad3"),
+    (HID_USAGE_KEYBOARD, 0x5C, "Numpad4"),
+    (HID_USAGE_KEYBOARD, 0x5D, "Numpad5"),
+    (HID_USAGE_KEYBOARD, 0x5E, "Numpad6"),
+    (HID_USAGE_KEYBOARD, 0x5F, "Numpad7"),
+    (HID_USAGE_KEYBOARD, 0x60, "Numpad8"),
+    (HID_USAGE_KEYBOARD, 0x61, "Numpad9"),
+    (HID_USAGE_KEYBOARD, 0x62, "Numpad0"),
+    (HID_USAGE_KEYBOARD, 0x63, "NumpadDecimal"),
+    (HID_USAGE_KEYBOARD, 0x64, "IntlBackslash"),
+    (HID_USAGE_KEYBOARD, 0x65, "ContextMenu"),
+    (HID_USAGE_KEYBOARD, 0x66, "Power"),
+    (HID_USAGE_KEYBOARD, 0x67, "NumpadEqual"),
+    (HID_USAGE_KEYBOARD, 0x68, "F13"),
+    (HID_USAGE_KEYBOARD, 0x69, "F14"),
+    (HID_USAGE_KEYBOARD, 0x6A, "F15"),
+    (HID_USAGE_KEYBOARD, 0x6B, "F16"),
+    (HID_USAGE_KEYBOARD, 0x6C, "F17"),
+    (HID_USAGE_KEYBOARD, 0x6D, "F18"),
+    (HID_USAGE_KEYBOARD, 0x6E, "F19"),
+    (HID_USAGE_KEYBOARD, 0x6F, "F20"),
+    (HID_USAGE_KEYBOARD, 0x70, "F21"),
+    (HID_USAGE_KEYBOARD, 0x71, "F22"),
+    (HID_USAGE_KEYBOARD, 0x72, "F23"),
+    (HID_USAGE_KEYBOARD, 0x73, "F24"),
+    (HID_USAGE_KEYBOARD, 0x75, "Help"),
+    (HID_USAGE_KEYBOARD, 0x7A, "Undo"),
+    (HID_USAGE_KEYBOARD, 0x7B, "Cut"),
+    (HID_USAGE_KEYBOARD, 0x7C, "Copy"),
+    (HID_USAGE_KEYBOARD, 0x7D, "Paste"),
+    (HID_USAGE_KEYBOARD, 0x7E, "Find"),
+    (HID_USAGE_KEYBOARD, 0x7F, "AudioVolumeMute"),
+    (HID_USAGE_KEYBOARD, 0x80, "AudioVolumeUp"),
+    (HID_USAGE_KEYBOARD, 0x81, "AudioVolumeDown"),
+    (HID_USAGE_KEYBOARD, 0x85, "NumpadComma"),
+    (HID_USAGE_KEYBOARD, 0x87, "IntlRo"),
+    (HID_USAGE_KEYBOARD, 0x88, "KanaMode"),
+    (HID_USAGE_KEYBOARD, 0x89, "IntlYen"),
+    (HID_USAGE_KEYBOARD, 0x8A, "Convert"),
+    (HID_USAGE_KEYBOARD, 0x8B, "NonConvert"),
+    (HID_USAGE_KEYBOARD, 0x90, "Lang1"),
+    (HID_USAGE_KEYBOARD, 0x91, "Lang2"),
+    (HID_USAGE_KEYBOARD, 0xE0, "ControlLeft"),
+    (HID_USAGE_KEYBOARD, 0xE1, "ShiftLeft"),
+    (HID_USAGE_KEYBOARD, 0xE2, "AltLeft"),
+    (HID_USAGE_KEYBOARD, 0xE3, "MetaLeft"),
+    (HID_USAGE_KEYBOARD, 0xE4, "ControlRight"),
+    (HID_USAGE_KEYBOARD, 0xE5, "ShiftRight"),
+    (HID_USAGE_KEYBOARD, 0xE6, "AltRight"),
+    (HID_USAGE_KEYBOARD, 0xE7, "MetaRight"),
+    (HID_USAGE_GENERIC_DESKTOP, 0x81, "Power"),
+    (HID_USAGE_GENERIC_DESKTOP, 0x82, "Sleep"),
+    (HID_USAGE_GENERIC_DESKTOP, 0x83, "WakeUp"),
+];
+
+impl HidUsage {
+    /// Returns the W3C `KeyboardEvent.code` string for this usage's base key.
+    ///
+    /// Modifiers are ignored, since browsers report them as separate key events.
+    pub fn to_web_code(self) -> Option<&'static str> {
+        WEB_CODES
+            .iter()
+            .find(|(page, id, _)| *page == self.page() && *id == self.id())
+            .map(|(_, _, code)| *code)
+    }
+
+    /// Converts a W3C `KeyboardEvent.code` string (such as `"KeyA"`,
+    /// `"Digit1"` or `"AudioVolumeUp"`) into a HID usage without modifiers.
+    pub fn from_web_code(code: &str) -> Option<Self> {
+        WEB_CODES
+            .iter()
+            .find(|(_, _, other)| *other == code)
+            .map(|(page, id, _)| Self::from_parts(*page, *id, 0))
+    }
+}
+
+impl Keycode {
+    /// Returns the W3C `KeyboardEvent.code` string for this keycode's base key.
+    pub fn to_web_code(self) -> Option<&'static str> {
+        HidUsage::from_encoded(self.to_hid_usage()).to_web_code()
+    }
+
+    /// Converts a W3C `KeyboardEvent.code` string into a known [`Keycode`].
+    pub fn from_web_code(code: &str) -> Option<Self> {
+        HidUsage::from_web_code(code)?.known_keycode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_keycodes_and_web_codes() {
+        assert_eq!(Keycode::A.to_web_code(), Some("KeyA"));
+        assert_eq!(Keycode::from_web_code("KeyA"), Some(Keycode::A));
+        assert_eq!(
+            Keycode::from_web_code("ShiftLeft"),
+            Some(Keycode::LEFT_SHIFT)
+        );
+        assert_eq!(Keycode::LEFT_ARROW.to_web_code(), Some("ArrowLeft"));
+
+        let volume_up = HidUsage::from_web_code("AudioVolumeUp").unwrap();
+        assert_eq!(
+            (volume_up.page(), volume_up.id()),
+            (HID_USAGE_CONSUMER, 0xE9)
+        );
+        assert_eq!(volume_up.to_web_code(), Some("AudioVolumeUp"));
+        assert_eq!(HidUsage::from_web_code("Unidentified"), None);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::hid_usage::{
+    HID_USAGE_CONSUMER, HID_USAGE_GENERIC_DESKTOP, HID_USAGE_KEYBOARD, HidUsage,
+};
+use crate::keycode::Keycode;
+
+/// `(usage page, usage ID, virtual-key code, scan code)` for each mapped key.
+///
+/// Scan codes are set 1 make codes as reported in `WM_KEYDOWN`; keys with the
+/// extended-key flag carry `0xE0` in the high byte. Keyboard-page entries come
+/// first so reverse lookups prefer them over consumer-page duplicates.
+#[rustfmt::skip]
+const WINDOWS_KEYS: &[(u16, u16, u16, u16)] = &[
+    (HID_USAGE_KEYBOARD, 0x04, 0x41, 0x001E), // A
+    (HID_USAGE_KEYBOARD, 0x05, 0x42, 0x0030), // B
+    (HID_USAGE_KEYBOARD, 0x06, 0x43, 0x002E), // C
+    (HID_USAGE_KEYBOARD, 0x07, 0x44, 0x0020), // D
+    (HID_USAGE_KEYBOARD, 0x08, 0x45, 0x0012), // E
+    (HID_USAGE_KEYBOARD, 0x09, 0x46, 0x0021), // F
+    (HID_USAGE_KEYBOARD, 0x0A, 0x47, 0x0022), // G
+    (HID_USAGE_KEYBOARD, 0x0B, 0x48, 0x0023), // H
+    (HID_USAGE_KEYBOARD, 0x0C, 0x49, 0x0017), // I
+    (HID_USAGE_KEYBOARD, 0x0D, 0x4A, 0x0024), // J
+    (HID_USAGE_KEYBOARD, 0x0E, 0x4B, 0x0025), // K
+    (HID_USAGE_KEYBOARD, 0x0F, 0x4C, 0x0026), // L
+    (HID_USAGE_KEYBOARD, 0x10, 0x4D, 0x0032), // M
+    (HID_USAGE_KEYBOARD, 0x11, 0x4E, 0x0031), // N
+    (HID_USAGE_KEYBOARD, 0x12, 0x4F, 0x0018), // O
+    (HID_USAGE_KEYBOARD, 0x13, 0x50, 0x0019), // P
+    (HID_USAGE_KEYBOARD, 0x14, 0x51, 0x0010), // Q
+    (HID_USAGE_KEYBOARD, 0x15, 0x52, 0x0013), // R
+    (HID_USAGE_KEYBOARD, 0x16, 0x53, 0x001F), // S
+    (HID_USAGE_KEYBOARD, 0x17, 0x54, 0x0014), // T
+    (HID_USAGE_KEYBOARD, 0x18, 0x55, 0x0016), // U
+    (HID_USAGE_KEYBOARD, 0x19, 0x56, 0x002F), // V
+    (HID_USAGE_KEYBOARD, 0x1A, 0x57, 0x0011), // W
+    (HID_USAGE_KEYBOARD, 0x1B, 0x58, 0x002D), // X
+    (HID_USAGE_KEYBOARD, 0x1C, 0x59, 0x0015), // Y
+    (HID_USAGE_KEYBOARD, 0x1D, 0x5A, 0x002C), // Z
+    (HID_USAGE_KEYBOARD, 0x1E, 0x31, 0x0002), // 1
+    (HID_USAGE_KEYBOARD, 0x1F, 0x32, 0x0003), // 2
+    (HID_USAGE_KEYBOARD, 0x20, 0x33, 0x0004), // 3
+    (HID_USAGE_KEYBOARD, 0x21, 0x34, 0x0005), // 4
+    (HID_USAGE_KEYBOARD, 0x22, 0x35, 0x0006), // 5
+    (HID_USAGE_KEYBOARD, 0x23, 0x36, 0x0007), // 6
+    (HID_USAGE_KEYBOARD, 0x24, 0x37, 0x0008), // 7
+    (HID_USAGE_KEYBOARD, 0x25, 0x38, 0x0009), // 8
+    (HID_USAGE_KEYBOARD, 0x26, 0x39, 0x000A), // 9
+    (HID_USAGE_KEYBOARD, 0x27, 0x30, 0x000B), // 0
+    (HID_USAGE_KEYBOARD, 0x28, 0x0D, 0x001C), // RETURN
+    (HID_USAGE_KEYBOARD, 0x29, 0x1B, 0x0001), // ESCAPE
+    (HID_USAGE_KEYBOARD, 0x2A, 0x08, 0x000E), // BACK
+    (HID_USAGE_KEYBOARD, 0x2B, 0x09, 0x000F), // TAB
+    (HID_USAGE_KEYBOARD, 0x2C, 0x20, 0x0039), // SPACE
+    (HID_USAGE_KEYBOARD, 0x2D, 0xBD, 0x000C), // OEM_MINUS
+    (HID_USAGE_KEYBOARD, 0x2E, 0xBB, 0x000D), // OEM_PLUS
+    (HID_USAGE_KEYBOARD, 0x2F, 0xDB, 0x001A), // OEM_4
+    (HID_USAGE_KEYBOARD, 0x30, 0xDD, 0x001B), // OEM_6
+    (HID_USAGE_KEYBOARD, 0x31, 0xDC, 0x002B), // OEM_5
+    (HID_USAGE_KEYBOARD, 0x33, 0xBA, 0x0027), // OEM_1
+    (HID_USAGE_KEYBOARD, 0x34, 0xDE, 0x0028), // OEM_7
+    (HID_USAGE_KEYBOARD, 0x35, 0xC0, 0x0029), // OEM_3
+    (HID_USAGE_KEYBOARD, 0x36, 0xBC, 0x0033), // OEM_COMMA
+    (HID_USAGE_KEYBOARD, 0x37, 0xBE, 0x0034), // OEM_PERIOD
+    (HID_USAGE_KEYBOARD, 0x38, 0xBF, 0x0035), // OEM_2
+    (HID_USAGE_KEYBOARD, 0x39, 0x14, 0x003A), // CAPITAL
+    (HID_USAGE_KEYBOARD, 0x3A, 0x70, 0x003B), // F1
+    (HID_USAGE_KEYBOARD, 0x3B, 0x71, 0x003C), // F2
+    (HID_USAGE_KEYBOARD, 0x3C, 0x72, 0x003D), // F3
+    (HID_USAGE_KEYBOARD, 0x3D, 0x73, 0x003E), // F4
+    (HID_USAGE_KEYBOARD, 0x3E, 0x74, 0x003F), // F5
+    (HID_USAGE_KEYBOARD, 0x3F, 0x75, 0x0040), // F6
+    (HID_USAGE_KEYBOARD, 0x40, 0x76, 0x0041), // F7
+    (HID_USAGE_KEYBOARD, 0x41, 0x77, 0x0042), // F8
+    (HID_USAGE_KEYBOARD, 0x42, 0x78, 0x0043), // F9
+    (HID_USAGE_KEYBOARD, 0x43, 0x79, 0x0044), // F10
+    (HID_USAGE_KEYBOARD, 0x44, 0x7A, 0x0057), // F11
+    (HID_USAGE_KEYBOARD, 0x45, 0x7B, 0x0058), // F12
+    (HID_USAGE_KEYBOARD, 0x46, 0x2C, 0xE037), // SNAPSHOT
+    (HID_USAGE_KEYBOARD, 0x47, 0x91, 0x0046), // SCROLL
+    (HID_USAGE_KEYBOARD, 0x48, 0x13, 0x0045), // PAUSE
+    (HID_USAGE_KEYBOARD, 0x49, 0x2D, 0xE052), // INSERT
+    (HID_USAGE_KEYBOARD, 0x4A, 0x24, 0xE047), // HOME
+    (HID_USAGE_KEYBOARD, 0x4B, 0x21, 0xE049), // PRIOR
+    (HID_USAGE_KEYBOARD, 0x4C, 0x2E, 0xE053), // DELETE
+    (HID_USAGE_KEYBOARD, 0x4D, 0x23, 0xE04F), // END
+    (HID_USAGE_KEYBOARD, 0x4E, 0x22, 0xE051), // NEXT
+    (HID_USAGE_KEYBOARD, 0x4F, 0x27, 0xE04D), // RIGHT
+    (HID_USAGE_KEYBOARD, 0x50, 0x25, 0xE04B), // LEFT
+    (HID_USAGE_KEYBOARD, 0x51, 0x28, 0xE050), // DOWN
+    (HID_USAGE_KEYBOARD, 0x52, 0x26, 0xE048), // UP
+    (HID_USAGE_KEYBOARD, 0x53, 0x90, 0xE045), // NUMLOCK
+    (HID_USAGE_KEYBOARD, 0x54, 0x6F, 0xE035), // DIVIDE
+    (HID_USAGE_KEYBOARD, 0x55, 0x6A, 0x0037), // MULTIPLY
+    (HID_USAGE_KEYBOARD, 0x56, 0x6D, 0x004A), // SUBTRACT
+    (HID_USAGE_KEYBOARD, 0x57, 0x6B, 0x004E), // ADD
+    (HID_USAGE_KEYBOARD, 0x58, 0x0D, 0xE01C), // RETURN (keypad)
+    (HID_USAGE_KEYBOARD, 0x59, 0x61, 0x004F), // NUMPAD1
+    (HID_USAGE_KEYBOARD, 0x5A, 0x62, 0x0050), // NUMPAD2
+    (HID_USAGE_KEYBOARD, 0x5B, 0x63, 0x0051), // NUMPAD3
+    (HID_USAGE_KEYBOARD, 0x5C, 0x64, 0x004B), // NUMPAD4
+    (HID_USAGE_KEYBOARD, 0x5D, 0x65, 0x004C), // NUMPAD5
+    (HID_USAGE_KEYBOARD, 0x5E, 0x66, 0x004D), // NUMPAD6
+    (HID_USAGE_KEYBOARD, 0x5F, 0x67, 0x0047), // NUMPAD7
+    (HID_USAGE_KEYBOARD, 0x60, 0x68, 0x0048), // NUMPAD8
+    (HID_USAGE_KEYBOARD, 0x61, 0x69, 0x0049), // NUMPAD9
+    (HID_USAGE_KEYBOARD, 0x62, 0x60, 0x0052), // NUMPAD0
+    (HID_USAGE_KEYBOARD, 0x63, 0x6E, 0x0053), // DECIMAL
+    (HID_USAGE_KEYBOARD, 0x64, 0xE2, 0x0056), // OEM_102
+    (HID_USAGE_KEYBOARD, 0x65, 0x5D, 0xE05D), // APPS
+    (HID_USAGE_KEYBOARD, 0x68, 0x7C, 0x0064), // F13
+    (HID_USAGE_KEYBOARD, 0x69, 0x7D, 0x0065), // F14
+    (HID_USAGE_KEYBOARD, 0x6A, 0x7E, 0x0066), // F15
+    (HID_USAGE_KEYBOARD, 0x6B, 0x7F, 0x0067), // F16
+    (HID_USAGE_KEYBOARD, 0x6C, 0x80, 0x0068), // F17
+    (HID_USAGE_KEYBOARD, 0x6D, 0x81, 0x0069), // F18
+    (HID_USAGE_KEYBOARD, 0x6E, 0x82, 0x006A), // F19
+    (HID_USAGE_KEYBOARD, 0x6F, 0x83, 0x006B), // F20
+    (HID_USAGE_KEYBOARD, 0x70, 0x84, 0x006C), // F21
+    (HID_USAGE_KEYBOARD, 0x71, 0x85, 0x006D), // F22
+    (HID_USAGE_KEYBOARD, 0x72, 0x86, 0x006E), // F23
+    (HID_USAGE_KEYBOARD, 0x73, 0x87, 0x0076), // F24
+    (HID_USAGE_KEYBOARD, 0x7F, 0xAD, 0xE020), // VOLUME_MUTE
+    (HID_USAGE_KEYBOARD, 0x80, 0xAF, 0xE030), // VOLUME_UP
+    (HID_USAGE_KEYBOARD, 0x81, 0xAE, 0xE02E), // VOLUME_DOWN
+    (HID_USAGE_KEYBOARD, 0x85, 0x6C, 0x007E), // SEPARATOR
+    (HID_USAGE_KEYBOARD, 0xE0, 0xA2, 0x001D), // LCONTROL
+    (HID_USAGE_KEYBOARD, 0xE1, 0xA0, 0x002A), // LSHIFT
+    (HID_USAGE_KEYBOARD, 0xE2, 0xA4, 0x0038), // LMENU
+    (HID_USAGE_KEYBOARD, 0xE3, 0x5B, 0xE05B), // LWIN
+    (HID_USAGE_KEYBOARD, 0xE4, 0xA3, 0xE01D), // RCONTROL
+    (HID_USAGE_KEYBOARD, 0xE5, 0xA1, 0x0036), // RSHIFT
+    (HID_USAGE_KEYBOARD, 0xE6, 0xA5, 0xE038), // RMENU
+    (HID_USAGE_KEYBOARD, 0xE7, 0x5C, 0xE05C), // RWIN
+    (HID_USAGE_CONSUMER, 0xB5, 0xB0, 0xE019), // MEDIA_NEXT_TRACK
+    (HID_USAGE_CONSUMER, 0xB6, 0xB1, 0xE010), // MEDIA_PREV_TRACK
+    (HID_USAGE_CONSUMER, 0xB7, 0xB2, 0xE024), // MEDIA_STOP
+    (HID_USAGE_CONSUMER, 0xCD, 0xB3, 0xE022), // MEDIA_PLAY_PAUSE
+    (HID_USAGE_CONSUMER, 0xE2, 0xAD, 0xE020), // VOLUME_MUTE
+    (HID_USAGE_CONSUMER, 0xE9, 0xAF, 0xE030), // VOLUME_UP
+    (HID_USAGE_CONSUMER, 0xEA, 0xAE, 0xE02E), // VOLUME_DOWN
+    (HID_USAGE_CONSUMER, 0x18A, 0xB4, 0xE06C), // LAUNCH_MAIL
+    (HID_USAGE_CONSUMER, 0x192, 0xB7, 0xE021), // LAUNCH_APP2
+    (HID_USAGE_CONSUMER, 0x221, 0xAA, 0xE065), // BROWSER_SEARCH
+    (HID_USAGE_CONSUMER, 0x223, 0xAC, 0xE032), // BROWSER_HOME
+    (HID_USAGE_CONSUMER, 0x224, 0xA6, 0xE06A), // BROWSER_BACK
+    (HID_USAGE_CONSUMER, 0x225, 0xA7, 0xE069), // BROWSER_FORWARD
+    (HID_USAGE_CONSUMER, 0x226, 0xA9, 0xE068), // BROWSER_STOP
+    (HID_USAGE_CONSUMER, 0x227, 0xA8, 0xE067), // BROWSER_REFRESH
+    (HID_USAGE_CONSUMER, 0x22A, 0xAB, 0xE066), // BROWSER_FAVORITES
+    (HID_USAGE_GENERIC_DESKTOP, 0x82, 0x5F, 0xE05F), // SLEEP
+];
+
+fn entry_for(usage: HidUsage) -> Option<&'static (u16, u16, u16, u16)> {
+    WINDOWS_KEYS
+        .iter()
+        .find(|(page, id, _, _)| *page == usage.page() && *id == usage.id())
+}
+
+fn usage_for(entry: &(u16, u16, u16, u16)) -> HidUsage {
+    HidUsage::from_parts(entry.0, entry.1, 0)
+}
+
+impl HidUsage {
+    /// Returns the Windows virtual-key code (`VK_*`) for this usage's base key.
+    ///
+    /// Modifiers are ignored, since Windows reports them as separate keys.
+    /// Left and right modifiers map to their sided codes such as `VK_LSHIFT`.
+    pub fn to_windows_vk(self) -> Option<u16> {
+        entry_for(self).map(|entry| entry.2)
+    }
+
+    /// Converts a Windows virtual-key code into a HID usage without modifiers.
+    ///
+    /// Codes shared by several keys resolve to the first match, so `VK_RETURN`
+    /// maps to the main Enter key rather than the keypad one. Use
+    /// [`HidUsage::from_windows_scan_code`] when the distinction matters.
+    pub fn from_windows_vk(vk: u16) -> Option<Self> {
+        WINDOWS_KEYS
+            .iter()
+            .find(|entry| entry.2 == vk)
+            .map(usage_for)
+    }
+
+    /// Returns the Windows set 1 scan code for this usage's base key.
+    ///
+    /// Extended keys carry `0xE0` in the high byte (for example `0xE04B` for
+    /// the left arrow), matching the extended-key flag of `WM_KEYDOWN`.
+    pub fn to_windows_scan_code(self) -> Option<u16> {
+        entry_for(self).map(|entry| entry.3)
+    }
+
+    /// Converts a Windows set 1 scan code into a HID usage without modifiers.
+    ///
+    /// Extended keys must include the `0xE0` prefix in the high byte.
+    pub fn from_windows_scan_code(scan_code: u16) -> Option<Self> {
+        WINDOWS_KEYS
+            .iter()
+            .find(|entry| entry.3 == scan_code)
+            .map(usage_for)
+    }
+}
+
+impl Keycode {
+    /// Returns the Windows virtual-key code for this keycode's base key.
+    ///
+    /// See [`HidUsage::to_windows_vk`].
+    pub fn to_windows_vk(self) -> Option<u16> {
+        HidUsage::from_encoded(self.to_hid_usage()).to_windows_vk()
+    }
+
+    /// Converts a Windows virtual-key code into a known [`Keycode`].
+    pub fn from_windows_vk(vk: u16) -> Option<Self> {
+        HidUsage::from_windows_vk(vk)?.known_keycode()
+    }
+
+    /// Returns the Windows set 1 scan code for this keycode's base key.
+    ///
+    /// See [`HidUsage::to_windows_scan_code`].
+    pub fn to_windows_scan_code(self) -> Option<u16> {
+        HidUsage::from_encoded(self.to_hid_usage()).to_windows_scan_code()
+    }
+
+    /// Converts a Windows set 1 scan code into a known [`Keycode`].
+    pub fn from_windows_scan_code(scan_code: u16) -> Option<Self> {
+        HidUsage::from_windows_scan_code(scan_code)?.known_keycode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_keycodes_and_windows_codes() {
+        assert_eq!(Keycode::A.to_windows_vk(), Some(0x41));
+        assert_eq!(Keycode::A.to_windows_scan_code(), Some(0x1E));
+        assert_eq!(Keycode::from_windows_vk(0xA0), Some(Keycode::LEFT_SHIFT));
+        assert_eq!(
+            Keycode::from_windows_scan_code(0xE04B),
+            Some(Keycode::LEFT_ARROW)
+        );
+
+        let keypad_enter = HidUsage::from_windows_scan_code(0xE01C).unwrap();
+        assert_eq!(keypad_enter.id(), 0x58);
+        assert_eq!(
+            HidUsage::from_windows_vk(0x0D).map(HidUsage::id),
+            Some(0x28)
+        );
+
+        let play_pause = HidUsage::from_parts(HID_USAGE_CONSUMER, 0xCD, 0);
+        assert_eq!(play_pause.to_windows_vk(), Some(0xB3));
+        assert_eq!(HidUsage::from_windows_vk(0xB3), Some(play_pause));
+        assert_eq!(HidUsage::from_windows_vk(0x07), None);
+    }
+}
@@ -0,0 +1,605 @@
+use crate::binding::Behavior;
+use crate::hid_usage::{HidUsage, MODIFIER_FUNCTIONS};
+use crate::keycode::Keycode;
+
+/// Error returned when ZMK keymap syntax cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZmkSyntaxError {
+    /// Layer the error occurred in, when parsing a whole keymap.
+    pub layer: Option<String>,
+    pub message: String,
+}
+
+impl ZmkSyntaxError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            layer: None,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn in_layer(mut self, layer: &str) -> Self {
+        self.layer = Some(layer.to_string());
+        self
+    }
+}
+
+impl std::fmt::Display for ZmkSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.layer {
+            Some(layer) => write!(f, "Syntax error in layer {layer}: {}", self.message),
+            None => write!(f, "Syntax error: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ZmkSyntaxError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Ampersand,
+    Open,
+    Close,
+    Word(&'a str),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>, ZmkSyntaxError> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() || c == ',' {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let token = match c {
+            '&' => Token::Ampersand,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let (word, tail) = rest.split_at(end);
+                tokens.push(Token::Word(word));
+                rest = tail;
+                continue;
+            }
+            other => {
+                return Err(ZmkSyntaxError::new(format!(
+                    "unexpected character {other:?}"
+                )));
+            }
+        };
+        tokens.push(token);
+        rest = &rest[1..];
+    }
+
+    Ok(tokens)
+}
+
+/// A binding parameter: a bare word or number, optionally wrapped in modifier functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Param<'a> {
+    Word(&'a str),
+    Call(&'a str, Box<Param<'a>>),
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn param(&mut self) -> Result<Param<'a>, ZmkSyntaxError> {
+        let Some(Token::Word(word)) = self.next() else {
+            return Err(ZmkSyntaxError::new("expected a parameter"));
+        };
+        if self.peek() != Some(&Token::Open) {
+            return Ok(Param::Word(word));
+        }
+        self.pos += 1;
+        let inner = self.param()?;
+        if self.next() != Some(Token::Close) {
+            return Err(ZmkSyntaxError::new(format!("unclosed `{word}(`")));
+        }
+        Ok(Param::Call(word, Box::new(inner)))
+    }
+}
+
+/// Parses a sequence of ZMK bindings such as `&kp A &mt LSHIFT ESC &mo 1`.
+///
+/// `layer_id` resolves layer names used as layer parameters.
+pub(crate) fn parse_bindings(
+    text: &str,
+    layer_id: &dyn Fn(&str) -> Option<u32>,
+) -> Result<Vec<Behavior>, ZmkSyntaxError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+    };
+    let mut bindings = Vec::new();
+
+    while let Some(token) = parser.next() {
+        if token != Token::Ampersand {
+            return Err(ZmkSyntaxError::new("expected `&` before a behavior"));
+        }
+        let Some(Token::Word(name)) = parser.next() else {
+            return Err(ZmkSyntaxError::new("expected a behavior name after `&`"));
+        };
+        let mut params = Vec::new();
+        while matches!(parser.peek(), Some(Token::Word(_))) {
+            params.push(parser.param()?);
+        }
+        let behavior = behavior(name, &params, layer_id)
+            .map_err(|message| ZmkSyntaxError::new(format!("&{name}: {message}")))?;
+        bindings.push(behavior);
+    }
+
+    Ok(bindings)
+}
+
+/// Parses exactly one ZMK binding such as `&kp LC(C)`.
+pub(crate) fn parse_binding(
+    text: &str,
+    layer_id: &dyn Fn(&str) -> Option<u32>,
+) -> Result<Behavior, ZmkSyntaxError> {
+    let mut bindings = parse_bindings(text, layer_id)?;
+    match bindings.len() {
+        1 => Ok(bindings.remove(0)),
+        count => Err(ZmkSyntaxError::new(format!(
+            "expected one binding, found {count}"
+        ))),
+    }
+}
+
+/// Parses a key value such as `A`, `LS(A)` or `0x00070004`.
+pub(crate) fn parse_usage(text: &str) -> Result<HidUsage, ZmkSyntaxError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+    };
+    let param = parser.param()?;
+    if parser.peek().is_some() {
+        return Err(ZmkSyntaxError::new("expected a single key"));
+    }
+    usage(&param).map_err(ZmkSyntaxError::new)
+}
+
+/// Formats a behavior in ZMK keymap syntax, using layer IDs for layer parameters.
+pub(crate) fn format_binding(behavior: &Behavior) -> String {
+    match behavior {
+        Behavior::KeyPress(key) => format!("&kp {}", format_usage(*key)),
+        Behavior::KeyToggle(key) => format!("&kt {}", format_usage(*key)),
+        Behavior::StickyKey(key) => format!("&sk {}", format_usage(*key)),
+        Behavior::ModTap { hold, tap } => {
+            format!("&mt {} {}", format_usage(*hold), format_usage(*tap))
+        }
+        Behavior::LayerTap { layer_id, tap } => format!("&lt {layer_id} {}", format_usage(*tap)),
+        Behavior::StickyLayer { layer_id } => format!("&sl {layer_id}"),
+        Behavior::MomentaryLayer { layer_id } => format!("&mo {layer_id}"),
+        Behavior::ToggleLayer { layer_id } => format!("&tog {layer_id}"),
+        Behavior::ToLayer { layer_id } => format!("&to {layer_id}"),
+        Behavior::Bluetooth { command, value } => {
+            // BT_SEL and BT_DISC take a profile index, even when it is 0.
+            let value = (*value != 0 || matches!(*command, 3 | 5)).then_some(*value);
+            format_command("&bt", *command, BT_COMMANDS, value)
+        }
+        Behavior::ExternalPower { value } => {
+            format_command("&ext_power", *value, EXT_POWER_COMMANDS, None)
+        }
+        Behavior::OutputSelection { value } => {
+            format_command("&out", *value, OUTPUT_COMMANDS, None)
+        }
+        Behavior::Backlight { command, value } => format_command(
+            "&bl",
+            *command,
+            BACKLIGHT_COMMANDS,
+            (*value != 0).then_some(*value),
+        ),
+        Behavior::Underglow { command, value } => format_command(
+            "&rgb_ug",
+            *command,
+            UNDERGLOW_COMMANDS,
+            (*value != 0).then_some(*value),
+        ),
+        Behavior::MouseKeyPress { value } => format_command("&mkp", *value, MOUSE_BUTTONS, None),
+        Behavior::MouseMove { value } => format!("&mmv 0x{value:08X}"),
+        Behavior::MouseScroll { value } => format!("&msc 0x{value:08X}"),
+        Behavior::CapsWord => "&caps_word".to_string(),
+        Behavior::KeyRepeat => "&key_repeat".to_string(),
+        Behavior::Reset => "&sys_reset".to_string(),
+        Behavior::Bootloader => "&bootloader".to_string(),
+        Behavior::SoftOff => "&soft_off".to_string(),
+        Behavior::StudioUnlock => "&studio_unlock".to_string(),
+        Behavior::GraveEscape => "&gresc".to_string(),
+        Behavior::Transparent => "&trans".to_string(),
+        Behavior::None => "&none".to_string(),
+        Behavior::Unknown {
+            behavior_id,
+            param1,
+            param2,
+        } => format!("&unknown {behavior_id} 0x{param1:X} 0x{param2:X}"),
+    }
+}
+
+/// Formats a key value as it appears in a binding parameter, such as `LS(A)`.
+pub(crate) fn format_usage(usage: HidUsage) -> String {
+    if let Some(keycode) = usage.known_keycode() {
+        return keycode.to_name().to_string();
+    }
+    let Some(base) = usage.known_base_keycode() else {
+        return format!("0x{:08X}", usage.to_hid_usage());
+    };
+
+    usage.modifier_set().wrap(base.to_name())
+}
+
+fn format_command(
+    behavior: &str,
+    command: u32,
+    names: &[(&str, u32)],
+    value: Option<u32>,
+) -> String {
+    let command = names
+        .iter()
+        .find(|(_, known)| *known == command)
+        .map_or_else(|| command.to_string(), |(name, _)| name.to_string());
+    match value {
+        Some(value) => format!("{behavior} {command} {value}"),
+        None => format!("{behavior} {command}"),
+    }
+}
+
+fn behavior(
+    name: &str,
+    params: &[Param<'_>],
+    layer_id: &dyn Fn(&str) -> Option<u32>,
+) -> Result<Behavior, String> {
+    let layer = |param: &Param<'_>| -> Result<u32, String> {
+        let Param::Word(word) = param else {
+            return Err("expected a layer".to_string());
+        };
+        number(word)
+            .or_else(|| layer_id(word))
+            .ok_or_else(|| format!("unknown layer `{word}`"))
+    };
+
+    let behavior = match (name, params) {
+        ("kp", [key]) => Behavior::KeyPress(usage(key)?),
+        ("kt", [key]) => Behavior::KeyToggle(usage(key)?),
+        ("sk", [key]) => Behavior::StickyKey(usage(key)?),
+        ("mt", [hold, tap]) => Behavior::ModTap {
+            hold: usage(hold)?,
+            tap: usage(tap)?,
+        },
+        ("lt", [layer_param, tap]) => Behavior::LayerTap {
+            layer_id: layer(layer_param)?,
+            tap: usage(tap)?,
+        },
+        ("sl", [layer_param]) => Behavior::StickyLayer {
+            layer_id: layer(layer_param)?,
+        },
+        ("mo", [layer_param]) => Behavior::MomentaryLayer {
+            layer_id: layer(layer_param)?,
+        },
+        ("tog", [layer_param]) => Behavior::ToggleLayer {
+            layer_id: layer(layer_param)?,
+        },
+        ("to", [layer_param]) => Behavior::ToLayer {
+            layer_id: layer(layer_param)?,
+        },
+        ("bt", [command, rest @ ..]) if rest.len() <= 1 => Behavior::Bluetooth {
+            command: constant(command, BT_COMMANDS)?,
+            value: optional_value(rest)?,
+        },
+        ("ext_power", [value]) => Behavior::ExternalPower {
+            value: constant(value, EXT_POWER_COMMANDS)?,
+        },
+        ("out", [value]) => Behavior::OutputSelection {
+            value: constant(value, OUTPUT_COMMANDS)?,
+        },
+        ("bl", [command, rest @ ..]) if rest.len() <= 1 => Behavior::Backlight {
+            command: constant(command, BACKLIGHT_COMMANDS)?,
+            value: optional_value(rest)?,
+        },
+        ("rgb_ug", [command, rest @ ..]) if rest.len() <= 1 => Behavior::Underglow {
+            command: constant(command, UNDERGLOW_COMMANDS)?,
+            value: optional_value(rest)?,
+        },
+        ("mkp", [value]) => Behavior::MouseKeyPress {
+            value: constant(value, MOUSE_BUTTONS)?,
+        },
+        ("mmv", [value]) => Behavior::MouseMove {
+            value: constant(value, &[])?,
+        },
+        ("msc", [value]) => Behavior::MouseScroll {
+            value: constant(value, &[])?,
+        },
+        ("caps_word", []) => Behavior::CapsWord,
+        ("key_repeat", []) => Behavior::KeyRepeat,
+        ("sys_reset", []) => Behavior::Reset,
+        ("bootloader", []) => Behavior::Bootloader,
+        ("soft_off", []) => Behavior::SoftOff,
+        ("studio_unlock", []) => Behavior::StudioUnlock,
+        ("gresc", []) => Behavior::GraveEscape,
+        ("trans", []) => Behavior::Transparent,
+        ("none", []) => Behavior::None,
+        // Not a ZMK behavior; written by `format_binding` for unrecognized firmware behaviors.
+        ("unknown", [behavior_id, param1, param2]) => Behavior::Unknown {
+            behavior_id: constant(behavior_id, &[])? as i32,
+            param1: constant(param1, &[])?,
+            param2: constant(param2, &[])?,
+        },
+        (name, _) if is_builtin_behavior(name) => {
+            return Err(format!("wrong number of parameters ({})", params.len()));
+        }
+        _ => return Err("unknown behavior".to_string()),
+    };
+
+    Ok(behavior)
+}
+
+/// Whether `name` (without `&`) is a behavior this module can parse.
+pub(crate) fn is_builtin_behavior(name: &str) -> bool {
+    matches!(
+        name,
+        "kp" | "kt"
+            | "sk"
+            | "mt"
+            | "lt"
+            | "sl"
+            | "mo"
+            | "tog"
+            | "to"
+            | "bt"
+            | "ext_power"
+            | "out"
+            | "bl"
+            | "rgb_ug"
+            | "mkp"
+            | "mmv"
+            | "msc"
+            | "caps_word"
+            | "key_repeat"
+            | "sys_reset"
+            | "bootloader"
+            | "soft_off"
+            | "studio_unlock"
+            | "gresc"
+            | "trans"
+            | "none"
+            | "unknown"
+    )
+}
+
+fn usage(param: &Param<'_>) -> Result<HidUsage, String> {
+    match param {
+        Param::Word(word) => number(word)
+            .map(HidUsage::from_encoded)
+            .or_else(|| {
+                Keycode::from_name(word).map(|key| HidUsage::from_encoded(key.to_hid_usage()))
+            })
+            .ok_or_else(|| format!("unknown keycode `{word}`")),
+        Param::Call(function, inner) => {
+            let modifier = modifier_function(function)
+                .ok_or_else(|| format!("unknown modifier function `{function}`"))?;
+            let inner = usage(inner)?;
+            Ok(HidUsage::from_parts(
+                inner.page(),
+                inner.id(),
+                inner.modifiers() | modifier,
+            ))
+        }
+    }
+}
+
+fn modifier_function(name: &str) -> Option<u8> {
+    MODIFIER_FUNCTIONS
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|(_, modifier)| *modifier)
+}
+
+fn constant(param: &Param<'_>, names: &[(&str, u32)]) -> Result<u32, String> {
+    let Param::Word(word) = param else {
+        return Err("expected a number".to_string());
+    };
+    number(word)
+        .or_else(|| {
+            names
+                .iter()
+                .find(|(name, _)| name == word)
+                .map(|(_, value)| *value)
+        })
+        .ok_or_else(|| format!("unknown value `{word}`"))
+}
+
+fn optional_value(params: &[Param<'_>]) -> Result<u32, String> {
+    params.first().map_or(Ok(0), |param| constant(param, &[]))
+}
+
+fn number(word: &str) -> Option<u32> {
+    match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => word.parse().ok(),
+    }
+}
+
+// Command values from zmk/app/include/dt-bindings/zmk/*.h
+const BT_COMMANDS: &[(&str, u32)] = &[
+    ("BT_CLR", 0),
+    ("BT_NXT", 1),
+    ("BT_PRV", 2),
+    ("BT_SEL", 3),
+    ("BT_CLR_ALL", 4),
+    ("BT_DISC", 5),
+];
+
+const EXT_POWER_COMMANDS: &[(&str, u32)] = &[("EP_OFF", 0), ("EP_ON", 1), ("EP_TOG", 2)];
+
+const OUTPUT_COMMANDS: &[(&str, u32)] = &[("OUT_TOG", 0), ("OUT_USB", 1), ("OUT_BLE", 2)];
+
+const BACKLIGHT_COMMANDS: &[(&str, u32)] = &[
+    ("BL_ON", 0),
+    ("BL_OFF", 1),
+    ("BL_TOG", 2),
+    ("BL_INC", 3),
+    ("BL_DEC", 4),
+    ("BL_CYCLE", 5),
+    ("BL_SET", 6),
+];
+
+const UNDERGLOW_COMMANDS: &[(&str, u32)] = &[
+    ("RGB_TOG", 0),
+    ("RGB_ON", 1),
+    ("RGB_OFF", 2),
+    ("RGB_HUI", 3),
+    ("RGB_HUD", 4),
+    ("RGB_SAI", 5),
+    ("RGB_SAD", 6),
+    ("RGB_BRI", 7),
+    ("RGB_BRD", 8),
+    ("RGB_SPI", 9),
+    ("RGB_SPD", 10),
+    ("RGB_EFF", 11),
+    ("RGB_EFR", 12),
+];
+
+const MOUSE_BUTTONS: &[(&str, u32)] = &[
+    ("LCLK", 0x01),
+    ("RCLK", 0x02),
+    ("MCLK", 0x04),
+    ("MB1", 0x01),
+    ("MB2", 0x02),
+    ("MB3", 0x04),
+    ("MB4", 0x08),
+    ("MB5", 0x10),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{format_binding, parse_binding, parse_bindings};
+    use crate::{Behavior, HidUsage, Keycode, MOD_LGUI, MOD_LSFT};
+
+    fn key(keycode: Keycode) -> HidUsage {
+        HidUsage::from_encoded(keycode.to_hid_usage())
+    }
+
+    fn no_layers(_: &str) -> Option<u32> {
+        None
+    }
+
+    #[test]
+    fn parses_common_behaviors() {
+        let nav = |name: &str| (name == "NAV").then_some(2);
+        let bindings = parse_bindings(
+            "&kp ESC &mt LSHIFT SPC &lt NAV TAB &mo 1 &bt BT_SEL 2 &out OUT_USB &trans",
+            &nav,
+        )
+        .expect("bindings should parse");
+
+        assert_eq!(
+            bindings,
+            vec![
+                Behavior::KeyPress(key(Keycode::ESCAPE)),
+                Behavior::ModTap {
+                    hold: key(Keycode::LEFT_SHIFT),
+                    tap: key(Keycode::SPACE),
+                },
+                Behavior::LayerTap {
+                    layer_id: 2,
+                    tap: key(Keycode::TAB),
+                },
+                Behavior::MomentaryLayer { layer_id: 1 },
+                Behavior::Bluetooth {
+                    command: 3,
+                    value: 2,
+                },
+                Behavior::OutputSelection { value: 1 },
+                Behavior::Transparent,
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_modifier_functions_with_spacing_from_stringify() {
+        let bindings = parse_bindings("& kp LS (N1)", &no_layers).expect("bindings should parse");
+        assert_eq!(
+            bindings,
+            vec![Behavior::KeyPress(HidUsage::from_parts(
+                0x07, 0x1E, MOD_LSFT
+            ))]
+        );
+    }
+
+    #[test]
+    fn parses_full_keys_h_names_for_system_keys() {
+        let bindings = parse_bindings("&kp SYSTEM_SLEEP &kp SYS_WAKE &kp LEFT_SHIFT", &no_layers)
+            .expect("bindings should parse");
+        assert_eq!(
+            bindings,
+            vec![
+                Behavior::KeyPress(key(Keycode::SYSTEM_SLEEP)),
+                Behavior::KeyPress(key(Keycode::SYSTEM_WAKE_UP)),
+                Behavior::KeyPress(key(Keycode::LEFT_SHIFT)),
+            ]
+        );
+        assert_eq!(format_binding(&bindings[0]), "&kp SYS_SLEEP");
+    }
+
+    #[test]
+    fn formats_bindings_that_parse_back() {
+        let behaviors = [
+            Behavior::KeyPress(HidUsage::from_parts(0x07, 0x16, MOD_LSFT | MOD_LGUI)),
+            Behavior::KeyPress(key(Keycode::EXCLAMATION)),
+            Behavior::LayerTap {
+                layer_id: 3,
+                tap: key(Keycode::SPACE),
+            },
+            Behavior::Bluetooth {
+                command: 3,
+                value: 0,
+            },
+            Behavior::MouseKeyPress { value: 1 },
+            Behavior::Unknown {
+                behavior_id: 40,
+                param1: 1,
+                param2: 2,
+            },
+        ];
+        let text: Vec<String> = behaviors.iter().map(format_binding).collect();
+        assert_eq!(
+            text,
+            [
+                "&kp LG(LS(S))",
+                "&kp EXCL",
+                "&lt 3 SPC",
+                "&bt BT_SEL 0",
+                "&mkp LCLK",
+                "&unknown 40 0x1 0x2",
+            ]
+        );
+        for (text, behavior) in text.iter().zip(behaviors) {
+            assert_eq!(parse_binding(text, &no_layers), Ok(behavior));
+        }
+    }
+
+    #[test]
+    fn reports_unknown_names() {
+        let err = parse_bindings("&kp NOPE", &no_layers).expect_err("parse should fail");
+        assert_eq!(err.message, "&kp: unknown keycode `NOPE`");
+        let err = parse_bindings("&mo NAV", &no_layers).expect_err("parse should fail");
+        assert_eq!(err.message, "&mo: unknown layer `NAV`");
+    }
+}